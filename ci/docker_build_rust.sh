@@ -42,6 +42,11 @@ pushd arrow
 cargo build --no-default-features
 popd
 
+# run the tests of the Arrow sub-crate that need optional features
+pushd arrow
+cargo test --features "gzip serde_rows"
+popd
+
 # run Arrow examples
 pushd arrow
 cargo run --example builders
@@ -40,6 +40,12 @@ popd
 @echo Test (release)
 @echo --------------
 cargo test --target %TARGET% --release || exit /B
+@echo
+@echo Test with optional features (release)
+@echo -------------------------------------
+pushd arrow
+cargo test --target %TARGET% --release --features "gzip serde_rows" || exit /B
+popd
 
 @echo
 @echo Run Arrow examples (release)
@@ -38,6 +38,10 @@ fn create_array(size: usize) -> Float32Array {
     builder.finish()
 }
 
+fn create_f64_array(size: usize) -> Float64Array {
+    Float64Array::from((0..size).map(|i| i as f64).collect::<Vec<f64>>())
+}
+
 fn bin_op_no_simd<F>(size: usize, op: F)
 where
     F: Fn(f32, f32) -> Result<f32>,
@@ -73,7 +77,7 @@ fn divide_simd(size: usize) {
 
 fn sum_no_simd(size: usize) {
     let arr_a = create_array(size);
-    criterion::black_box(sum(&arr_a).unwrap());
+    criterion::black_box(sum(&arr_a).unwrap().unwrap());
 }
 
 fn limit_no_simd(size: usize, max: usize) {
@@ -115,5 +119,31 @@ fn add_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, add_benchmark);
+fn add_f64_benchmark(c: &mut Criterion) {
+    // a few million elements, so that the arrays don't fit in the caches
+    let size = 4 * 1024 * 1024;
+    let arr_a = Arc::new(create_f64_array(size));
+    let arr_b = Arc::new(create_f64_array(size));
+
+    let (a, b) = (arr_a.clone(), arr_b.clone());
+    c.bench_function("add 4M f64", move |bencher| {
+        bencher
+            .iter(|| criterion::black_box(math_op(&*a, &*b, |x, y| Ok(x + y)).unwrap()))
+    });
+    let (a, b) = (arr_a.clone(), arr_b.clone());
+    c.bench_function("add 4M f64 simd", move |bencher| {
+        bencher.iter(|| criterion::black_box(add(&*a, &*b).unwrap()))
+    });
+    let (a, b) = (arr_a.clone(), arr_b.clone());
+    c.bench_function("multiply 4M f64", move |bencher| {
+        bencher
+            .iter(|| criterion::black_box(math_op(&*a, &*b, |x, y| Ok(x * y)).unwrap()))
+    });
+    let (a, b) = (arr_a, arr_b);
+    c.bench_function("multiply 4M f64 simd", move |bencher| {
+        bencher.iter(|| criterion::black_box(multiply(&*a, &*b).unwrap()))
+    });
+}
+
+criterion_group!(benches, add_benchmark, add_f64_benchmark);
 criterion_main!(benches);
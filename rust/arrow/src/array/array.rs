@@ -19,6 +19,7 @@ use std::any::Any;
 use std::convert::{From, TryFrom};
 use std::fmt;
 use std::io::Write;
+use std::iter::FromIterator;
 use std::mem;
 use std::sync::Arc;
 
@@ -26,12 +27,14 @@ use chrono::prelude::*;
 
 use super::*;
 use crate::array::equal::JsonEqual;
+use crate::bitmap::Bitmap;
 use crate::buffer::{Buffer, MutableBuffer};
 use crate::datatypes::DataType::Struct;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
 use crate::memory;
 use crate::util::bit_util;
+use crate::util::decimal;
 
 /// Number of seconds in a day
 const SECONDS_IN_DAY: i64 = 86_400;
@@ -88,14 +91,54 @@ pub trait Array: fmt::Debug + Send + Sync + ArrayEqual + JsonEqual {
     fn null_count(&self) -> usize {
         self.data().null_count()
     }
+
+    /// Returns the validity bitmap of this array, or `None` if the array has no nulls.
+    ///
+    /// The bitmap shares its buffer with the array. Like the underlying buffer, it is
+    /// indexed by `offset() + i` for the element at index `i`.
+    fn null_bitmap(&self) -> Option<Bitmap> {
+        if self.null_count() == 0 {
+            None
+        } else {
+            self.data_ref().null_bitmap().clone()
+        }
+    }
+
+    /// Returns the total number of bytes of memory occupied by this array, including
+    /// the buffers of any child arrays.
+    fn get_array_memory_size(&self) -> usize {
+        self.data_ref().get_array_memory_size()
+    }
 }
 
 pub type ArrayRef = Arc<Array>;
 
+/// Checked downcasting of an `ArrayRef` to a concrete array type
+pub trait ArrayDowncast {
+    /// Returns a reference to the array as a `T`, or an `ArrowError::CastError` naming
+    /// the actual data type and the requested array type if the array is not a `T`.
+    ///
+    /// This is the fallible counterpart of `as_any().downcast_ref::<T>().unwrap()`.
+    fn downcast<T: Array + 'static>(&self) -> Result<&T>;
+}
+
+impl ArrayDowncast for ArrayRef {
+    fn downcast<T: Array + 'static>(&self) -> Result<&T> {
+        self.as_any().downcast_ref::<T>().ok_or_else(|| {
+            ArrowError::CastError(format!(
+                "Cannot downcast an array of type {:?} to {}",
+                self.data_type(),
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+}
+
 /// Constructs an array using the input `data`. Returns a reference-counted `Array`
 /// instance.
 pub fn make_array(data: ArrayDataRef) -> ArrayRef {
     match data.data_type() {
+        DataType::Null => Arc::new(NullArray::from(data)) as ArrayRef,
         DataType::Boolean => Arc::new(BooleanArray::from(data)) as ArrayRef,
         DataType::Int8 => Arc::new(Int8Array::from(data)) as ArrayRef,
         DataType::Int16 => Arc::new(Int16Array::from(data)) as ArrayRef,
@@ -135,16 +178,61 @@ pub fn make_array(data: ArrayDataRef) -> ArrayRef {
         DataType::Timestamp(TimeUnit::Nanosecond) => {
             Arc::new(TimestampNanosecondArray::from(data)) as ArrayRef
         }
-        DataType::Utf8 => Arc::new(BinaryArray::from(data)) as ArrayRef,
+        DataType::Utf8 | DataType::Binary => {
+            Arc::new(BinaryArray::from(data)) as ArrayRef
+        }
+        DataType::FixedSizeBinary(_) => {
+            Arc::new(FixedSizeBinaryArray::from(data)) as ArrayRef
+        }
+        DataType::Decimal(_, _) => Arc::new(DecimalArray::from(data)) as ArrayRef,
         DataType::List(_) => Arc::new(ListArray::from(data)) as ArrayRef,
+        DataType::LargeList(_) => Arc::new(LargeListArray::from(data)) as ArrayRef,
         DataType::Struct(_) => Arc::new(StructArray::from(data)) as ArrayRef,
         DataType::FixedSizeList(_) => {
             Arc::new(FixedSizeListArray::from(data)) as ArrayRef
         }
+        DataType::Dictionary(ref key_type, _) => match key_type.as_ref() {
+            DataType::Int8 => {
+                Arc::new(DictionaryArray::<Int8Type>::from(data)) as ArrayRef
+            }
+            DataType::Int16 => {
+                Arc::new(DictionaryArray::<Int16Type>::from(data)) as ArrayRef
+            }
+            DataType::Int32 => {
+                Arc::new(DictionaryArray::<Int32Type>::from(data)) as ArrayRef
+            }
+            DataType::Int64 => {
+                Arc::new(DictionaryArray::<Int64Type>::from(data)) as ArrayRef
+            }
+            DataType::UInt8 => {
+                Arc::new(DictionaryArray::<UInt8Type>::from(data)) as ArrayRef
+            }
+            DataType::UInt16 => {
+                Arc::new(DictionaryArray::<UInt16Type>::from(data)) as ArrayRef
+            }
+            DataType::UInt32 => {
+                Arc::new(DictionaryArray::<UInt32Type>::from(data)) as ArrayRef
+            }
+            DataType::UInt64 => {
+                Arc::new(DictionaryArray::<UInt64Type>::from(data)) as ArrayRef
+            }
+            dt => panic!("Unexpected dictionary key type {:?}", dt),
+        },
         dt => panic!("Unexpected data type {:?}", dt),
     }
 }
 
+/// Returns a `BinaryArray` over the data of the `Utf8` or `Binary` array `array`.
+///
+/// `Utf8` data may be held by either a `BinaryArray` or a `StringArray`, so `Utf8`
+/// arrays are read through this function rather than downcast.
+pub(crate) fn as_binary_array(array: &Array) -> BinaryArray {
+    match array.data_type() {
+        DataType::Utf8 | DataType::Binary => BinaryArray::from(array.data()),
+        dt => panic!("Expected Utf8 or Binary data, not {:?}", dt),
+    }
+}
+
 fn slice_data(data: ArrayDataRef, mut offset: usize, length: usize) -> ArrayDataRef {
     assert!((offset + length) <= data.len());
 
@@ -160,6 +248,8 @@ fn slice_data(data: ArrayDataRef, mut offset: usize, length: usize) -> ArrayData
         let valid_bits = bitmap.bits.data();
         len.checked_sub(bit_util::count_set_bits_offset(valid_bits, offset, length))
             .unwrap()
+    } else if new_data.data_type() == &DataType::Null {
+        len
     } else {
         0
     };
@@ -271,6 +361,15 @@ impl<T: ArrowNumericType> PrimitiveArray<T> {
         self.data.buffers()[0].clone()
     }
 
+    /// Returns a slice of the values of this array, taking the offset of this array
+    /// into account.
+    ///
+    /// Note the values at null positions are undefined, so callers still need to
+    /// consult the null bitmap.
+    pub fn values_slice(&self) -> &[T::Native] {
+        self.value_slice(0, self.len())
+    }
+
     /// Returns the length of this array
     pub fn len(&self) -> usize {
         self.data.len()
@@ -476,14 +575,42 @@ where
 
 /// Specific implementation for Boolean arrays due to bit-packing
 impl PrimitiveArray<BooleanType> {
-    pub fn new(length: usize, values: Buffer, null_count: usize, offset: usize) -> Self {
-        let array_data = ArrayData::builder(DataType::Boolean)
+    /// Creates a boolean array of `length` slots, starting at bit `offset`, directly
+    /// from bit-packed `values` and an optional bit-packed `null_bit_buffer`.
+    ///
+    /// This allows kernels that compute bitmaps to build their results without going
+    /// through a `BooleanBuilder`. The null count is computed from `null_bit_buffer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` or `null_bit_buffer` have fewer than `offset + length` bits.
+    pub fn new(
+        length: usize,
+        values: Buffer,
+        null_bit_buffer: Option<Buffer>,
+        offset: usize,
+    ) -> Self {
+        let required_len = bit_util::ceil(offset + length, 8);
+        assert!(
+            values.len() >= required_len,
+            "BooleanArray values buffer of {} byte(s) is too short for {} bit(s)",
+            values.len(),
+            offset + length
+        );
+        let mut builder = ArrayData::builder(DataType::Boolean)
             .len(length)
             .add_buffer(values)
-            .null_count(null_count)
-            .offset(offset)
-            .build();
-        BooleanArray::from(array_data)
+            .offset(offset);
+        if let Some(null_bit_buffer) = null_bit_buffer {
+            assert!(
+                null_bit_buffer.len() >= required_len,
+                "BooleanArray null bit buffer of {} byte(s) is too short for {} bit(s)",
+                null_bit_buffer.len(),
+                offset + length
+            );
+            builder = builder.null_bit_buffer(null_bit_buffer);
+        }
+        BooleanArray::from(builder.build())
     }
 
     /// Returns a `Buffer` holds all the values of this array.
@@ -837,6 +964,124 @@ where
     Ok(())
 }
 
+/// Writes the value at `index` of `array` on a single line, with nested values written
+/// recursively, e.g. `[1, null]` for a list or `{a: 1, b: "x"}` for a struct
+fn write_value(array: &Array, index: usize, f: &mut fmt::Formatter) -> fmt::Result {
+    macro_rules! write_primitive {
+        ($ARRAY_TYPE:ident) => {{
+            let array = array.as_any().downcast_ref::<$ARRAY_TYPE>().unwrap();
+            write!(f, "{}", array.value(index))
+        }};
+    }
+
+    if array.is_null(index) {
+        return write!(f, "null");
+    }
+    match array.data_type() {
+        DataType::Boolean => write_primitive!(BooleanArray),
+        DataType::Int8 => write_primitive!(Int8Array),
+        DataType::Int16 => write_primitive!(Int16Array),
+        DataType::Int32 => write_primitive!(Int32Array),
+        DataType::Int64 => write_primitive!(Int64Array),
+        DataType::UInt8 => write_primitive!(UInt8Array),
+        DataType::UInt16 => write_primitive!(UInt16Array),
+        DataType::UInt32 => write_primitive!(UInt32Array),
+        DataType::UInt64 => write_primitive!(UInt64Array),
+        DataType::Float32 => write_primitive!(Float32Array),
+        DataType::Float64 => write_primitive!(Float64Array),
+        DataType::Date32(_) => write_primitive!(Date32Array),
+        DataType::Date64(_) => write_primitive!(Date64Array),
+        DataType::Time32(TimeUnit::Second) => write_primitive!(Time32SecondArray),
+        DataType::Time32(TimeUnit::Millisecond) => {
+            write_primitive!(Time32MillisecondArray)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            write_primitive!(Time64MicrosecondArray)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            write_primitive!(Time64NanosecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Second) => write_primitive!(TimestampSecondArray),
+        DataType::Timestamp(TimeUnit::Millisecond) => {
+            write_primitive!(TimestampMillisecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond) => {
+            write_primitive!(TimestampMicrosecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond) => {
+            write_primitive!(TimestampNanosecondArray)
+        }
+        DataType::Utf8 | DataType::Binary => {
+            let array = as_binary_array(array);
+            match std::str::from_utf8(array.value(index)) {
+                Ok(s) => write!(f, "{:?}", s),
+                Err(_) => write!(f, "{:?}", array.value(index)),
+            }
+        }
+        DataType::Decimal(_, _) => {
+            let array = array.as_any().downcast_ref::<DecimalArray>().unwrap();
+            write!(f, "{}", array.value_as_string(index))
+        }
+        DataType::List(_) => {
+            let array = array.as_any().downcast_ref::<ListArray>().unwrap();
+            write_values(array.value(index).as_ref(), f)
+        }
+        DataType::LargeList(_) => {
+            let array = array.as_any().downcast_ref::<LargeListArray>().unwrap();
+            write_values(array.value(index).as_ref(), f)
+        }
+        DataType::FixedSizeList(_) => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            write_values(array.value(index).as_ref(), f)
+        }
+        DataType::Struct(fields) => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            write!(f, "{{")?;
+            for (i, field) in fields.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}: ", field.name())?;
+                write_value(array.column(i).as_ref(), index, f)?;
+            }
+            write!(f, "}}")
+        }
+        other => write!(f, "<{:?}>", other),
+    }
+}
+
+/// Returns the value at `index` of `array` as written by `write_value`, except that a
+/// string value isn't quoted
+pub(crate) fn array_value_to_string(array: &Array, index: usize) -> String {
+    struct Value<'a>(&'a Array, usize);
+
+    impl fmt::Display for Value<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write_value(self.0, self.1, f)
+        }
+    }
+
+    match array.data_type() {
+        DataType::Utf8 if array.is_valid(index) => {
+            let array = as_binary_array(array);
+            String::from_utf8_lossy(array.value(index)).into_owned()
+        }
+        _ => Value(array, index).to_string(),
+    }
+}
+
+/// Writes all values of `array` on a single line, e.g. `[1, null, 3]`
+fn write_values(array: &Array, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "[")?;
+    for i in 0..array.len() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write_value(array, i, f)?;
+    }
+    write!(f, "]")
+}
+
 impl fmt::Debug for ListArray {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "ListArray\n[\n")?;
@@ -847,6 +1092,116 @@ impl fmt::Debug for ListArray {
     }
 }
 
+/// Writes the values of the list array on a single line, e.g. `[[1, null], null, []]`
+impl fmt::Display for ListArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_values(self, f)
+    }
+}
+
+/// A list array like `ListArray`, but with 64-bit value offsets so that the values
+/// array may hold more than `i32::max_value()` items.
+pub struct LargeListArray {
+    data: ArrayDataRef,
+    values: ArrayRef,
+    value_offsets: RawPtrBox<i64>,
+}
+
+impl LargeListArray {
+    /// Returns an reference to the values of this list.
+    pub fn values(&self) -> ArrayRef {
+        self.values.clone()
+    }
+
+    /// Returns a clone of the value type of this list.
+    pub fn value_type(&self) -> DataType {
+        self.values.data().data_type().clone()
+    }
+
+    /// Returns ith value of this list array.
+    pub fn value(&self, i: usize) -> ArrayRef {
+        self.values
+            .slice(self.value_offset(i) as usize, self.value_length(i) as usize)
+    }
+
+    /// Returns the offset for value at index `i`.
+    ///
+    /// Note this doesn't do any bound checking, for performance reason.
+    #[inline]
+    pub fn value_offset(&self, i: usize) -> i64 {
+        self.value_offset_at(self.data.offset() + i)
+    }
+
+    /// Returns the length for value at index `i`.
+    ///
+    /// Note this doesn't do any bound checking, for performance reason.
+    #[inline]
+    pub fn value_length(&self, mut i: usize) -> i64 {
+        i += self.data.offset();
+        self.value_offset_at(i + 1) - self.value_offset_at(i)
+    }
+
+    #[inline]
+    fn value_offset_at(&self, i: usize) -> i64 {
+        unsafe { *self.value_offsets.get().offset(i as isize) }
+    }
+}
+
+/// Constructs a `LargeListArray` from an array data reference.
+impl From<ArrayDataRef> for LargeListArray {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.buffers().len(),
+            1,
+            "LargeListArray data should contain a single buffer only (value offsets)"
+        );
+        assert_eq!(
+            data.child_data().len(),
+            1,
+            "LargeListArray should contain a single child array (values array)"
+        );
+        let values = make_array(data.child_data()[0].clone());
+        let raw_value_offsets = data.buffers()[0].raw_data();
+        assert!(
+            memory::is_aligned(raw_value_offsets, mem::align_of::<i64>()),
+            "memory is not aligned"
+        );
+        let value_offsets = raw_value_offsets as *const i64;
+        unsafe {
+            assert_eq!(*value_offsets.offset(0), 0, "offsets do not start at zero");
+        }
+        Self {
+            data: data.clone(),
+            values,
+            value_offsets: RawPtrBox::new(value_offsets),
+        }
+    }
+}
+
+impl Array for LargeListArray {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+impl fmt::Debug for LargeListArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LargeListArray\n[\n")?;
+        print_long_array(self, f, |array, index, f| {
+            fmt::Debug::fmt(&array.value(index), f)
+        })?;
+        write!(f, "]")
+    }
+}
+
 /// A list array where each element is a fixed-size sequence of values with the same
 /// type.
 pub struct FixedSizeListArray {
@@ -1119,22 +1474,409 @@ impl From<ListArray> for BinaryArray {
                 .null_bit_buffer(bitmap.bits.clone())
         }
 
-        let data = builder.build();
-        Self::from(data)
+        let data = builder.build();
+        Self::from(data)
+    }
+}
+
+impl fmt::Debug for BinaryArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BinaryArray\n[\n")?;
+        print_long_array(self, f, |array, index, f| {
+            fmt::Debug::fmt(&array.value(index), f)
+        })?;
+        write!(f, "]")
+    }
+}
+
+impl Array for BinaryArray {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+/// An array of UTF-8 strings, backed by the same offsets and values layout as
+/// `BinaryArray`.
+///
+/// Every value of a `StringArray`, including the values of null slots, is checked to be
+/// valid UTF-8 when the array is created.
+///
+/// Note that `make_array` creates a `BinaryArray` for `Utf8` data, as creating a
+/// `StringArray` costs a pass over its values, and that the compute kernels accept
+/// `Utf8` arrays of either type. A `StringArray` can be created from the same data with
+/// `StringArray::try_from_data(data)` to access the values as `&str`.
+pub struct StringArray {
+    values: BinaryArray,
+}
+
+impl StringArray {
+    /// Creates a `StringArray` from `Utf8` data, returning an `InvalidArgumentError` if
+    /// the data type is not `Utf8` or if any value is not valid UTF-8.
+    pub fn try_from_data(data: ArrayDataRef) -> Result<Self> {
+        if data.data_type() != &DataType::Utf8 {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "StringArray can only be created from Utf8 data, not {:?}",
+                data.data_type()
+            )));
+        }
+        let values = BinaryArray::from(data);
+        for i in 0..values.len() {
+            if let Err(e) = ::std::str::from_utf8(values.value(i)) {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Value at index {} of StringArray is not valid UTF-8: {}",
+                    i, e
+                )));
+            }
+        }
+        Ok(Self { values })
+    }
+
+    /// Returns the element at index `i` as a string slice.
+    pub fn value(&self, i: usize) -> &str {
+        assert!(i < self.len(), "StringArray out of bounds access");
+        // the values were checked to be valid UTF-8 when the array was created
+        unsafe { ::std::str::from_utf8_unchecked(self.values.value(i)) }
+    }
+
+    /// Returns the offset for the element at index `i`.
+    ///
+    /// Note this doesn't do any bound checking, for performance reason.
+    #[inline]
+    pub fn value_offset(&self, i: usize) -> i32 {
+        self.values.value_offset(i)
+    }
+
+    /// Returns the length in bytes for the element at index `i`.
+    ///
+    /// Note this doesn't do any bound checking, for performance reason.
+    #[inline]
+    pub fn value_length(&self, i: usize) -> i32 {
+        self.values.value_length(i)
+    }
+
+    /// Returns a clone of the value offset buffer
+    pub fn value_offsets(&self) -> Buffer {
+        self.values.value_offsets()
+    }
+
+    /// Returns a clone of the value data buffer
+    pub fn value_data(&self) -> Buffer {
+        self.values.value_data()
+    }
+}
+
+impl From<ArrayDataRef> for StringArray {
+    /// Creates a `StringArray` from `Utf8` data, panicking if any value is not valid
+    /// UTF-8. Use `StringArray::try_from_data` to handle invalid data.
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.data_type(),
+            &DataType::Utf8,
+            "StringArray can only be created from Utf8 data"
+        );
+        Self::try_from_data(data).unwrap()
+    }
+}
+
+impl<'a> From<Vec<&'a str>> for StringArray {
+    fn from(v: Vec<&'a str>) -> Self {
+        // the values are `&str` and therefore valid UTF-8
+        Self {
+            values: BinaryArray::from(v),
+        }
+    }
+}
+
+impl<'a> From<Vec<Option<&'a str>>> for StringArray {
+    fn from(v: Vec<Option<&'a str>>) -> Self {
+        let mut offsets = Vec::with_capacity(v.len() + 1);
+        let mut values = Vec::new();
+        let num_byte = bit_util::ceil(v.len(), 8);
+        let mut null_buf = MutableBuffer::new(num_byte).with_bitset(num_byte, false);
+        let mut length_so_far = 0;
+        offsets.push(length_so_far);
+        {
+            let null_slice = null_buf.data_mut();
+            for (i, s) in v.iter().enumerate() {
+                if let Some(s) = s {
+                    bit_util::set_bit(null_slice, i);
+                    length_so_far += s.len() as i32;
+                    values.extend_from_slice(s.as_bytes());
+                }
+                offsets.push(length_so_far);
+            }
+        }
+        let array_data = ArrayData::builder(DataType::Utf8)
+            .len(v.len())
+            .add_buffer(Buffer::from(offsets.to_byte_slice()))
+            .add_buffer(Buffer::from(&values[..]))
+            .null_bit_buffer(null_buf.freeze())
+            .build();
+        StringArray::from(array_data)
+    }
+}
+
+impl fmt::Debug for StringArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StringArray\n[\n")?;
+        print_long_array(self, f, |array, index, f| {
+            fmt::Debug::fmt(&array.value(index), f)
+        })?;
+        write!(f, "]")
+    }
+}
+
+impl Array for StringArray {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.values.data()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        self.values.data_ref()
+    }
+}
+
+/// An array where each element is a binary of the same fixed width, given by
+/// `DataType::FixedSizeBinary`.
+pub struct FixedSizeBinaryArray {
+    data: ArrayDataRef,
+    value_data: RawPtrBox<u8>,
+    length: i32,
+}
+
+impl FixedSizeBinaryArray {
+    /// Returns the element at index `i` as a byte slice.
+    pub fn value(&self, i: usize) -> &[u8] {
+        assert!(
+            i < self.data.len(),
+            "FixedSizeBinaryArray out of bounds access"
+        );
+        let offset = i.checked_add(self.data.offset()).unwrap();
+        unsafe {
+            ::std::slice::from_raw_parts(
+                self.value_data.get().add(self.value_offset_at(offset)),
+                self.length as usize,
+            )
+        }
+    }
+
+    /// Returns the offset for the element at index `i`.
+    ///
+    /// Note this doesn't do any bound checking, for performance reason.
+    #[inline]
+    pub fn value_offset(&self, i: usize) -> i32 {
+        self.value_offset_at(self.data.offset() + i) as i32
+    }
+
+    /// Returns the length in bytes of each element of this array.
+    #[inline]
+    pub fn value_length(&self) -> i32 {
+        self.length
+    }
+
+    /// Returns a clone of the value data buffer
+    pub fn value_data(&self) -> Buffer {
+        self.data.buffers()[0].clone()
+    }
+
+    #[inline]
+    fn value_offset_at(&self, i: usize) -> usize {
+        self.length as usize * i
+    }
+}
+
+impl From<ArrayDataRef> for FixedSizeBinaryArray {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.buffers().len(),
+            1,
+            "FixedSizeBinaryArray data should contain 1 buffer only (values)"
+        );
+        let length = match data.data_type() {
+            DataType::FixedSizeBinary(len) => *len,
+            _ => panic!(
+                "FixedSizeBinaryArray data should contain a FixedSizeBinary data type"
+            ),
+        };
+        assert!(
+            length >= 0,
+            "FixedSizeBinaryArray byte width should not be negative, got {}",
+            length
+        );
+        assert!(
+            data.buffers()[0].len() >= (data.offset() + data.len()) * length as usize,
+            "FixedSizeBinaryArray data buffer is too short for its length"
+        );
+        let value_data = data.buffers()[0].raw_data();
+        Self {
+            data: data.clone(),
+            value_data: RawPtrBox::new(value_data),
+            length,
+        }
+    }
+}
+
+impl fmt::Debug for FixedSizeBinaryArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FixedSizeBinaryArray<{}>\n[\n", self.value_length())?;
+        print_long_array(self, f, |array, index, f| {
+            fmt::Debug::fmt(&array.value(index), f)
+        })?;
+        write!(f, "]")
+    }
+}
+
+impl Array for FixedSizeBinaryArray {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+/// An array of `DataType::Decimal(precision, scale)`, where each element is stored as
+/// its unscaled `i128` value in 16 little-endian bytes.
+pub struct DecimalArray {
+    data: ArrayDataRef,
+    value_data: RawPtrBox<u8>,
+    precision: usize,
+    scale: usize,
+}
+
+impl DecimalArray {
+    /// Returns the unscaled value of the element at index `i`, e.g. `15000` for `150.00`
+    /// in a `Decimal(6, 2)` array.
+    pub fn value(&self, i: usize) -> i128 {
+        assert!(i < self.data.len(), "DecimalArray out of bounds access");
+        let offset = (i + self.data.offset()) * 16;
+        let bytes = unsafe {
+            ::std::slice::from_raw_parts(self.value_data.get().add(offset), 16)
+        };
+        let mut value = [0u8; 16];
+        value.copy_from_slice(bytes);
+        i128::from_le_bytes(value)
+    }
+
+    /// Returns the element at index `i` formatted with `scale` fraction digits, e.g.
+    /// `"150.00"`
+    pub fn value_as_string(&self, i: usize) -> String {
+        decimal::format_decimal(self.value(i), self.scale)
+    }
+
+    /// Returns the precision of the elements of this array
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Returns the scale of the elements of this array
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+}
+
+impl From<ArrayDataRef> for DecimalArray {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.buffers().len(),
+            1,
+            "DecimalArray data should contain 1 buffer only (values)"
+        );
+        let (precision, scale) = match data.data_type() {
+            DataType::Decimal(precision, scale) => (*precision, *scale),
+            _ => panic!("DecimalArray data should contain a Decimal data type"),
+        };
+        assert!(
+            data.buffers()[0].len() >= (data.offset() + data.len()) * 16,
+            "DecimalArray data buffer is too short for its length"
+        );
+        let value_data = data.buffers()[0].raw_data();
+        Self {
+            data,
+            value_data: RawPtrBox::new(value_data),
+            precision,
+            scale,
+        }
+    }
+}
+
+impl fmt::Debug for DecimalArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DecimalArray<{}, {}>\n[\n", self.precision, self.scale)?;
+        print_long_array(self, f, |array, index, f| {
+            write!(f, "{}", array.value_as_string(index))
+        })?;
+        write!(f, "]")
+    }
+}
+
+impl Array for DecimalArray {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+/// An array of `DataType::Null`, where every element is null.
+///
+/// The array holds no buffers, not even a null bitmap, and its null count is its length.
+pub struct NullArray {
+    data: ArrayDataRef,
+}
+
+impl NullArray {
+    /// Creates an array of `length` nulls
+    pub fn new(length: usize) -> Self {
+        NullArray::from(ArrayData::builder(DataType::Null).len(length).build())
+    }
+}
+
+impl From<ArrayDataRef> for NullArray {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.data_type(),
+            &DataType::Null,
+            "NullArray data should contain a Null data type"
+        );
+        assert!(
+            data.buffers().is_empty(),
+            "NullArray data should not contain any buffers"
+        );
+        Self { data }
     }
 }
 
-impl fmt::Debug for BinaryArray {
+impl fmt::Debug for NullArray {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "BinaryArray\n[\n")?;
-        print_long_array(self, f, |array, index, f| {
-            fmt::Debug::fmt(&array.value(index), f)
-        })?;
-        write!(f, "]")
+        write!(f, "NullArray({})", self.len())
     }
 }
 
-impl Array for BinaryArray {
+impl Array for NullArray {
     fn as_any(&self) -> &Any {
         self
     }
@@ -1194,6 +1936,28 @@ impl StructArray {
             .position(|c| c == &column_name)
             .map(|pos| self.column(pos))
     }
+
+    /// Returns a validity bitmap of `self.len()` bits, derived from the children only:
+    /// a row is null where every child is null, and valid otherwise. Rows of a struct
+    /// without fields are valid.
+    ///
+    /// The validity of the struct itself is not taken into account, see
+    /// `compute::collapse_all_null_structs` for combining both.
+    pub fn validity_from_children(&self) -> Buffer {
+        let num_bytes = bit_util::ceil(self.len(), 8);
+        let mut buffer = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+        {
+            let slice = buffer.data_mut();
+            for i in 0..self.len() {
+                if self.boxed_fields.is_empty()
+                    || self.boxed_fields.iter().any(|child| child.is_valid(i))
+                {
+                    bit_util::set_bit(slice, i);
+                }
+            }
+        }
+        buffer.freeze()
+    }
 }
 
 impl From<ArrayDataRef> for StructArray {
@@ -1240,7 +2004,12 @@ impl From<Vec<(Field, ArrayRef)>> for StructArray {
             assert_eq!(
                 length,
                 field_values[i].len(),
-                "all child arrays of a StructArray must have the same length"
+                "all child arrays of a StructArray must have the same length, but field \
+                 '{}' has length {} and field '{}' has length {}",
+                field_types[0].name(),
+                length,
+                field_types[i].name(),
+                field_values[i].len()
             );
             assert_eq!(
                 field_types[i].data_type(),
@@ -1276,10 +2045,224 @@ impl fmt::Debug for StructArray {
     }
 }
 
+/// Writes the values of the struct array on a single line, e.g.
+/// `[{a: 1, b: "x"}, null]`
+impl fmt::Display for StructArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_values(self, f)
+    }
+}
+
+/// A dictionary array where each element is a single value indexed by an integer key.
+///
+/// The keys are stored like a primitive array of the key type `K`, and the distinct
+/// values are held in a child array. Null keys represent null elements.
+pub struct DictionaryArray<K: ArrowDictionaryKeyType> {
+    data: ArrayDataRef,
+    keys: PrimitiveArray<K>,
+    values: ArrayRef,
+    is_ordered: bool,
+}
+
+impl<K: ArrowDictionaryKeyType> DictionaryArray<K> {
+    /// Returns the array of keys of this dictionary array.
+    pub fn keys(&self) -> &PrimitiveArray<K> {
+        &self.keys
+    }
+
+    /// Returns the key of the element at index `i` as an index into `values()`, or
+    /// `None` if the element is null.
+    pub fn key(&self, i: usize) -> Option<usize> {
+        if self.keys.is_null(i) {
+            None
+        } else {
+            self.keys.value(i).to_usize()
+        }
+    }
+
+    /// Returns an `ArrayRef` to the dictionary values.
+    pub fn values(&self) -> ArrayRef {
+        self.values.clone()
+    }
+
+    /// Returns a clone of the value type of this dictionary.
+    pub fn value_type(&self) -> DataType {
+        self.values.data_ref().data_type().clone()
+    }
+
+    /// Returns whether the dictionary values are ordered.
+    pub fn is_ordered(&self) -> bool {
+        self.is_ordered
+    }
+
+    /// Returns an iterator over the decoded elements of this dictionary array, yielding
+    /// a reference into `values()` for each element, or `None` if the element is null.
+    ///
+    /// The values are looked up as the iterator advances, so no decoded array is built.
+    pub fn iter_decoded(&self) -> DecodedIter<K> {
+        DecodedIter {
+            array: self,
+            index: 0,
+        }
+    }
+}
+
+/// A reference to the value at `index()` of `array()`
+#[derive(Clone, Copy)]
+pub struct ValueRef<'a> {
+    array: &'a Array,
+    index: usize,
+}
+
+impl<'a> ValueRef<'a> {
+    /// Returns the array that holds the value.
+    pub fn array(&self) -> &'a Array {
+        self.array
+    }
+
+    /// Returns the index of the value in `array()`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<'a> fmt::Debug for ValueRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_value(self.array, self.index, f)
+    }
+}
+
+/// An iterator over the decoded elements of a `DictionaryArray`
+pub struct DecodedIter<'a, K: ArrowDictionaryKeyType> {
+    array: &'a DictionaryArray<K>,
+    index: usize,
+}
+
+impl<'a, K: ArrowDictionaryKeyType> Iterator for DecodedIter<'a, K> {
+    type Item = Option<ValueRef<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.array.len() {
+            return None;
+        }
+        let key = self.array.key(self.index);
+        self.index += 1;
+        Some(key.map(|index| ValueRef {
+            array: self.array.values.as_ref(),
+            index,
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.array.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, K: ArrowDictionaryKeyType> ExactSizeIterator for DecodedIter<'a, K> {}
+
+/// Constructs a `DictionaryArray` from an array data reference.
+impl<K: ArrowDictionaryKeyType> From<ArrayDataRef> for DictionaryArray<K> {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.buffers().len(),
+            1,
+            "DictionaryArray data should contain a single buffer only (keys)."
+        );
+        assert_eq!(
+            data.child_data().len(),
+            1,
+            "DictionaryArray should contain a single child array (values)."
+        );
+        match data.data_type() {
+            DataType::Dictionary(key_type, _) => assert_eq!(
+                key_type.as_ref(),
+                &K::get_data_type(),
+                "DictionaryArray's key type must match its data type."
+            ),
+            _ => panic!("DictionaryArray must have Dictionary data type."),
+        }
+
+        let mut keys_builder = ArrayData::builder(K::get_data_type())
+            .len(data.len())
+            .offset(data.offset())
+            .add_buffer(data.buffers()[0].clone());
+        if let Some(bitmap) = data.null_bitmap() {
+            keys_builder = keys_builder
+                .null_count(data.null_count())
+                .null_bit_buffer(bitmap.bits.clone());
+        }
+        let keys = PrimitiveArray::<K>::from(keys_builder.build());
+        let values = make_array(data.child_data()[0].clone());
+        Self {
+            data,
+            keys,
+            values,
+            is_ordered: false,
+        }
+    }
+}
+
+/// Constructs a `DictionaryArray` of strings, encoding each distinct string once.
+impl<'a, K: ArrowDictionaryKeyType> FromIterator<Option<&'a str>> for DictionaryArray<K> {
+    fn from_iter<I: IntoIterator<Item = Option<&'a str>>>(iter: I) -> Self {
+        let it = iter.into_iter();
+        let (lower, _) = it.size_hint();
+        let keys_builder = PrimitiveBuilder::<K>::new(lower);
+        let values_builder = BinaryBuilder::new(256);
+        let mut builder = StringDictionaryBuilder::new(keys_builder, values_builder);
+        for value in it {
+            if let Some(value) = value {
+                builder
+                    .append(value)
+                    .expect("Unable to append a value to a dictionary array.");
+            } else {
+                builder
+                    .append_null()
+                    .expect("Unable to append a null value to a dictionary array.");
+            }
+        }
+        builder.finish()
+    }
+}
+
+/// Constructs a `DictionaryArray` of non-null strings, encoding each distinct string
+/// once.
+impl<'a, K: ArrowDictionaryKeyType> FromIterator<&'a str> for DictionaryArray<K> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        iter.into_iter().map(Some).collect()
+    }
+}
+
+impl<K: ArrowDictionaryKeyType> Array for DictionaryArray<K> {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+impl<K: ArrowDictionaryKeyType> fmt::Debug for DictionaryArray<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DictionaryArray {{keys: {:?} values: {:?}}}",
+            self.keys, self.values
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::convert::TryFrom;
     use std::sync::Arc;
     use std::thread;
 
@@ -1293,7 +2276,8 @@ mod tests {
         let buf2 = buf.clone();
         let arr = Int32Array::new(5, buf, 0, 0);
         let slice = unsafe { ::std::slice::from_raw_parts(arr.raw_values(), 5) };
-        assert_eq!(buf2, arr.values());
+        assert_eq!(buf2, arr.data().buffers()[0]);
+        assert_eq!(&[0, 1, 2, 3, 4], arr.values_slice());
         assert_eq!(&[0, 1, 2, 3, 4], slice);
         assert_eq!(5, arr.len());
         assert_eq!(0, arr.offset());
@@ -1324,6 +2308,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_combine_null_bitmaps() {
+        let a = Int32Array::from(vec![Some(0), None, Some(2), Some(3), None]);
+        let b = Int32Array::from(vec![Some(0), Some(1), None, Some(3), None]);
+        let c = Int32Array::from(vec![0, 1, 2, 3, 4]);
+        assert!(c.null_bitmap().is_none());
+
+        let combined = (&a.null_bitmap().unwrap() & &b.null_bitmap().unwrap()).unwrap();
+        assert!(combined.is_set(0));
+        assert!(!combined.is_set(1));
+        assert!(!combined.is_set(2));
+        assert!(combined.is_set(3));
+        assert!(!combined.is_set(4));
+    }
+
     #[test]
     fn test_date64_array_from_vec_option() {
         // Test building a primitive array with null values
@@ -1434,6 +2433,17 @@ mod tests {
         assert_eq!(6, int_arr.value(2));
     }
 
+    #[test]
+    fn test_primitive_array_values() {
+        let arr = Int32Array::from(vec![Some(0), None, Some(2), Some(3), Some(4), None]);
+        let sliced = arr.slice(2, 3);
+        let sliced = sliced.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(&[2, 3, 4], sliced.values_slice());
+        assert_eq!(6, arr.values_slice().len());
+        // unlike `values_slice`, `values` returns the whole buffer
+        assert_eq!(arr.data().buffers()[0], sliced.values());
+    }
+
     #[test]
     fn test_value_slice_no_bounds_check() {
         let arr = Int32Array::from(vec![2, 3, 4]);
@@ -1466,7 +2476,7 @@ mod tests {
     #[test]
     fn test_boolean_fmt_debug() {
         let buf = Buffer::from(&[true, false, false].to_byte_slice());
-        let arr = BooleanArray::new(3, buf, 0, 0);
+        let arr = BooleanArray::new(3, buf, None, 0);
         assert_eq!(
             "PrimitiveArray<Boolean>\n[\n  true,\n  false,\n  false,\n]",
             format!("{:?}", arr)
@@ -1525,7 +2535,7 @@ mod tests {
             .add_buffer(buf)
             .build();
         let arr = Int32Array::from(data);
-        assert_eq!(buf2, arr.values());
+        assert_eq!(buf2, arr.data().buffers()[0]);
         assert_eq!(5, arr.len());
         assert_eq!(0, arr.null_count());
         for i in 0..3 {
@@ -1546,7 +2556,7 @@ mod tests {
         // 00000010 01001000
         let buf = Buffer::from([72_u8, 2_u8]);
         let buf2 = buf.clone();
-        let arr = BooleanArray::new(10, buf, 0, 0);
+        let arr = BooleanArray::new(10, buf, None, 0);
         assert_eq!(buf2, arr.values());
         assert_eq!(10, arr.len());
         assert_eq!(0, arr.offset());
@@ -1558,6 +2568,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_boolean_array_new_from_packed_buffers() {
+        // bits are read least significant first, starting at bit 1
+        let values = Buffer::from([0b0110_1101_u8, 0b0000_0001]);
+        let validity = Buffer::from([0b1111_0111_u8, 0b0000_0000]);
+        let arr = BooleanArray::new(8, values, Some(validity), 1);
+        assert_eq!(8, arr.len());
+        assert_eq!(1, arr.offset());
+        assert_eq!(2, arr.null_count());
+        let expected = vec![
+            Some(false),
+            Some(true),
+            None,
+            Some(false),
+            Some(true),
+            Some(true),
+            Some(false),
+            None,
+        ];
+        for (i, expected) in expected.into_iter().enumerate() {
+            match expected {
+                Some(v) => {
+                    assert!(arr.is_valid(i), "expected a value at {}", i);
+                    assert_eq!(v, arr.value(i), "failed at {}", i);
+                }
+                None => assert!(arr.is_null(i), "expected a null at {}", i),
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "BooleanArray values buffer of 1 byte(s) is too short for 9 bit(s)"
+    )]
+    fn test_boolean_array_new_short_buffer() {
+        BooleanArray::new(9, Buffer::from([0_u8]), None, 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "BooleanArray null bit buffer of 1 byte(s) is too short for 10 bit(s)"
+    )]
+    fn test_boolean_array_new_short_null_bit_buffer() {
+        BooleanArray::new(8, Buffer::from([0_u8, 0]), Some(Buffer::from([0_u8])), 2);
+    }
+
     #[test]
     fn test_boolean_array_from_vec() {
         let buf = Buffer::from([10_u8]);
@@ -1738,6 +2794,46 @@ mod tests {
         FixedSizeListArray::from(list_data);
     }
 
+    #[test]
+    fn test_fixed_size_binary_array() {
+        let data = ArrayData::builder(DataType::FixedSizeBinary(2))
+            .len(3)
+            .add_buffer(Buffer::from(&[1u8, 2, 3, 4, 5, 6][..]))
+            .build();
+        let array = FixedSizeBinaryArray::from(data);
+        assert_eq!(2, array.value_length());
+        assert_eq!(&[3u8, 4], array.value(1));
+        assert_eq!(4, array.value_offset(2));
+
+        let sliced = array.slice(1, 2);
+        let sliced = sliced
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+        assert_eq!(&[5u8, 6], sliced.value(1));
+        assert_eq!(4, sliced.value_offset(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedSizeBinaryArray data buffer is too short")]
+    fn test_fixed_size_binary_array_short_buffer() {
+        let data = ArrayData::builder(DataType::FixedSizeBinary(2))
+            .len(3)
+            .add_buffer(Buffer::from(&[1u8, 2, 3, 4, 5][..]))
+            .build();
+        FixedSizeBinaryArray::from(data);
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedSizeBinaryArray byte width should not be negative")]
+    fn test_fixed_size_binary_array_negative_width() {
+        let data = ArrayData::builder(DataType::FixedSizeBinary(-1))
+            .len(0)
+            .add_buffer(Buffer::from(&[0u8; 0][..]))
+            .build();
+        FixedSizeBinaryArray::from(data);
+    }
+
     #[test]
     fn test_list_array_slice() {
         // Construct a value array
@@ -1802,6 +2898,59 @@ mod tests {
         assert_eq!(3, sliced_list_array.value_length(5));
     }
 
+    #[test]
+    fn test_binary_array_slice() {
+        let array =
+            BinaryArray::try_from(vec![Some("hello"), None, Some("arrow"), Some("!")])
+                .unwrap();
+        let sliced = array.slice(2, 2);
+        let sliced = sliced.as_any().downcast_ref::<BinaryArray>().unwrap();
+
+        // the buffers are shared, only the logical window moves
+        assert_eq!(2, sliced.len());
+        assert_eq!(2, sliced.offset());
+        assert_eq!(array.value_offsets(), sliced.value_offsets());
+        assert_eq!(array.value_data(), sliced.value_data());
+        assert_eq!(b"arrow", sliced.value(0));
+        assert_eq!(5, sliced.value_offset(0));
+        assert_eq!(5, sliced.value_length(0));
+        assert_eq!(b"!", sliced.value(1));
+        assert_eq!(0, sliced.null_count());
+
+        // slicing a slice adds up the offsets
+        let sliced = sliced.slice(1, 1);
+        let sliced = sliced.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(3, sliced.offset());
+        assert_eq!("!", sliced.get_string(0));
+
+        let sliced = array.slice(1, 2);
+        assert!(sliced.is_null(0));
+        assert_eq!(1, sliced.null_count());
+    }
+
+    #[test]
+    fn test_list_array_slice_value() {
+        let mut builder = ListBuilder::new(Int32Builder::new(8));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_slice(&[3]).unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_slice(&[4, 5, 6]).unwrap();
+        builder.append(true).unwrap();
+        let array = builder.finish();
+
+        let sliced = array.slice(1, 2);
+        let sliced = sliced.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(2, sliced.len());
+        assert_eq!(array.values().data(), sliced.values().data());
+        let first = sliced.value(0);
+        let first = first.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(&[3], first.value_slice(0, 1));
+        let second = sliced.value(1);
+        let second = second.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(&[4, 5, 6], second.value_slice(0, 3));
+    }
+
     #[test]
     fn test_fixed_size_list_array_slice() {
         // Construct a value array
@@ -2092,6 +3241,80 @@ mod tests {
         binary_array.value(4);
     }
 
+    #[test]
+    fn test_string_array() {
+        let array = StringArray::from(vec![Some("hello"), None, Some(""), Some("arrow")]);
+        assert_eq!(4, array.len());
+        assert_eq!(1, array.null_count());
+        assert_eq!("hello", array.value(0));
+        assert!(array.is_null(1));
+        assert_eq!("", array.value(2));
+        assert_eq!(0, array.value_length(2));
+        assert_eq!("arrow", array.value(3));
+        assert_eq!(5, array.value_offset(3));
+        assert_eq!(b"hello", as_binary_array(&array).value(0));
+        assert_eq!("hello", array_value_to_string(&array, 0));
+        assert_eq!("null", array_value_to_string(&array, 1));
+
+        let sliced = array.slice(3, 1);
+        let sliced = StringArray::from(sliced.data());
+        assert_eq!("arrow", sliced.value(0));
+
+        // views the data of a `BinaryArray` created from `Utf8` data
+        let binary = BinaryArray::from(vec!["a", "bc"]);
+        let array = StringArray::from(binary.data());
+        assert_eq!("bc", array.value(1));
+    }
+
+    #[test]
+    fn test_string_array_invalid_utf8() {
+        let array_data = ArrayData::builder(DataType::Utf8)
+            .len(2)
+            .add_buffer(Buffer::from([0i32, 1, 3].to_byte_slice()))
+            .add_buffer(Buffer::from(&[b'a', 0xc3, 0x28]))
+            .build();
+        match StringArray::try_from_data(array_data) {
+            Err(ArrowError::InvalidArgumentError(message)) => assert!(
+                message.starts_with("Value at index 1 of StringArray is not valid UTF-8"),
+                message
+            ),
+            _ => panic!("expected invalid UTF-8 to be rejected"),
+        }
+
+        let binary = BinaryArray::from(vec!["a", "bc"]);
+        assert!(StringArray::try_from_data(binary.data()).is_ok());
+        let int = Int32Array::from(vec![1]);
+        assert!(StringArray::try_from_data(int.data()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "StringArray can only be created from Utf8 data")]
+    fn test_string_array_from_incorrect_data_type() {
+        let array_data = ArrayData::builder(DataType::Int32)
+            .len(1)
+            .add_buffer(Buffer::from(&[0u8; 4]))
+            .build();
+        StringArray::from(array_data);
+    }
+
+    #[test]
+    fn test_null_array() {
+        let array = NullArray::new(4);
+        assert_eq!(&DataType::Null, array.data_type());
+        assert_eq!(4, array.len());
+        assert_eq!(4, array.null_count());
+        assert!(array.data().buffers().is_empty());
+        assert!(array.data().null_bitmap().is_none());
+        assert!((0..4).all(|i| array.is_null(i) && !array.is_valid(i)));
+        assert_eq!("NullArray(4)", format!("{:?}", array));
+
+        let slice = array.slice(1, 2);
+        assert_eq!(2, slice.len());
+        assert_eq!(2, slice.null_count());
+        assert!(slice.as_any().downcast_ref::<NullArray>().is_some());
+        assert!(slice.is_null(1));
+    }
+
     #[test]
     fn test_struct_array_builder() {
         let boolean_data = ArrayData::builder(DataType::Boolean)
@@ -2144,6 +3367,50 @@ mod tests {
         assert_eq!(0, struct_array.offset());
     }
 
+    #[test]
+    fn test_nested_array_display() {
+        // the "lists" and "structs" columns of test/data/integration.json
+        let mut builder = ListBuilder::new(Int32Builder::new(4));
+        builder.values().append_null().unwrap();
+        builder.values().append_value(2).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        let lists = builder.finish();
+        assert_eq!("[[null, 2, null], [null], null]", format!("{}", lists));
+
+        let int32s = Int32Array::from(vec![None, Some(-2), None]);
+        let utf8s = BinaryArray::try_from(vec![None, None, Some("aaaaaa")]).unwrap();
+        let fields = vec![
+            Field::new("int32s", DataType::Int32, true),
+            Field::new("utf8s", DataType::Utf8, true),
+        ];
+        let structs = StructArray::from(
+            ArrayData::builder(DataType::Struct(fields))
+                .len(3)
+                .null_bit_buffer(Buffer::from([0b011_u8]))
+                .add_child_data(int32s.data())
+                .add_child_data(utf8s.data())
+                .build(),
+        );
+        assert_eq!(
+            "[{int32s: null, utf8s: null}, {int32s: -2, utf8s: null}, null]",
+            format!("{}", structs)
+        );
+        let sliced = structs.slice(1, 2);
+        let sliced = sliced.as_any().downcast_ref::<StructArray>().unwrap();
+        assert_eq!("[{int32s: -2, utf8s: null}, null]", format!("{}", sliced));
+
+        let mut builder = ListBuilder::new(ListBuilder::new(BinaryBuilder::new(2)));
+        builder.values().values().append_string("a").unwrap();
+        builder.values().append(true).unwrap();
+        builder.values().append(true).unwrap();
+        builder.append(true).unwrap();
+        assert_eq!("[[[\"a\"], []]]", format!("{}", builder.finish()));
+    }
+
     #[test]
     #[should_panic(
         expected = "the field data types must match the array data in a StructArray"
@@ -2265,6 +3532,39 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_struct_array_validity_from_children() {
+        let structs = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(1), None, None])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![None, None, Some(3)])) as ArrayRef,
+            ),
+        ]);
+        assert_eq!(Buffer::from([0b101]), structs.validity_from_children());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "all child arrays of a StructArray must have the same length, but \
+                    field 'a' has length 3 and field 'b' has length 2"
+    )]
+    fn test_struct_child_array_length_mismatch_names_fields() {
+        StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![4, 5])) as ArrayRef,
+            ),
+        ]);
+    }
+
     #[test]
     #[should_panic(expected = "memory is not aligned")]
     fn test_primitive_array_alignment() {
@@ -2311,6 +3611,89 @@ mod tests {
         BinaryArray::from(array_data);
     }
 
+    #[test]
+    fn test_dictionary_array() {
+        // values: ["a", "bc"], keys: [0, null, 1, 1, 0]
+        let value_data = BinaryArray::from(vec!["a", "bc"]).data();
+        let keys = Int16Array::from(vec![Some(0), None, Some(1), Some(1), Some(0)]);
+        let dict_data = ArrayData::builder(DataType::Dictionary(
+            Box::new(DataType::Int16),
+            Box::new(DataType::Utf8),
+        ))
+        .len(5)
+        .add_buffer(keys.data().buffers()[0].clone())
+        .null_bit_buffer(keys.data().null_bitmap().clone().unwrap().bits)
+        .add_child_data(value_data)
+        .build();
+        let dict_array = Int16DictionaryArray::from(dict_data);
+
+        assert_eq!(5, dict_array.len());
+        assert_eq!(1, dict_array.null_count());
+        assert_eq!(DataType::Utf8, dict_array.value_type());
+        assert_eq!(2, dict_array.values().len());
+        assert_eq!(Some(0), dict_array.key(0));
+        assert!(dict_array.is_null(1));
+        assert_eq!(None, dict_array.key(1));
+        assert_eq!(Some(1), dict_array.key(2));
+
+        // the keys are sliced along with the array
+        let sliced = dict_array.slice(2, 3);
+        let sliced = sliced
+            .as_any()
+            .downcast_ref::<Int16DictionaryArray>()
+            .unwrap();
+        assert_eq!(3, sliced.len());
+        assert_eq!(0, sliced.null_count());
+        assert_eq!(Some(1), sliced.key(0));
+        assert_eq!(Some(0), sliced.key(2));
+    }
+
+    #[test]
+    fn test_dictionary_array_from_iter() {
+        let array: Int8DictionaryArray = vec!["a", "b", "a", "c"].into_iter().collect();
+        assert_eq!(4, array.len());
+        assert_eq!(3, array.values().len());
+        assert_eq!(&[0, 1, 0, 2], array.keys().value_slice(0, 4));
+    }
+
+    #[test]
+    fn test_dictionary_array_iter_decoded() {
+        let array: Int8DictionaryArray = vec![Some("a"), None, Some("b"), Some("a")]
+            .into_iter()
+            .collect();
+        let decode = |array: &Int8DictionaryArray| -> Vec<Option<String>> {
+            array
+                .iter_decoded()
+                .map(|v| {
+                    v.map(|v| {
+                        v.array()
+                            .as_any()
+                            .downcast_ref::<BinaryArray>()
+                            .unwrap()
+                            .get_string(v.index())
+                    })
+                })
+                .collect()
+        };
+        assert_eq!(4, array.iter_decoded().len());
+        assert_eq!(
+            vec![
+                Some("a".to_string()),
+                None,
+                Some("b".to_string()),
+                Some("a".to_string())
+            ],
+            decode(&array)
+        );
+
+        let sliced = array.slice(1, 2);
+        let sliced = sliced
+            .as_any()
+            .downcast_ref::<Int8DictionaryArray>()
+            .unwrap();
+        assert_eq!(vec![None, Some("b".to_string())], decode(sliced));
+    }
+
     #[test]
     fn test_access_array_concurrently() {
         let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
@@ -2319,4 +3702,119 @@ mod tests {
         assert!(ret.is_ok());
         assert_eq!(8, ret.ok().unwrap());
     }
+
+    #[test]
+    fn test_array_downcast() {
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        assert_eq!(2, array.downcast::<Int32Array>().unwrap().value(1));
+        match array.downcast::<Float64Array>() {
+            Err(ArrowError::CastError(msg)) => {
+                assert!(msg.starts_with("Cannot downcast an array of type Int32 to "));
+                assert!(msg.contains("Float64Type"));
+            }
+            _ => panic!("expected downcasting an Int32Array to Float64Array to fail"),
+        }
+    }
+
+    /// Asserts that `array` equals the array created from its array data, both
+    /// directly and through `make_array`, and that slices of it do too
+    fn assert_array_data_roundtrip<T>(array: &T)
+    where
+        T: Array + From<ArrayDataRef>,
+    {
+        let from_data = T::from(array.data());
+        assert_eq!(array.data(), from_data.data());
+        assert!(array.equals(&from_data));
+        assert!(from_data.equals(array));
+        assert!(array.equals(make_array(array.data()).as_ref()));
+
+        if array.len() > 1 {
+            let sliced = array.slice(1, array.len() - 1);
+            let from_data = T::from(sliced.data());
+            assert!(sliced.equals(&from_data));
+            assert!(from_data.equals(sliced.as_ref()));
+            assert!(array.range_equals(&from_data, 1, array.len(), 0));
+        }
+    }
+
+    #[test]
+    fn test_array_data_roundtrip() {
+        assert_array_data_roundtrip(&Int32Array::from(vec![Some(1), None, Some(3)]));
+        assert_array_data_roundtrip(&Float64Array::from(vec![None, Some(2.5), None]));
+        assert_array_data_roundtrip(&BooleanArray::from(vec![
+            Some(true),
+            None,
+            Some(false),
+        ]));
+        assert_array_data_roundtrip(&Date32Array::from(vec![1, 2, 3]));
+        assert_array_data_roundtrip(
+            &BinaryArray::try_from(vec![Some("hello"), None, Some(""), Some("arrow")])
+                .unwrap(),
+        );
+
+        let int8_dict: Int8DictionaryArray = vec![Some("a"), None, Some("b"), Some("a")]
+            .into_iter()
+            .collect();
+        assert_array_data_roundtrip(&int8_dict);
+
+        let value_data = ArrayData::builder(DataType::Int32)
+            .len(6)
+            .add_buffer(Buffer::from(&[0, 1, 2, 3, 4, 5].to_byte_slice()))
+            .build();
+        let fixed_size_list_data =
+            ArrayData::builder(DataType::FixedSizeList((Box::new(DataType::Int32), 2)))
+                .len(3)
+                .null_bit_buffer(Buffer::from([0b101]))
+                .add_child_data(value_data)
+                .build();
+        assert_array_data_roundtrip(&FixedSizeListArray::from(fixed_size_list_data));
+    }
+
+    #[test]
+    fn test_nested_array_data_roundtrip() {
+        // a list of lists with null inner lists
+        let mut builder = ListBuilder::new(ListBuilder::new(Int32Builder::new(8)));
+        builder.values().values().append_value(1).unwrap();
+        builder.values().append(true).unwrap();
+        builder.values().append(false).unwrap();
+        builder.values().values().append_value(2).unwrap();
+        builder.values().values().append_null().unwrap();
+        builder.values().append(true).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append(false).unwrap();
+        builder.values().values().append_value(3).unwrap();
+        builder.values().append(true).unwrap();
+        builder.append(true).unwrap();
+        assert_array_data_roundtrip(&builder.finish());
+
+        let strings: ArrayRef = Arc::new(
+            BinaryArray::try_from(vec![Some("a"), None, Some("c"), Some("d")]).unwrap(),
+        );
+        let ints: ArrayRef =
+            Arc::new(Int32Array::from(vec![None, Some(2), None, Some(4)]));
+        let struct_data = ArrayData::builder(DataType::Struct(vec![
+            Field::new("strings", DataType::Utf8, true),
+            Field::new("ints", DataType::Int32, true),
+        ]))
+        .len(4)
+        .null_bit_buffer(Buffer::from([0b1011]))
+        .add_child_data(strings.data())
+        .add_child_data(ints.data())
+        .build();
+        let structs = StructArray::from(struct_data);
+        assert_array_data_roundtrip(&structs);
+
+        // a list of structs
+        let offsets = Buffer::from(&[0, 2, 2, 4].to_byte_slice());
+        let list_data = ArrayData::builder(DataType::List(Box::new(
+            structs.data().data_type().clone(),
+        )))
+        .len(3)
+        .null_bit_buffer(Buffer::from([0b101]))
+        .add_buffer(offsets)
+        .add_child_data(structs.data())
+        .build();
+        assert_array_data_roundtrip(&ListArray::from(list_data));
+    }
 }
@@ -19,16 +19,20 @@
 //! internal buffer in an `ArrayData` object.
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::io::Write;
 use std::marker::PhantomData;
 use std::mem;
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+
 use crate::array::*;
 use crate::buffer::{Buffer, MutableBuffer};
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
 use crate::util::bit_util;
+use crate::util::decimal::{self, DecimalRounding};
 
 /// Buffer builder with zero-copy build method
 pub struct BufferBuilder<T: ArrowPrimitiveType> {
@@ -207,7 +211,79 @@ impl BufferBuilderTrait<BooleanType> for BufferBuilder<BooleanType> {
     }
 }
 
+/// Finishes a validity bitmap builder holding `len` slots, counting its nulls once.
+///
+/// Returns the null count together with the validity buffer, or `None` in place of the
+/// buffer if every slot is valid, so that the built array carries no validity buffer.
+fn finish_null_bitmap(
+    bitmap_builder: &mut BooleanBufferBuilder,
+    len: usize,
+) -> (usize, Option<Buffer>) {
+    let null_bit_buffer = bitmap_builder.finish();
+    let null_count = len - bit_util::count_set_bits(null_bit_buffer.data());
+    if null_count > 0 {
+        (null_count, Some(null_bit_buffer))
+    } else {
+        (0, None)
+    }
+}
+
+/// Returns the error of appending a null into a builder that isn't nullable
+fn null_not_allowed() -> ArrowError {
+    ArrowError::InvalidArgumentError(
+        "Cannot append a null into the builder of a non-nullable field".to_string(),
+    )
+}
+
+/// Builder of the bit-packed data and validity buffers of a boolean array, which sets
+/// the data bit and the validity bit of each slot together.
+///
+/// This suits kernels that compute the value and the validity of each slot at once,
+/// e.g. boolean kernels with Kleene logic where a null input may give a valid output,
+/// and can build their result with `BooleanArray::new`.
+pub struct NullableBooleanBufferBuilder {
+    values_builder: BooleanBufferBuilder,
+    bitmap_builder: BooleanBufferBuilder,
+}
+
+impl NullableBooleanBufferBuilder {
+    /// Creates a builder with a fixed initial capacity
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            values_builder: BooleanBufferBuilder::new(capacity),
+            bitmap_builder: BooleanBufferBuilder::new(capacity),
+        }
+    }
+
+    /// Returns the number of slots in the builder
+    pub fn len(&self) -> usize {
+        self.bitmap_builder.len()
+    }
+
+    /// Appends a slot, which is null if `value` is `None`
+    pub fn append(&mut self, value: Option<bool>) -> Result<()> {
+        self.values_builder.append(value.unwrap_or(false))?;
+        self.bitmap_builder.append(value.is_some())
+    }
+
+    /// Resets the builder, returning the data buffer and the validity buffer, or `None`
+    /// in place of the validity buffer if every slot is valid
+    pub fn finish(&mut self) -> (Buffer, Option<Buffer>) {
+        let len = self.len();
+        let values = self.values_builder.finish();
+        let (_, null_bit_buffer) = finish_null_bitmap(&mut self.bitmap_builder, len);
+        (values, null_bit_buffer)
+    }
+}
+
 /// Trait for dealing with different array builders at runtime
+///
+/// Every builder that can append a null has a `with_nullable` option, and returns an
+/// `InvalidArgumentError` from any method that would append a null into a builder
+/// that isn't nullable: `append_null` and `append_option` of `PrimitiveBuilder`,
+/// `append(false)` and `append_null` of the list, binary and struct builders, and
+/// `append_null` of `DecimalBuilder` and `StringDictionaryBuilder`. Methods that only
+/// append valid values, such as `append_value` and `append_slice`, need no check.
 pub trait ArrayBuilder: Any {
     /// Returns the number of array slots in the builder
     fn len(&self) -> usize;
@@ -237,6 +313,7 @@ pub trait ArrayBuilder: Any {
 pub struct PrimitiveBuilder<T: ArrowPrimitiveType> {
     values_builder: BufferBuilder<T>,
     bitmap_builder: BooleanBufferBuilder,
+    nullable: bool,
 }
 
 impl<T: ArrowPrimitiveType> ArrayBuilder for PrimitiveBuilder<T> {
@@ -272,9 +349,18 @@ impl<T: ArrowPrimitiveType> PrimitiveBuilder<T> {
         Self {
             values_builder: BufferBuilder::<T>::new(capacity),
             bitmap_builder: BooleanBufferBuilder::new(capacity),
+            nullable: true,
         }
     }
 
+    /// Sets whether nulls can be appended into the builder, e.g. to the nullability of
+    /// the field the array is built for. Appending a null into a builder that isn't
+    /// nullable returns an error.
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
     /// Returns the capacity of this builder measured in slots of type `T`
     pub fn capacity(&self) -> usize {
         self.values_builder.capacity()
@@ -289,6 +375,9 @@ impl<T: ArrowPrimitiveType> PrimitiveBuilder<T> {
 
     /// Appends a null slot into the builder
     pub fn append_null(&mut self) -> Result<()> {
+        if !self.nullable {
+            return Err(null_not_allowed());
+        }
         self.bitmap_builder.append(false)?;
         self.values_builder.advance(1)?;
         Ok(())
@@ -313,27 +402,68 @@ impl<T: ArrowPrimitiveType> PrimitiveBuilder<T> {
     /// Builds the `PrimitiveArray` and reset this builder.
     pub fn finish(&mut self) -> PrimitiveArray<T> {
         let len = self.len();
-        let null_bit_buffer = self.bitmap_builder.finish();
-        let null_count = len - bit_util::count_set_bits(null_bit_buffer.data());
+        let (null_count, null_bit_buffer) =
+            finish_null_bitmap(&mut self.bitmap_builder, len);
         let mut builder = ArrayData::builder(T::get_data_type())
             .len(len)
+            .null_count(null_count)
             .add_buffer(self.values_builder.finish());
-        if null_count > 0 {
-            builder = builder
-                .null_count(null_count)
-                .null_bit_buffer(null_bit_buffer);
+        if let Some(null_bit_buffer) = null_bit_buffer {
+            builder = builder.null_bit_buffer(null_bit_buffer);
         }
         let data = builder.build();
         PrimitiveArray::<T>::from(data)
     }
 }
 
+impl<T: ArrowTimestampType> PrimitiveBuilder<T> {
+    /// Appends a chrono `DateTime` into the builder, converted to the time unit of `T`
+    ///
+    /// Sub-unit precision is truncated, e.g. microseconds are dropped when appending into
+    /// a millisecond timestamp builder. Returns an error if the timestamp can not be
+    /// represented in the time unit of `T`.
+    pub fn append_datetime(&mut self, datetime: DateTime<Utc>) -> Result<()> {
+        let (multiplier, nanos_divisor) = match T::get_time_unit() {
+            TimeUnit::Second => (1, 1_000_000_000),
+            TimeUnit::Millisecond => (1_000, 1_000_000),
+            TimeUnit::Microsecond => (1_000_000, 1_000),
+            TimeUnit::Nanosecond => (1_000_000_000, 1),
+        };
+        let subsec = i64::from(datetime.timestamp_subsec_nanos()) / nanos_divisor;
+        let value = datetime
+            .timestamp()
+            .checked_mul(multiplier)
+            .and_then(|v| v.checked_add(subsec))
+            .ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!(
+                    "{} is out of range for a {:?} timestamp",
+                    datetime,
+                    T::get_time_unit()
+                ))
+            })?;
+        self.append_value(value)
+    }
+
+    /// Parses an RFC 3339 date and time string (e.g. `2019-10-01T12:34:56.789Z`) and
+    /// appends it into the builder, as `append_datetime` does
+    pub fn append_rfc3339(&mut self, s: &str) -> Result<()> {
+        let datetime = DateTime::parse_from_rfc3339(s).map_err(|e| {
+            ArrowError::ParseError(format!(
+                "Error parsing '{}' as an RFC 3339 timestamp: {}",
+                s, e
+            ))
+        })?;
+        self.append_datetime(datetime.with_timezone(&Utc))
+    }
+}
+
 ///  Array builder for `ListArray`
 pub struct ListBuilder<T: ArrayBuilder> {
     offsets_builder: Int32BufferBuilder,
     bitmap_builder: BooleanBufferBuilder,
     values_builder: T,
     len: usize,
+    nullable: bool,
 }
 
 impl<T: ArrayBuilder> ListBuilder<T> {
@@ -346,8 +476,16 @@ impl<T: ArrayBuilder> ListBuilder<T> {
             bitmap_builder: BooleanBufferBuilder::new(values_builder.len()),
             values_builder,
             len: 0,
+            nullable: true,
         }
     }
+
+    /// Sets whether null lists can be appended into the builder. Appending a null list
+    /// into a builder that isn't nullable returns an error.
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
 }
 
 impl<T: ArrayBuilder> ArrayBuilder for ListBuilder<T>
@@ -394,6 +532,9 @@ where
 
     /// Finish the current variable-length list array slot
     pub fn append(&mut self, is_valid: bool) -> Result<()> {
+        if !is_valid && !self.nullable {
+            return Err(null_not_allowed());
+        }
         self.offsets_builder
             .append(self.values_builder.len() as i32)?;
         self.bitmap_builder.append(is_valid)?;
@@ -414,18 +555,20 @@ where
         let values_data = values_arr.data();
 
         let offset_buffer = self.offsets_builder.finish();
-        let null_bit_buffer = self.bitmap_builder.finish();
+        let (null_count, null_bit_buffer) =
+            finish_null_bitmap(&mut self.bitmap_builder, len);
         self.offsets_builder.append(0).unwrap();
-        let data =
+        let mut builder =
             ArrayData::builder(DataType::List(Box::new(values_data.data_type().clone())))
                 .len(len)
-                .null_count(len - bit_util::count_set_bits(null_bit_buffer.data()))
+                .null_count(null_count)
                 .add_buffer(offset_buffer)
-                .add_child_data(values_data)
-                .null_bit_buffer(null_bit_buffer)
-                .build();
+                .add_child_data(values_data);
+        if let Some(null_bit_buffer) = null_bit_buffer {
+            builder = builder.null_bit_buffer(null_bit_buffer);
+        }
 
-        ListArray::from(data)
+        ListArray::from(builder.build())
     }
 }
 
@@ -435,6 +578,7 @@ pub struct FixedSizeListBuilder<T: ArrayBuilder> {
     values_builder: T,
     len: usize,
     list_len: i32,
+    nullable: bool,
 }
 
 impl<T: ArrayBuilder> FixedSizeListBuilder<T> {
@@ -447,8 +591,16 @@ impl<T: ArrayBuilder> FixedSizeListBuilder<T> {
             values_builder,
             len: 0,
             list_len: length,
+            nullable: true,
         }
     }
+
+    /// Sets whether null lists can be appended into the builder. Appending a null list
+    /// into a builder that isn't nullable returns an error.
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
 }
 
 impl<T: ArrayBuilder> ArrayBuilder for FixedSizeListBuilder<T>
@@ -499,6 +651,9 @@ where
 
     /// Finish the current variable-length list array slot
     pub fn append(&mut self, is_valid: bool) -> Result<()> {
+        if !is_valid && !self.nullable {
+            return Err(null_not_allowed());
+        }
         self.bitmap_builder.append(is_valid)?;
         self.len += 1;
         Ok(())
@@ -524,18 +679,20 @@ where
             len
         );
 
-        let null_bit_buffer = self.bitmap_builder.finish();
-        let data = ArrayData::builder(DataType::FixedSizeList((
+        let (null_count, null_bit_buffer) =
+            finish_null_bitmap(&mut self.bitmap_builder, len);
+        let mut builder = ArrayData::builder(DataType::FixedSizeList((
             Box::new(values_data.data_type().clone()),
             self.list_len,
         )))
         .len(len)
-        .null_count(len - bit_util::count_set_bits(null_bit_buffer.data()))
-        .add_child_data(values_data)
-        .null_bit_buffer(null_bit_buffer)
-        .build();
+        .null_count(null_count)
+        .add_child_data(values_data);
+        if let Some(null_bit_buffer) = null_bit_buffer {
+            builder = builder.null_bit_buffer(null_bit_buffer);
+        }
 
-        FixedSizeListArray::from(data)
+        FixedSizeListArray::from(builder.build())
     }
 }
 
@@ -581,6 +738,14 @@ impl BinaryBuilder {
         }
     }
 
+    /// Sets whether nulls can be appended into the builder. Appending a null into a
+    /// builder that isn't nullable returns an error.
+    pub fn with_nullable(self, nullable: bool) -> Self {
+        Self {
+            builder: self.builder.with_nullable(nullable),
+        }
+    }
+
     /// Appends a single byte value into the builder's values array.
     ///
     /// Note, when appending individual byte values you must call `append` to delimit each
@@ -626,6 +791,224 @@ impl BinaryBuilder {
     }
 }
 
+///  Array builder for `DecimalArray`
+pub struct DecimalBuilder {
+    values_builder: MutableBuffer,
+    bitmap_builder: BooleanBufferBuilder,
+    precision: usize,
+    scale: usize,
+    rounding: DecimalRounding,
+    nullable: bool,
+}
+
+impl ArrayBuilder for DecimalBuilder {
+    /// Returns the builder as an non-mutable `Any` reference.
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    /// Returns the builder as an mutable `Any` reference.
+    fn as_any_mut(&mut self) -> &mut Any {
+        self
+    }
+
+    /// Returns the boxed builder as a box of `Any`.
+    fn into_box_any(self: Box<Self>) -> Box<Any> {
+        self
+    }
+
+    /// Returns the number of array slots in the builder
+    fn len(&self) -> usize {
+        self.bitmap_builder.len()
+    }
+
+    /// Builds the array and reset this builder.
+    fn finish(&mut self) -> ArrayRef {
+        Arc::new(self.finish())
+    }
+}
+
+impl DecimalBuilder {
+    /// Creates a new builder of `Decimal(precision, scale)` values.
+    ///
+    /// Panics if `Decimal(precision, scale)` isn't a valid decimal type.
+    pub fn new(capacity: usize, precision: usize, scale: usize) -> Self {
+        decimal::validate_decimal_type(precision, scale).unwrap();
+        Self {
+            values_builder: MutableBuffer::new(capacity * 16),
+            bitmap_builder: BooleanBufferBuilder::new(capacity),
+            precision,
+            scale,
+            rounding: DecimalRounding::default(),
+            nullable: true,
+        }
+    }
+
+    /// Sets whether nulls can be appended into the builder. Appending a null into a
+    /// builder that isn't nullable returns an error.
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    /// Sets how `append_str` rounds fraction digits beyond the scale, `HalfUp` by default
+    pub fn with_rounding(mut self, rounding: DecimalRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Appends an unscaled value, e.g. `15000` for `150.00` in a `Decimal(6, 2)`.
+    ///
+    /// Returns an error if the value has more digits than the precision.
+    pub fn append_value(&mut self, v: i128) -> Result<()> {
+        if v.checked_abs()
+            .map_or(true, |v| v >= 10i128.pow(self.precision as u32))
+        {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{} is too large to store in a Decimal({}, {})",
+                v, self.precision, self.scale
+            )));
+        }
+        self.bitmap_builder.append(true)?;
+        self.values_builder.write_all(&v.to_le_bytes())?;
+        Ok(())
+    }
+
+    /// Parses a decimal string, e.g. `"150.005"`, and appends its value, rounding digits
+    /// beyond the scale per the rounding of this builder.
+    pub fn append_str(&mut self, s: &str) -> Result<()> {
+        let v = decimal::parse_decimal(s, self.precision, self.scale, self.rounding)?;
+        self.append_value(v)
+    }
+
+    /// Appends a null slot into the builder
+    pub fn append_null(&mut self) -> Result<()> {
+        if !self.nullable {
+            return Err(null_not_allowed());
+        }
+        self.bitmap_builder.append(false)?;
+        self.values_builder.write_all(&[0u8; 16])?;
+        Ok(())
+    }
+
+    /// Builds the `DecimalArray` and reset this builder.
+    pub fn finish(&mut self) -> DecimalArray {
+        let len = self.len();
+        let (null_count, null_bit_buffer) =
+            finish_null_bitmap(&mut self.bitmap_builder, len);
+        let values = mem::replace(&mut self.values_builder, MutableBuffer::new(0));
+        let mut builder =
+            ArrayData::builder(DataType::Decimal(self.precision, self.scale))
+                .len(len)
+                .null_count(null_count)
+                .add_buffer(values.freeze());
+        if let Some(null_bit_buffer) = null_bit_buffer {
+            builder = builder.null_bit_buffer(null_bit_buffer);
+        }
+        DecimalArray::from(builder.build())
+    }
+}
+
+/// Array builder for `DictionaryArray`s of strings. Each distinct string is appended to
+/// the values only once, and every appended element becomes a key into the values.
+pub struct StringDictionaryBuilder<K: ArrowDictionaryKeyType> {
+    keys_builder: PrimitiveBuilder<K>,
+    values_builder: BinaryBuilder,
+    map: HashMap<Box<[u8]>, K::Native>,
+}
+
+impl<K: ArrowDictionaryKeyType> StringDictionaryBuilder<K> {
+    /// Creates a new `StringDictionaryBuilder` from a keys builder and a value builder.
+    pub fn new(keys_builder: PrimitiveBuilder<K>, values_builder: BinaryBuilder) -> Self {
+        Self {
+            keys_builder,
+            values_builder,
+            map: HashMap::new(),
+        }
+    }
+
+    /// Appends a string to the array, adding it to the values if it has not been seen
+    /// before. Returns the key of the appended value.
+    ///
+    /// Returns `ArrowError::DictionaryKeyOverflowError` if the number of distinct values
+    /// exceeds the range of the key type.
+    pub fn append(&mut self, value: &str) -> Result<K::Native> {
+        if let Some(&key) = self.map.get(value.as_bytes()) {
+            self.keys_builder.append_value(key)?;
+            return Ok(key);
+        }
+        let key = K::Native::from_usize(self.values_builder.len())
+            .ok_or(ArrowError::DictionaryKeyOverflowError)?;
+        self.values_builder.append_string(value)?;
+        self.keys_builder.append_value(key)?;
+        self.map.insert(value.as_bytes().into(), key);
+        Ok(key)
+    }
+
+    /// Sets whether nulls can be appended into the builder. Appending a null into a
+    /// builder that isn't nullable returns an error.
+    pub fn with_nullable(self, nullable: bool) -> Self {
+        Self {
+            keys_builder: self.keys_builder.with_nullable(nullable),
+            ..self
+        }
+    }
+
+    /// Appends a null element to the array.
+    ///
+    /// Nulls are recorded in the keys, so appending a null fails if the keys builder
+    /// isn't nullable.
+    pub fn append_null(&mut self) -> Result<()> {
+        self.keys_builder.append_null()
+    }
+
+    /// Builds the `DictionaryArray` and reset this builder.
+    pub fn finish(&mut self) -> DictionaryArray<K> {
+        self.map.clear();
+        let keys = self.keys_builder.finish();
+        let values = self.values_builder.finish();
+        let data_type =
+            DataType::Dictionary(Box::new(K::get_data_type()), Box::new(DataType::Utf8));
+        let mut builder = ArrayData::builder(data_type)
+            .len(keys.len())
+            .add_buffer(keys.data_ref().buffers()[0].clone())
+            .add_child_data(values.data());
+        if let Some(bitmap) = keys.data_ref().null_bitmap() {
+            builder = builder
+                .null_count(keys.null_count())
+                .null_bit_buffer(bitmap.bits.clone());
+        }
+        DictionaryArray::from(builder.build())
+    }
+}
+
+impl<K: ArrowDictionaryKeyType> ArrayBuilder for StringDictionaryBuilder<K> {
+    /// Returns the builder as an non-mutable `Any` reference.
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    /// Returns the builder as an mutable `Any` reference.
+    fn as_any_mut(&mut self) -> &mut Any {
+        self
+    }
+
+    /// Returns the boxed builder as a box of `Any`.
+    fn into_box_any(self: Box<Self>) -> Box<Any> {
+        self
+    }
+
+    /// Returns the number of array slots in the builder
+    fn len(&self) -> usize {
+        self.keys_builder.len()
+    }
+
+    /// Builds the array and reset this builder.
+    fn finish(&mut self) -> ArrayRef {
+        Arc::new(self.finish())
+    }
+}
+
 /// Array builder for Struct types.
 ///
 /// Note that callers should make sure that methods of all the child field builders are
@@ -636,6 +1019,7 @@ pub struct StructBuilder {
     field_builders: Vec<Box<ArrayBuilder>>,
     bitmap_builder: BooleanBufferBuilder,
     len: usize,
+    nullable: bool,
 }
 
 impl ArrayBuilder for StructBuilder {
@@ -700,9 +1084,19 @@ impl StructBuilder {
             field_builders,
             bitmap_builder: BooleanBufferBuilder::new(0),
             len: 0,
+            nullable: true,
         }
     }
 
+    /// Sets whether null structs can be appended into the builder. Appending a null
+    /// struct into a builder that isn't nullable returns an error.
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    /// Creates a builder of structs with the fields of `schema`, where the builder of
+    /// each field only accepts nulls if the field is nullable
     pub fn from_schema(schema: Schema, capacity: usize) -> Self {
         let fields = schema.fields();
         let mut builders = Vec::with_capacity(fields.len());
@@ -713,50 +1107,80 @@ impl StructBuilder {
     }
 
     fn from_field(f: Field, capacity: usize) -> Box<ArrayBuilder> {
+        let nullable = f.is_nullable();
         match f.data_type() {
-            DataType::Boolean => Box::new(BooleanBuilder::new(capacity)),
-            DataType::Int8 => Box::new(Int8Builder::new(capacity)),
-            DataType::Int16 => Box::new(Int16Builder::new(capacity)),
-            DataType::Int32 => Box::new(Int32Builder::new(capacity)),
-            DataType::Int64 => Box::new(Int64Builder::new(capacity)),
-            DataType::UInt8 => Box::new(UInt8Builder::new(capacity)),
-            DataType::UInt16 => Box::new(UInt16Builder::new(capacity)),
-            DataType::UInt32 => Box::new(UInt32Builder::new(capacity)),
-            DataType::UInt64 => Box::new(UInt64Builder::new(capacity)),
-            DataType::Float32 => Box::new(Float32Builder::new(capacity)),
-            DataType::Float64 => Box::new(Float64Builder::new(capacity)),
-            DataType::Utf8 => Box::new(BinaryBuilder::new(capacity)),
-            DataType::Date32(DateUnit::Day) => Box::new(Date32Builder::new(capacity)),
+            DataType::Boolean => {
+                Box::new(BooleanBuilder::new(capacity).with_nullable(nullable))
+            }
+            DataType::Int8 => {
+                Box::new(Int8Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::Int16 => {
+                Box::new(Int16Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::Int32 => {
+                Box::new(Int32Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::Int64 => {
+                Box::new(Int64Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::UInt8 => {
+                Box::new(UInt8Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::UInt16 => {
+                Box::new(UInt16Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::UInt32 => {
+                Box::new(UInt32Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::UInt64 => {
+                Box::new(UInt64Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::Float32 => {
+                Box::new(Float32Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::Float64 => {
+                Box::new(Float64Builder::new(capacity).with_nullable(nullable))
+            }
+            DataType::Utf8 => {
+                Box::new(BinaryBuilder::new(capacity).with_nullable(nullable))
+            }
+            DataType::Date32(DateUnit::Day) => {
+                Box::new(Date32Builder::new(capacity).with_nullable(nullable))
+            }
             DataType::Date64(DateUnit::Millisecond) => {
-                Box::new(Date64Builder::new(capacity))
+                Box::new(Date64Builder::new(capacity).with_nullable(nullable))
             }
             DataType::Time32(TimeUnit::Second) => {
-                Box::new(Time32SecondBuilder::new(capacity))
+                Box::new(Time32SecondBuilder::new(capacity).with_nullable(nullable))
             }
             DataType::Time32(TimeUnit::Millisecond) => {
-                Box::new(Time32MillisecondBuilder::new(capacity))
+                Box::new(Time32MillisecondBuilder::new(capacity).with_nullable(nullable))
             }
             DataType::Time64(TimeUnit::Microsecond) => {
-                Box::new(Time64MicrosecondBuilder::new(capacity))
+                Box::new(Time64MicrosecondBuilder::new(capacity).with_nullable(nullable))
             }
             DataType::Time64(TimeUnit::Nanosecond) => {
-                Box::new(Time64NanosecondBuilder::new(capacity))
+                Box::new(Time64NanosecondBuilder::new(capacity).with_nullable(nullable))
             }
             DataType::Timestamp(TimeUnit::Second) => {
-                Box::new(TimestampSecondBuilder::new(capacity))
-            }
-            DataType::Timestamp(TimeUnit::Millisecond) => {
-                Box::new(TimestampMillisecondBuilder::new(capacity))
-            }
-            DataType::Timestamp(TimeUnit::Microsecond) => {
-                Box::new(TimestampMicrosecondBuilder::new(capacity))
-            }
-            DataType::Timestamp(TimeUnit::Nanosecond) => {
-                Box::new(TimestampNanosecondBuilder::new(capacity))
+                Box::new(TimestampSecondBuilder::new(capacity).with_nullable(nullable))
             }
+            DataType::Timestamp(TimeUnit::Millisecond) => Box::new(
+                TimestampMillisecondBuilder::new(capacity).with_nullable(nullable),
+            ),
+            DataType::Timestamp(TimeUnit::Microsecond) => Box::new(
+                TimestampMicrosecondBuilder::new(capacity).with_nullable(nullable),
+            ),
+            DataType::Timestamp(TimeUnit::Nanosecond) => Box::new(
+                TimestampNanosecondBuilder::new(capacity).with_nullable(nullable),
+            ),
+            DataType::Decimal(precision, scale) => Box::new(
+                DecimalBuilder::new(capacity, *precision, *scale).with_nullable(nullable),
+            ),
             DataType::Struct(fields) => {
                 let schema = Schema::new(fields.clone());
-                Box::new(Self::from_schema(schema, capacity))
+                Box::new(Self::from_schema(schema, capacity).with_nullable(nullable))
             }
             t @ _ => panic!("Data type {:?} is not currently supported", t),
         }
@@ -777,6 +1201,9 @@ impl StructBuilder {
     /// Appends an element (either null or non-null) to the struct. The actual elements
     /// should be appended for each child sub-array in a consistent way.
     pub fn append(&mut self, is_valid: bool) -> Result<()> {
+        if !is_valid && !self.nullable {
+            return Err(null_not_allowed());
+        }
         self.bitmap_builder.append(is_valid)?;
         self.len += 1;
         Ok(())
@@ -795,15 +1222,14 @@ impl StructBuilder {
             child_data.push(arr.data());
         }
 
-        let null_bit_buffer = self.bitmap_builder.finish();
-        let null_count = self.len - bit_util::count_set_bits(null_bit_buffer.data());
+        let (null_count, null_bit_buffer) =
+            finish_null_bitmap(&mut self.bitmap_builder, self.len);
         let mut builder = ArrayData::builder(DataType::Struct(self.fields.clone()))
             .len(self.len)
+            .null_count(null_count)
             .child_data(child_data);
-        if null_count > 0 {
-            builder = builder
-                .null_count(null_count)
-                .null_bit_buffer(null_bit_buffer);
+        if let Some(null_bit_buffer) = null_bit_buffer {
+            builder = builder.null_bit_buffer(null_bit_buffer);
         }
 
         self.len = 0;
@@ -824,6 +1250,8 @@ impl Drop for StructBuilder {
 mod tests {
     use super::*;
 
+    use chrono::TimeZone;
+
     use crate::array::Array;
     use crate::bitmap::Bitmap;
 
@@ -939,6 +1367,25 @@ mod tests {
         assert_eq!(1, buffer.len());
     }
 
+    #[test]
+    fn test_nullable_boolean_buffer_builder() {
+        let mut b = NullableBooleanBufferBuilder::new(4);
+        b.append(Some(true)).unwrap();
+        b.append(None).unwrap();
+        b.append(Some(false)).unwrap();
+        b.append(Some(true)).unwrap();
+        assert_eq!(4, b.len());
+        let (values, null_bit_buffer) = b.finish();
+        assert_eq!(0, b.len());
+        assert_eq!(Buffer::from([0b1001_u8]), values);
+        assert_eq!(Some(Buffer::from([0b1101_u8])), null_bit_buffer);
+
+        b.append(Some(false)).unwrap();
+        let (values, null_bit_buffer) = b.finish();
+        assert_eq!(Buffer::from([0_u8]), values);
+        assert_eq!(None, null_bit_buffer);
+    }
+
     #[test]
     fn test_write_bytes_i32() {
         let mut b = Int32BufferBuilder::new(4);
@@ -1162,6 +1609,71 @@ mod tests {
         assert_eq!(0, builder.len());
     }
 
+    #[test]
+    fn test_primitive_array_builder_finish_null_count() {
+        let mut builder = Int32Builder::new(6);
+        builder.append_value(1).unwrap();
+        builder.append_null().unwrap();
+        builder.append_value(3).unwrap();
+        builder.append_null().unwrap();
+        builder.append_null().unwrap();
+        builder.append_value(6).unwrap();
+        let arr = builder.finish();
+        assert_eq!(3, arr.data().null_count);
+        assert_eq!(3, arr.null_count());
+        assert!(arr.data().null_bitmap().is_some());
+
+        builder.append_slice(&[1, 2, 3]).unwrap();
+        let arr = builder.finish();
+        assert_eq!(0, arr.null_count());
+        assert!(arr.data().null_bitmap().is_none());
+    }
+
+    #[test]
+    fn test_list_array_builder_finish_without_nulls() {
+        let mut builder = ListBuilder::new(Int32Builder::new(4));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        let list_array = builder.finish();
+        assert_eq!(0, list_array.null_count());
+        assert!(list_array.data().null_bitmap().is_none());
+        assert_eq!(1, list_array.values().null_count());
+
+        let mut builder = FixedSizeListBuilder::new(Int32Builder::new(4), 2);
+        builder.values().append_slice(&[1, 2, 3, 4]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(true).unwrap();
+        let list_array = builder.finish();
+        assert_eq!(0, list_array.null_count());
+        assert!(list_array.data().null_bitmap().is_none());
+    }
+
+    #[test]
+    fn test_timestamp_builder_append_datetime() {
+        let datetime = Utc.ymd(2019, 10, 1).and_hms_micro(12, 34, 56, 789_012);
+        let mut builder = TimestampMicrosecondBuilder::new(3);
+        builder.append_datetime(datetime).unwrap();
+        builder
+            .append_rfc3339("2019-10-01T14:34:56.789012+02:00")
+            .unwrap();
+        builder.append_null().unwrap();
+        let arr = builder.finish();
+        assert_eq!(1_569_933_296_789_012, arr.value(0));
+        assert_eq!(1_569_933_296_789_012, arr.value(1));
+        assert!(arr.is_null(2));
+
+        let mut builder = TimestampSecondBuilder::new(1);
+        builder.append_datetime(datetime).unwrap();
+        assert_eq!(1_569_933_296, builder.finish().value(0));
+
+        let mut builder = TimestampNanosecondBuilder::new(1);
+        assert!(builder.append_rfc3339("not a timestamp").is_err());
+        let far_future = Utc.ymd(3000, 1, 1).and_hms(0, 0, 0);
+        assert!(builder.append_datetime(far_future).is_err());
+    }
+
     #[test]
     fn test_list_array_builder() {
         let values_builder = Int32Builder::new(10);
@@ -1301,6 +1813,42 @@ mod tests {
         assert_eq!(0, builder.len());
     }
 
+    #[test]
+    fn test_decimal_builder() {
+        let mut builder = DecimalBuilder::new(4, 5, 2);
+        builder.append_value(12345).unwrap();
+        builder.append_null().unwrap();
+        builder.append_str("-1.005").unwrap();
+        assert!(builder.append_value(100_000).is_err());
+        assert!(builder.append_str("1000").is_err());
+        assert!(builder.append_str("abc").is_err());
+
+        let arr = builder.finish();
+        assert_eq!(&DataType::Decimal(5, 2), arr.data_type());
+        assert_eq!(3, arr.len());
+        assert_eq!(1, arr.null_count());
+        assert_eq!(12345, arr.value(0));
+        assert_eq!("123.45", arr.value_as_string(0));
+        assert!(arr.is_null(1));
+        assert_eq!(-101, arr.value(2));
+        assert_eq!(
+            -101,
+            arr.slice(2, 1)
+                .as_any()
+                .downcast_ref::<DecimalArray>()
+                .unwrap()
+                .value(0)
+        );
+        assert_eq!(0, builder.len());
+
+        let mut builder = DecimalBuilder::new(1, 5, 2)
+            .with_rounding(DecimalRounding::Truncate)
+            .with_nullable(false);
+        builder.append_str("-1.005").unwrap();
+        assert!(builder.append_null().is_err());
+        assert_eq!(-100, builder.finish().value(0));
+    }
+
     #[test]
     fn test_list_list_array_builder() {
         let primitive_builder = Int32Builder::new(10);
@@ -1432,6 +1980,58 @@ mod tests {
         assert_eq!(5, binary_array.value_length(2));
     }
 
+    #[test]
+    fn test_string_dictionary_builder() {
+        let keys_builder = Int8Builder::new(5);
+        let values_builder = BinaryBuilder::new(10);
+        let mut builder = StringDictionaryBuilder::new(keys_builder, values_builder);
+        assert_eq!(0, builder.append("abc").unwrap());
+        builder.append_null().unwrap();
+        assert_eq!(1, builder.append("def").unwrap());
+        assert_eq!(0, builder.append("abc").unwrap());
+        let array = builder.finish();
+
+        assert_eq!(4, array.len());
+        assert_eq!(1, array.null_count());
+        assert_eq!(Some(0), array.key(0));
+        assert_eq!(None, array.key(1));
+        assert_eq!(Some(1), array.key(2));
+        assert_eq!(Some(0), array.key(3));
+
+        let values = array.values();
+        let values = values.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(2, values.len());
+        assert_eq!("abc", values.get_string(0));
+        assert_eq!("def", values.get_string(1));
+    }
+
+    #[test]
+    fn test_string_dictionary_builder_not_nullable() {
+        let keys_builder = Int8Builder::new(5);
+        let values_builder = BinaryBuilder::new(10);
+        let mut builder = StringDictionaryBuilder::new(keys_builder, values_builder)
+            .with_nullable(false);
+        builder.append("abc").unwrap();
+        assert!(builder.append_null().is_err());
+        let array = builder.finish();
+        assert_eq!(1, array.len());
+        assert_eq!(0, array.null_count());
+    }
+
+    #[test]
+    fn test_string_dictionary_builder_key_overflow() {
+        let keys_builder = UInt8Builder::new(300);
+        let values_builder = BinaryBuilder::new(300);
+        let mut builder = StringDictionaryBuilder::new(keys_builder, values_builder);
+        for i in 0..256 {
+            builder.append(&format!("{}", i)).unwrap();
+        }
+        assert_eq!(
+            Err(ArrowError::DictionaryKeyOverflowError),
+            builder.append("256")
+        );
+    }
+
     #[test]
     fn test_struct_array_builder() {
         let string_builder = BinaryBuilder::new(4);
@@ -1597,6 +2197,47 @@ mod tests {
         assert!(builder.field_builder::<StructBuilder>(2).is_some());
     }
 
+    #[test]
+    fn test_append_null_into_non_nullable_builder() {
+        let mut builder = Int32Builder::new(2).with_nullable(false);
+        assert!(builder.append_null().is_err());
+        assert!(builder.append_option(None).is_err());
+        builder.append_value(1).unwrap();
+        assert_eq!(1, builder.finish().len());
+
+        let mut builder = BinaryBuilder::new(2).with_nullable(false);
+        assert!(builder.append_null().is_err());
+        builder.append_string("a").unwrap();
+        assert_eq!(1, builder.finish().len());
+
+        let mut builder = ListBuilder::new(Int32Builder::new(2)).with_nullable(false);
+        assert!(builder.append(false).is_err());
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        assert_eq!(1, builder.finish().len());
+    }
+
+    #[test]
+    fn test_struct_array_builder_from_schema_nullability() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ];
+        let mut builder =
+            StructBuilder::from_schema(Schema::new(fields), 2).with_nullable(false);
+        let a = builder.field_builder::<Int32Builder>(0).unwrap();
+        assert!(a.append_null().is_err());
+        a.append_value(1).unwrap();
+        let b = builder.field_builder::<Int32Builder>(1).unwrap();
+        b.append_null().unwrap();
+        assert!(builder.append_null().is_err());
+        builder.append(true).unwrap();
+
+        let array = builder.finish();
+        assert_eq!(1, array.len());
+        assert_eq!(1, array.column(1).null_count());
+    }
+
     #[test]
     #[should_panic(expected = "Data type List(Int64) is not currently supported")]
     fn test_struct_array_builder_from_schema_unsupported_type() {
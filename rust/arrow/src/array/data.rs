@@ -23,6 +23,7 @@ use std::sync::Arc;
 use crate::bitmap::Bitmap;
 use crate::buffer::Buffer;
 use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
 use crate::util::bit_util;
 
 /// An generic representation of Arrow array data which encapsulates common attributes and
@@ -77,6 +78,8 @@ impl ArrayData {
                         len,
                     ))
                     .unwrap()
+                } else if data_type == DataType::Null {
+                    len
                 } else {
                     0
                 }
@@ -120,7 +123,7 @@ impl ArrayData {
         if let Some(ref b) = self.null_bitmap {
             return !b.is_set(i);
         }
-        false
+        self.data_type == DataType::Null
     }
 
     /// Returns a reference to the null bitmap of this array data
@@ -133,7 +136,7 @@ impl ArrayData {
         if let Some(ref b) = self.null_bitmap {
             return b.is_set(i);
         }
-        true
+        self.data_type != DataType::Null
     }
 
     /// Returns the length (i.e., number of elements) of this array
@@ -150,8 +153,64 @@ impl ArrayData {
     pub fn null_count(&self) -> usize {
         self.null_count
     }
+
+    /// Returns the total number of bytes of memory occupied by the buffers owned by
+    /// this array data, its null bitmap and all of its children.
+    ///
+    /// Buffers shared between slices are counted in full for each slice.
+    pub fn get_array_memory_size(&self) -> usize {
+        let mut size = ::std::mem::size_of::<Self>();
+        for buffer in &self.buffers {
+            size += buffer.len();
+        }
+        if let Some(bitmap) = &self.null_bitmap {
+            size += bitmap.len();
+        }
+        for child in &self.child_data {
+            size += child.get_array_memory_size();
+        }
+        size
+    }
+
+    /// Returns a checksum of the bytes of the buffers of this array data, its null
+    /// bitmap and all of its children, computed with 64-bit FNV-1a.
+    ///
+    /// The checksum covers the whole of each buffer regardless of the offset of the
+    /// array, so that it can be compared before and after an operation to detect an
+    /// accidental mutation of shared buffers. It is not computed unless requested.
+    pub fn buffers_checksum(&self) -> u64 {
+        let mut hash = FNV_OFFSET_BASIS;
+        self.update_checksum(&mut hash);
+        hash
+    }
+
+    fn update_checksum(&self, hash: &mut u64) {
+        let mut write = |bytes: &[u8]| {
+            // include the length so that bytes cannot move between adjacent buffers
+            for byte in (bytes.len() as u64)
+                .to_le_bytes()
+                .iter()
+                .chain(bytes.iter())
+            {
+                *hash ^= u64::from(*byte);
+                *hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+        for buffer in &self.buffers {
+            write(buffer.data());
+        }
+        if let Some(bitmap) = &self.null_bitmap {
+            write(bitmap.bits.data());
+        }
+        for child in &self.child_data {
+            child.update_checksum(hash);
+        }
+    }
 }
 
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
 /// Builder for `ArrayData` type
 pub struct ArrayDataBuilder {
     data_type: DataType,
@@ -228,6 +287,57 @@ impl ArrayDataBuilder {
         );
         Arc::new(data)
     }
+
+    /// Builds the `ArrayData` after checking that its buffers and child data match the
+    /// layout required by its data type.
+    ///
+    /// `build` accepts any combination of buffers and child data, which can produce
+    /// arrays that only fail once they are accessed. This instead returns an
+    /// `InvalidArgumentError` describing the mismatch, e.g. for a `List` without child
+    /// data, or for a null bit buffer that is too short for the array.
+    pub fn build_checked(self) -> Result<ArrayDataRef> {
+        let (num_buffers, num_children) = expected_layout(&self.data_type);
+        if self.buffers.len() != num_buffers {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{:?} array requires {} buffer(s), but {} were given",
+                self.data_type,
+                num_buffers,
+                self.buffers.len()
+            )));
+        }
+        if self.child_data.len() != num_children {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{:?} array requires {} child array(s), but {} were given",
+                self.data_type,
+                num_children,
+                self.child_data.len()
+            )));
+        }
+        if let Some(ref buf) = self.null_bit_buffer {
+            let required_len = bit_util::ceil(self.offset + self.len, 8);
+            if buf.len() < required_len {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Null bit buffer of {} byte(s) is too short for {} slot(s) at offset {}",
+                    buf.len(),
+                    self.len,
+                    self.offset
+                )));
+            }
+        }
+        Ok(self.build())
+    }
+}
+
+/// Returns the number of buffers and child arrays that array data of `data_type` holds
+fn expected_layout(data_type: &DataType) -> (usize, usize) {
+    match data_type {
+        DataType::Null => (0, 0),
+        DataType::Utf8 | DataType::Binary => (2, 0),
+        DataType::List(_) | DataType::LargeList(_) | DataType::Dictionary(_, _) => (1, 1),
+        DataType::FixedSizeList(_) => (0, 1),
+        DataType::Struct(fields) => (0, fields.len()),
+        _ => (1, 0),
+    }
 }
 
 #[cfg(test)]
@@ -237,6 +347,7 @@ mod tests {
     use std::sync::Arc;
 
     use crate::buffer::Buffer;
+    use crate::datatypes::ToByteSlice;
     use crate::util::bit_util;
 
     #[test]
@@ -304,4 +415,73 @@ mod tests {
             .build();
         assert_eq!(10, arr_data.null_count());
     }
+
+    #[test]
+    fn test_buffers_checksum() {
+        let build = |values: Vec<i32>| {
+            ArrayData::builder(DataType::Int32)
+                .len(values.len())
+                .add_buffer(Buffer::from(values.to_byte_slice()))
+                .null_bit_buffer(Buffer::from([0b1101_u8]))
+                .build()
+        };
+        let a = build(vec![1, 2, 3, 4]);
+        let b = build(vec![1, 2, 3, 4]);
+        let c = build(vec![1, 2, 3, 5]);
+        assert_eq!(a.buffers_checksum(), b.buffers_checksum());
+        assert_ne!(a.buffers_checksum(), c.buffers_checksum());
+
+        // the null bitmap and children are covered too
+        let d = ArrayData::builder(DataType::Int32)
+            .len(4)
+            .add_buffer(a.buffers()[0].clone())
+            .null_bit_buffer(Buffer::from([0b1111_u8]))
+            .build();
+        assert_ne!(a.buffers_checksum(), d.buffers_checksum());
+        let list = |child: ArrayDataRef| {
+            ArrayData::builder(DataType::List(Box::new(DataType::Int32)))
+                .len(1)
+                .add_buffer(Buffer::from([0, 4].to_byte_slice()))
+                .add_child_data(child)
+                .build()
+        };
+        assert_eq!(
+            list(a.clone()).buffers_checksum(),
+            list(b).buffers_checksum()
+        );
+        assert_ne!(list(a).buffers_checksum(), list(c).buffers_checksum());
+    }
+
+    #[test]
+    fn test_build_checked() {
+        let data = ArrayData::builder(DataType::Int32)
+            .len(2)
+            .add_buffer(Buffer::from(&[1, 2].to_byte_slice()))
+            .build_checked()
+            .unwrap();
+        assert_eq!(2, data.len());
+
+        let list_type = DataType::List(Box::new(DataType::Int32));
+        let result = ArrayData::builder(list_type)
+            .len(2)
+            .add_buffer(Buffer::from(&[0, 1, 2].to_byte_slice()))
+            .build_checked();
+        match result {
+            Err(ArrowError::InvalidArgumentError(msg)) => assert_eq!(
+                "List(Int32) array requires 1 child array(s), but 0 were given",
+                msg
+            ),
+            _ => panic!("expected a List without child data to be rejected"),
+        }
+    }
+
+    #[test]
+    fn test_build_checked_short_null_bit_buffer() {
+        let result = ArrayData::builder(DataType::Int8)
+            .len(10)
+            .add_buffer(Buffer::from(&[0u8; 10]))
+            .null_bit_buffer(Buffer::from(&[0xFFu8]))
+            .build_checked();
+        assert!(result.is_err());
+    }
 }
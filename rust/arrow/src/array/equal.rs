@@ -21,6 +21,15 @@ use crate::util::bit_util;
 use serde_json::value::Value::{Null as JNull, Object, String as JString};
 use serde_json::Value;
 
+/// Returns whether `a` and `b` have the same data type, length, null bitmap and values.
+///
+/// Arrays are compared by their logical values, so a slice of an array is equal to an
+/// array without an offset that holds the same values. `NaN` values in float arrays are
+/// equal to each other.
+pub fn array_equals(a: &Array, b: &Array) -> bool {
+    a.equals(b)
+}
+
 /// Trait for `Array` equality.
 pub trait ArrayEqual {
     /// Returns true if this array is equal to the `other` array
@@ -101,6 +110,45 @@ impl<T: ArrowPrimitiveType> ArrayEqual for PrimitiveArray<T> {
     }
 }
 
+/// Implements `ArrayEqual` for arrays of a float type by comparing their values with
+/// `NaN` equal to `NaN`, so that an array with `NaN` values is equal to itself
+macro_rules! float_array_equal {
+    ($T:ty) => {
+        impl ArrayEqual for PrimitiveArray<$T> {
+            fn equals(&self, other: &dyn Array) -> bool {
+                base_equal(&self.data(), &other.data())
+                    && self.range_equals(other, 0, self.len(), 0)
+            }
+
+            fn range_equals(
+                &self,
+                other: &dyn Array,
+                start_idx: usize,
+                end_idx: usize,
+                other_start_idx: usize,
+            ) -> bool {
+                assert!(other_start_idx + (end_idx - start_idx) <= other.len());
+                let other = other.as_any().downcast_ref::<PrimitiveArray<$T>>().unwrap();
+
+                (start_idx..end_idx).zip(other_start_idx..).all(|(i, j)| {
+                    match (self.is_null(i), other.is_null(j)) {
+                        (true, true) => true,
+                        (false, false) => {
+                            let (value, other_value) = (self.value(i), other.value(j));
+                            value == other_value
+                                || (value.is_nan() && other_value.is_nan())
+                        }
+                        _ => false,
+                    }
+                })
+            }
+        }
+    };
+}
+
+float_array_equal!(Float32Type);
+float_array_equal!(Float64Type);
+
 impl ArrayEqual for BooleanArray {
     fn equals(&self, other: &dyn Array) -> bool {
         if !base_equal(&self.data(), &other.data()) {
@@ -171,8 +219,7 @@ impl ArrayEqual for ListArray {
         assert!(other_start_idx + (end_idx - start_idx) <= other.len());
         let other = other.as_any().downcast_ref::<ListArray>().unwrap();
 
-        let mut j = other_start_idx;
-        for i in start_idx..end_idx {
+        for (i, j) in (start_idx..end_idx).zip(other_start_idx..) {
             let is_null = self.is_null(i);
             let other_is_null = other.is_null(j);
 
@@ -201,8 +248,60 @@ impl ArrayEqual for ListArray {
             ) {
                 return false;
             }
+        }
 
-            j += 1;
+        true
+    }
+}
+
+impl ArrayEqual for LargeListArray {
+    fn equals(&self, other: &dyn Array) -> bool {
+        if !base_equal(&self.data(), &other.data()) {
+            return false;
+        }
+
+        self.range_equals(other, 0, self.len(), 0)
+    }
+
+    fn range_equals(
+        &self,
+        other: &dyn Array,
+        start_idx: usize,
+        end_idx: usize,
+        other_start_idx: usize,
+    ) -> bool {
+        assert!(other_start_idx + (end_idx - start_idx) <= other.len());
+        let other = other.as_any().downcast_ref::<LargeListArray>().unwrap();
+
+        for (i, j) in (start_idx..end_idx).zip(other_start_idx..) {
+            let is_null = self.is_null(i);
+            let other_is_null = other.is_null(j);
+
+            if is_null != other_is_null {
+                return false;
+            }
+
+            if is_null {
+                continue;
+            }
+
+            let start_offset = self.value_offset(i) as usize;
+            let end_offset = self.value_offset(i + 1) as usize;
+            let other_start_offset = other.value_offset(j) as usize;
+            let other_end_offset = other.value_offset(j + 1) as usize;
+
+            if end_offset - start_offset != other_end_offset - other_start_offset {
+                return false;
+            }
+
+            if !self.values().range_equals(
+                &*other.values(),
+                start_offset,
+                end_offset,
+                other_start_offset,
+            ) {
+                return false;
+            }
         }
 
         true
@@ -239,8 +338,7 @@ impl ArrayEqual for FixedSizeListArray {
         assert!(other_start_idx + (end_idx - start_idx) <= other.len());
         let other = other.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
 
-        let mut j = other_start_idx;
-        for i in start_idx..end_idx {
+        for (i, j) in (start_idx..end_idx).zip(other_start_idx..) {
             let is_null = self.is_null(i);
             let other_is_null = other.is_null(j);
 
@@ -269,8 +367,6 @@ impl ArrayEqual for FixedSizeListArray {
             ) {
                 return false;
             }
-
-            j += 1;
         }
 
         true
@@ -283,7 +379,7 @@ impl ArrayEqual for BinaryArray {
             return false;
         }
 
-        let other = other.as_any().downcast_ref::<BinaryArray>().unwrap();
+        let other = &as_binary_array(other);
 
         if !value_offset_equal(self, other) {
             return false;
@@ -297,17 +393,11 @@ impl ArrayEqual for BinaryArray {
         let other_value_data = other_value_buf.data();
 
         if self.null_count() == 0 {
-            // No offset in both - just do memcmp
-            if self.offset() == 0 && other.offset() == 0 {
-                let len = self.value_offset(self.len()) as usize;
-                return value_data[..len] == other_value_data[..len];
-            } else {
-                let start = self.value_offset(0) as usize;
-                let other_start = other.value_offset(0) as usize;
-                let len = (self.value_offset(self.len()) - self.value_offset(0)) as usize;
-                return value_data[start..(start + len)]
-                    == other_value_data[other_start..(other_start + len)];
-            }
+            let start = self.value_offset(0) as usize;
+            let other_start = other.value_offset(0) as usize;
+            let len = (self.value_offset(self.len()) - self.value_offset(0)) as usize;
+            return value_data[start..(start + len)]
+                == other_value_data[other_start..(other_start + len)];
         } else {
             for i in 0..self.len() {
                 if self.is_null(i) {
@@ -336,10 +426,9 @@ impl ArrayEqual for BinaryArray {
         other_start_idx: usize,
     ) -> bool {
         assert!(other_start_idx + (end_idx - start_idx) <= other.len());
-        let other = other.as_any().downcast_ref::<BinaryArray>().unwrap();
+        let other = &as_binary_array(other);
 
-        let mut j = other_start_idx;
-        for i in start_idx..end_idx {
+        for (i, j) in (start_idx..end_idx).zip(other_start_idx..) {
             let is_null = self.is_null(i);
             let other_is_null = other.is_null(j);
 
@@ -373,14 +462,97 @@ impl ArrayEqual for BinaryArray {
                     return false;
                 }
             }
-
-            j += 1;
         }
 
         true
     }
 }
 
+impl ArrayEqual for FixedSizeBinaryArray {
+    fn equals(&self, other: &dyn Array) -> bool {
+        if !base_equal(&self.data(), &other.data()) {
+            return false;
+        }
+
+        self.range_equals(other, 0, self.len(), 0)
+    }
+
+    fn range_equals(
+        &self,
+        other: &dyn Array,
+        start_idx: usize,
+        end_idx: usize,
+        other_start_idx: usize,
+    ) -> bool {
+        assert!(other_start_idx + (end_idx - start_idx) <= other.len());
+        let other = other
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+
+        if self.value_length() != other.value_length() {
+            return false;
+        }
+
+        (start_idx..end_idx).zip(other_start_idx..).all(|(i, j)| {
+            match (self.is_null(i), other.is_null(j)) {
+                (true, true) => true,
+                (false, false) => self.value(i) == other.value(j),
+                _ => false,
+            }
+        })
+    }
+}
+
+impl ArrayEqual for DecimalArray {
+    fn equals(&self, other: &dyn Array) -> bool {
+        if !base_equal(&self.data(), &other.data()) {
+            return false;
+        }
+
+        self.range_equals(other, 0, self.len(), 0)
+    }
+
+    fn range_equals(
+        &self,
+        other: &dyn Array,
+        start_idx: usize,
+        end_idx: usize,
+        other_start_idx: usize,
+    ) -> bool {
+        assert!(other_start_idx + (end_idx - start_idx) <= other.len());
+        let other = other.as_any().downcast_ref::<DecimalArray>().unwrap();
+
+        if self.data_type() != other.data_type() {
+            return false;
+        }
+
+        (start_idx..end_idx).zip(other_start_idx..).all(|(i, j)| {
+            match (self.is_null(i), other.is_null(j)) {
+                (true, true) => true,
+                (false, false) => self.value(i) == other.value(j),
+                _ => false,
+            }
+        })
+    }
+}
+
+impl ArrayEqual for StringArray {
+    fn equals(&self, other: &dyn Array) -> bool {
+        as_binary_array(self).equals(other)
+    }
+
+    fn range_equals(
+        &self,
+        other: &dyn Array,
+        start_idx: usize,
+        end_idx: usize,
+        other_start_idx: usize,
+    ) -> bool {
+        as_binary_array(self).range_equals(other, start_idx, end_idx, other_start_idx)
+    }
+}
+
 impl ArrayEqual for StructArray {
     fn equals(&self, other: &dyn Array) -> bool {
         if !base_equal(&self.data(), &other.data()) {
@@ -400,8 +572,15 @@ impl ArrayEqual for StructArray {
             if is_null {
                 continue;
             }
+            // the children of a sliced struct array aren't sliced
+            let (child_i, other_child_i) = (i + self.offset(), i + other.offset());
             for j in 0..self.num_columns() {
-                if !self.column(j).range_equals(&**other.column(j), i, i + 1, i) {
+                if !self.column(j).range_equals(
+                    &**other.column(j),
+                    child_i,
+                    child_i + 1,
+                    other_child_i,
+                ) {
                     return false;
                 }
             }
@@ -420,10 +599,9 @@ impl ArrayEqual for StructArray {
         assert!(other_start_idx + (end_idx - start_idx) <= other.len());
         let other = other.as_any().downcast_ref::<StructArray>().unwrap();
 
-        let mut j = other_start_idx;
-        for i in start_idx..end_idx {
+        for (i, j) in (start_idx..end_idx).zip(other_start_idx..) {
             let is_null = self.is_null(i);
-            let other_is_null = other.is_null(i);
+            let other_is_null = other.is_null(j);
 
             if is_null != other_is_null {
                 return false;
@@ -432,11 +610,59 @@ impl ArrayEqual for StructArray {
             if is_null {
                 continue;
             }
+            let (child_i, other_child_i) = (i + self.offset(), j + other.offset());
             for k in 0..self.num_columns() {
-                if !self.column(k).range_equals(&**other.column(k), i, i + 1, j) {
+                if !self.column(k).range_equals(
+                    &**other.column(k),
+                    child_i,
+                    child_i + 1,
+                    other_child_i,
+                ) {
                     return false;
                 }
             }
+        }
+
+        true
+    }
+}
+
+impl<K: ArrowDictionaryKeyType> ArrayEqual for DictionaryArray<K> {
+    fn equals(&self, other: &dyn Array) -> bool {
+        if !base_equal(&self.data(), &other.data()) {
+            return false;
+        }
+
+        self.range_equals(other, 0, self.len(), 0)
+    }
+
+    fn range_equals(
+        &self,
+        other: &dyn Array,
+        start_idx: usize,
+        end_idx: usize,
+        other_start_idx: usize,
+    ) -> bool {
+        assert!(other_start_idx + (end_idx - start_idx) <= other.len());
+        let other = other.as_any().downcast_ref::<DictionaryArray<K>>().unwrap();
+
+        let mut j = other_start_idx;
+        for i in start_idx..end_idx {
+            // compare the decoded values, as equal arrays may have different dictionaries
+            match (self.key(i), other.key(j)) {
+                (None, None) => {}
+                (Some(key), Some(other_key)) => {
+                    if !self.values().range_equals(
+                        &*other.values(),
+                        key,
+                        key + 1,
+                        other_key,
+                    ) {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
 
             j += 1;
         }
@@ -445,6 +671,24 @@ impl ArrayEqual for StructArray {
     }
 }
 
+impl ArrayEqual for NullArray {
+    fn equals(&self, other: &dyn Array) -> bool {
+        // null arrays have no null bitmap to compare
+        self.data_type() == other.data_type() && self.len() == other.len()
+    }
+
+    fn range_equals(
+        &self,
+        other: &dyn Array,
+        start_idx: usize,
+        end_idx: usize,
+        other_start_idx: usize,
+    ) -> bool {
+        assert!(other_start_idx + (end_idx - start_idx) <= other.len());
+        self.data_type() == other.data_type()
+    }
+}
+
 // Compare if the common basic fields between the two arrays are equal
 fn base_equal(this: &ArrayDataRef, other: &ArrayDataRef) -> bool {
     if this.data_type() != other.data_type() {
@@ -472,10 +716,14 @@ fn base_equal(this: &ArrayDataRef, other: &ArrayDataRef) -> bool {
     true
 }
 
-// Compare if the value offsets are equal between the two list arrays
+// Compare if the value offsets are equal between the two list arrays, relative to the
+// offset of their first value, as either array may be a slice
 fn value_offset_equal<T: Array + ListArrayOps>(this: &T, other: &T) -> bool {
+    let start = this.value_offset_at(this.offset());
+    let other_start = other.value_offset_at(other.offset());
+
     // Check if offsets differ
-    if this.offset() == 0 && other.offset() == 0 {
+    if this.offset() == 0 && other.offset() == 0 && start == other_start {
         let offset_data = &this.data_ref().buffers()[0];
         let other_offset_data = &other.data_ref().buffers()[0];
         return offset_data.data()[0..((this.len() + 1) * 4)]
@@ -484,8 +732,8 @@ fn value_offset_equal<T: Array + ListArrayOps>(this: &T, other: &T) -> bool {
 
     // The expensive case
     for i in 0..this.len() + 1 {
-        if this.value_offset_at(i) - this.value_offset_at(0)
-            != other.value_offset_at(i) - other.value_offset_at(0)
+        if this.value_offset_at(this.offset() + i) - start
+            != other.value_offset_at(other.offset() + i) - other_start
         {
             return false;
         }
@@ -575,6 +823,38 @@ impl PartialEq<ListArray> for Value {
     }
 }
 
+impl JsonEqual for LargeListArray {
+    fn equals_json(&self, json: &[&Value]) -> bool {
+        if self.len() != json.len() {
+            return false;
+        }
+
+        (0..self.len()).all(|i| match json[i] {
+            Value::Array(v) => self.is_valid(i) && self.value(i).equals_json_values(v),
+            Value::Null => self.is_null(i) || self.value_length(i) == 0,
+            _ => false,
+        })
+    }
+}
+
+impl PartialEq<Value> for LargeListArray {
+    fn eq(&self, json: &Value) -> bool {
+        match json {
+            Value::Array(json_array) => self.equals_json_values(json_array),
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<LargeListArray> for Value {
+    fn eq(&self, arrow: &LargeListArray) -> bool {
+        match self {
+            Value::Array(json_array) => arrow.equals_json_values(json_array),
+            _ => false,
+        }
+    }
+}
+
 impl JsonEqual for FixedSizeListArray {
     fn equals_json(&self, json: &[&Value]) -> bool {
         if self.len() != json.len() {
@@ -661,13 +941,18 @@ impl PartialEq<StructArray> for Value {
     }
 }
 
+/// Values of `DataType::Binary` are compared with JSON strings holding their hexadecimal
+/// encoding, as written by the integration JSON format, while values of `DataType::Utf8`
+/// are compared with the JSON strings themselves.
 impl JsonEqual for BinaryArray {
     fn equals_json(&self, json: &[&Value]) -> bool {
         if self.len() != json.len() {
             return false;
         }
 
+        let is_hex = self.data_type() == &DataType::Binary;
         (0..self.len()).all(|i| match json[i] {
+            JString(s) if is_hex => self.is_valid(i) && hex_equals(s, self.value(i)),
             JString(s) => self.is_valid(i) && s.as_str().as_bytes() == self.value(i),
             JNull => self.is_null(i),
             _ => false,
@@ -693,11 +978,166 @@ impl PartialEq<BinaryArray> for Value {
     }
 }
 
+/// Values are compared with JSON strings holding their hexadecimal encoding, as
+/// written by the integration JSON format.
+impl JsonEqual for FixedSizeBinaryArray {
+    fn equals_json(&self, json: &[&Value]) -> bool {
+        if self.len() != json.len() {
+            return false;
+        }
+
+        (0..self.len()).all(|i| match json[i] {
+            JString(s) => self.is_valid(i) && hex_equals(s, self.value(i)),
+            JNull => self.is_null(i),
+            _ => false,
+        })
+    }
+}
+
+/// Checks whether `hex` is the hexadecimal encoding of `bytes`, in either case
+fn hex_equals(hex: &str, bytes: &[u8]) -> bool {
+    hex.len() == bytes.len() * 2
+        && hex.bytes().all(|c| c.is_ascii_hexdigit())
+        && bytes.iter().enumerate().all(|(i, b)| {
+            hex.get(i * 2..i * 2 + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                == Some(*b)
+        })
+}
+
+impl PartialEq<Value> for FixedSizeBinaryArray {
+    fn eq(&self, json: &Value) -> bool {
+        match json {
+            Value::Array(json_array) => self.equals_json_values(&json_array),
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<FixedSizeBinaryArray> for Value {
+    fn eq(&self, arrow: &FixedSizeBinaryArray) -> bool {
+        match self {
+            Value::Array(json_array) => arrow.equals_json_values(&json_array),
+            _ => false,
+        }
+    }
+}
+
+/// Values are compared with JSON strings holding their unscaled value, as written by
+/// the integration JSON format.
+impl JsonEqual for DecimalArray {
+    fn equals_json(&self, json: &[&Value]) -> bool {
+        if self.len() != json.len() {
+            return false;
+        }
+
+        (0..self.len()).all(|i| match json[i] {
+            JString(s) => {
+                self.is_valid(i) && s.parse::<i128>().ok() == Some(self.value(i))
+            }
+            JNull => self.is_null(i),
+            _ => false,
+        })
+    }
+}
+
+impl PartialEq<Value> for DecimalArray {
+    fn eq(&self, json: &Value) -> bool {
+        match json {
+            Value::Array(json_array) => self.equals_json_values(&json_array),
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<DecimalArray> for Value {
+    fn eq(&self, arrow: &DecimalArray) -> bool {
+        match self {
+            Value::Array(json_array) => arrow.equals_json_values(&json_array),
+            _ => false,
+        }
+    }
+}
+
+impl JsonEqual for StringArray {
+    fn equals_json(&self, json: &[&Value]) -> bool {
+        if self.len() != json.len() {
+            return false;
+        }
+
+        (0..self.len()).all(|i| match json[i] {
+            JString(s) => self.is_valid(i) && s.as_str() == self.value(i),
+            JNull => self.is_null(i),
+            _ => false,
+        })
+    }
+}
+
+impl PartialEq<Value> for StringArray {
+    fn eq(&self, json: &Value) -> bool {
+        match json {
+            Value::Array(json_array) => self.equals_json_values(&json_array),
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<StringArray> for Value {
+    fn eq(&self, arrow: &StringArray) -> bool {
+        match self {
+            Value::Array(json_array) => arrow.equals_json_values(&json_array),
+            _ => false,
+        }
+    }
+}
+
+impl JsonEqual for NullArray {
+    fn equals_json(&self, json: &[&Value]) -> bool {
+        self.len() == json.len() && json.iter().all(|v| v.is_null())
+    }
+}
+
+impl PartialEq<Value> for NullArray {
+    fn eq(&self, json: &Value) -> bool {
+        match json {
+            Value::Array(json_array) => self.equals_json_values(&json_array),
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<NullArray> for Value {
+    fn eq(&self, arrow: &NullArray) -> bool {
+        match self {
+            Value::Array(json_array) => arrow.equals_json_values(&json_array),
+            _ => false,
+        }
+    }
+}
+
+impl<K: ArrowDictionaryKeyType> JsonEqual for DictionaryArray<K> {
+    fn equals_json(&self, json: &[&Value]) -> bool {
+        if self.len() != json.len() {
+            return false;
+        }
+
+        let values = self.values();
+        (0..self.len()).all(|i| match (json[i], self.key(i)) {
+            (JNull, _) => self.keys().is_null(i),
+            (v, Some(key)) if key < values.len() => {
+                values.slice(key, 1).equals_json(&[v])
+            }
+            _ => false,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use std::convert::TryFrom;
+    use std::sync::Arc;
 
     use crate::error::Result;
 
@@ -735,6 +1175,71 @@ mod tests {
         assert!(b_slice.equals(&*a_slice));
     }
 
+    #[test]
+    fn test_array_equals_slices() {
+        let a = Int32Array::from(vec![Some(0), Some(1), None, Some(3)]);
+        let b = Int32Array::from(vec![Some(1), None, Some(3)]);
+        assert!(array_equals(&*a.slice(1, 3), &b));
+        assert!(array_equals(&b, &*a.slice(1, 3)));
+        assert!(!array_equals(&*a.slice(0, 3), &b));
+
+        let a = BinaryArray::try_from(vec![Some("x"), Some("hello"), None, Some("")])
+            .unwrap();
+        let b = StringArray::from(vec![Some("hello"), None, Some("")]);
+        assert!(array_equals(&*a.slice(1, 3), &b));
+        assert!(array_equals(&b, &*a.slice(1, 3)));
+
+        let mut builder = ListBuilder::new(Int32Builder::new(10));
+        let a = create_list_array(
+            &mut builder,
+            &[Some(&[0, 1][..]), Some(&[2, 3]), None, Some(&[4])],
+        )
+        .unwrap();
+        let b = create_list_array(&mut builder, &[Some(&[2, 3][..]), None, Some(&[4])])
+            .unwrap();
+        assert!(array_equals(&*a.slice(1, 3), &b));
+        assert!(array_equals(&*a.slice(1, 3), &*a.slice(1, 3)));
+        assert!(!array_equals(&*a.slice(0, 3), &b));
+
+        let a = StructArray::from(vec![(
+            Field::new("f", DataType::Int32, true),
+            Arc::new(Int32Array::from(vec![Some(7), None, Some(8)])) as ArrayRef,
+        )]);
+        let b = StructArray::from(vec![(
+            Field::new("f", DataType::Int32, true),
+            Arc::new(Int32Array::from(vec![None, Some(8)])) as ArrayRef,
+        )]);
+        assert!(array_equals(&*a.slice(1, 2), &b));
+        assert!(!array_equals(&*a.slice(0, 2), &b));
+    }
+
+    #[test]
+    fn test_array_equals_different_types() {
+        let a = Int32Array::from(vec![1, 2]);
+        let b = Int64Array::from(vec![1, 2]);
+        assert!(!array_equals(&a, &b));
+        assert!(!array_equals(&a, &Int32Array::from(vec![1])));
+    }
+
+    #[test]
+    fn test_float_equal_nan() {
+        let a = Float64Array::from(vec![Some(1.0), Some(std::f64::NAN), None]);
+        let b = Float64Array::from(vec![Some(1.0), Some(-std::f64::NAN), None]);
+        assert!(array_equals(&a, &b));
+        assert!(a.range_equals(&b, 1, 3, 1));
+        assert!(!array_equals(
+            &a,
+            &Float64Array::from(vec![Some(1.0), Some(2.0), None])
+        ));
+
+        let a = Float32Array::from(vec![std::f32::NAN, 2.0]);
+        assert!(array_equals(&a, &a));
+        assert!(array_equals(
+            &*a.slice(1, 1),
+            &Float32Array::from(vec![2.0])
+        ));
+    }
+
     #[test]
     fn test_boolean_equal() {
         let a = BooleanArray::from(vec![false, false, true]);
@@ -1193,6 +1698,46 @@ mod tests {
         assert!(json_array.ne(&arrow_array));
     }
 
+    fn create_large_list_array(offsets: &[i64], values: &[i32]) -> LargeListArray {
+        let values = Int32Array::from(values.to_vec());
+        let data = ArrayData::builder(DataType::LargeList(Box::new(DataType::Int32)))
+            .len(offsets.len() - 1)
+            .add_buffer(crate::buffer::Buffer::from(offsets.to_byte_slice()))
+            .add_child_data(values.data())
+            .build();
+        LargeListArray::from(data)
+    }
+
+    #[test]
+    fn test_large_list_equal() {
+        let a = create_large_list_array(&[0, 2, 2, 5], &[1, 2, 3, 4, 5]);
+        let b = create_large_list_array(&[0, 2, 2, 5], &[1, 2, 3, 4, 5]);
+        assert!(a.equals(&b));
+        assert!(b.equals(&a));
+
+        let b = create_large_list_array(&[0, 2, 3, 5], &[1, 2, 3, 4, 5]);
+        assert!(!a.equals(&b));
+        assert!(!b.equals(&a));
+
+        // Test the case where offset != 0
+        let b = create_large_list_array(&[0, 1, 3, 3, 6], &[0, 1, 2, 3, 4, 5]);
+        let b_slice = b.slice(1, 3);
+        assert!(a.equals(&*b_slice));
+        assert!(b_slice.equals(&a));
+    }
+
+    #[test]
+    fn test_large_list_json_equal() {
+        let arrow_array = create_large_list_array(&[0, 2, 2, 5], &[1, 2, 3, 4, 5]);
+        let json_array: Value = serde_json::from_str("[[1, 2], [], [3, 4, 5]]").unwrap();
+        assert!(arrow_array.eq(&json_array));
+        assert!(json_array.eq(&arrow_array));
+
+        let json_array: Value = serde_json::from_str("[[1, 2], [3], [4, 5]]").unwrap();
+        assert!(arrow_array.ne(&json_array));
+        assert!(json_array.ne(&arrow_array));
+    }
+
     #[test]
     fn test_fixed_size_list_json_equal() {
         // Test equal case
@@ -1361,6 +1906,158 @@ mod tests {
         assert!(json_array.ne(&arrow_array));
     }
 
+    fn create_fixed_size_binary_array(
+        values: &[u8],
+        validity: u8,
+    ) -> FixedSizeBinaryArray {
+        let data = ArrayData::builder(DataType::FixedSizeBinary(2))
+            .len(values.len() / 2)
+            .add_buffer(crate::buffer::Buffer::from(values))
+            .null_bit_buffer(crate::buffer::Buffer::from([validity]))
+            .build();
+        FixedSizeBinaryArray::from(data)
+    }
+
+    #[test]
+    fn test_fixed_size_binary_equal() {
+        let a = create_fixed_size_binary_array(&[1, 2, 0, 0, 0xab, 0xcd], 0b101);
+        let b = create_fixed_size_binary_array(&[1, 2, 9, 9, 0xab, 0xcd], 0b101);
+        assert!(a.equals(&b));
+        assert!(b.equals(&a));
+
+        let b = create_fixed_size_binary_array(&[1, 2, 0, 0, 0xab, 0xce], 0b101);
+        assert!(!a.equals(&b));
+
+        let b = create_fixed_size_binary_array(&[0, 0, 1, 2, 0xab, 0xcd], 0b110);
+        assert!(a.slice(2, 1).equals(&*b.slice(2, 1)));
+        assert!(!a.equals(&b));
+    }
+
+    #[test]
+    fn test_fixed_size_binary_json_equal() {
+        let arrow_array =
+            create_fixed_size_binary_array(&[1, 2, 0, 0, 0xab, 0xcd], 0b101);
+        let json_array: Value =
+            serde_json::from_str(r#"["0102", null, "ABcd"]"#).unwrap();
+        assert!(arrow_array.eq(&json_array));
+        assert!(json_array.eq(&arrow_array));
+
+        let json_array: Value =
+            serde_json::from_str(r#"["0102", null, "ABCE"]"#).unwrap();
+        assert!(arrow_array.ne(&json_array));
+        let json_array: Value = serde_json::from_str(r#"["01", null, "ABCD"]"#).unwrap();
+        assert!(arrow_array.ne(&json_array));
+    }
+
+    fn create_decimal_array(values: &[Option<i128>], precision: usize) -> DecimalArray {
+        let mut builder = DecimalBuilder::new(values.len(), precision, 2);
+        for v in values {
+            match v {
+                Some(v) => builder.append_value(*v).unwrap(),
+                None => builder.append_null().unwrap(),
+            }
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn test_decimal_equal() {
+        let a = create_decimal_array(&[Some(15000), None, Some(-1)], 6);
+        assert!(a.equals(&create_decimal_array(&[Some(15000), None, Some(-1)], 6)));
+        assert!(!a.equals(&create_decimal_array(&[Some(15000), None, Some(1)], 6)));
+        assert!(!a.equals(&create_decimal_array(&[Some(15000), None, Some(-1)], 7)));
+        assert!(a
+            .slice(1, 2)
+            .equals(&create_decimal_array(&[None, Some(-1)], 6)));
+    }
+
+    #[test]
+    fn test_decimal_json_equal() {
+        let arrow_array = create_decimal_array(&[Some(15000), None, Some(-1)], 6);
+        let json_array: Value = serde_json::from_str(r#"["15000", null, "-1"]"#).unwrap();
+        assert!(arrow_array.eq(&json_array));
+        assert!(json_array.eq(&arrow_array));
+
+        let json_array: Value =
+            serde_json::from_str(r#"["150.00", null, "-1"]"#).unwrap();
+        assert!(arrow_array.ne(&json_array));
+    }
+
+    #[test]
+    fn test_null_equal() {
+        let a = NullArray::new(4);
+        assert!(a.equals(&NullArray::new(4)));
+        assert!(!a.equals(&NullArray::new(3)));
+        assert!(a.slice(1, 3).equals(&NullArray::new(3)));
+        assert!(a.range_equals(&NullArray::new(2), 1, 3, 0));
+        assert!(!a.equals(&Int32Array::from(vec![None, None, None, None])));
+    }
+
+    #[test]
+    fn test_null_json_equal() {
+        let arrow_array = NullArray::new(3);
+        let json_array: Value = serde_json::from_str("[null, null, null]").unwrap();
+        assert!(arrow_array.eq(&json_array));
+        assert!(json_array.eq(&arrow_array));
+
+        let json_array: Value = serde_json::from_str("[null, 1, null]").unwrap();
+        assert!(arrow_array.ne(&json_array));
+        let json_array: Value = serde_json::from_str("[null, null]").unwrap();
+        assert!(arrow_array.ne(&json_array));
+    }
+
+    #[test]
+    fn test_dictionary_json_equal() {
+        let values = StringArray::from(vec!["a", "b"]);
+        let dictionary = |keys: &[i8], null_bits: u8| {
+            let data = ArrayData::builder(DataType::Dictionary(
+                Box::new(DataType::Int8),
+                Box::new(DataType::Utf8),
+            ))
+            .len(keys.len())
+            .add_buffer(crate::buffer::Buffer::from(keys.to_byte_slice()))
+            .null_bit_buffer(crate::buffer::Buffer::from([null_bits]))
+            .add_child_data(values.data())
+            .build();
+            Int8DictionaryArray::from(data)
+        };
+        let json_array: Vec<Value> = serde_json::from_str(r#"["a", "b", null]"#).unwrap();
+
+        assert!(dictionary(&[0, 1, 0], 0b011).equals_json_values(&json_array));
+        assert!(!dictionary(&[0, 0, 0], 0b011).equals_json_values(&json_array));
+        // keys out of bounds of the dictionary are not equal to any value
+        assert!(!dictionary(&[0, 2, 0], 0b011).equals_json_values(&json_array));
+        // a negative key is not null
+        assert!(!dictionary(&[0, 1, -1], 0b111).equals_json_values(&json_array));
+    }
+
+    #[test]
+    fn test_string_json_equal() {
+        let arrow_array = StringArray::from(vec![Some("hello"), None, Some("world")]);
+        let json_array: Value =
+            serde_json::from_str(r#"["hello", null, "world"]"#).unwrap();
+        assert!(arrow_array.eq(&json_array));
+        assert!(json_array.eq(&arrow_array));
+
+        let json_array: Value =
+            serde_json::from_str(r#"["hello", "arrow", "world"]"#).unwrap();
+        assert!(arrow_array.ne(&json_array));
+        assert!(json_array.ne(&arrow_array));
+    }
+
+    #[test]
+    fn test_string_binary_equal() {
+        let a = StringArray::from(vec![Some("hello"), None, Some("world")]);
+        let b = BinaryArray::try_from(vec![Some("hello"), None, Some("world")]).unwrap();
+        assert!(a.equals(&b));
+        assert!(b.equals(&a));
+
+        let b = BinaryArray::try_from(vec![Some("hello"), None, Some("arrow")]).unwrap();
+        assert!(!a.equals(&b));
+        assert!(!b.equals(&a));
+        assert!(a.range_equals(&b, 0, 2, 0));
+    }
+
     #[test]
     fn test_struct_json_equal() {
         // Test equal case
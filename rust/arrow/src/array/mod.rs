@@ -64,17 +64,28 @@ use crate::datatypes::*;
 // --------------------- Array & ArrayData ---------------------
 
 pub use self::array::Array;
+pub use self::array::ArrayDowncast;
 pub use self::array::ArrayRef;
 pub use self::data::ArrayData;
 pub use self::data::ArrayDataBuilder;
 pub use self::data::ArrayDataRef;
 
 pub use self::array::BinaryArray;
+pub use self::array::DecimalArray;
+pub use self::array::DecodedIter;
+pub use self::array::DictionaryArray;
+pub use self::array::FixedSizeBinaryArray;
 pub use self::array::FixedSizeListArray;
+pub use self::array::LargeListArray;
 pub use self::array::ListArray;
+pub use self::array::NullArray;
 pub use self::array::PrimitiveArray;
+pub use self::array::StringArray;
 pub use self::array::StructArray;
+pub use self::array::ValueRef;
 
+pub(crate) use self::array::array_value_to_string;
+pub(crate) use self::array::as_binary_array;
 pub(crate) use self::array::make_array;
 
 pub type BooleanArray = PrimitiveArray<BooleanType>;
@@ -101,6 +112,15 @@ pub type Time64MicrosecondArray = PrimitiveArray<Time64MicrosecondType>;
 pub type Time64NanosecondArray = PrimitiveArray<Time64NanosecondType>;
 // TODO add interval
 
+pub type Int8DictionaryArray = DictionaryArray<Int8Type>;
+pub type Int16DictionaryArray = DictionaryArray<Int16Type>;
+pub type Int32DictionaryArray = DictionaryArray<Int32Type>;
+pub type Int64DictionaryArray = DictionaryArray<Int64Type>;
+pub type UInt8DictionaryArray = DictionaryArray<UInt8Type>;
+pub type UInt16DictionaryArray = DictionaryArray<UInt16Type>;
+pub type UInt32DictionaryArray = DictionaryArray<UInt32Type>;
+pub type UInt64DictionaryArray = DictionaryArray<UInt64Type>;
+
 pub use self::array::ListArrayOps;
 pub use self::array::PrimitiveArrayOps;
 
@@ -108,6 +128,7 @@ pub use self::array::PrimitiveArrayOps;
 
 pub use self::builder::BufferBuilder;
 pub use self::builder::BufferBuilderTrait;
+pub use self::builder::NullableBooleanBufferBuilder;
 
 pub type BooleanBufferBuilder = BufferBuilder<BooleanType>;
 pub type Int8BufferBuilder = BufferBuilder<Int8Type>;
@@ -134,9 +155,11 @@ pub type Time64NanosecondBufferBuilder = BufferBuilder<Time64NanosecondType>;
 
 pub use self::builder::ArrayBuilder;
 pub use self::builder::BinaryBuilder;
+pub use self::builder::DecimalBuilder;
 pub use self::builder::FixedSizeListBuilder;
 pub use self::builder::ListBuilder;
 pub use self::builder::PrimitiveBuilder;
+pub use self::builder::StringDictionaryBuilder;
 pub use self::builder::StructBuilder;
 
 pub type BooleanBuilder = PrimitiveBuilder<BooleanType>;
@@ -164,5 +187,6 @@ pub type Time64NanosecondBuilder = PrimitiveBuilder<Time64NanosecondType>;
 
 // --------------------- Array Equality ---------------------
 
+pub use self::equal::array_equals;
 pub use self::equal::ArrayEqual;
 pub use self::equal::JsonEqual;
@@ -142,6 +142,26 @@ impl Buffer {
         unsafe { self.data.ptr.offset(self.offset as isize) }
     }
 
+    /// Returns whether the data of this buffer starts at a multiple of
+    /// `memory::ALIGNMENT` bytes.
+    ///
+    /// Buffers allocated by this crate are aligned, but a slice of one is not unless its
+    /// offset is a multiple of the alignment, nor need a buffer created from external
+    /// memory be.
+    pub fn is_aligned(&self) -> bool {
+        memory::is_aligned(self.raw_data(), memory::ALIGNMENT)
+    }
+
+    /// Returns this buffer if it is aligned, or a copy of its data in a newly allocated,
+    /// aligned buffer if it isn't.
+    pub fn to_aligned(&self) -> Self {
+        if self.is_aligned() {
+            self.clone()
+        } else {
+            Buffer::from(self.data())
+        }
+    }
+
     /// View buffer as typed slice.
     pub fn typed_data<T: ArrowNativeType + num::Num>(&self) -> &[T] {
         assert_eq!(self.len() % mem::size_of::<T>(), 0);
@@ -580,6 +600,18 @@ mod tests {
         assert!(buf4.is_empty());
     }
 
+    #[test]
+    fn test_is_aligned() {
+        let buf = Buffer::from(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert!(buf.is_aligned());
+
+        let sliced = buf.slice(1);
+        assert!(!sliced.is_aligned());
+        let aligned = sliced.to_aligned();
+        assert!(aligned.is_aligned());
+        assert_eq!(&[1, 2, 3, 4, 5, 6, 7], aligned.data());
+    }
+
     #[test]
     #[should_panic(
         expected = "the offset of the new Buffer cannot exceed the existing length"
@@ -18,16 +18,14 @@
 //! Defines primitive computations on arrays, e.g. addition, equality, boolean logic.
 
 use std::ops::Add;
-use std::sync::Arc;
-
-use crate::array::{
-    Array, ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array, Int16Array,
-    Int32Array, Int64Array, Int8Array, PrimitiveArray, UInt16Array, UInt32Array,
-    UInt64Array, UInt8Array,
-};
-use crate::datatypes::{ArrowNumericType, DataType};
+
+use crate::array::{Array, ArrayRef, BooleanArray, PrimitiveArray};
+use crate::compute::kernels::aggregate::{sum_with_options, AggregateOptions};
+use crate::datatypes::ArrowNumericType;
 use crate::error::{ArrowError, Result};
 
+pub use crate::compute::kernels::filter::filter;
+
 /// Returns the minimum value in the array, according to the natural order.
 pub fn min<T>(array: &PrimitiveArray<T>) -> Option<T::Native>
 where
@@ -71,36 +69,15 @@ where
 
 /// Returns the sum of values in the array.
 ///
-/// Returns `None` if the array is empty or only contains null values.
-pub fn sum<T>(array: &PrimitiveArray<T>) -> Option<T::Native>
+/// Returns `None` if the array is empty or only contains null values, and an error if
+/// an integer sum overflows, as with the default `AggregateOptions`. Use
+/// `sum_with_options` to wrap or saturate instead.
+pub fn sum<T>(array: &PrimitiveArray<T>) -> Result<Option<T::Native>>
 where
     T: ArrowNumericType,
     T::Native: Add<Output = T::Native>,
 {
-    let null_count = array.null_count();
-
-    if null_count == array.len() {
-        None
-    } else if null_count == 0 {
-        // optimized path for arrays without null values
-        let mut n: T::Native = T::default_value();
-        let data = array.data();
-        let m = array.value_slice(0, data.len());
-        for i in 0..data.len() {
-            n = n + m[i];
-        }
-        Some(n)
-    } else {
-        let mut n: T::Native = T::default_value();
-        let data = array.data();
-        let m = array.value_slice(0, data.len());
-        for i in 0..data.len() {
-            if data.is_valid(i) {
-                n = n + m[i];
-            }
-        }
-        Some(n)
-    }
+    sum_with_options(array, AggregateOptions::default())
 }
 
 /// Helper function to perform boolean lambda function on values from two arrays.
@@ -136,54 +113,6 @@ where
     Ok(b.finish())
 }
 
-macro_rules! filter_array {
-    ($array:expr, $filter:expr, $array_type:ident) => {{
-        let b = $array.as_any().downcast_ref::<$array_type>().unwrap();
-        let mut builder = $array_type::builder(b.len());
-        for i in 0..b.len() {
-            if $filter.value(i) {
-                if b.is_null(i) {
-                    builder.append_null()?;
-                } else {
-                    builder.append_value(b.value(i))?;
-                }
-            }
-        }
-        Ok(Arc::new(builder.finish()))
-    }};
-}
-
-/// Returns the array, taking only the elements matching the filter
-pub fn filter(array: &Array, filter: &BooleanArray) -> Result<ArrayRef> {
-    match array.data_type() {
-        DataType::UInt8 => filter_array!(array, filter, UInt8Array),
-        DataType::UInt16 => filter_array!(array, filter, UInt16Array),
-        DataType::UInt32 => filter_array!(array, filter, UInt32Array),
-        DataType::UInt64 => filter_array!(array, filter, UInt64Array),
-        DataType::Int8 => filter_array!(array, filter, Int8Array),
-        DataType::Int16 => filter_array!(array, filter, Int16Array),
-        DataType::Int32 => filter_array!(array, filter, Int32Array),
-        DataType::Int64 => filter_array!(array, filter, Int64Array),
-        DataType::Float32 => filter_array!(array, filter, Float32Array),
-        DataType::Float64 => filter_array!(array, filter, Float64Array),
-        DataType::Boolean => filter_array!(array, filter, BooleanArray),
-        DataType::Utf8 => {
-            let b = array.as_any().downcast_ref::<BinaryArray>().unwrap();
-            let mut values: Vec<&[u8]> = Vec::with_capacity(b.len());
-            for i in 0..b.len() {
-                if filter.value(i) {
-                    values.push(b.value(i));
-                }
-            }
-            Ok(Arc::new(BinaryArray::from(values)))
-        }
-        other => Err(ArrowError::ComputeError(format!(
-            "filter not supported for {:?}",
-            other
-        ))),
-    }
-}
-
 /// Returns the array, taking only the number of elements specified
 ///
 /// Limit performs a zero-copy slice of the array, and is a convenience method on slice
@@ -200,7 +129,7 @@ mod tests {
     use super::*;
     use crate::array::*;
     use crate::buffer::Buffer;
-    use crate::datatypes::{Field, ToByteSlice};
+    use crate::datatypes::{DataType, Field, ToByteSlice};
     use crate::util::bit_util;
 
     use std::sync::Arc;
@@ -208,25 +137,31 @@ mod tests {
     #[test]
     fn test_primitive_array_sum() {
         let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
-        assert_eq!(15, sum(&a).unwrap());
+        assert_eq!(Some(15), sum(&a).unwrap());
     }
 
     #[test]
     fn test_primitive_array_float_sum() {
         let a = Float64Array::from(vec![1.1, 2.2, 3.3, 4.4, 5.5]);
-        assert_eq!(16.5, sum(&a).unwrap());
+        assert_eq!(Some(16.5), sum(&a).unwrap());
     }
 
     #[test]
     fn test_primitive_array_sum_with_nulls() {
         let a = Int32Array::from(vec![None, Some(2), Some(3), None, Some(5)]);
-        assert_eq!(10, sum(&a).unwrap());
+        assert_eq!(Some(10), sum(&a).unwrap());
     }
 
     #[test]
     fn test_primitive_array_sum_all_nulls() {
         let a = Int32Array::from(vec![None, None, None]);
-        assert_eq!(None, sum(&a));
+        assert_eq!(None, sum(&a).unwrap());
+    }
+
+    #[test]
+    fn test_primitive_array_sum_overflow() {
+        let a = Int64Array::from(vec![std::i64::MAX, 1]);
+        assert!(sum(&a).is_err());
     }
 
     #[test]
@@ -243,38 +178,6 @@ mod tests {
         assert_eq!(9, max(&a).unwrap());
     }
 
-    #[test]
-    fn test_filter_array() {
-        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
-        let b = BooleanArray::from(vec![true, false, false, true, false]);
-        let c = filter(&a, &b).unwrap();
-        let d = c.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
-        assert_eq!(2, d.len());
-        assert_eq!(5, d.value(0));
-        assert_eq!(8, d.value(1));
-    }
-
-    #[test]
-    fn test_filter_binary_array() {
-        let a = BinaryArray::from(vec!["hello", " ", "world", "!"]);
-        let b = BooleanArray::from(vec![true, false, true, false]);
-        let c = filter(&a, &b).unwrap();
-        let d = c.as_ref().as_any().downcast_ref::<BinaryArray>().unwrap();
-        assert_eq!(2, d.len());
-        assert_eq!("hello", d.get_string(0));
-        assert_eq!("world", d.get_string(1));
-    }
-
-    #[test]
-    fn test_filter_array_with_null() {
-        let a = Int32Array::from(vec![Some(5), None]);
-        let b = BooleanArray::from(vec![false, true]);
-        let c = filter(&a, &b).unwrap();
-        let d = c.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
-        assert_eq!(1, d.len());
-        assert_eq!(true, d.is_null(0));
-    }
-
     #[test]
     fn test_limit_array() {
         let a: ArrayRef = Arc::new(Int32Array::from(vec![5, 6, 7, 8, 9]));
@@ -0,0 +1,1081 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines aggregate kernels that summarize the values of an array into a single value.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::{Add, Range};
+
+use num::traits::{CheckedAdd, Saturating, WrappingAdd};
+use num::Float;
+
+use crate::array::*;
+use crate::compute::kernels::sort::{value_comparator, SortOptions};
+use crate::compute::kernels::take::take;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::scalar::ScalarValue;
+
+/// What an integer aggregate does when its result overflows the integer type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverflowPolicy {
+    /// Wrap around at the boundary of the type
+    Wrapping,
+    /// Return an error
+    Checked,
+    /// Clamp the result to the minimum or maximum value of the type
+    Saturating,
+}
+
+/// Options that define how an aggregate is computed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AggregateOptions {
+    /// What to do when an integer result overflows. Defaults to `Checked`.
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for AggregateOptions {
+    fn default() -> Self {
+        AggregateOptions {
+            overflow: OverflowPolicy::Checked,
+        }
+    }
+}
+
+/// Adds two integers, handling an overflow according to `policy`
+pub(crate) fn add_with_overflow_policy<N>(a: N, b: N, policy: OverflowPolicy) -> Result<N>
+where
+    N: CheckedAdd + WrappingAdd + Saturating,
+{
+    match policy {
+        OverflowPolicy::Wrapping => Ok(a.wrapping_add(&b)),
+        OverflowPolicy::Saturating => Ok(a.saturating_add(b)),
+        OverflowPolicy::Checked => a.checked_add(&b).ok_or_else(|| {
+            ArrowError::ComputeError("Overflow happened on integer sum".to_string())
+        }),
+    }
+}
+
+/// Addition that handles an overflow of integers according to an `OverflowPolicy`,
+/// while other types, such as floats, are added with `+`
+pub(crate) trait PolicyAdd: Sized {
+    fn add_with_policy(self, rhs: Self, policy: OverflowPolicy) -> Result<Self>;
+}
+
+impl<N: Add<Output = N>> PolicyAdd for N {
+    default fn add_with_policy(self, rhs: Self, _policy: OverflowPolicy) -> Result<Self> {
+        Ok(self + rhs)
+    }
+}
+
+macro_rules! policy_add_int {
+    ($native_ty:ty) => {
+        impl PolicyAdd for $native_ty {
+            fn add_with_policy(self, rhs: Self, policy: OverflowPolicy) -> Result<Self> {
+                add_with_overflow_policy(self, rhs, policy)
+            }
+        }
+    };
+}
+
+policy_add_int!(i8);
+policy_add_int!(i16);
+policy_add_int!(i32);
+policy_add_int!(i64);
+policy_add_int!(u8);
+policy_add_int!(u16);
+policy_add_int!(u32);
+policy_add_int!(u64);
+
+/// How a quantile is computed when it falls between two values `lower` and `higher`
+/// of the sorted array.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+    /// `lower + (higher - lower) * fraction`, where `fraction` is the fractional part of
+    /// the index of the quantile.
+    Linear,
+    /// The value whose index is the nearest to the index of the quantile.
+    Nearest,
+    /// The lower of the two values.
+    Lower,
+    /// The higher of the two values.
+    Higher,
+}
+
+/// Options that define how a quantile is computed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuantileOptions {
+    /// How an exact quantile between two values is derived. Defaults to `Linear`.
+    pub interpolation: Interpolation,
+    /// The compression of a `TDigest` that estimates the quantile in bounded memory,
+    /// instead of sorting all values, or `None` to compute the quantile exactly.
+    /// Defaults to `None`.
+    pub approximate: Option<f64>,
+}
+
+impl Default for QuantileOptions {
+    fn default() -> Self {
+        QuantileOptions {
+            interpolation: Interpolation::Linear,
+            approximate: None,
+        }
+    }
+}
+
+/// Returns the `q`-th quantile of the non-null values in `array`, where `q` is in the
+/// range `0.0..=1.0`. For example, `q = 0.5` computes the median.
+///
+/// The exact quantile is located at index `q * (n - 1)` of the `n` sorted non-null
+/// values. When that index is not a whole number, the value is derived from the values
+/// on either side of it according to `options.interpolation`. `NaN` values sort after
+/// all other values. If `options.approximate` is set, the quantile is estimated with a
+/// `TDigest` instead, which ignores `NaN` values.
+///
+/// Returns `None` if the array is empty or only contains null values, and an
+/// `InvalidArgumentError` if `q` is not in the range `0.0..=1.0` or the compression is
+/// not positive.
+pub fn quantile<T>(
+    array: &PrimitiveArray<T>,
+    q: f64,
+    options: QuantileOptions,
+) -> Result<Option<f64>>
+where
+    T: ArrowNumericType,
+{
+    if !(q >= 0.0 && q <= 1.0) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "quantile must be between 0 and 1, got {}",
+            q
+        )));
+    }
+    match options.approximate {
+        Some(compression) if !(compression > 0.0) => {
+            Err(ArrowError::InvalidArgumentError(format!(
+                "compression must be positive, got {}",
+                compression
+            )))
+        }
+        Some(compression) => {
+            let mut digest = TDigest::new(compression);
+            digest.add_batch(array);
+            Ok(digest.quantile(q))
+        }
+        None => Ok(exact_quantile(array, q, options.interpolation)),
+    }
+}
+
+/// Computes the `q`-th quantile of `array` by sorting its non-null values
+fn exact_quantile<T>(
+    array: &PrimitiveArray<T>,
+    q: f64,
+    interpolation: Interpolation,
+) -> Option<f64>
+where
+    T: ArrowNumericType,
+{
+    let mut values: Vec<f64> = (0..array.len())
+        .filter(|i| array.is_valid(*i))
+        .filter_map(|i| array.value(i).to_f64())
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| {
+        a.partial_cmp(b)
+            .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+    });
+
+    let index = q * (values.len() - 1) as f64;
+    let lower = index.floor() as usize;
+    let higher = index.ceil() as usize;
+    let result = match interpolation {
+        Interpolation::Linear => {
+            let fraction = index - lower as f64;
+            if fraction == 0.0 {
+                values[lower]
+            } else {
+                values[lower] + (values[higher] - values[lower]) * fraction
+            }
+        }
+        Interpolation::Nearest => values[index.round() as usize],
+        Interpolation::Lower => values[lower],
+        Interpolation::Higher => values[higher],
+    };
+    Some(result)
+}
+
+/// Returns the median of the non-null values in `array`, interpolating linearly between
+/// the two middle values when there is an even number of them.
+///
+/// Returns `None` if the array is empty or only contains null values.
+pub fn median<T>(array: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: ArrowNumericType,
+{
+    exact_quantile(array, 0.5, Interpolation::Linear)
+}
+
+/// Running co-moments of the pairs of values of two arrays, updated with Welford's
+/// algorithm so that large values do not lose precision
+struct CoMoments {
+    count: usize,
+    mean_a: f64,
+    mean_b: f64,
+    /// Sum of the products of the deviations of `a` and `b` from their means
+    c_ab: f64,
+    /// Sum of the squared deviations of `a` from its mean
+    m2_a: f64,
+    /// Sum of the squared deviations of `b` from its mean
+    m2_b: f64,
+}
+
+impl CoMoments {
+    /// Computes the co-moments over the positions where both `a` and `b` are non-null
+    fn new<T>(a: &PrimitiveArray<T>, b: &PrimitiveArray<T>) -> Self
+    where
+        T: ArrowNumericType,
+    {
+        assert_eq!(
+            a.len(),
+            b.len(),
+            "arrays must have the same length to be paired"
+        );
+        let mut moments = CoMoments {
+            count: 0,
+            mean_a: 0.0,
+            mean_b: 0.0,
+            c_ab: 0.0,
+            m2_a: 0.0,
+            m2_b: 0.0,
+        };
+        for i in 0..a.len() {
+            if a.is_null(i) || b.is_null(i) {
+                continue;
+            }
+            if let (Some(x), Some(y)) = (a.value(i).to_f64(), b.value(i).to_f64()) {
+                moments.count += 1;
+                let n = moments.count as f64;
+                let delta_a = x - moments.mean_a;
+                let delta_b = y - moments.mean_b;
+                moments.mean_a += delta_a / n;
+                moments.mean_b += delta_b / n;
+                moments.c_ab += delta_a * (y - moments.mean_b);
+                moments.m2_a += delta_a * (x - moments.mean_a);
+                moments.m2_b += delta_b * (y - moments.mean_b);
+            }
+        }
+        moments
+    }
+}
+
+/// Returns the sample covariance of `a` and `b`, computed over the positions where both
+/// arrays are non-null.
+///
+/// Returns `None` if there are fewer than two such positions.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn covariance<T>(a: &PrimitiveArray<T>, b: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: ArrowNumericType,
+{
+    let moments = CoMoments::new(a, b);
+    if moments.count < 2 {
+        return None;
+    }
+    Some(moments.c_ab / (moments.count - 1) as f64)
+}
+
+/// Returns the Pearson correlation coefficient of `a` and `b`, computed over the
+/// positions where both arrays are non-null.
+///
+/// Returns `None` if there are fewer than two such positions. The result is `NaN` when
+/// the values of either array are all equal.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn correlation<T>(a: &PrimitiveArray<T>, b: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: ArrowNumericType,
+{
+    let moments = CoMoments::new(a, b);
+    if moments.count < 2 {
+        return None;
+    }
+    Some(moments.c_ab / (moments.m2_a * moments.m2_b).sqrt())
+}
+
+/// A cluster of nearby values of a `TDigest`, represented by their mean
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// An approximation of the distribution of a stream of values that answers quantile
+/// and CDF queries with bounded memory.
+///
+/// Values are added one batch at a time with `add_batch`, and digests built from
+/// different parts of a stream, e.g. by parallel workers, can be combined with `merge`.
+/// Null and `NaN` values are ignored.
+///
+/// # Accuracy
+///
+/// The digest keeps a number of clusters of values that is proportional to
+/// `compression` and only grows logarithmically with the number of values added.
+/// Clusters are kept smaller where the quantile `q` is close to 0 or 1, so that the
+/// error of an estimated quantile, measured as a fraction of the number of values, is
+/// roughly bounded by `q * (1 - q) / compression`. Estimates are therefore most
+/// accurate at the tails, and the minimum and maximum are exact. The bound is typical rather than guaranteed,
+/// since it depends on the order in which values are added.
+#[derive(Debug, Clone)]
+pub struct TDigest {
+    compression: f64,
+    /// Clusters sorted by their means
+    centroids: Vec<Centroid>,
+    count: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        TDigest::new(100.0)
+    }
+}
+
+impl TDigest {
+    /// Creates an empty digest, where a larger `compression` trades memory for accuracy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `compression` is not positive.
+    pub fn new(compression: f64) -> Self {
+        assert!(
+            compression > 0.0,
+            "compression must be positive, got {}",
+            compression
+        );
+        TDigest {
+            compression,
+            centroids: vec![],
+            count: 0.0,
+            min: std::f64::INFINITY,
+            max: std::f64::NEG_INFINITY,
+        }
+    }
+
+    /// Returns the number of values added to the digest.
+    pub fn count(&self) -> u64 {
+        self.count as u64
+    }
+
+    /// Adds the non-null values of `array` to the digest.
+    pub fn add_batch<T>(&mut self, array: &PrimitiveArray<T>)
+    where
+        T: ArrowNumericType,
+    {
+        let values = (0..array.len())
+            .filter(|i| array.is_valid(*i))
+            .filter_map(|i| array.value(i).to_f64())
+            .filter(|v| !v.is_nan())
+            .map(|mean| Centroid { mean, weight: 1.0 });
+        self.compress(values.collect());
+    }
+
+    /// Adds the values summarized by `other` to this digest.
+    pub fn merge(&mut self, other: &TDigest) {
+        // the extremes of `other` may have been merged into its clusters
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress(other.centroids.clone());
+    }
+
+    /// Returns an estimate of the `q`-th quantile of the values, where `q` is in the
+    /// range `0.0..=1.0`, or `None` if no values were added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not in the range `0.0..=1.0`.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        assert!(
+            q >= 0.0 && q <= 1.0,
+            "quantile must be between 0 and 1, got {}",
+            q
+        );
+        if self.centroids.is_empty() {
+            return None;
+        }
+        let rank = q * self.count;
+        // the mean of each centroid is placed at the middle of its range of ranks,
+        // and values are interpolated linearly between those points
+        let mut previous = (0.0, self.min);
+        let mut cumulative = 0.0;
+        for centroid in &self.centroids {
+            let center = cumulative + centroid.weight / 2.0;
+            if rank < center {
+                return Some(interpolate(previous, (center, centroid.mean), rank));
+            }
+            previous = (center, centroid.mean);
+            cumulative += centroid.weight;
+        }
+        Some(interpolate(previous, (self.count, self.max), rank))
+    }
+
+    /// Returns an estimate of the fraction of the values that are less than or equal
+    /// to `value`, or `None` if no values were added.
+    pub fn cdf(&self, value: f64) -> Option<f64> {
+        if self.centroids.is_empty() {
+            return None;
+        }
+        if value < self.min {
+            return Some(0.0);
+        }
+        if value >= self.max {
+            return Some(1.0);
+        }
+        let mut previous = (self.min, 0.0);
+        let mut cumulative = 0.0;
+        for centroid in &self.centroids {
+            let center = cumulative + centroid.weight / 2.0;
+            if value < centroid.mean {
+                let rank = interpolate(previous, (centroid.mean, center), value);
+                return Some(rank / self.count);
+            }
+            previous = (centroid.mean, center);
+            cumulative += centroid.weight;
+        }
+        let rank = interpolate(previous, (self.max, self.count), value);
+        Some(rank / self.count)
+    }
+
+    /// Merges `incoming` into the clusters of the digest, combining adjacent clusters
+    /// for as long as they stay within the size limit of their quantile.
+    fn compress(&mut self, mut incoming: Vec<Centroid>) {
+        if incoming.is_empty() {
+            return;
+        }
+        for centroid in &incoming {
+            self.count += centroid.weight;
+            self.min = self.min.min(centroid.mean);
+            self.max = self.max.max(centroid.mean);
+        }
+        incoming.append(&mut self.centroids);
+        incoming.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap());
+
+        let mut centroids: Vec<Centroid> = Vec::with_capacity(incoming.len());
+        let mut cumulative = 0.0;
+        for centroid in incoming {
+            if let Some(last) = centroids.last_mut() {
+                let weight = last.weight + centroid.weight;
+                let q = (cumulative + weight / 2.0) / self.count;
+                let limit = 4.0 * self.count * q * (1.0 - q) / self.compression;
+                if weight <= limit {
+                    last.mean += (centroid.mean - last.mean) * centroid.weight / weight;
+                    last.weight = weight;
+                    continue;
+                }
+                cumulative += last.weight;
+            }
+            centroids.push(centroid);
+        }
+        self.centroids = centroids;
+    }
+}
+
+/// Returns the `y` of the point at `x` on the line through the points `from` and `to`
+fn interpolate(from: (f64, f64), to: (f64, f64), x: f64) -> f64 {
+    if to.0 <= from.0 {
+        return to.1;
+    }
+    from.1 + (to.1 - from.1) * (x - from.0) / (to.0 - from.0)
+}
+
+/// Returns the sum of the non-null values in `array`, handling an overflow of an
+/// integer sum according to `options.overflow`. Float values are added with `+`.
+///
+/// Returns `None` if the array is empty or only contains null values.
+pub fn sum_with_options<T>(
+    array: &PrimitiveArray<T>,
+    options: AggregateOptions,
+) -> Result<Option<T::Native>>
+where
+    T: ArrowNumericType,
+    T::Native: Add<Output = T::Native>,
+{
+    let mut sum: Option<T::Native> = None;
+    for i in 0..array.len() {
+        if array.is_valid(i) {
+            let value = array.value(i);
+            sum = Some(match sum {
+                None => value,
+                Some(sum) => sum.add_with_policy(value, options.overflow)?,
+            });
+        }
+    }
+    Ok(sum)
+}
+
+/// Counts the occurrences of each non-null value of an array
+macro_rules! count_values {
+    ($ARRAY:expr, $ARRAY_TYPE:ident, $VARIANT:ident) => {{
+        let array = $ARRAY.as_any().downcast_ref::<$ARRAY_TYPE>().unwrap();
+        let mut counts = HashMap::new();
+        for i in 0..array.len() {
+            if array.is_valid(i) {
+                *counts
+                    .entry(ScalarValue::$VARIANT(array.value(i)))
+                    .or_insert(0) += 1;
+            }
+        }
+        counts
+    }};
+}
+
+/// Returns the most frequent non-null value in `array`.
+///
+/// Ties are broken by returning the smallest of the most frequent values, where `NaN`
+/// is greater than all other values, so the result does not depend on the order of the
+/// values.
+///
+/// Returns `None` if the array is empty or only contains null values. Primitive and
+/// string (`Utf8`) arrays are supported; `None` is returned for other types.
+pub fn mode(array: &Array) -> Option<ScalarValue> {
+    let counts: HashMap<ScalarValue, usize> = match array.data_type() {
+        DataType::Boolean => count_values!(array, BooleanArray, Boolean),
+        DataType::Int8 => count_values!(array, Int8Array, Int8),
+        DataType::Int16 => count_values!(array, Int16Array, Int16),
+        DataType::Int32 => count_values!(array, Int32Array, Int32),
+        DataType::Int64 => count_values!(array, Int64Array, Int64),
+        DataType::UInt8 => count_values!(array, UInt8Array, UInt8),
+        DataType::UInt16 => count_values!(array, UInt16Array, UInt16),
+        DataType::UInt32 => count_values!(array, UInt32Array, UInt32),
+        DataType::UInt64 => count_values!(array, UInt64Array, UInt64),
+        DataType::Float32 => count_values!(array, Float32Array, Float32),
+        DataType::Float64 => count_values!(array, Float64Array, Float64),
+        DataType::Utf8 => {
+            let array = as_binary_array(array);
+            let mut counts = HashMap::new();
+            for i in 0..array.len() {
+                if array.is_valid(i) {
+                    *counts
+                        .entry(ScalarValue::Utf8(array.get_string(i)))
+                        .or_insert(0) += 1;
+                }
+            }
+            counts
+        }
+        _ => return None,
+    };
+
+    let mut mode: Option<(ScalarValue, usize)> = None;
+    for (value, count) in counts {
+        let is_better = match &mode {
+            None => true,
+            Some((mode_value, mode_count)) => {
+                count > *mode_count
+                    || (count == *mode_count
+                        && compare_scalars(&value, mode_value) == Ordering::Less)
+            }
+        };
+        if is_better {
+            mode = Some((value, count));
+        }
+    }
+    mode.map(|(value, _)| value)
+}
+
+/// Compares two scalar values of the same type, with `NaN` greater than all numbers
+fn compare_scalars(a: &ScalarValue, b: &ScalarValue) -> Ordering {
+    fn compare_floats<F: Float>(a: F, b: F) -> Ordering {
+        a.partial_cmp(&b)
+            .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+    }
+    match (a, b) {
+        (ScalarValue::Boolean(a), ScalarValue::Boolean(b)) => a.cmp(b),
+        (ScalarValue::Int8(a), ScalarValue::Int8(b)) => a.cmp(b),
+        (ScalarValue::Int16(a), ScalarValue::Int16(b)) => a.cmp(b),
+        (ScalarValue::Int32(a), ScalarValue::Int32(b)) => a.cmp(b),
+        (ScalarValue::Int64(a), ScalarValue::Int64(b)) => a.cmp(b),
+        (ScalarValue::UInt8(a), ScalarValue::UInt8(b)) => a.cmp(b),
+        (ScalarValue::UInt16(a), ScalarValue::UInt16(b)) => a.cmp(b),
+        (ScalarValue::UInt32(a), ScalarValue::UInt32(b)) => a.cmp(b),
+        (ScalarValue::UInt64(a), ScalarValue::UInt64(b)) => a.cmp(b),
+        (ScalarValue::Float32(a), ScalarValue::Float32(b)) => compare_floats(*a, *b),
+        (ScalarValue::Float64(a), ScalarValue::Float64(b)) => compare_floats(*a, *b),
+        (ScalarValue::Utf8(a), ScalarValue::Utf8(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+/// An index of an array in a heap, ordered by the values of the array
+struct HeapEntry<'a> {
+    index: usize,
+    cmp: &'a Fn(usize, usize) -> Ordering,
+}
+
+impl<'a> Ord for HeapEntry<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.cmp)(self.index, other.index)
+    }
+}
+
+impl<'a> PartialOrd for HeapEntry<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for HeapEntry<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for HeapEntry<'a> {}
+
+/// Returns the first `k` elements of `array` in the order given by `options`, i.e. the
+/// `k` largest elements in descending order if `options.descending` is set, and the
+/// `k` smallest elements in ascending order otherwise.
+///
+/// The result is the same as sorting the array and taking its first `k` elements, so
+/// null values come first if `options.nulls_first` is set, and are only returned when
+/// there are fewer than `k` non-null values otherwise. Equal values keep their order
+/// in `array`. The non-null values are selected with a heap of `k` elements in
+/// `O(n log k)` time, instead of sorting all of them.
+///
+/// Supports the same types as `sort_to_indices`.
+pub fn top_k(array: &Array, k: usize, options: SortOptions) -> Result<ArrayRef> {
+    let values = value_comparator(array)?;
+    let cmp = |i: usize, j: usize| {
+        let ordering = values(i, j);
+        let ordering = if options.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        ordering.then(i.cmp(&j))
+    };
+
+    let (valid, nulls): (Vec<usize>, Vec<usize>) =
+        (0..array.len()).partition(|i| array.is_valid(*i));
+    let num_nulls = if options.nulls_first {
+        k.min(nulls.len())
+    } else {
+        k.saturating_sub(valid.len()).min(nulls.len())
+    };
+    let num_valid = (k - num_nulls).min(valid.len());
+
+    // the heap holds the best values seen so far, with the worst of them on top
+    let mut heap = BinaryHeap::with_capacity(num_valid + 1);
+    for index in valid {
+        let entry = HeapEntry { index, cmp: &cmp };
+        if heap.len() < num_valid {
+            heap.push(entry);
+        } else if let Some(mut worst) = heap.peek_mut() {
+            if entry < *worst {
+                *worst = entry;
+            }
+        }
+    }
+    let top: Vec<usize> = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|entry| entry.index)
+        .collect();
+
+    let nulls = nulls.into_iter().take(num_nulls);
+    let indices: Vec<u32> = if options.nulls_first {
+        nulls.chain(top.into_iter()).map(|i| i as u32).collect()
+    } else {
+        top.into_iter().chain(nulls).map(|i| i as u32).collect()
+    };
+    take(&make_array(array.data()), &UInt32Array::from(indices), None)
+}
+
+/// Returns the first value of each group of rows of `array`, where the groups are
+/// ranges of rows such as those returned by `lexicographical_partition_ranges`.
+///
+/// When `ignore_nulls` is set, the first non-null value of each group is returned
+/// instead. The result has one value per group, which is null if the value is null or
+/// the group is empty.
+pub fn first(
+    array: &Array,
+    groups: &[Range<usize>],
+    ignore_nulls: bool,
+) -> Result<ArrayRef> {
+    take_per_group(array, groups, |group| {
+        if ignore_nulls {
+            group.clone().find(|i| array.is_valid(*i))
+        } else {
+            group.clone().next()
+        }
+    })
+}
+
+/// Returns the last value of each group of rows of `array`, where the groups are
+/// ranges of rows such as those returned by `lexicographical_partition_ranges`.
+///
+/// When `ignore_nulls` is set, the last non-null value of each group is returned
+/// instead. The result has one value per group, which is null if the value is null or
+/// the group is empty.
+pub fn last(
+    array: &Array,
+    groups: &[Range<usize>],
+    ignore_nulls: bool,
+) -> Result<ArrayRef> {
+    take_per_group(array, groups, |group| {
+        if ignore_nulls {
+            group.clone().rev().find(|i| array.is_valid(*i))
+        } else {
+            group.clone().next_back()
+        }
+    })
+}
+
+/// Takes the value at the index chosen by `pick` from each group, or a null if no index
+/// is chosen
+fn take_per_group<F>(array: &Array, groups: &[Range<usize>], pick: F) -> Result<ArrayRef>
+where
+    F: Fn(&Range<usize>) -> Option<usize>,
+{
+    if let Some(group) = groups.iter().find(|group| group.end > array.len()) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Group {:?} is out of bounds of an array of length {}",
+            group,
+            array.len()
+        )));
+    }
+    let indices: Vec<Option<u32>> = groups
+        .iter()
+        .map(|group| pick(group).map(|i| i as u32))
+        .collect();
+    take(&make_array(array.data()), &UInt32Array::from(indices), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+    use std::sync::Arc;
+
+    fn interpolated(interpolation: Interpolation) -> QuantileOptions {
+        QuantileOptions {
+            interpolation,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_quantile_linear() {
+        let a = Int32Array::from(vec![4, 1, 3, 2]);
+        let options = QuantileOptions::default();
+        assert_eq!(Some(2.5), quantile(&a, 0.5, options).unwrap());
+        assert_eq!(Some(1.0), quantile(&a, 0.0, options).unwrap());
+        assert_eq!(Some(4.0), quantile(&a, 1.0, options).unwrap());
+        assert_eq!(Some(1.75), quantile(&a, 0.25, options).unwrap());
+        assert_eq!(Some(2.5), median(&a));
+    }
+
+    #[test]
+    fn test_quantile_interpolation() {
+        let a = Float64Array::from(vec![1.0, 2.0, 3.0, 4.0]);
+        let quantile_with =
+            |q, interpolation| quantile(&a, q, interpolated(interpolation)).unwrap();
+        assert_eq!(Some(2.0), quantile_with(0.5, Interpolation::Lower));
+        assert_eq!(Some(3.0), quantile_with(0.5, Interpolation::Higher));
+        assert_eq!(Some(2.0), quantile_with(0.4, Interpolation::Nearest));
+        assert_eq!(Some(3.0), quantile_with(0.6, Interpolation::Nearest));
+    }
+
+    #[test]
+    fn test_quantile_with_nulls() {
+        let options = QuantileOptions::default();
+        let a = Int64Array::from(vec![Some(10), None, Some(30), None, Some(20)]);
+        assert_eq!(Some(20.0), quantile(&a, 0.5, options).unwrap());
+
+        let a = Int64Array::from(vec![None, None]);
+        assert_eq!(None, quantile(&a, 0.5, options).unwrap());
+
+        let a = Int64Array::from(Vec::<i64>::new());
+        assert_eq!(None, quantile(&a, 0.5, options).unwrap());
+    }
+
+    #[test]
+    fn test_quantile_approximate() {
+        let a = Float64Array::from((0..1000).map(|v| v as f64).collect::<Vec<_>>());
+        let options = QuantileOptions {
+            approximate: Some(100.0),
+            ..Default::default()
+        };
+        let estimate = quantile(&a, 0.5, options).unwrap().unwrap();
+        assert!((estimate - 499.5).abs() < 10.0, "estimate {}", estimate);
+        assert_eq!(Some(0.0), quantile(&a, 0.0, options).unwrap());
+        assert_eq!(Some(999.0), quantile(&a, 1.0, options).unwrap());
+
+        let a = Int32Array::from(vec![None, None]);
+        assert_eq!(None, quantile(&a, 0.5, options).unwrap());
+    }
+
+    #[test]
+    fn test_quantile_invalid_arguments() {
+        let a = Int32Array::from(vec![1, 2]);
+        for q in &[1.5, -0.1, std::f64::NAN] {
+            match quantile(&a, *q, QuantileOptions::default()) {
+                Err(ArrowError::InvalidArgumentError(_)) => {}
+                other => panic!("Expected an invalid argument error, got {:?}", other),
+            }
+        }
+        let options = QuantileOptions {
+            approximate: Some(0.0),
+            ..Default::default()
+        };
+        match quantile(&a, 0.5, options) {
+            Err(ArrowError::InvalidArgumentError(_)) => {}
+            other => panic!("Expected an invalid argument error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_covariance_and_correlation() {
+        let a = Float64Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        let b = Float64Array::from(vec![3.0, 5.0, 7.0, 9.0, 11.0]);
+        assert_eq!(Some(1.0), correlation(&a, &b));
+        assert_eq!(Some(5.0), covariance(&a, &b));
+
+        let c = Float64Array::from(vec![10.0, 8.0, 6.0, 4.0, 2.0]);
+        assert_eq!(Some(-1.0), correlation(&a, &c));
+        assert_eq!(Some(-5.0), covariance(&a, &c));
+    }
+
+    #[test]
+    fn test_correlation_with_nulls() {
+        let a = Int32Array::from(vec![Some(1), None, Some(2), Some(3), Some(100)]);
+        let b = Int32Array::from(vec![Some(2), Some(7), Some(4), Some(6), None]);
+        assert_eq!(Some(1.0), correlation(&a, &b));
+        assert_eq!(Some(2.0), covariance(&a, &b));
+
+        let a = Int32Array::from(vec![Some(1), None, Some(2)]);
+        let b = Int32Array::from(vec![Some(2), Some(7), None]);
+        assert_eq!(None, correlation(&a, &b));
+        assert_eq!(None, covariance(&a, &b));
+    }
+
+    /// The values `0..10007` in a scrambled order, split into batches
+    fn scrambled_batches() -> Vec<Int64Array> {
+        let values: Vec<i64> = (0..10007).map(|i| (i * 7919) % 10007).collect();
+        values
+            .chunks(1000)
+            .map(|chunk| Int64Array::from(chunk.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_tdigest() {
+        let mut digest = TDigest::default();
+        assert_eq!(None, digest.quantile(0.5));
+        assert_eq!(None, digest.cdf(0.0));
+        for batch in scrambled_batches() {
+            digest.add_batch(&batch);
+        }
+        digest.add_batch(&Int64Array::from(vec![None, None]));
+
+        assert_eq!(10007, digest.count());
+        assert!((digest.quantile(0.5).unwrap() - 5003.0).abs() < 20.0);
+        assert!((digest.quantile(0.99).unwrap() - 9906.0).abs() < 20.0);
+        assert_eq!(Some(0.0), digest.quantile(0.0));
+        assert_eq!(Some(10006.0), digest.quantile(1.0));
+        assert!((digest.cdf(1000.0).unwrap() - 0.1).abs() < 0.002);
+        assert_eq!(Some(0.0), digest.cdf(-1.0));
+        assert_eq!(Some(1.0), digest.cdf(10006.0));
+    }
+
+    #[test]
+    fn test_tdigest_merge() {
+        let batches = scrambled_batches();
+        let (left, right) = batches.split_at(batches.len() / 2);
+        let mut digest = TDigest::new(100.0);
+        let mut other = TDigest::new(100.0);
+        left.iter().for_each(|batch| digest.add_batch(batch));
+        right.iter().for_each(|batch| other.add_batch(batch));
+        digest.merge(&other);
+
+        assert_eq!(10007, digest.count());
+        assert!((digest.quantile(0.5).unwrap() - 5003.0).abs() < 20.0);
+        assert!((digest.cdf(2500.0).unwrap() - 0.25).abs() < 0.002);
+    }
+
+    #[test]
+    fn test_sum_overflow_policy() {
+        let a = Int64Array::from(vec![Some(std::i64::MAX), None, Some(1)]);
+        let options = |overflow| AggregateOptions { overflow };
+
+        assert!(sum_with_options(&a, AggregateOptions::default()).is_err());
+        assert_eq!(
+            Some(std::i64::MIN),
+            sum_with_options(&a, options(OverflowPolicy::Wrapping)).unwrap()
+        );
+        assert_eq!(
+            Some(std::i64::MAX),
+            sum_with_options(&a, options(OverflowPolicy::Saturating)).unwrap()
+        );
+
+        let a = Int8Array::from(vec![-100, -100]);
+        assert_eq!(
+            Some(std::i8::MIN),
+            sum_with_options(&a, options(OverflowPolicy::Saturating)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sum_with_options() {
+        let a = Int32Array::from(vec![Some(1), None, Some(2), Some(3)]);
+        assert_eq!(Some(6), sum_with_options(&a, Default::default()).unwrap());
+
+        let a = Int32Array::from(vec![None, None]);
+        assert_eq!(None, sum_with_options(&a, Default::default()).unwrap());
+    }
+
+    #[test]
+    fn test_mode() {
+        let a = Int32Array::from(vec![1, 2, 2, 3, 3, 3]);
+        assert_eq!(Some(ScalarValue::Int32(3)), mode(&a));
+
+        // ties resolve to the smallest value
+        let a = Int32Array::from(vec![Some(5), None, Some(2), Some(5), Some(2), None]);
+        assert_eq!(Some(ScalarValue::Int32(2)), mode(&a));
+
+        let a = Float64Array::from(vec![std::f64::NAN, 1.5, std::f64::NAN, 1.5]);
+        assert_eq!(Some(ScalarValue::Float64(1.5)), mode(&a));
+
+        let a = Int32Array::from(vec![None, None]);
+        assert_eq!(None, mode(&a));
+        let a = Int32Array::from(Vec::<i32>::new());
+        assert_eq!(None, mode(&a));
+    }
+
+    #[test]
+    fn test_mode_string() {
+        let a = BinaryArray::from(vec!["b", "a", "c", "b", "a"]);
+        assert_eq!(Some(ScalarValue::Utf8("a".to_string())), mode(&a));
+        let a = StringArray::from(vec![Some("b"), None, Some("c"), Some("b")]);
+        assert_eq!(Some(ScalarValue::Utf8("b".to_string())), mode(&a));
+    }
+
+    #[test]
+    fn test_first_last() {
+        // two groups whose boundary values are null
+        let a = Int32Array::from(vec![None, Some(1), Some(2), None, None, Some(3), None]);
+        let groups = vec![0..4, 4..7];
+
+        let check = |result: ArrayRef, expected: Vec<Option<i32>>| {
+            let result = result.as_any().downcast_ref::<Int32Array>().unwrap();
+            let result: Vec<Option<i32>> = (0..result.len())
+                .map(|i| {
+                    if result.is_valid(i) {
+                        Some(result.value(i))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            assert_eq!(expected, result);
+        };
+        check(first(&a, &groups, true).unwrap(), vec![Some(1), Some(3)]);
+        check(last(&a, &groups, true).unwrap(), vec![Some(2), Some(3)]);
+        check(first(&a, &groups, false).unwrap(), vec![None, None]);
+        check(last(&a, &groups, false).unwrap(), vec![None, None]);
+
+        // groups without a non-null value
+        let groups = vec![3..5, 5..5];
+        check(first(&a, &groups, true).unwrap(), vec![None, None]);
+        check(last(&a, &groups, true).unwrap(), vec![None, None]);
+
+        assert!(first(&a, &[0..8], true).is_err());
+    }
+
+    #[test]
+    fn test_first_last_string() {
+        let a =
+            BinaryArray::try_from(vec![Some("a"), None, Some("b"), Some("c")]).unwrap();
+        let result = last(&a, &[0..2, 2..4], true).unwrap();
+        let result = result.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("a", result.get_string(0));
+        assert_eq!("c", result.get_string(1));
+    }
+
+    #[test]
+    fn test_top_k() {
+        use crate::compute::kernels::sort::sort_to_indices;
+
+        // a permutation of 0..1000
+        let values: Vec<i32> = (0..1000).map(|i| (i * 367) % 1000).collect();
+        let a: ArrayRef = Arc::new(Int32Array::from(values));
+        for descending in &[true, false] {
+            let options = SortOptions {
+                descending: *descending,
+                nulls_first: false,
+            };
+            let top = top_k(a.as_ref(), 5, options).unwrap();
+            let indices = sort_to_indices(a.as_ref(), options).unwrap();
+            let sorted = take(&a, &indices, None).unwrap();
+            assert!(top.equals(sorted.slice(0, 5).as_ref()));
+        }
+
+        let top = top_k(
+            a.as_ref(),
+            5,
+            SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+        )
+        .unwrap();
+        let top = top.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(
+            vec![999, 998, 997, 996, 995],
+            top.value_slice(0, 5).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_top_k_with_nulls() {
+        let a = Float64Array::from(vec![Some(2.0), None, Some(5.0), Some(1.0), None]);
+        let options = SortOptions {
+            descending: true,
+            nulls_first: true,
+        };
+        let top = top_k(&a, 3, options).unwrap();
+        let expected = Float64Array::from(vec![None, None, Some(5.0)]);
+        assert!(top.equals(&expected));
+
+        let options = SortOptions {
+            descending: false,
+            nulls_first: false,
+        };
+        let top = top_k(&a, 4, options).unwrap();
+        let expected = Float64Array::from(vec![Some(1.0), Some(2.0), Some(5.0), None]);
+        assert!(top.equals(&expected));
+
+        assert_eq!(5, top_k(&a, 10, options).unwrap().len());
+        assert_eq!(0, top_k(&a, 0, options).unwrap().len());
+    }
+}
@@ -22,6 +22,7 @@
 //! `RUSTFLAGS="-C target-feature=+avx2"` for example.  See the documentation
 //! [here](https://doc.rust-lang.org/stable/core/arch/) for more information.
 
+use std::cmp::Ordering;
 #[cfg(feature = "simd")]
 use std::mem;
 use std::ops::{Add, Div, Mul, Sub};
@@ -37,10 +38,13 @@ use crate::array::*;
 use crate::bitmap::Bitmap;
 #[cfg(feature = "simd")]
 use crate::buffer::MutableBuffer;
+use crate::compute::kernels::sort::total_cmp;
 #[cfg(feature = "simd")]
-use crate::compute::util::{apply_bin_op_to_option_bitmap, simd_load_set_invalid};
+use crate::compute::util::{combine_option_bitmaps, simd_load_set_invalid};
 use crate::datatypes;
 use crate::error::{ArrowError, Result};
+#[cfg(feature = "simd")]
+use crate::util::bit_util;
 
 /// Helper function to perform math lambda function on values from two arrays. If either
 /// left or right value is null then the output value is also null, so `1 + null` is
@@ -72,11 +76,16 @@ where
 }
 
 /// SIMD vectorized version of `math_op` above.
+///
+/// `simd_op` is applied to the full SIMD chunks of the arrays and `scalar_op` to the
+/// values after the last full chunk, so that no values are read past the end of the
+/// arrays.
 #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "simd"))]
-fn simd_math_op<T, F>(
+fn simd_math_op<T, SIMD_OP, SCALAR_OP>(
     left: &PrimitiveArray<T>,
     right: &PrimitiveArray<T>,
-    op: F,
+    simd_op: SIMD_OP,
+    scalar_op: SCALAR_OP,
 ) -> Result<PrimitiveArray<T>>
 where
     T: datatypes::ArrowNumericType,
@@ -84,7 +93,8 @@ where
         + Sub<Output = T::Simd>
         + Mul<Output = T::Simd>
         + Div<Output = T::Simd>,
-    F: Fn(T::Simd, T::Simd) -> T::Simd,
+    SIMD_OP: Fn(T::Simd, T::Simd) -> T::Simd,
+    SCALAR_OP: Fn(T::Native, T::Native) -> T::Native,
 {
     if left.len() != right.len() {
         return Err(ArrowError::ComputeError(
@@ -92,20 +102,18 @@ where
         ));
     }
 
-    let null_bit_buffer = apply_bin_op_to_option_bitmap(
-        left.data().null_bitmap(),
-        right.data().null_bitmap(),
-        |a, b| a & b,
-    )?;
+    let null_bit_buffer =
+        combine_option_bitmaps(&left.data(), &right.data(), left.len())?;
 
     let lanes = T::lanes();
     let buffer_size = left.len() * mem::size_of::<T::Native>();
     let mut result = MutableBuffer::new(buffer_size).with_bitset(buffer_size, false);
+    let simd_len = left.len() - left.len() % lanes;
 
-    for i in (0..left.len()).step_by(lanes) {
+    for i in (0..simd_len).step_by(lanes) {
         let simd_left = T::load(left.value_slice(i, lanes));
         let simd_right = T::load(right.value_slice(i, lanes));
-        let simd_result = T::bin_op(simd_left, simd_right, &op);
+        let simd_result = T::bin_op(simd_left, simd_right, &simd_op);
 
         let result_slice: &mut [T::Native] = unsafe {
             from_raw_parts_mut(
@@ -116,12 +124,25 @@ where
         T::write(simd_result, result_slice);
     }
 
+    let result_slice: &mut [T::Native] = unsafe {
+        from_raw_parts_mut(result.data_mut().as_mut_ptr() as *mut T::Native, left.len())
+    };
+    for i in simd_len..left.len() {
+        // null slots are left as zero
+        let is_valid = null_bit_buffer
+            .as_ref()
+            .map_or(true, |b| bit_util::get_bit(b.data(), i));
+        if is_valid {
+            result_slice[i] = scalar_op(left.value(i), right.value(i));
+        }
+    }
+
     let data = ArrayData::new(
         T::get_data_type(),
         left.len(),
         None,
         null_bit_buffer,
-        left.offset(),
+        0,
         vec![result.freeze()],
         vec![],
     );
@@ -138,7 +159,7 @@ fn simd_divide<T>(
 ) -> Result<PrimitiveArray<T>>
 where
     T: datatypes::ArrowNumericType,
-    T::Native: One + Zero,
+    T::Native: One + Zero + Div<Output = T::Native>,
 {
     if left.len() != right.len() {
         return Err(ArrowError::ComputeError(
@@ -147,18 +168,16 @@ where
     }
 
     // Create the combined `Bitmap`
-    let null_bit_buffer = apply_bin_op_to_option_bitmap(
-        left.data().null_bitmap(),
-        right.data().null_bitmap(),
-        |a, b| a & b,
-    )?;
+    let null_bit_buffer =
+        combine_option_bitmaps(&left.data(), &right.data(), left.len())?;
     let bitmap = null_bit_buffer.map(Bitmap::from);
 
     let lanes = T::lanes();
     let buffer_size = left.len() * mem::size_of::<T::Native>();
     let mut result = MutableBuffer::new(buffer_size).with_bitset(buffer_size, false);
+    let simd_len = left.len() - left.len() % lanes;
 
-    for i in (0..left.len()).step_by(lanes) {
+    for i in (0..simd_len).step_by(lanes) {
         let right_no_invalid_zeros =
             unsafe { simd_load_set_invalid(right, &bitmap, i, lanes, T::Native::one()) };
         let is_zero = T::eq(T::init(T::Native::zero()), right_no_invalid_zeros);
@@ -179,6 +198,19 @@ where
         T::write(simd_result, result_slice);
     }
 
+    let result_slice: &mut [T::Native] = unsafe {
+        from_raw_parts_mut(result.data_mut().as_mut_ptr() as *mut T::Native, left.len())
+    };
+    for i in simd_len..left.len() {
+        // null slots are left as zero
+        if bitmap.as_ref().map_or(true, |b| b.is_set(i)) {
+            if right.value(i).is_zero() {
+                return Err(ArrowError::DivideByZero);
+            }
+            result_slice[i] = left.value(i) / right.value(i);
+        }
+    }
+
     let null_bit_buffer = bitmap.and_then(|b| Some(b.bits));
 
     let data = ArrayData::new(
@@ -186,7 +218,7 @@ where
         left.len(),
         None,
         null_bit_buffer,
-        left.offset(),
+        0,
         vec![result.freeze()],
         vec![],
     );
@@ -208,7 +240,7 @@ where
         + Zero,
 {
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "simd"))]
-    return simd_math_op(&left, &right, |a, b| a + b);
+    return simd_math_op(&left, &right, |a, b| a + b, |a, b| a + b);
 
     #[allow(unreachable_code)]
     math_op(left, right, |a, b| Ok(a + b))
@@ -229,7 +261,7 @@ where
         + Zero,
 {
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "simd"))]
-    return simd_math_op(&left, &right, |a, b| a - b);
+    return simd_math_op(&left, &right, |a, b| a - b, |a, b| a - b);
 
     #[allow(unreachable_code)]
     math_op(left, right, |a, b| Ok(a - b))
@@ -250,7 +282,7 @@ where
         + Zero,
 {
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "simd"))]
-    return simd_math_op(&left, &right, |a, b| a * b);
+    return simd_math_op(&left, &right, |a, b| a * b, |a, b| a * b);
 
     #[allow(unreachable_code)]
     math_op(left, right, |a, b| Ok(a * b))
@@ -285,6 +317,143 @@ where
     })
 }
 
+/// Division that returns `None` where `a / b` would panic, i.e. where `b` is zero or,
+/// for signed integers, where the quotient overflows as in `MIN / -1`
+trait CheckedDivide: Sized {
+    fn checked_divide(self, rhs: Self) -> Option<Self>;
+}
+
+impl<T: Div<Output = T> + Zero> CheckedDivide for T {
+    default fn checked_divide(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            Some(self / rhs)
+        }
+    }
+}
+
+macro_rules! checked_divide_int {
+    ($native_ty:ty) => {
+        impl CheckedDivide for $native_ty {
+            fn checked_divide(self, rhs: Self) -> Option<Self> {
+                self.checked_div(rhs)
+            }
+        }
+    };
+}
+
+checked_divide_int!(i8);
+checked_divide_int!(i16);
+checked_divide_int!(i32);
+checked_divide_int!(i64);
+
+/// Perform `left / right` operation on two arrays, returning null at every position
+/// where the right hand value is zero, or where the quotient of signed integers
+/// overflows as in `i32::MIN / -1`, instead of failing. If either left or right value
+/// is null then the result is also null.
+pub fn divide_checked<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    T::Native: Div<Output = T::Native> + Zero,
+{
+    if left.len() != right.len() {
+        return Err(ArrowError::ComputeError(
+            "Cannot perform math operation on arrays of different length".to_string(),
+        ));
+    }
+    let mut b = PrimitiveBuilder::<T>::new(left.len());
+    for i in 0..left.len() {
+        if left.is_null(i) || right.is_null(i) {
+            b.append_null()?;
+        } else {
+            b.append_option(left.value(i).checked_divide(right.value(i)))?;
+        }
+    }
+    Ok(b.finish())
+}
+
+/// Returns the smaller of the two values at each position of `left` and `right`, like
+/// SQL's `LEAST` with two arguments. If either value is null then the result is also
+/// null.
+///
+/// `NaN` is treated as larger than any other value, so it is only returned if both
+/// values are `NaN`.
+pub fn min_elementwise<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+{
+    math_op(left, right, |a, b| Ok(pick(a, b, Ordering::Less)))
+}
+
+/// Returns the larger of the two values at each position of `left` and `right`, like
+/// SQL's `GREATEST` with two arguments. If either value is null then the result is
+/// also null.
+///
+/// `NaN` is treated as larger than any other value, so it is returned if either value
+/// is `NaN`.
+pub fn max_elementwise<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+{
+    math_op(left, right, |a, b| Ok(pick(a, b, Ordering::Greater)))
+}
+
+/// Returns the smallest value at each position of `arrays`, as `min_elementwise` does
+/// for two arrays. Returns an error if `arrays` is empty.
+pub fn min_elementwise_all<T>(arrays: &[&PrimitiveArray<T>]) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+{
+    fold_elementwise(arrays, Ordering::Less)
+}
+
+/// Returns the largest value at each position of `arrays`, as `max_elementwise` does
+/// for two arrays. Returns an error if `arrays` is empty.
+pub fn max_elementwise_all<T>(arrays: &[&PrimitiveArray<T>]) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+{
+    fold_elementwise(arrays, Ordering::Greater)
+}
+
+/// Returns `b` if it is ordered as `preferred` relative to `a`, otherwise `a`
+fn pick<N: PartialOrd>(a: N, b: N, preferred: Ordering) -> N {
+    if total_cmp(&b, &a) == preferred {
+        b
+    } else {
+        a
+    }
+}
+
+fn fold_elementwise<T>(
+    arrays: &[&PrimitiveArray<T>],
+    preferred: Ordering,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+{
+    let (first, rest) = arrays.split_first().ok_or_else(|| {
+        ArrowError::ComputeError(
+            "Cannot compute an element-wise minimum or maximum of no arrays".to_string(),
+        )
+    })?;
+    let mut result = math_op(first, first, |a, _| Ok(a))?;
+    for array in rest {
+        result = math_op(&result, array, |a, b| Ok(pick(a, b, preferred)))?;
+    }
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -374,6 +543,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_primitive_array_divide_checked() {
+        let a = Int32Array::from(vec![10, 20]);
+        let b = Int32Array::from(vec![2, 0]);
+        let c = divide_checked(&a, &b).unwrap();
+        assert_eq!(5, c.value(0));
+        assert!(c.is_null(1));
+
+        let a = Int8Array::from(vec![std::i8::MIN, std::i8::MIN, 7]);
+        let b = Int8Array::from(vec![-1, 1, -1]);
+        let c = divide_checked(&a, &b).unwrap();
+        assert!(c.is_null(0));
+        assert_eq!(std::i8::MIN, c.value(1));
+        assert_eq!(-7, c.value(2));
+
+        let a = Int64Array::from(vec![std::i64::MIN, std::i64::MIN + 1]);
+        let b = Int64Array::from(vec![-1, -1]);
+        let c = divide_checked(&a, &b).unwrap();
+        assert!(c.is_null(0));
+        assert_eq!(std::i64::MAX, c.value(1));
+
+        let a = UInt8Array::from(vec![std::u8::MAX]);
+        let b = UInt8Array::from(vec![1]);
+        assert_eq!(std::u8::MAX, divide_checked(&a, &b).unwrap().value(0));
+
+        let a = Float64Array::from(vec![Some(1.0), None, Some(3.0)]);
+        let b = Float64Array::from(vec![Some(0.0), Some(1.0), Some(2.0)]);
+        let c = divide_checked(&a, &b).unwrap();
+        assert!(c.is_null(0));
+        assert!(c.is_null(1));
+        assert_eq!(1.5, c.value(2));
+    }
+
     #[test]
     fn test_primitive_array_divide_f64() {
         let a = Float64Array::from(vec![15.0, 15.0, 8.0]);
@@ -395,4 +597,104 @@ mod tests {
         assert_eq!(true, c.is_null(3));
         assert_eq!(13, c.value(2));
     }
+
+    #[test]
+    fn test_primitive_array_add_sliced() {
+        // longer than a SIMD register, so that there is a tail, with nulls at bits that
+        // aren't aligned with the slices
+        let a: Vec<Option<f64>> = (0..40)
+            .map(|i| if i % 3 == 0 { None } else { Some(i as f64) })
+            .collect();
+        let b: Vec<Option<f64>> = (0..40)
+            .map(|i| {
+                if i % 5 == 0 {
+                    None
+                } else {
+                    Some(i as f64 * 10.0)
+                }
+            })
+            .collect();
+        let a_slice = Float64Array::from(a.clone()).slice(3, 35);
+        let b_slice = Float64Array::from(b.clone()).slice(1, 35);
+        let a_slice = a_slice.as_any().downcast_ref::<Float64Array>().unwrap();
+        let b_slice = b_slice.as_any().downcast_ref::<Float64Array>().unwrap();
+
+        let c = add(a_slice, b_slice).unwrap();
+        let expected: Vec<Option<f64>> = (0..35)
+            .map(|i| match (a[i + 3], b[i + 1]) {
+                (Some(a), Some(b)) => Some(a + b),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(Float64Array::from(expected), c);
+
+        let d = divide(a_slice, b_slice).unwrap();
+        assert_eq!(35, d.len());
+        assert_eq!(c.null_count(), d.null_count());
+        assert!(d.is_null(0));
+        assert_eq!(4.0 / 20.0, d.value(1));
+        assert_eq!(35.0 / 330.0, d.value(32));
+        assert!(d.is_null(34));
+    }
+
+    #[test]
+    fn test_primitive_array_divide_by_zero_in_tail() {
+        // the zero divisor is after the last full SIMD chunk
+        let a = Int32Array::from((1..=19).collect::<Vec<i32>>());
+        let mut b: Vec<Option<i32>> = (1..=19).map(Some).collect();
+        b[18] = None;
+        let c = divide(&a, &Int32Array::from(b.clone())).unwrap();
+        assert!(c.is_null(18));
+        assert_eq!(1, c.value(17));
+
+        b[18] = Some(0);
+        match divide(&a, &Int32Array::from(b)) {
+            Err(ArrowError::DivideByZero) => {}
+            _ => panic!("DivideByZero error expected"),
+        }
+    }
+
+    #[test]
+    fn test_primitive_array_min_max_elementwise() {
+        let a = Int32Array::from(vec![Some(1), Some(5), None]);
+        let b = Int32Array::from(vec![Some(4), Some(2), Some(3)]);
+        let c = max_elementwise(&a, &b).unwrap();
+        assert_eq!(4, c.value(0));
+        assert_eq!(5, c.value(1));
+        assert!(c.is_null(2));
+        let c = min_elementwise(&a, &b).unwrap();
+        assert_eq!(1, c.value(0));
+        assert_eq!(2, c.value(1));
+        assert!(c.is_null(2));
+    }
+
+    #[test]
+    fn test_primitive_array_min_max_elementwise_nan() {
+        let a = Float64Array::from(vec![std::f64::NAN, 1.0, std::f64::NAN]);
+        let b = Float64Array::from(vec![2.0, std::f64::NAN, std::f64::NAN]);
+        let c = max_elementwise(&a, &b).unwrap();
+        assert!(c.value(0).is_nan());
+        assert!(c.value(1).is_nan());
+        assert!(c.value(2).is_nan());
+        let c = min_elementwise(&a, &b).unwrap();
+        assert_eq!(2.0, c.value(0));
+        assert_eq!(1.0, c.value(1));
+        assert!(c.value(2).is_nan());
+    }
+
+    #[test]
+    fn test_primitive_array_min_max_elementwise_all() {
+        let a = Int32Array::from(vec![Some(1), Some(9), Some(4)]);
+        let b = Int32Array::from(vec![Some(7), Some(2), None]);
+        let c = Int32Array::from(vec![Some(3), Some(5), Some(6)]);
+        let max = max_elementwise_all(&[&a, &b, &c]).unwrap();
+        assert_eq!(7, max.value(0));
+        assert_eq!(9, max.value(1));
+        assert!(max.is_null(2));
+        let min = min_elementwise_all(&[&a, &b, &c]).unwrap();
+        assert_eq!(1, min.value(0));
+        assert_eq!(2, min.value(1));
+        assert!(min.is_null(2));
+        assert!(min_elementwise_all::<datatypes::Int32Type>(&[]).is_err());
+    }
 }
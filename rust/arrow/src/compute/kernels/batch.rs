@@ -0,0 +1,381 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that reshape a stream of record batches.
+
+use crate::array::ArrayRef;
+use crate::datatypes::SchemaRef;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::{RecordBatch, RecordBatchOptions, RecordBatchReader};
+use crate::scalar::ScalarValue;
+
+use super::cast::{cast_with_options, CastOptions};
+use super::concat::concat_batches;
+use super::repeat::repeat;
+
+/// A reader that combines the small batches of another reader into batches of a target
+/// number of rows
+///
+/// Created by `coalesce`.
+pub struct CoalesceReader<R: RecordBatchReader> {
+    reader: R,
+    target_rows: usize,
+    buffered: Vec<RecordBatch>,
+    buffered_rows: usize,
+    finished: bool,
+}
+
+/// Returns a reader that buffers the batches of `reader` and concatenates them into
+/// batches of `target_rows` rows, which reduces the per-batch overhead of processing
+/// the output of sources that produce many small batches.
+///
+/// Incoming batches are split if needed, so that all batches but the last have exactly
+/// `target_rows` rows. The last batch holds the remaining rows once `reader` is
+/// exhausted.
+///
+/// # Panics
+///
+/// Panics if `target_rows` is 0.
+pub fn coalesce<R: RecordBatchReader>(
+    reader: R,
+    target_rows: usize,
+) -> CoalesceReader<R> {
+    assert!(
+        target_rows > 0,
+        "Coalescing batches requires a positive target_rows"
+    );
+    CoalesceReader {
+        reader,
+        target_rows,
+        buffered: vec![],
+        buffered_rows: 0,
+        finished: false,
+    }
+}
+
+impl<R: RecordBatchReader> CoalesceReader<R> {
+    /// Consumes the coalescing reader, returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: RecordBatchReader> RecordBatchReader for CoalesceReader<R> {
+    fn schema(&mut self) -> SchemaRef {
+        self.reader.schema()
+    }
+
+    fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        while !self.finished && self.buffered_rows < self.target_rows {
+            match self.reader.next_batch()? {
+                Some(batch) => {
+                    if batch.num_rows() > 0 {
+                        self.buffered_rows += batch.num_rows();
+                        self.buffered.push(batch);
+                    }
+                }
+                None => self.finished = true,
+            }
+        }
+        if self.buffered.is_empty() {
+            return Ok(None);
+        }
+
+        let combined = if self.buffered.len() == 1 {
+            self.buffered.pop().unwrap()
+        } else {
+            let combined = concat_batches(&self.buffered)?;
+            self.buffered.clear();
+            combined
+        };
+        self.buffered_rows = 0;
+        if combined.num_rows() <= self.target_rows {
+            return Ok(Some(combined));
+        }
+
+        let remaining = combined.num_rows() - self.target_rows;
+        self.buffered
+            .push(combined.slice(self.target_rows, remaining));
+        self.buffered_rows = remaining;
+        Ok(Some(combined.slice(0, self.target_rows)))
+    }
+}
+
+/// A reader that adapts the batches of another reader to a target schema
+///
+/// Each column of the target schema is taken from the column of the same name of an
+/// incoming batch, cast to the type of the target field if it differs. Columns that the
+/// target schema doesn't have are dropped, and fields of the target schema that a batch
+/// has no column for are filled with nulls. This lets sources whose schemas evolved
+/// present a uniform schema to downstream code.
+pub struct SchemaAdaptingReader<R: RecordBatchReader> {
+    reader: R,
+    schema: SchemaRef,
+    options: CastOptions,
+}
+
+impl<R: RecordBatchReader> SchemaAdaptingReader<R> {
+    /// Creates a reader that adapts the batches of `reader` to `schema`, casting columns
+    /// with the default `CastOptions`
+    pub fn new(reader: R, schema: SchemaRef) -> Self {
+        Self {
+            reader,
+            schema,
+            options: CastOptions::default(),
+        }
+    }
+
+    /// Sets the options used to cast columns to the types of the target schema
+    pub fn with_cast_options(mut self, options: CastOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Consumes the adapting reader, returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Adapts `batch` to the target schema
+    ///
+    /// Returns an error if a column can't be cast to the type of its target field, or
+    /// if a batch has no column for a target field that isn't nullable.
+    fn adapt(&self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let schema = batch.schema();
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let index = schema
+                    .fields()
+                    .iter()
+                    .position(|f| f.name() == field.name());
+                match index {
+                    Some(i) => {
+                        let column = batch.column(i);
+                        if column.data_type() == field.data_type() {
+                            Ok(column.clone())
+                        } else {
+                            cast_with_options(column, field.data_type(), &self.options)
+                        }
+                    }
+                    None if field.is_nullable() => {
+                        repeat(&ScalarValue::Null, field.data_type(), batch.num_rows())
+                    }
+                    None => Err(ArrowError::InvalidArgumentError(format!(
+                        "batch has no column for the non-nullable field \"{}\"",
+                        field.name()
+                    ))),
+                }
+            })
+            .collect::<Result<Vec<ArrayRef>>>()?;
+        RecordBatch::try_new_with_options(
+            self.schema.clone(),
+            columns,
+            &RecordBatchOptions {
+                match_field_names: true,
+                row_count: Some(batch.num_rows()),
+            },
+        )
+    }
+}
+
+impl<R: RecordBatchReader> RecordBatchReader for SchemaAdaptingReader<R> {
+    fn schema(&mut self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        match self.reader.next_batch()? {
+            Some(batch) => self.adapt(&batch).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::array::*;
+    use crate::datatypes::{DataType, Field, Schema};
+
+    /// Reads batches from a vector
+    struct VecReader {
+        schema: SchemaRef,
+        batches: Vec<RecordBatch>,
+    }
+
+    impl RecordBatchReader for VecReader {
+        fn schema(&mut self) -> SchemaRef {
+            self.schema.clone()
+        }
+
+        fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+            if self.batches.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(self.batches.remove(0)))
+            }
+        }
+    }
+
+    fn batch(schema: &SchemaRef, values: Vec<Option<i32>>) -> RecordBatch {
+        RecordBatch::try_new(schema.clone(), vec![Arc::new(Int32Array::from(values))])
+            .unwrap()
+    }
+
+    fn read_all<R: RecordBatchReader>(reader: &mut R) -> Vec<RecordBatch> {
+        let mut batches = vec![];
+        while let Some(batch) = reader.next_batch().unwrap() {
+            batches.push(batch);
+        }
+        batches
+    }
+
+    #[test]
+    fn test_coalesce_single_row_batches() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let batches = (0..25)
+            .map(|i| batch(&schema, vec![if i % 5 == 0 { None } else { Some(i) }]))
+            .collect();
+        let reader = VecReader {
+            schema: schema.clone(),
+            batches,
+        };
+
+        let mut coalesced = coalesce(reader, 10);
+        assert_eq!(schema, coalesced.schema());
+        let batches = read_all(&mut coalesced);
+        assert_eq!(
+            vec![10, 10, 5],
+            batches.iter().map(|b| b.num_rows()).collect::<Vec<_>>()
+        );
+
+        let values = concat_batches(&batches).unwrap();
+        let expected = batch(
+            &schema,
+            (0..25)
+                .map(|i| if i % 5 == 0 { None } else { Some(i) })
+                .collect(),
+        );
+        assert!(expected.column(0).equals(values.column(0).as_ref()));
+    }
+
+    #[test]
+    fn test_coalesce_splits_large_batches() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let reader = VecReader {
+            schema: schema.clone(),
+            batches: vec![
+                batch(&schema, vec![Some(1), Some(2)]),
+                batch(&schema, vec![]),
+                batch(&schema, vec![Some(3), None, Some(5), Some(6), Some(7)]),
+                batch(&schema, vec![Some(8)]),
+            ],
+        };
+
+        let batches = read_all(&mut coalesce(reader, 3));
+        assert_eq!(
+            vec![3, 3, 2],
+            batches.iter().map(|b| b.num_rows()).collect::<Vec<_>>()
+        );
+        let expected = batch(&schema, vec![None, Some(5), Some(6)]);
+        assert!(expected.column(0).equals(batches[1].column(0).as_ref()));
+    }
+
+    #[test]
+    fn test_coalesce_empty_reader() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let reader = VecReader {
+            schema,
+            batches: vec![],
+        };
+        assert!(read_all(&mut coalesce(reader, 3)).is_empty());
+    }
+
+    #[test]
+    fn test_schema_adapting_reader() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("extra", DataType::Utf8, false),
+            Field::new("b", DataType::Int16, true),
+            Field::new("a", DataType::Int32, false),
+        ]));
+        let source = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(BinaryArray::from(vec!["x", "y"])),
+                Arc::new(Int16Array::from(vec![Some(1), None])),
+                Arc::new(Int32Array::from(vec![3, 4])),
+            ],
+        )
+        .unwrap();
+        let reader = VecReader {
+            schema,
+            batches: vec![source],
+        };
+
+        let target = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int64, true),
+            Field::new("c", DataType::Float64, true),
+        ]));
+        let mut adapted = SchemaAdaptingReader::new(reader, target.clone());
+        assert_eq!(target, adapted.schema());
+        let batches = read_all(&mut adapted);
+        assert_eq!(1, batches.len());
+        let batch = &batches[0];
+        assert_eq!(&target, batch.schema());
+        assert_eq!(2, batch.num_rows());
+
+        let a = batch.column(0).downcast::<Int32Array>().unwrap();
+        assert_eq!(3, a.value(0));
+        assert_eq!(4, a.value(1));
+        let b = batch.column(1).downcast::<Int64Array>().unwrap();
+        assert_eq!(1, b.value(0));
+        assert!(b.is_null(1));
+        assert_eq!(2, batch.column(2).null_count());
+        assert_eq!(&DataType::Float64, batch.column(2).data_type());
+    }
+
+    #[test]
+    fn test_schema_adapting_reader_errors() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let reader = VecReader {
+            schema: schema.clone(),
+            batches: vec![batch(&schema, vec![Some(1)])],
+        };
+        let target = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            DataType::Struct(vec![Field::new("x", DataType::Int32, true)]),
+            true,
+        )]));
+        let mut adapted = SchemaAdaptingReader::new(reader, target);
+        assert!(adapted.next_batch().is_err());
+
+        let reader = VecReader {
+            schema: schema.clone(),
+            batches: vec![batch(&schema, vec![Some(1)])],
+        };
+        let target = Arc::new(Schema::new(vec![Field::new("b", DataType::Int32, false)]));
+        let mut adapted = SchemaAdaptingReader::new(reader, target);
+        assert!(adapted.next_batch().is_err());
+    }
+}
@@ -0,0 +1,194 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines bitwise kernels for integer `PrimitiveArrays`.
+//!
+//! Unlike the kernels in `boolean`, which implement logic on `BooleanArray`s, these
+//! kernels operate on the bits of each integer value. A null in any input makes the
+//! output value null.
+
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+use crate::array::*;
+use crate::compute::kernels::arithmetic::math_op;
+use crate::datatypes::ArrowNumericType;
+use crate::error::{ArrowError, Result};
+
+/// Helper function to apply a lambda function to each non-null value of an array.
+fn unary_op<T, F>(array: &PrimitiveArray<T>, op: F) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    F: Fn(T::Native) -> T::Native,
+{
+    let mut b = PrimitiveBuilder::<T>::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else {
+            b.append_value(op(array.value(i)))?;
+        }
+    }
+    Ok(b.finish())
+}
+
+/// Checks that shifting a value of type `T` by `shift` bits is defined.
+fn check_shift<T: ArrowNumericType>(shift: u32) -> Result<()> {
+    if shift as usize >= T::get_bit_width() {
+        return Err(ArrowError::ComputeError(format!(
+            "Cannot shift a {}-bit value by {} bits",
+            T::get_bit_width(),
+            shift
+        )));
+    }
+    Ok(())
+}
+
+/// Perform `left & right` operation on two integer arrays. If either left or right value
+/// is null then the result is also null.
+pub fn bitwise_and<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitAnd<Output = T::Native>,
+{
+    math_op(left, right, |a, b| Ok(a & b))
+}
+
+/// Perform `left | right` operation on two integer arrays. If either left or right value
+/// is null then the result is also null.
+pub fn bitwise_or<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitOr<Output = T::Native>,
+{
+    math_op(left, right, |a, b| Ok(a | b))
+}
+
+/// Perform `left ^ right` operation on two integer arrays. If either left or right value
+/// is null then the result is also null.
+pub fn bitwise_xor<T>(
+    left: &PrimitiveArray<T>,
+    right: &PrimitiveArray<T>,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: BitXor<Output = T::Native>,
+{
+    math_op(left, right, |a, b| Ok(a ^ b))
+}
+
+/// Perform `!array` operation on an integer array, inverting every bit of each value.
+/// Null values stay null.
+pub fn bitwise_not<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: Not<Output = T::Native>,
+{
+    unary_op(array, |a| !a)
+}
+
+/// Perform `array << shift` operation on an integer array. Null values stay null.
+///
+/// Returns an error if `shift` is not less than the bit width of the type.
+pub fn shift_left<T>(array: &PrimitiveArray<T>, shift: u32) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: Shl<u32, Output = T::Native>,
+{
+    check_shift::<T>(shift)?;
+    unary_op(array, |a| a << shift)
+}
+
+/// Perform `array >> shift` operation on an integer array. Null values stay null.
+///
+/// Signed values are shifted arithmetically, keeping their sign bit, while unsigned
+/// values are filled with zeros. Returns an error if `shift` is not less than the bit
+/// width of the type.
+pub fn shift_right<T>(array: &PrimitiveArray<T>, shift: u32) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: Shr<u32, Output = T::Native>,
+{
+    check_shift::<T>(shift)?;
+    unary_op(array, |a| a >> shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitwise_and() {
+        let a = Int32Array::from(vec![Some(0b110), Some(0b011), None]);
+        let b = Int32Array::from(vec![Some(0b101), Some(0b101), Some(0b111)]);
+        let c = bitwise_and(&a, &b).unwrap();
+        assert_eq!(3, c.len());
+        assert_eq!(0b100, c.value(0));
+        assert_eq!(0b001, c.value(1));
+        assert!(c.is_null(2));
+    }
+
+    #[test]
+    fn test_bitwise_or_xor_not() {
+        let a = UInt8Array::from(vec![Some(0b1100), None, Some(0b1111)]);
+        let b = UInt8Array::from(vec![Some(0b1010), Some(0b0001), Some(0b0101)]);
+
+        let c = bitwise_or(&a, &b).unwrap();
+        assert_eq!(0b1110, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(0b1111, c.value(2));
+
+        let c = bitwise_xor(&a, &b).unwrap();
+        assert_eq!(0b0110, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(0b1010, c.value(2));
+
+        let c = bitwise_not(&a).unwrap();
+        assert_eq!(0b1111_0011, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(0b1111_0000, c.value(2));
+    }
+
+    #[test]
+    fn test_bitwise_shift() {
+        let a = Int8Array::from(vec![Some(1), None, Some(-8)]);
+
+        let c = shift_left(&a, 3).unwrap();
+        assert_eq!(8, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(-64, c.value(2));
+
+        let c = shift_right(&a, 2).unwrap();
+        assert_eq!(0, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(-2, c.value(2));
+
+        assert!(shift_left(&a, 8).is_err());
+    }
+
+    #[test]
+    fn test_bitwise_different_lengths() {
+        let a = Int64Array::from(vec![1, 2]);
+        let b = Int64Array::from(vec![1]);
+        assert!(bitwise_and(&a, &b).is_err());
+    }
+}
@@ -24,7 +24,7 @@
 
 use std::sync::Arc;
 
-use crate::array::{Array, ArrayData, BooleanArray};
+use crate::array::{Array, ArrayData, BooleanArray, NullableBooleanBufferBuilder};
 use crate::buffer::Buffer;
 use crate::compute::util::apply_bin_op_to_option_bitmap;
 use crate::datatypes::DataType;
@@ -78,6 +78,58 @@ pub fn or(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
     binary_boolean_kernel(&left, &right, |a, b| a | b)
 }
 
+/// Helper function to implement binary kernels with Kleene logic, where `op` computes
+/// the value and validity of each slot from the optional input values
+fn kleene_boolean_kernel<F>(
+    left: &BooleanArray,
+    right: &BooleanArray,
+    op: F,
+) -> Result<BooleanArray>
+where
+    F: Fn(Option<bool>, Option<bool>) -> Option<bool>,
+{
+    if left.len() != right.len() {
+        return Err(ArrowError::ComputeError(
+            "Cannot perform boolean operation on arrays of different length".to_string(),
+        ));
+    }
+    let value = |array: &BooleanArray, i| {
+        if array.is_valid(i) {
+            Some(array.value(i))
+        } else {
+            None
+        }
+    };
+    let mut builder = NullableBooleanBufferBuilder::new(left.len());
+    for i in 0..left.len() {
+        builder.append(op(value(left, i), value(right, i)))?;
+    }
+    let (values, null_bit_buffer) = builder.finish();
+    Ok(BooleanArray::new(left.len(), values, null_bit_buffer, 0))
+}
+
+/// Performs `AND` operation on two arrays with Kleene logic. The result is false if
+/// either value is false, even if the other value is null, and null if one value is
+/// null and the other is true.
+pub fn and_kleene(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
+    kleene_boolean_kernel(left, right, |a, b| match (a, b) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    })
+}
+
+/// Performs `OR` operation on two arrays with Kleene logic. The result is true if
+/// either value is true, even if the other value is null, and null if one value is
+/// null and the other is false.
+pub fn or_kleene(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
+    kleene_boolean_kernel(left, right, |a, b| match (a, b) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    })
+}
+
 /// Performs unary `NOT` operation on an arrays. If value is null then the result is also
 /// null.
 pub fn not(left: &BooleanArray) -> Result<BooleanArray> {
@@ -157,4 +209,70 @@ mod tests {
         assert_eq!(true, c.is_null(2));
         assert_eq!(false, c.is_null(3));
     }
+
+    #[test]
+    fn test_bool_array_and_kleene() {
+        let a = BooleanArray::from(vec![
+            Some(true),
+            Some(true),
+            Some(true),
+            Some(false),
+            Some(false),
+            Some(false),
+            None,
+            None,
+            None,
+        ]);
+        let b = BooleanArray::from(vec![
+            Some(true),
+            Some(false),
+            None,
+            Some(true),
+            Some(false),
+            None,
+            Some(true),
+            Some(false),
+            None,
+        ]);
+        let c = and_kleene(&a, &b).unwrap();
+        let data = c.data();
+        // slots 0 to 7 in the first byte, slot 8 in the second
+        assert_eq!(Buffer::from([0b0000_0001_u8, 0]), data.buffers()[0]);
+        assert_eq!(
+            Buffer::from([0b1011_1011_u8, 0]),
+            data.null_bitmap().as_ref().unwrap().bits
+        );
+        let expected = BooleanArray::from(vec![
+            Some(true),
+            Some(false),
+            None,
+            Some(false),
+            Some(false),
+            Some(false),
+            None,
+            Some(false),
+            None,
+        ]);
+        assert!(c.equals(&expected));
+        assert_eq!(3, c.null_count());
+    }
+
+    #[test]
+    fn test_bool_array_or_kleene() {
+        let a =
+            BooleanArray::from(vec![Some(true), Some(false), None, None, Some(false)]);
+        let b = BooleanArray::from(vec![None, None, Some(true), None, Some(false)]);
+        let c = or_kleene(&a, &b).unwrap();
+        let expected =
+            BooleanArray::from(vec![Some(true), None, Some(true), None, Some(false)]);
+        assert!(c.equals(&expected));
+        assert_eq!(2, c.null_count());
+    }
+
+    #[test]
+    fn test_bool_array_kleene_different_lengths() {
+        let a = BooleanArray::from(vec![true, false]);
+        let b = BooleanArray::from(vec![true]);
+        assert!(and_kleene(&a, &b).is_err());
+    }
 }
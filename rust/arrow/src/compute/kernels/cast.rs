@@ -37,11 +37,50 @@
 
 use std::sync::Arc;
 
+use num::Integer;
+
 use crate::array::*;
-use crate::buffer::Buffer;
+use crate::buffer::{Buffer, MutableBuffer};
 use crate::compute::kernels::arithmetic::{divide, multiply};
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
+use crate::util::bit_util;
+use crate::util::decimal;
+
+/// Options that control the behaviour of `cast_with_options`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastOptions {
+    /// If `true`, values that can't be converted to the target type become null. If
+    /// `false`, such values make the cast return an error instead.
+    pub safe: bool,
+}
+
+impl Default for CastOptions {
+    fn default() -> Self {
+        Self { safe: true }
+    }
+}
+
+/// Cast array to provided data type, using `options` to decide how values that can't be
+/// converted are handled.
+///
+/// See `cast` for the supported casts.
+pub fn cast_with_options(
+    array: &ArrayRef,
+    to_type: &DataType,
+    options: &CastOptions,
+) -> Result<ArrayRef> {
+    let cast_array = cast(array, to_type)?;
+    if !options.safe && cast_array.null_count() != array.null_count() {
+        return Err(ArrowError::ComputeError(format!(
+            "Could not convert {} values from {:?} to {:?}",
+            cast_array.null_count() - array.null_count(),
+            array.data_type(),
+            to_type
+        )));
+    }
+    Ok(cast_array)
+}
 
 /// Cast array to provided data type
 ///
@@ -55,7 +94,17 @@ use crate::error::{ArrowError, Result};
 /// * Date32 and Date64: precision lost when going to higher interval
 /// * Time32 and Time64: precision lost when going to higher interval
 /// * Timestamp and Date{32|64}: precision lost when going to higher interval
+/// * Between timestamp units, and from Date{32|64} to Timestamp: values that overflow
+///   an `i64` in a finer unit return null
+/// * Temporal casts to a 32-bit type: values out of range of the new type return null
 /// * Temporal to/from backing primitive: zero-copy with data type change
+/// * Dictionary to Dictionary with the same value type: the keys are cast and the values
+///   are shared, failing if a key does not fit in the new key type
+/// * Binary to Utf8: values that are not valid UTF-8 return null, the buffers are shared
+/// * Utf8 to Binary: zero-copy with data type change
+/// * Utf8 to Decimal: fraction digits beyond the scale are rounded half up, strings that
+///   can't be parsed or have more digits than the precision return null
+/// * Decimal to Utf8: values are written with `scale` fraction digits
 ///
 /// Unsupported Casts
 /// * To or from `StructArray`
@@ -76,6 +125,17 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
         (_, Struct(_)) => Err(ArrowError::ComputeError(
             "Cannot cast to struct from other types".to_string(),
         )),
+        (Dictionary(_, from_value), Dictionary(_, to_value))
+            if from_value == to_value =>
+        {
+            dictionary_key_cast(array, to_type)
+        }
+        (Dictionary(_, _), _) | (_, Dictionary(_, _)) => {
+            Err(ArrowError::ComputeError(format!(
+                "Casting from {:?} to {:?} not supported",
+                from_type, to_type,
+            )))
+        }
         (List(_), List(ref to)) => {
             let data = array.data_ref();
             let underlying_array = make_array(data.child_data()[0].clone());
@@ -123,6 +183,16 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
 
             Ok(list_array)
         }
+        (Binary, Utf8) => cast_binary_to_utf8(array),
+        (Utf8, Binary) => Ok(make_array(Arc::new(ArrayData::new(
+            Binary,
+            array.len(),
+            Some(array.null_count()),
+            array.data().null_bitmap().clone().map(|bitmap| bitmap.bits),
+            array.offset(),
+            array.data().buffers().to_vec(),
+            vec![],
+        )))),
         (_, Boolean) => match from_type {
             UInt8 => cast_numeric_to_bool::<UInt8Type>(array),
             UInt16 => cast_numeric_to_bool::<UInt16Type>(array),
@@ -186,6 +256,9 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
             Int64 => cast_string_to_numeric::<Int64Type>(array),
             Float32 => cast_string_to_numeric::<Float32Type>(array),
             Float64 => cast_string_to_numeric::<Float64Type>(array),
+            Decimal(precision, scale) => {
+                cast_string_to_decimal(array, *precision, *scale)
+            }
             _ => Err(ArrowError::ComputeError(format!(
                 "Casting from {:?} to {:?} not supported",
                 from_type, to_type,
@@ -202,6 +275,7 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
             Int64 => cast_numeric_to_string::<Int64Type>(array),
             Float32 => cast_numeric_to_string::<Float32Type>(array),
             Float64 => cast_numeric_to_string::<Float64Type>(array),
+            Decimal(_, _) => cast_decimal_to_string(array),
             _ => Err(ArrowError::ComputeError(format!(
                 "Casting from {:?} to {:?} not supported",
                 from_type, to_type,
@@ -322,7 +396,9 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
                 if array.is_null(i) {
                     b.append_null()?;
                 } else {
-                    b.append_value(date_array.value(i) as i64 * MILLISECONDS_IN_DAY)?;
+                    b.append_value(
+                        date_array.value(i) as i64 * DateUnit::Day.to_millis_factor(),
+                    )?;
                 }
             }
 
@@ -335,7 +411,11 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
                 if array.is_null(i) {
                     b.append_null()?;
                 } else {
-                    b.append_value((date_array.value(i) / MILLISECONDS_IN_DAY) as i32)?;
+                    // floor so that pre-epoch instants map to the day they fall on
+                    let days = date_array
+                        .value(i)
+                        .div_floor(&DateUnit::Day.to_millis_factor());
+                    b.append_option(NumCast::from_i64(days))?;
                 }
             }
 
@@ -380,11 +460,9 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
             }
         }
         (Time64(TimeUnit::Microsecond), Time64(TimeUnit::Nanosecond)) => {
-            let time_array = Time64NanosecondArray::from(array.data());
-            let mult = Time64NanosecondArray::from(vec![MILLISECONDS; array.len()]);
-            let time64_ns = multiply(&time_array, &mult)?;
-
-            Ok(Arc::new(time64_ns) as ArrayRef)
+            let time_array = Int64Array::from(array.data());
+            let converted = rescale_i64(&time_array, MICROSECONDS, NANOSECONDS)?;
+            cast_array_data::<Time64NanosecondType>(&converted, to_type.clone())
         }
         (Time64(TimeUnit::Nanosecond), Time64(TimeUnit::Microsecond)) => {
             let time_array = Time64MicrosecondArray::from(array.data());
@@ -405,9 +483,9 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
                         if array.is_null(i) {
                             b.append_null()?;
                         } else {
-                            b.append_value(
-                                (time_array.value(i) as i64 / divisor) as i32,
-                            )?;
+                            b.append_option(NumCast::from_i64(
+                                time_array.value(i).div_floor(&divisor),
+                            ))?;
                         }
                     }
 
@@ -420,9 +498,9 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
                         if array.is_null(i) {
                             b.append_null()?;
                         } else {
-                            b.append_value(
-                                (time_array.value(i) as i64 / divisor) as i32,
-                            )?;
+                            b.append_option(NumCast::from_i64(
+                                time_array.value(i).div_floor(&divisor),
+                            ))?;
                         }
                     }
 
@@ -449,40 +527,25 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
         }
         (Timestamp(from_unit), Timestamp(to_unit)) => {
             let time_array = Int64Array::from(array.data());
-            let from_size = time_unit_multiple(&from_unit);
-            let to_size = time_unit_multiple(&to_unit);
-            // we either divide or multiply, depending on size of each unit
-            // units are never the same when the types are the same
-            let converted = if from_size >= to_size {
-                divide(
-                    &time_array,
-                    &Int64Array::from(vec![from_size / to_size; array.len()]),
-                )?
-            } else {
-                multiply(
-                    &time_array,
-                    &Int64Array::from(vec![to_size / from_size; array.len()]),
-                )?
-            };
-            let array_ref = Arc::new(converted) as ArrayRef;
-            use TimeUnit::*;
-            match to_unit {
-                Second => {
-                    cast_array_data::<TimestampSecondType>(&array_ref, to_type.clone())
-                }
-                Millisecond => cast_array_data::<TimestampMillisecondType>(
-                    &array_ref,
-                    to_type.clone(),
-                ),
-                Microsecond => cast_array_data::<TimestampMicrosecondType>(
-                    &array_ref,
-                    to_type.clone(),
-                ),
-                Nanosecond => cast_array_data::<TimestampNanosecondType>(
-                    &array_ref,
-                    to_type.clone(),
-                ),
-            }
+            let converted = rescale_i64(
+                &time_array,
+                time_unit_multiple(&from_unit),
+                time_unit_multiple(&to_unit),
+            )?;
+            cast_to_timestamp(&converted, to_unit, to_type)
+        }
+        (Date32(_), Timestamp(to_unit)) => {
+            let date_array = Int32Array::from(array.data());
+            let days: Int64Array = numeric_cast(&date_array)?;
+            let converted =
+                rescale_i64(&days, 1, time_unit_multiple(&to_unit) * SECONDS_IN_DAY)?;
+            cast_to_timestamp(&converted, to_unit, to_type)
+        }
+        (Date64(_), Timestamp(to_unit)) => {
+            let date_array = Int64Array::from(array.data());
+            let converted =
+                rescale_i64(&date_array, MILLISECONDS, time_unit_multiple(&to_unit))?;
+            cast_to_timestamp(&converted, to_unit, to_type)
         }
         (Timestamp(from_unit), Date32(_)) => {
             let time_array = Int64Array::from(array.data());
@@ -492,26 +555,20 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
                 if array.is_null(i) {
                     b.append_null()?;
                 } else {
-                    b.append_value((time_array.value(i) / from_size) as i32)?;
+                    // floor so that pre-epoch instants map to the day they fall on
+                    let days = time_array.value(i).div_floor(&from_size);
+                    b.append_option(NumCast::from_i64(days))?;
                 }
             }
 
             Ok(Arc::new(b.finish()) as ArrayRef)
         }
         (Timestamp(from_unit), Date64(_)) => {
-            let from_size = time_unit_multiple(&from_unit);
-            let to_size = MILLISECONDS;
-            if from_size != to_size {
-                let time_array = Date64Array::from(array.data());
-                Ok(Arc::new(divide(
-                    &time_array,
-                    &Date64Array::from(vec![from_size / to_size; array.len()]),
-                )?) as ArrayRef)
-            } else {
-                cast_array_data::<Date64Type>(array, to_type.clone())
-            }
+            let time_array = Int64Array::from(array.data());
+            let converted =
+                rescale_i64(&time_array, time_unit_multiple(&from_unit), MILLISECONDS)?;
+            cast_array_data::<Date64Type>(&converted, to_type.clone())
         }
-        // date64 to timestamp might not make sense,
 
         // end temporal casts
         (_, _) => Err(ArrowError::ComputeError(format!(
@@ -521,6 +578,49 @@ pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
     }
 }
 
+/// Rescales `array` from values in units of which there are `from_size` in a period to
+/// units of which there are `to_size`, e.g. from milliseconds to nanoseconds with sizes
+/// of `MILLISECONDS` and `NANOSECONDS` in a second.
+///
+/// Values are floored when converting to a coarser unit, so that instants before the
+/// epoch map to the earlier unit as in the `Timestamp` to `Date32` cast, and become null
+/// when they overflow an `i64` when converting to a finer one.
+fn rescale_i64(array: &Int64Array, from_size: i64, to_size: i64) -> Result<ArrayRef> {
+    let mut b = Int64Builder::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else if from_size >= to_size {
+            b.append_value(array.value(i).div_floor(&(from_size / to_size)))?;
+        } else {
+            b.append_option(array.value(i).checked_mul(to_size / from_size))?;
+        }
+    }
+    Ok(Arc::new(b.finish()) as ArrayRef)
+}
+
+/// Reinterprets the `Int64` array `array` as a timestamp array of `to_unit`
+fn cast_to_timestamp(
+    array: &ArrayRef,
+    to_unit: &TimeUnit,
+    to_type: &DataType,
+) -> Result<ArrayRef> {
+    match to_unit {
+        TimeUnit::Second => {
+            cast_array_data::<TimestampSecondType>(array, to_type.clone())
+        }
+        TimeUnit::Millisecond => {
+            cast_array_data::<TimestampMillisecondType>(array, to_type.clone())
+        }
+        TimeUnit::Microsecond => {
+            cast_array_data::<TimestampMicrosecondType>(array, to_type.clone())
+        }
+        TimeUnit::Nanosecond => {
+            cast_array_data::<TimestampNanosecondType>(array, to_type.clone())
+        }
+    }
+}
+
 /// Get the time unit as a multiple of a second
 fn time_unit_multiple(unit: &TimeUnit) -> i64 {
     match unit {
@@ -539,8 +639,50 @@ const MILLISECONDS: i64 = 1_000;
 const MICROSECONDS: i64 = 1_000_000;
 /// Number of nanoseconds in a second
 const NANOSECONDS: i64 = 1_000_000_000;
-/// Number of milliseconds in a day
-const MILLISECONDS_IN_DAY: i64 = SECONDS_IN_DAY * MILLISECONDS;
+
+/// Cast the keys of a dictionary array to the key type of `to_type`, sharing the values.
+///
+/// Returns an error if any key is out of range for the new key type.
+fn dictionary_key_cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
+    let (from_key_type, to_key_type) = match (array.data_type(), to_type) {
+        (DataType::Dictionary(from, _), DataType::Dictionary(to, _)) => (from, to),
+        _ => unreachable!("both types should be dictionaries"),
+    };
+    let data = array.data_ref();
+
+    // the keys have the same layout as a primitive array of the key type
+    let keys = make_array(Arc::new(ArrayData::new(
+        *from_key_type.clone(),
+        array.len(),
+        Some(array.null_count()),
+        data.null_bitmap().clone().map(|bitmap| bitmap.bits),
+        array.offset(),
+        data.buffers().to_vec(),
+        vec![],
+    )));
+    // out of range keys become null in the cast
+    let cast_keys = cast(&keys, to_key_type)?;
+    if cast_keys.null_count() != keys.null_count() {
+        return Err(ArrowError::ComputeError(format!(
+            "Could not convert {} dictionary keys from {:?} to {:?}",
+            cast_keys.null_count() - keys.null_count(),
+            from_key_type,
+            to_key_type
+        )));
+    }
+
+    let cast_data = cast_keys.data_ref();
+    let dictionary_data = ArrayData::new(
+        to_type.clone(),
+        cast_keys.len(),
+        Some(cast_keys.null_count()),
+        cast_data.null_bitmap().clone().map(|bitmap| bitmap.bits),
+        cast_keys.offset(),
+        cast_data.buffers().to_vec(),
+        data.child_data().to_vec(),
+    );
+    Ok(make_array(Arc::new(dictionary_data)))
+}
 
 /// Cast an array by changing its array_data type to the desired type
 ///
@@ -563,6 +705,47 @@ where
     Ok(Arc::new(PrimitiveArray::<TO>::from(data)) as ArrayRef)
 }
 
+/// Cast a binary array to Utf8, validating that each value is well-formed UTF-8.
+///
+/// Values that are not become null. If every slot of `array` is valid UTF-8, null slots
+/// included, its buffers are shared with the result; otherwise the values are copied so
+/// that null slots are empty, as every slot of `Utf8` data must be valid UTF-8.
+fn cast_binary_to_utf8(array: &ArrayRef) -> Result<ArrayRef> {
+    let from = as_binary_array(array);
+    let is_utf8 = |i: usize| std::str::from_utf8(from.value(i)).is_ok();
+    if (0..from.len()).all(is_utf8) {
+        let data = array.data();
+        return Ok(make_array(Arc::new(ArrayData::new(
+            DataType::Utf8,
+            data.len(),
+            Some(data.null_count()),
+            data.null_bitmap().clone().map(|bitmap| bitmap.bits),
+            data.offset(),
+            data.buffers().to_vec(),
+            vec![],
+        ))));
+    }
+
+    let mut builder = BinaryBuilder::new(from.len());
+    for i in 0..from.len() {
+        if from.is_valid(i) && is_utf8(i) {
+            builder.append_value(from.value(i))?;
+        } else {
+            builder.append(false)?;
+        }
+    }
+    let cast = builder.finish().data();
+    Ok(make_array(Arc::new(ArrayData::new(
+        DataType::Utf8,
+        cast.len(),
+        Some(cast.null_count()),
+        cast.null_bitmap().clone().map(|bitmap| bitmap.bits),
+        cast.offset(),
+        cast.buffers().to_vec(),
+        vec![],
+    ))))
+}
+
 /// Convert Array into a PrimitiveArray of type, and apply numeric cast
 fn cast_numeric_arrays<FROM, TO>(from: &ArrayRef) -> Result<ArrayRef>
 where
@@ -646,9 +829,7 @@ fn cast_string_to_numeric<TO>(from: &ArrayRef) -> Result<ArrayRef>
 where
     TO: ArrowNumericType,
 {
-    match string_to_numeric_cast::<TO>(
-        from.as_any().downcast_ref::<BinaryArray>().unwrap(),
-    ) {
+    match string_to_numeric_cast::<TO>(&as_binary_array(from)) {
         Ok(to) => Ok(Arc::new(to) as ArrayRef),
         Err(e) => Err(e),
     }
@@ -678,6 +859,49 @@ where
     Ok(b.finish())
 }
 
+/// Cast Utf8 to Decimal, rounding fraction digits beyond the scale half up
+///
+/// Strings that can't be parsed, or whose value has more digits than the precision,
+/// become nulls.
+fn cast_string_to_decimal(
+    from: &ArrayRef,
+    precision: usize,
+    scale: usize,
+) -> Result<ArrayRef> {
+    decimal::validate_decimal_type(precision, scale)?;
+    let from = as_binary_array(from);
+    let mut b = DecimalBuilder::new(from.len(), precision, scale);
+
+    for i in 0..from.len() {
+        if from.is_null(i) {
+            b.append_null()?;
+        } else {
+            match std::str::from_utf8(from.value(i)) {
+                Ok(s) if b.append_str(s).is_ok() => {}
+                _ => b.append_null()?,
+            };
+        }
+    }
+
+    Ok(Arc::new(b.finish()) as ArrayRef)
+}
+
+/// Cast Decimal to Utf8, writing each value with `scale` fraction digits
+fn cast_decimal_to_string(from: &ArrayRef) -> Result<ArrayRef> {
+    let from = from.as_any().downcast_ref::<DecimalArray>().unwrap();
+    let mut b = BinaryBuilder::new(from.len());
+
+    for i in 0..from.len() {
+        if from.is_null(i) {
+            b.append(false)?;
+        } else {
+            b.append_string(&from.value_as_string(i))?;
+        }
+    }
+
+    Ok(Arc::new(b.finish()) as ArrayRef)
+}
+
 /// Cast numeric types to Boolean
 ///
 /// Any zero value returns `false` while non-zero returns `true`
@@ -911,6 +1135,14 @@ mod tests {
         assert_eq!(false, c.is_valid(2));
         assert_eq!(8, c.value(3));
         assert_eq!(false, c.is_valid(2));
+
+        let a = StringArray::from(vec![Some("5"), None, Some("seven")]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Int32).unwrap();
+        let c = b.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(5, c.value(0));
+        assert!(c.is_null(1));
+        assert!(c.is_null(2));
     }
 
     #[test]
@@ -1044,6 +1276,55 @@ mod tests {
         assert!(c.is_null(2));
     }
 
+    #[test]
+    fn test_cast_date32_to_date64_round_trip() {
+        // 2018-12-25 and 1969-12-31, the latter being before the epoch
+        let a = Date32Array::from(vec![Some(17890), Some(-1), None]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Date64(DateUnit::Millisecond)).unwrap();
+        let c = b.as_any().downcast_ref::<Date64Array>().unwrap();
+        assert_eq!(1545696000000, c.value(0));
+        assert_eq!(-86400000, c.value(1));
+        assert!(c.is_null(2));
+
+        let d = cast(&b, &DataType::Date32(DateUnit::Day)).unwrap();
+        let e = d.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(17890, e.value(0));
+        assert_eq!(-1, e.value(1));
+        assert!(e.is_null(2));
+    }
+
+    #[test]
+    fn test_cast_date64_to_date32_pre_epoch() {
+        let a = Date64Array::from(vec![-1, -86400000, -86400001]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Date32(DateUnit::Day)).unwrap();
+        let c = b.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(-1, c.value(0));
+        assert_eq!(-1, c.value(1));
+        assert_eq!(-2, c.value(2));
+    }
+
+    #[test]
+    fn test_cast_date64_to_date32_out_of_range() {
+        let a = Date64Array::from(vec![86400000, std::i64::MAX]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Date32(DateUnit::Day)).unwrap();
+        let c = b.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(1, c.value(0));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_cast_time64_to_time32_negative() {
+        let a = Time64MicrosecondArray::from(vec![-1, 1_500_000]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Time32(TimeUnit::Second)).unwrap();
+        let c = b.as_any().downcast_ref::<Time32SecondArray>().unwrap();
+        assert_eq!(-1, c.value(0));
+        assert_eq!(1, c.value(1));
+    }
+
     #[test]
     fn test_cast_date32_to_int32() {
         let a = Date32Array::from(vec![10000, 17890]);
@@ -1079,6 +1360,24 @@ mod tests {
         assert!(c.is_null(2));
     }
 
+    #[test]
+    fn test_cast_timestamp_to_date32_floors() {
+        // 2020-03-15T13:45:00Z, 1969-12-31T23:59:59.999999Z and 1969-12-31T00:00:00Z
+        let a = TimestampMicrosecondArray::from(vec![
+            Some(1584279900000000),
+            Some(-1),
+            Some(-86400000000),
+            None,
+        ]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Date32(DateUnit::Day)).unwrap();
+        let c = b.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(18336, c.value(0));
+        assert_eq!(-1, c.value(1));
+        assert_eq!(-1, c.value(2));
+        assert!(c.is_null(3));
+    }
+
     #[test]
     fn test_cast_timestamp_to_date64() {
         let a = TimestampMillisecondArray::from(vec![
@@ -1094,6 +1393,51 @@ mod tests {
         assert!(c.is_null(2));
     }
 
+    #[test]
+    fn test_cast_timestamp_seconds_to_date64() {
+        let a = TimestampSecondArray::from(vec![Some(864000000), None]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Date64(DateUnit::Millisecond)).unwrap();
+        let c = b.as_any().downcast_ref::<Date64Array>().unwrap();
+        assert_eq!(864000000000, c.value(0));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_cast_date_to_timestamp() {
+        let a = Date32Array::from(vec![Some(1), Some(-1), None]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Timestamp(TimeUnit::Millisecond)).unwrap();
+        let c = b
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(86_400_000, c.value(0));
+        assert_eq!(-86_400_000, c.value(1));
+        assert!(c.is_null(2));
+
+        let a = Date64Array::from(vec![Some(1_500), None]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Timestamp(TimeUnit::Second)).unwrap();
+        let c = b.as_any().downcast_ref::<TimestampSecondArray>().unwrap();
+        assert_eq!(1, c.value(0));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_cast_timestamp_overflow_is_null() {
+        let a = TimestampSecondArray::from(vec![Some(std::i64::MAX / 10), Some(2), None]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Timestamp(TimeUnit::Nanosecond)).unwrap();
+        let c = b
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        assert!(c.is_null(0));
+        assert_eq!(2_000_000_000, c.value(1));
+        assert!(c.is_null(2));
+    }
+
     #[test]
     fn test_cast_timestamp_to_i64() {
         let a = TimestampMillisecondArray::from(vec![
@@ -1125,6 +1469,21 @@ mod tests {
         assert!(c.is_null(2));
     }
 
+    #[test]
+    fn test_cast_between_timestamps_floors() {
+        let a = TimestampMillisecondArray::from(vec![Some(-1), Some(-1000), Some(-1001)]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Timestamp(TimeUnit::Second)).unwrap();
+        let c = b.as_any().downcast_ref::<TimestampSecondArray>().unwrap();
+        assert_eq!(vec![-1, -1, -2], c.values_slice().to_vec());
+
+        let a = TimestampNanosecondArray::from(vec![Some(-1), Some(1)]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Date64(DateUnit::Millisecond)).unwrap();
+        let c = b.as_any().downcast_ref::<Date64Array>().unwrap();
+        assert_eq!(vec![-1, 0], c.values_slice().to_vec());
+    }
+
     #[test]
     fn test_cast_from_f64() {
         let f64_values: Vec<f64> = vec![
@@ -2055,6 +2414,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cast_with_options() {
+        let a = Int32Array::from(vec![Some(1), None, Some(-1)]);
+        let array = Arc::new(a) as ArrayRef;
+
+        let b =
+            cast_with_options(&array, &DataType::UInt8, &CastOptions::default()).unwrap();
+        assert_eq!(2, b.null_count());
+
+        let options = CastOptions { safe: false };
+        assert!(cast_with_options(&array, &DataType::UInt8, &options).is_err());
+        let b = cast_with_options(&array, &DataType::Int64, &options).unwrap();
+        assert_eq!(1, b.null_count());
+    }
+
+    fn binary_array(values: Vec<Option<&[u8]>>) -> ArrayRef {
+        let mut builder = BinaryBuilder::new(values.len());
+        for value in values {
+            match value {
+                Some(value) => builder.append_value(value).unwrap(),
+                None => builder.append(false).unwrap(),
+            }
+        }
+        let data = builder.finish().data();
+        let data = ArrayData::new(
+            DataType::Binary,
+            data.len(),
+            Some(data.null_count()),
+            data.null_bitmap().clone().map(|bitmap| bitmap.bits),
+            0,
+            data.buffers().to_vec(),
+            vec![],
+        );
+        make_array(Arc::new(data))
+    }
+
+    #[test]
+    fn test_cast_binary_to_utf8() {
+        let array = binary_array(vec![
+            Some(&b"hello"[..]),
+            None,
+            Some(&[0xff, 0xfe]),
+            Some(&b""[..]),
+        ]);
+
+        let b = cast(&array, &DataType::Utf8).unwrap();
+        assert_eq!(&DataType::Utf8, b.data_type());
+        assert_eq!(2, b.null_count());
+        let c = StringArray::from(b.data());
+        assert_eq!("hello", c.value(0));
+        assert!(c.is_null(1));
+        assert!(c.is_null(2));
+        assert_eq!("", c.value(3));
+        // the invalid value is not kept in the null slot
+        assert_eq!(0, c.value_length(2));
+        assert!(StringArray::try_from_data(b.data()).is_ok());
+
+        let options = CastOptions { safe: false };
+        assert!(cast_with_options(&array, &DataType::Utf8, &options).is_err());
+        let valid = array.slice(0, 2);
+        let b = cast_with_options(&valid, &DataType::Utf8, &options).unwrap();
+        assert_eq!(1, b.null_count());
+        assert_eq!(valid.data().buffers(), b.data().buffers());
+
+        let sliced = array.slice(2, 2);
+        let b = cast(&sliced, &DataType::Utf8).unwrap();
+        assert!(b.is_null(0));
+        assert_eq!("", StringArray::try_from_data(b.data()).unwrap().value(1));
+    }
+
+    #[test]
+    fn test_cast_utf8_to_binary() {
+        let a = StringArray::from(vec![Some("hello"), None, Some("arrow")]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Binary).unwrap();
+        assert_eq!(&DataType::Binary, b.data_type());
+        assert_eq!(1, b.null_count());
+        assert_eq!(array.data().buffers(), b.data().buffers());
+        let c = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(b"hello", c.value(0));
+        assert_eq!(b"arrow", c.value(2));
+    }
+
+    #[test]
+    fn test_cast_utf8_to_decimal() {
+        let a = StringArray::from(vec![
+            Some("150.005"),
+            None,
+            Some("-0.5"),
+            Some("not a number"),
+            Some("100000"),
+        ]);
+        let array = Arc::new(a) as ArrayRef;
+        let b = cast(&array, &DataType::Decimal(7, 2)).unwrap();
+        assert_eq!(&DataType::Decimal(7, 2), b.data_type());
+        let c = b.as_any().downcast_ref::<DecimalArray>().unwrap();
+        assert_eq!(15001, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(-50, c.value(2));
+        assert!(c.is_null(3));
+        assert!(c.is_null(4));
+
+        let d = cast(&b, &DataType::Utf8).unwrap();
+        let d = d.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(b"150.01", d.value(0));
+        assert!(d.is_null(1));
+        assert_eq!(b"-0.50", d.value(2));
+
+        match cast(&array, &DataType::Decimal(40, 2)) {
+            Err(ArrowError::InvalidArgumentError(_)) => {}
+            _ => panic!("Expected an invalid decimal type to be rejected"),
+        }
+    }
+
+    #[test]
+    fn test_cast_dictionary_widen_keys() {
+        let a: Int8DictionaryArray = vec![Some("one"), None, Some("two"), Some("one")]
+            .into_iter()
+            .collect();
+        let values_data = a.values().data().buffers()[1].raw_data();
+        let array = Arc::new(a) as ArrayRef;
+
+        let to_type =
+            DataType::Dictionary(Box::new(DataType::Int16), Box::new(DataType::Utf8));
+        let b = cast(&array, &to_type).unwrap();
+        assert_eq!(&to_type, b.data_type());
+        let c = b.as_any().downcast_ref::<Int16DictionaryArray>().unwrap();
+        assert_eq!(4, c.len());
+        assert_eq!(1, c.null_count());
+        assert_eq!(Some(0), c.key(0));
+        assert_eq!(None, c.key(1));
+        assert_eq!(Some(1), c.key(2));
+        assert_eq!(Some(0), c.key(3));
+        // the values are shared, not copied
+        assert_eq!(values_data, c.values().data().buffers()[1].raw_data());
+    }
+
+    #[test]
+    fn test_cast_dictionary_narrow_keys_overflow() {
+        let strings: Vec<String> = (0..200).map(|i| format!("{}", i)).collect();
+        let a: Int16DictionaryArray = strings.iter().map(|s| s.as_str()).collect();
+        let array = Arc::new(a) as ArrayRef;
+
+        let to_type =
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8));
+        assert!(cast(&array, &to_type).is_err());
+
+        // narrowing keys that fit is fine
+        let b = array.slice(0, 100);
+        let c = cast(&b, &to_type).unwrap();
+        let d = c.as_any().downcast_ref::<Int8DictionaryArray>().unwrap();
+        assert_eq!(Some(99), d.key(99));
+    }
+
     fn get_cast_values<T>(array: &ArrayRef, dt: &DataType) -> Vec<String>
     where
         T: ArrowNumericType,
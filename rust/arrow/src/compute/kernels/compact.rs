@@ -0,0 +1,158 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the compact kernel, which copies arrays into buffers holding only their
+//! values.
+
+use crate::array::*;
+use crate::error::Result;
+
+use super::concat::concat_copy;
+
+/// Returns a copy of `array` with an offset of zero, whose buffers hold exactly its
+/// values.
+///
+/// A slice shares the buffers of the array it was sliced from, so a small slice keeps
+/// all of a large array's memory alive. Compacting the slice, and dropping the large
+/// array, reclaims this memory. The value offsets of string and list arrays are
+/// recomputed to start at zero, and the children of list and struct arrays are
+/// compacted too.
+///
+/// Supports boolean, primitive, string, list and struct arrays, and returns a
+/// `ComputeError` for arrays of other types, such as dictionaries.
+pub fn compact(array: &Array) -> Result<ArrayRef> {
+    concat_copy(&[make_array(array.data())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+    use std::sync::Arc;
+
+    use crate::datatypes::{DataType, Field};
+    use crate::error::ArrowError;
+
+    #[test]
+    fn test_compact_primitive_array() {
+        let values = (0..1000)
+            .map(|i| if i % 3 == 0 { None } else { Some(i) })
+            .collect::<Vec<_>>();
+        let array = Int32Array::from(values);
+        let slice = array.slice(600, 4);
+
+        let compacted = compact(slice.as_ref()).unwrap();
+        assert_eq!(0, compacted.offset());
+        assert_eq!(4 * 4, compacted.data().buffers()[0].len());
+        assert_eq!(
+            1,
+            compacted.data().null_bitmap().as_ref().unwrap().bits.len()
+        );
+        assert!(compacted.equals(slice.as_ref()));
+        assert!(compacted.equals(&Int32Array::from(vec![
+            None,
+            Some(601),
+            Some(602),
+            None
+        ])));
+    }
+
+    #[test]
+    fn test_compact_boolean_array() {
+        let array = BooleanArray::from(vec![true; 100]);
+        let slice = array.slice(90, 9);
+
+        let compacted = compact(slice.as_ref()).unwrap();
+        assert_eq!(0, compacted.offset());
+        assert_eq!(2, compacted.data().buffers()[0].len());
+        assert!(compacted.equals(slice.as_ref()));
+    }
+
+    #[test]
+    fn test_compact_string_array() {
+        let strings = (0..100).map(|i| format!("value {}", i)).collect::<Vec<_>>();
+        let array =
+            BinaryArray::from(strings.iter().map(|s| s.as_str()).collect::<Vec<_>>());
+        let slice = array.slice(10, 2);
+
+        let compacted = compact(slice.as_ref()).unwrap();
+        let compacted = compacted.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(0, compacted.offset());
+        assert_eq!(0, compacted.value_offset(0));
+        assert_eq!(3 * 4, compacted.data().buffers()[0].len());
+        assert_eq!(
+            "value 10value 11".len(),
+            compacted.data().buffers()[1].len()
+        );
+        assert_eq!(b"value 10", compacted.value(0));
+        assert_eq!(b"value 11", compacted.value(1));
+
+        let array =
+            BinaryArray::try_from(vec![Some("a"), None, Some("bc"), Some("d")]).unwrap();
+        let slice = array.slice(1, 2);
+        assert!(compact(slice.as_ref()).unwrap().equals(slice.as_ref()));
+    }
+
+    #[test]
+    fn test_compact_list_array() {
+        let mut builder = ListBuilder::new(Int64Builder::new(300));
+        for i in 0..100 {
+            builder.values().append_slice(&[i, i + 1, i + 2]).unwrap();
+            builder.append(i % 10 != 0).unwrap();
+        }
+        let array = builder.finish();
+        let slice = array.slice(49, 2);
+
+        let compacted = compact(slice.as_ref()).unwrap();
+        let compacted = compacted.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(0, compacted.offset());
+        assert!(compacted.equals(slice.as_ref()));
+        assert_eq!(0, compacted.value_offset(0));
+        assert_eq!(6, compacted.value_offset(2));
+        assert_eq!(3 * 4, compacted.data().buffers()[0].len());
+        let values = compacted.values();
+        assert_eq!(0, values.offset());
+        assert_eq!(6 * 8, values.data().buffers()[0].len());
+    }
+
+    #[test]
+    fn test_compact_struct_array() {
+        let ints: ArrayRef = Arc::new(Int32Array::from((0..64).collect::<Vec<_>>()));
+        let array =
+            StructArray::from(vec![(Field::new("ints", DataType::Int32, false), ints)]);
+        let slice = array.slice(32, 3);
+
+        let compacted = compact(slice.as_ref()).unwrap();
+        let compacted = compacted.as_any().downcast_ref::<StructArray>().unwrap();
+        assert!(compacted.equals(slice.as_ref()));
+        assert_eq!(0, compacted.column(0).offset());
+        assert_eq!(3 * 4, compacted.column(0).data().buffers()[0].len());
+    }
+
+    #[test]
+    fn test_compact_unsupported_array() {
+        let array: Int8DictionaryArray = vec!["a", "b", "a"].into_iter().collect();
+        match compact(&array) {
+            Err(ArrowError::ComputeError(message)) => assert_eq!(
+                "Copying arrays of data type Dictionary(Int8, Utf8) is not supported",
+                message
+            ),
+            _ => panic!("expected an unsupported data type error"),
+        }
+    }
+}
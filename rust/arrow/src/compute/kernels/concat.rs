@@ -0,0 +1,326 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines concat kernels that append arrays, or record batches, into a single one.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::buffer::{Buffer, MutableBuffer};
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::util::bit_util;
+
+/// Concatenates `arrays`, which must all have the same data type, into a single array
+/// with their values in order.
+///
+/// Supports boolean, primitive, string, binary, list and struct arrays. The validity
+/// bits and the value offsets of the arrays are copied one by one, so that arrays that
+/// are slices with any offset can be concatenated.
+pub fn concat(arrays: &[ArrayRef]) -> Result<ArrayRef> {
+    if arrays.is_empty() {
+        return Err(ArrowError::InvalidArgumentError(
+            "Concat requires at least one array".to_string(),
+        ));
+    }
+    let data_type = arrays[0].data_type();
+    if let Some(array) = arrays.iter().find(|a| a.data_type() != data_type) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Cannot concat arrays of different types {:?} and {:?}",
+            data_type,
+            array.data_type()
+        )));
+    }
+    if arrays.len() == 1 {
+        return Ok(arrays[0].clone());
+    }
+    concat_copy(arrays)
+}
+
+/// Like `concat`, but always copies the values of `arrays`, which must all have the
+/// same data type, into new buffers, even if there is a single array.
+pub(crate) fn concat_copy(arrays: &[ArrayRef]) -> Result<ArrayRef> {
+    let data_type = arrays[0].data_type();
+    let len = arrays.iter().map(|a| a.len()).sum();
+    let null_count = arrays.iter().map(|a| a.null_count()).sum();
+    let mut builder = ArrayData::builder(data_type.clone())
+        .len(len)
+        .null_count(null_count);
+    if null_count > 0 {
+        builder =
+            builder.null_bit_buffer(concat_bits(arrays, len, |a, i| a.is_valid(i))?);
+    }
+
+    builder = match data_type {
+        DataType::Boolean => builder.add_buffer(concat_bits(arrays, len, |a, i| {
+            let data = a.data_ref();
+            bit_util::get_bit(data.buffers()[0].data(), data.offset() + i)
+        })?),
+        DataType::Utf8 | DataType::Binary => {
+            let arrays: Vec<ArrayRef> = arrays
+                .iter()
+                .map(|a| Arc::new(as_binary_array(a)) as ArrayRef)
+                .collect();
+            let (offsets, ranges) = concat_offsets(&arrays, len, |a, i| {
+                a.as_any()
+                    .downcast_ref::<BinaryArray>()
+                    .unwrap()
+                    .value_offset(i)
+            })?;
+            let mut values = MutableBuffer::new(ranges.iter().map(|(_, n)| n).sum());
+            for (array, (start, length)) in arrays.iter().zip(ranges) {
+                let array = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+                values.write_all(&array.value_data().data()[start..start + length])?;
+            }
+            builder.add_buffer(offsets).add_buffer(values.freeze())
+        }
+        DataType::List(_) => {
+            let (offsets, ranges) = concat_offsets(arrays, len, |a, i| {
+                a.as_any()
+                    .downcast_ref::<ListArray>()
+                    .unwrap()
+                    .value_offset(i)
+            })?;
+            let children = arrays
+                .iter()
+                .zip(ranges)
+                .map(|(array, (start, length))| {
+                    let array = array.as_any().downcast_ref::<ListArray>().unwrap();
+                    array.values().slice(start, length)
+                })
+                .collect::<Vec<_>>();
+            builder
+                .add_buffer(offsets)
+                .add_child_data(concat_copy(&children)?.data())
+        }
+        DataType::Struct(fields) => {
+            for i in 0..fields.len() {
+                let children = arrays
+                    .iter()
+                    .map(|a| {
+                        let array = a.as_any().downcast_ref::<StructArray>().unwrap();
+                        array.column(i).clone()
+                    })
+                    .collect::<Vec<_>>();
+                builder = builder.add_child_data(concat_copy(&children)?.data());
+            }
+            builder
+        }
+        t => match t.size_of() {
+            Some(width) => {
+                let mut values = MutableBuffer::new(len * width);
+                for array in arrays {
+                    let data = array.data_ref();
+                    let start = data.offset() * width;
+                    let end = start + array.len() * width;
+                    values.write_all(&data.buffers()[0].data()[start..end])?;
+                }
+                builder.add_buffer(values.freeze())
+            }
+            None => {
+                return Err(ArrowError::ComputeError(format!(
+                    "Copying arrays of data type {:?} is not supported",
+                    t
+                )))
+            }
+        },
+    };
+    Ok(make_array(builder.build()))
+}
+
+/// Concatenates `batches`, which must all have the same schema, into a single batch
+/// with their rows in order.
+pub fn concat_batches(batches: &[RecordBatch]) -> Result<RecordBatch> {
+    if batches.is_empty() {
+        return Err(ArrowError::InvalidArgumentError(
+            "Concat requires at least one record batch".to_string(),
+        ));
+    }
+    let schema = batches[0].schema();
+    if batches.iter().any(|b| b.schema() != schema) {
+        return Err(ArrowError::InvalidArgumentError(
+            "Cannot concat record batches with different schemas".to_string(),
+        ));
+    }
+    let columns = (0..schema.fields().len())
+        .map(|i| {
+            let arrays = batches
+                .iter()
+                .map(|b| b.column(i).clone())
+                .collect::<Vec<_>>();
+            concat(&arrays)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    RecordBatch::try_new(schema.clone(), columns)
+}
+
+/// Packs the bit returned by `bit` for each element of `arrays` into a buffer
+fn concat_bits<F>(arrays: &[ArrayRef], len: usize, bit: F) -> Result<Buffer>
+where
+    F: Fn(&ArrayRef, usize) -> bool,
+{
+    let mut builder = BooleanBufferBuilder::new(len);
+    for array in arrays {
+        for i in 0..array.len() {
+            builder.append(bit(array, i))?;
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Rebases the value offsets of `arrays`, as returned by `offset`, so that the offsets
+/// of each array continue where the previous one ended.
+///
+/// Returns the offsets together with the start and length of the values referenced by
+/// each array.
+fn concat_offsets<F>(
+    arrays: &[ArrayRef],
+    len: usize,
+    offset: F,
+) -> Result<(Buffer, Vec<(usize, usize)>)>
+where
+    F: Fn(&ArrayRef, usize) -> i32,
+{
+    let mut builder = Int32BufferBuilder::new(len + 1);
+    let mut ranges = Vec::with_capacity(arrays.len());
+    let mut end = 0;
+    builder.append(end)?;
+    for array in arrays {
+        let start = offset(array, 0);
+        for i in 1..=array.len() {
+            builder.append(end + offset(array, i) - start)?;
+        }
+        let length = offset(array, array.len()) - start;
+        ranges.push((start as usize, length as usize));
+        end += length;
+    }
+    Ok((builder.finish(), ranges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concat_primitive_arrays() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]));
+        let b: ArrayRef = Int32Array::from(vec![4, 5, 6, 7]).slice(1, 2);
+        let c = concat(&[a, b]).unwrap();
+        let expected = Int32Array::from(vec![Some(1), None, Some(3), Some(5), Some(6)]);
+        assert!(c.equals(&expected));
+        assert_eq!(1, c.null_count());
+    }
+
+    #[test]
+    fn test_concat_string_arrays() {
+        let a: ArrayRef = BinaryArray::from(vec!["hello", "arrow"]).slice(1, 1);
+        let b: ArrayRef = Arc::new(BinaryArray::from(vec!["a", "bc"]));
+        let c = concat(&[a, b]).unwrap();
+        let c = c.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(3, c.len());
+        assert_eq!(b"arrow", c.value(0));
+        assert_eq!(b"a", c.value(1));
+        assert_eq!(b"bc", c.value(2));
+    }
+
+    #[test]
+    fn test_concat_all_null_and_no_null_arrays() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let b: ArrayRef = Arc::new(Int32Array::from(vec![None, None]));
+        let c: ArrayRef = Int32Array::from(vec![Some(4), None, Some(5)]).slice(1, 2);
+        let d: ArrayRef = Arc::new(Int32Array::from(vec![6]));
+        let concatenated = concat(&[a, b, c, d]).unwrap();
+        let expected = Int32Array::from(vec![
+            Some(1),
+            Some(2),
+            Some(3),
+            None,
+            None,
+            None,
+            Some(5),
+            Some(6),
+        ]);
+        assert!(concatenated.equals(&expected));
+        assert_eq!(3, concatenated.null_count());
+
+        let a: ArrayRef = Arc::new(BooleanArray::from(vec![None, None, None]));
+        let b: ArrayRef = Arc::new(BooleanArray::from(vec![true, false]));
+        let concatenated = concat(&[a, b]).unwrap();
+        let expected =
+            BooleanArray::from(vec![None, None, None, Some(true), Some(false)]);
+        assert!(concatenated.equals(&expected));
+    }
+
+    #[test]
+    fn test_concat_string_and_binary_arrays() {
+        let a: ArrayRef = Arc::new(StringArray::from(vec![Some("x"), None]));
+        let b: ArrayRef = Arc::new(BinaryArray::from(vec!["yz"]));
+        let c = concat(&[a, b]).unwrap();
+        let c = BinaryArray::from(c.data());
+        assert_eq!(&DataType::Utf8, c.data_type());
+        assert_eq!(b"x", c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(b"yz", c.value(2));
+
+        let a: ArrayRef = Arc::new(BinaryArray::from(
+            ArrayData::builder(DataType::Binary)
+                .len(1)
+                .add_buffer(Buffer::from(vec![0i32, 2].to_byte_slice()))
+                .add_buffer(Buffer::from(&[0u8, 1]))
+                .build(),
+        ));
+        let c = concat(&[a.clone(), a]).unwrap();
+        let c = c.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(&DataType::Binary, c.data_type());
+        assert_eq!(&[0u8, 1], c.value(1));
+    }
+
+    #[test]
+    fn test_concat_list_arrays() {
+        let mut builder = ListBuilder::new(Int32Builder::new(8));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_value(3).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        let a = builder.finish();
+        let a: ArrayRef = a.slice(1, 2);
+        builder.values().append_slice(&[4, 5, 6]).unwrap();
+        builder.append(true).unwrap();
+        let b: ArrayRef = Arc::new(builder.finish());
+
+        let c = concat(&[a, b]).unwrap();
+        let c = c.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(3, c.len());
+        assert_eq!(1, c.null_count());
+        let offsets: Vec<i32> = (0..=3).map(|i| c.value_offset(i)).collect();
+        assert_eq!(vec![0, 1, 1, 4], offsets);
+        let values = c.values();
+        let values = values.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(&[3, 4, 5, 6], values.value_slice(0, 4));
+    }
+
+    #[test]
+    fn test_concat_different_types() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        let b: ArrayRef = Arc::new(Int64Array::from(vec![1]));
+        assert!(concat(&[a, b]).is_err());
+        assert!(concat(&[]).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the explode kernel, which turns the elements of a list column into rows.
+
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::compute::kernels::take::take;
+use crate::datatypes::{DataType, Field, Schema};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::{RecordBatch, RecordBatchOptions};
+
+/// Options that define how `explode_with_options` should behave
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExplodeOptions {
+    /// Whether a row whose list is null or empty results in one row with a null
+    /// element. If `false`, such rows are dropped.
+    pub preserve_empty: bool,
+}
+
+/// Returns a batch with a row for each element of the lists in the column at
+/// `list_column` of `batch`, which is SQL `UNNEST`.
+///
+/// The values of the other columns are repeated for each element of the list of their
+/// row, and the list column is replaced by a column of the elements. Rows whose list is
+/// null or empty are dropped.
+pub fn explode(batch: &RecordBatch, list_column: usize) -> Result<RecordBatch> {
+    explode_with_options(batch, list_column, ExplodeOptions::default())
+}
+
+/// Returns a batch with a row for each element of the lists in the column at
+/// `list_column` of `batch` like `explode`, handling rows whose list is null or empty
+/// according to `options`.
+///
+/// Returns an error if `list_column` is out of bounds or isn't a `List` column.
+pub fn explode_with_options(
+    batch: &RecordBatch,
+    list_column: usize,
+    options: ExplodeOptions,
+) -> Result<RecordBatch> {
+    if list_column >= batch.num_columns() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Column index {} out of bounds for a batch of {} columns",
+            list_column,
+            batch.num_columns()
+        )));
+    }
+    let list_field = batch.schema().field(list_column);
+    let value_type = match list_field.data_type() {
+        DataType::List(value_type) => value_type.as_ref().clone(),
+        t => {
+            return Err(ArrowError::ComputeError(format!(
+                "Cannot explode column \"{}\" of type {:?}, only lists can be exploded",
+                list_field.name(),
+                t
+            )))
+        }
+    };
+    let list = batch.column(list_column).downcast::<ListArray>()?;
+
+    // the row of `batch` and the index into the list values of each output row
+    let mut rows = vec![];
+    let mut elements = vec![];
+    for i in 0..list.len() {
+        let length = if list.is_valid(i) {
+            list.value_length(i) as u32
+        } else {
+            0
+        };
+        if length == 0 {
+            if options.preserve_empty {
+                rows.push(i as u32);
+                elements.push(None);
+            }
+            continue;
+        }
+        let offset = list.value_offset(i) as u32;
+        for j in offset..offset + length {
+            rows.push(i as u32);
+            elements.push(Some(j));
+        }
+    }
+    let row_count = rows.len();
+    let rows = UInt32Array::from(rows);
+    let elements = UInt32Array::from(elements);
+
+    let mut fields = batch.schema().fields().clone();
+    fields[list_column] = Field::new(list_field.name(), value_type, true);
+    let columns = batch
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            if i == list_column {
+                take(&list.values(), &elements, None)
+            } else {
+                take(column, &rows, None)
+            }
+        })
+        .collect::<Result<Vec<ArrayRef>>>()?;
+    RecordBatch::try_new_with_options(
+        Arc::new(Schema::new(fields)),
+        columns,
+        &RecordBatchOptions {
+            match_field_names: true,
+            row_count: Some(row_count),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch() -> RecordBatch {
+        let mut builder = ListBuilder::new(Int32Builder::new(8));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_value(3).unwrap();
+        builder.values().append_null().unwrap();
+        builder.values().append_value(4).unwrap();
+        builder.append(true).unwrap();
+        let list = builder.finish();
+
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("values", DataType::List(Box::new(DataType::Int32)), true),
+            Field::new("name", DataType::Utf8, false),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![10, 20, 30, 40])),
+                Arc::new(list),
+                Arc::new(BinaryArray::from(vec!["a", "b", "c", "d"])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_explode() {
+        let exploded = explode(&batch(), 1).unwrap();
+        assert_eq!(5, exploded.num_rows());
+        assert_eq!(&DataType::Int32, exploded.schema().field(1).data_type());
+
+        let ids = exploded.column(0).downcast::<Int32Array>().unwrap();
+        assert_eq!(&[10, 10, 40, 40, 40], ids.value_slice(0, 5));
+        let values = exploded.column(1).downcast::<Int32Array>().unwrap();
+        assert_eq!(1, values.value(0));
+        assert_eq!(2, values.value(1));
+        assert_eq!(3, values.value(2));
+        assert!(values.is_null(3));
+        assert_eq!(4, values.value(4));
+        let names = exploded.column(2).downcast::<BinaryArray>().unwrap();
+        let names: Vec<String> = (0..5).map(|i| names.get_string(i)).collect();
+        assert_eq!(vec!["a", "a", "d", "d", "d"], names);
+    }
+
+    #[test]
+    fn test_explode_preserve_empty() {
+        let options = ExplodeOptions {
+            preserve_empty: true,
+        };
+        let exploded = explode_with_options(&batch(), 1, options).unwrap();
+        assert_eq!(7, exploded.num_rows());
+        let ids = exploded.column(0).downcast::<Int32Array>().unwrap();
+        assert_eq!(&[10, 10, 20, 30, 40, 40, 40], ids.value_slice(0, 7));
+        let values = exploded.column(1).downcast::<Int32Array>().unwrap();
+        assert!(values.is_null(2));
+        assert!(values.is_null(3));
+        assert_eq!(3, values.value(4));
+    }
+
+    #[test]
+    fn test_explode_non_list_column() {
+        assert!(explode(&batch(), 0).is_err());
+        assert!(explode(&batch(), 3).is_err());
+    }
+}
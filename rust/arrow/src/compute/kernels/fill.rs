@@ -0,0 +1,119 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that fill null slots of an array with neighbouring values.
+
+use crate::array::*;
+use crate::compute::kernels::take::take;
+use crate::error::Result;
+
+/// Returns a copy of `array` in which every null slot holds the last non-null value
+/// before it.
+///
+/// Null slots before the first non-null value stay null. All data types supported by
+/// `take` are supported, including primitive and string arrays.
+pub fn forward_fill(array: &Array) -> Result<ArrayRef> {
+    let mut last_valid = None;
+    let indices: Vec<Option<u32>> = (0..array.len())
+        .map(|i| {
+            if array.is_valid(i) {
+                last_valid = Some(i as u32);
+            }
+            last_valid
+        })
+        .collect();
+    take(&make_array(array.data()), &UInt32Array::from(indices), None)
+}
+
+/// Returns a copy of `array` in which every null slot holds the first non-null value
+/// after it.
+///
+/// Null slots after the last non-null value stay null. All data types supported by
+/// `take` are supported, including primitive and string arrays.
+pub fn backward_fill(array: &Array) -> Result<ArrayRef> {
+    let mut next_valid = None;
+    let mut indices: Vec<Option<u32>> = (0..array.len())
+        .rev()
+        .map(|i| {
+            if array.is_valid(i) {
+                next_valid = Some(i as u32);
+            }
+            next_valid
+        })
+        .collect();
+    indices.reverse();
+    take(&make_array(array.data()), &UInt32Array::from(indices), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+
+    fn to_vec(array: &ArrayRef) -> Vec<Option<i32>> {
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        (0..array.len())
+            .map(|i| {
+                if array.is_valid(i) {
+                    Some(array.value(i))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_forward_fill() {
+        let a = Int32Array::from(vec![Some(1), None, None, Some(2), None]);
+        let b = forward_fill(&a).unwrap();
+        assert_eq!(
+            vec![Some(1), Some(1), Some(1), Some(2), Some(2)],
+            to_vec(&b)
+        );
+
+        let a = Int32Array::from(vec![None, Some(1), None]);
+        let b = forward_fill(&a).unwrap();
+        assert_eq!(vec![None, Some(1), Some(1)], to_vec(&b));
+    }
+
+    #[test]
+    fn test_backward_fill() {
+        let a = Int32Array::from(vec![Some(1), None, None, Some(2), None]);
+        let b = backward_fill(&a).unwrap();
+        assert_eq!(vec![Some(1), Some(2), Some(2), Some(2), None], to_vec(&b));
+    }
+
+    #[test]
+    fn test_fill_sliced_strings() {
+        let a =
+            BinaryArray::try_from(vec![Some("a"), None, Some("b"), None, None]).unwrap();
+        let a = a.slice(1, 4);
+        let b = forward_fill(a.as_ref()).unwrap();
+        let b = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert!(b.is_null(0));
+        assert_eq!("b", b.get_string(1));
+        assert_eq!("b", b.get_string(2));
+        assert_eq!("b", b.get_string(3));
+
+        let b = backward_fill(a.as_ref()).unwrap();
+        let b = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("b", b.get_string(0));
+        assert!(b.is_null(3));
+    }
+}
@@ -0,0 +1,375 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines filter kernels that select the slots of arrays and the rows of record
+//! batches for which a boolean predicate is true.
+
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::compute::util::{dictionary_from_keys, dictionary_keys};
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::{RecordBatch, RecordBatchOptions};
+
+macro_rules! filter_array {
+    ($array:expr, $predicate:expr, $array_type:ident) => {{
+        let b = $array.as_any().downcast_ref::<$array_type>().unwrap();
+        let mut builder = $array_type::builder(b.len());
+        for i in 0..b.len() {
+            if is_selected($predicate, i) {
+                if b.is_null(i) {
+                    builder.append_null()?;
+                } else {
+                    builder.append_value(b.value(i))?;
+                }
+            }
+        }
+        Ok(Arc::new(builder.finish()))
+    }};
+}
+
+/// Returns the array, taking only the elements for which `predicate` is true
+///
+/// Null slots of `predicate` are treated as false, and null slots of `array` that are
+/// selected stay null. Returns an error if `predicate` and `array` differ in length.
+///
+/// If every slot of `predicate` is a non-null `true`, the returned array shares the
+/// data of `array` instead of copying it. Dictionary arrays stay dictionary encoded.
+pub fn filter(array: &Array, predicate: &BooleanArray) -> Result<ArrayRef> {
+    if predicate.len() != array.len() {
+        return Err(ArrowError::ComputeError(format!(
+            "Filter predicate of length {} can not filter an array of length {}",
+            predicate.len(),
+            array.len()
+        )));
+    }
+    if predicate.null_count() == 0 && (0..predicate.len()).all(|i| predicate.value(i)) {
+        return Ok(make_array(array.data()));
+    }
+    match array.data_type() {
+        DataType::Null => {
+            let len = (0..array.len())
+                .filter(|i| is_selected(predicate, *i))
+                .count();
+            Ok(Arc::new(NullArray::new(len)))
+        }
+        DataType::UInt8 => filter_array!(array, predicate, UInt8Array),
+        DataType::UInt16 => filter_array!(array, predicate, UInt16Array),
+        DataType::UInt32 => filter_array!(array, predicate, UInt32Array),
+        DataType::UInt64 => filter_array!(array, predicate, UInt64Array),
+        DataType::Int8 => filter_array!(array, predicate, Int8Array),
+        DataType::Int16 => filter_array!(array, predicate, Int16Array),
+        DataType::Int32 => filter_array!(array, predicate, Int32Array),
+        DataType::Int64 => filter_array!(array, predicate, Int64Array),
+        DataType::Float32 => filter_array!(array, predicate, Float32Array),
+        DataType::Float64 => filter_array!(array, predicate, Float64Array),
+        DataType::Boolean => filter_array!(array, predicate, BooleanArray),
+        DataType::Utf8 | DataType::Binary => {
+            let b = as_binary_array(array);
+            let mut builder = BinaryBuilder::new(b.len());
+            for i in 0..b.len() {
+                if is_selected(predicate, i) {
+                    if b.is_null(i) {
+                        builder.append_null()?;
+                    } else {
+                        builder.append_value(b.value(i))?;
+                    }
+                }
+            }
+            let filtered = builder.finish().data();
+            Ok(make_array(Arc::new(ArrayData::new(
+                array.data_type().clone(),
+                filtered.len(),
+                Some(filtered.null_count()),
+                filtered.null_bitmap().clone().map(|bitmap| bitmap.bits),
+                filtered.offset(),
+                filtered.buffers().to_vec(),
+                vec![],
+            ))))
+        }
+        DataType::Dictionary(_, _) => {
+            let keys = dictionary_keys(array.data_ref());
+            let keys = filter(keys.as_ref(), predicate)?;
+            Ok(dictionary_from_keys(array.data_ref(), &keys))
+        }
+        other => Err(ArrowError::ComputeError(format!(
+            "filter not supported for {:?}",
+            other
+        ))),
+    }
+}
+
+/// Returns whether slot `i` of `predicate` is a non-null `true`
+fn is_selected(predicate: &BooleanArray, i: usize) -> bool {
+    predicate.is_valid(i) && predicate.value(i)
+}
+
+/// Returns the rows of `batch` for which `predicate` is true, by applying `filter` to
+/// each of its columns.
+///
+/// Dictionary encoded columns stay encoded and share their dictionary values with the
+/// columns of `batch`.
+pub fn filter_record_batch(
+    batch: &RecordBatch,
+    predicate: &BooleanArray,
+) -> Result<RecordBatch> {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|c| filter(c.as_ref(), predicate))
+        .collect::<Result<Vec<_>>>()?;
+    let row_count = (0..predicate.len())
+        .filter(|i| is_selected(predicate, *i))
+        .count();
+    RecordBatch::try_new_with_options(
+        batch.schema().clone(),
+        columns,
+        &RecordBatchOptions {
+            match_field_names: true,
+            row_count: Some(row_count),
+        },
+    )
+}
+
+/// Returns a copy of `batch` without the rows in which all columns are null.
+///
+/// If `subset` is given, only the columns at those indices are considered, so rows are
+/// dropped when all of these columns are null, whatever the values of the other
+/// columns. This is the equivalent of `DROPNA` with the `all` strategy.
+pub fn drop_null_rows(
+    batch: &RecordBatch,
+    subset: Option<&[usize]>,
+) -> Result<RecordBatch> {
+    let indices: Vec<usize> = match subset {
+        Some(subset) => subset.to_vec(),
+        None => (0..batch.num_columns()).collect(),
+    };
+    if let Some(i) = indices.iter().find(|i| **i >= batch.num_columns()) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Column index {} out of bounds for a batch of {} columns",
+            i,
+            batch.num_columns()
+        )));
+    }
+    let columns: Vec<&ArrayRef> = indices.iter().map(|i| batch.column(*i)).collect();
+    // a row is only dropped if it is null in every column, so a column without
+    // nulls keeps all rows
+    if columns.is_empty() || columns.iter().any(|c| c.null_count() == 0) {
+        return Ok(batch.clone());
+    }
+
+    let keep: Vec<bool> = (0..batch.num_rows())
+        .map(|i| columns.iter().any(|c| c.is_valid(i)))
+        .collect();
+    let keep = BooleanArray::from(keep);
+    let filtered = batch
+        .columns()
+        .iter()
+        .map(|c| filter(c.as_ref(), &keep))
+        .collect::<Result<Vec<_>>>()?;
+    RecordBatch::try_new(batch.schema().clone(), filtered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+    use std::sync::Arc;
+
+    use crate::datatypes::{DataType, Field, Int8Type, Schema};
+
+    fn batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Float64, true),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, None, Some(4)])),
+                Arc::new(
+                    BinaryArray::try_from(vec![None, Some("b"), None, None]).unwrap(),
+                ),
+                Arc::new(Float64Array::from(vec![None, None, Some(3.0), None])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_drop_null_rows_subset() {
+        let batch = drop_null_rows(&batch(), Some(&[0, 1])).unwrap();
+        assert_eq!(3, batch.num_rows());
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        let b = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!(1, a.value(0));
+        assert!(b.is_null(0));
+        assert!(a.is_null(1));
+        assert_eq!("b", b.get_string(1));
+        assert_eq!(4, a.value(2));
+        assert!(b.is_null(2));
+    }
+
+    #[test]
+    fn test_drop_null_rows_all_columns() {
+        let batch = drop_null_rows(&batch(), None).unwrap();
+        assert_eq!(4, batch.num_rows());
+
+        let batch = drop_null_rows(&batch, Some(&[2])).unwrap();
+        assert_eq!(1, batch.num_rows());
+        assert_eq!(0, batch.column(2).null_count());
+    }
+
+    #[test]
+    fn test_drop_null_rows_null_column() {
+        let schema = Schema::new(vec![
+            Field::new("n", DataType::Null, true),
+            Field::new("a", DataType::Int32, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(NullArray::new(3)),
+                Arc::new(Int32Array::from(vec![None, Some(2), None])),
+            ],
+        )
+        .unwrap();
+        let batch = drop_null_rows(&batch, Some(&[0, 1])).unwrap();
+        assert_eq!(1, batch.num_rows());
+        assert_eq!(1, batch.column(0).null_count());
+        assert_eq!(0, batch.column(1).null_count());
+    }
+
+    #[test]
+    fn test_drop_null_rows_invalid_subset() {
+        assert!(drop_null_rows(&batch(), Some(&[3])).is_err());
+    }
+
+    #[test]
+    fn test_filter_record_batch_keeps_dictionary() {
+        let dictionary: DictionaryArray<Int8Type> =
+            vec![Some("a"), None, Some("b"), Some("a")]
+                .into_iter()
+                .collect();
+        let data_type =
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8));
+        let schema = Schema::new(vec![Field::new("a", data_type.clone(), true)]);
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(dictionary)]).unwrap();
+
+        let predicate = BooleanArray::from(vec![false, true, true, false]);
+        let filtered = filter_record_batch(&batch, &predicate).unwrap();
+        assert_eq!(2, filtered.num_rows());
+        assert_eq!(&data_type, filtered.column(0).data_type());
+        let a = filtered
+            .column(0)
+            .as_any()
+            .downcast_ref::<DictionaryArray<Int8Type>>()
+            .unwrap();
+        assert_eq!(None, a.key(0));
+        assert_eq!(Some(1), a.key(1));
+        assert!(Arc::ptr_eq(
+            &batch.column(0).data().child_data()[0],
+            &a.data_ref().child_data()[0]
+        ));
+    }
+
+    #[test]
+    fn test_filter_array() {
+        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
+        let b = BooleanArray::from(vec![true, false, false, true, false]);
+        let c = filter(&a, &b).unwrap();
+        let d = c.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(2, d.len());
+        assert_eq!(5, d.value(0));
+        assert_eq!(8, d.value(1));
+    }
+
+    #[test]
+    fn test_filter_binary_array() {
+        let a = BinaryArray::from(vec!["hello", " ", "world", "!"]);
+        let b = BooleanArray::from(vec![true, false, true, false]);
+        let c = filter(&a, &b).unwrap();
+        let d = c.as_ref().as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(2, d.len());
+        assert_eq!("hello", d.get_string(0));
+        assert_eq!("world", d.get_string(1));
+    }
+
+    #[test]
+    fn test_filter_array_with_null() {
+        let a = Int32Array::from(vec![Some(5), None]);
+        let b = BooleanArray::from(vec![false, true]);
+        let c = filter(&a, &b).unwrap();
+        let d = c.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(1, d.len());
+        assert_eq!(true, d.is_null(0));
+    }
+
+    #[test]
+    fn test_filter_all_true_shares_data() {
+        let a = Int32Array::from(vec![Some(5), None, Some(7)]);
+        let b = BooleanArray::from(vec![true, true, true]);
+        let c = filter(&a, &b).unwrap();
+        assert!(Arc::ptr_eq(&a.data(), &c.data()));
+
+        let b = BooleanArray::from(vec![Some(true), None, Some(true)]);
+        let c = filter(&a, &b).unwrap();
+        assert!(!Arc::ptr_eq(&a.data(), &c.data()));
+    }
+
+    #[test]
+    fn test_filter_null_predicate_is_false() {
+        let a = Int32Array::from(vec![Some(1), Some(2), None]);
+        let b = BooleanArray::from(vec![Some(true), None, Some(true)]);
+        let c = filter(&a, &b).unwrap();
+        let d = c.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(2, d.len());
+        assert_eq!(1, d.value(0));
+        assert!(d.is_null(1));
+    }
+
+    #[test]
+    fn test_filter_string_array() {
+        let a = StringArray::from(vec![Some("hello"), None, Some("world")]);
+        let b = BooleanArray::from(vec![false, true, true]);
+        let c = filter(&a, &b).unwrap();
+        assert_eq!(&DataType::Utf8, c.data_type());
+        let d = StringArray::from(c.data());
+        assert_eq!(2, d.len());
+        assert!(d.is_null(0));
+        assert_eq!("world", d.value(1));
+    }
+
+    #[test]
+    fn test_filter_length_mismatch() {
+        let a = Int32Array::from(vec![1, 2, 3]);
+        let b = BooleanArray::from(vec![true, false]);
+        assert!(filter(&a, &b).is_err());
+    }
+}
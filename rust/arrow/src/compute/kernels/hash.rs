@@ -0,0 +1,178 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines a hash kernel that hashes the rows of one or more arrays.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::array::*;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// Returns a hash of each row of `columns`, combining the values of all columns.
+///
+/// Rows that are equal have equal hashes: null values hash alike, as do `0.0` and
+/// `-0.0`, and all `NaN` values. The hashes are deterministic, but they are not
+/// guaranteed to be stable across releases of this crate.
+///
+/// Supports boolean, integer, floating point, date and string arrays.
+pub fn hash(columns: &[ArrayRef]) -> Result<UInt64Array> {
+    let len = columns.first().map(|c| c.len()).unwrap_or(0);
+    if columns.iter().any(|c| c.len() != len) {
+        return Err(ArrowError::InvalidArgumentError(
+            "Cannot hash columns of different length".to_string(),
+        ));
+    }
+    let mut hashers = vec![DefaultHasher::new(); len];
+    for column in columns {
+        hash_column(&mut hashers, column.as_ref())?;
+    }
+    Ok(UInt64Array::from(
+        hashers.iter().map(|h| h.finish()).collect::<Vec<u64>>(),
+    ))
+}
+
+fn hash_column(hashers: &mut [DefaultHasher], column: &Array) -> Result<()> {
+    match column.data_type() {
+        DataType::Boolean => {
+            hash_values(hashers, column, |a: &BooleanArray, i, h| a.value(i).hash(h))
+        }
+        DataType::Int8 => hash_primitive::<Int8Type>(hashers, column),
+        DataType::Int16 => hash_primitive::<Int16Type>(hashers, column),
+        DataType::Int32 => hash_primitive::<Int32Type>(hashers, column),
+        DataType::Int64 => hash_primitive::<Int64Type>(hashers, column),
+        DataType::UInt8 => hash_primitive::<UInt8Type>(hashers, column),
+        DataType::UInt16 => hash_primitive::<UInt16Type>(hashers, column),
+        DataType::UInt32 => hash_primitive::<UInt32Type>(hashers, column),
+        DataType::UInt64 => hash_primitive::<UInt64Type>(hashers, column),
+        DataType::Float32 => hash_values(hashers, column, |a: &Float32Array, i, h| {
+            float_bits(f64::from(a.value(i))).hash(h)
+        }),
+        DataType::Float64 => hash_values(hashers, column, |a: &Float64Array, i, h| {
+            float_bits(a.value(i)).hash(h)
+        }),
+        DataType::Date32(_) => hash_primitive::<Date32Type>(hashers, column),
+        DataType::Date64(_) => hash_primitive::<Date64Type>(hashers, column),
+        DataType::Utf8 => hash_values(
+            hashers,
+            &as_binary_array(column),
+            |a: &BinaryArray, i, h| a.value(i).hash(h),
+        ),
+        t => {
+            return Err(ArrowError::ComputeError(format!(
+                "Hash not supported for data type {:?}",
+                t
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Feeds whether each row of `column` is valid and, if it is, its value as written by
+/// `write` into the hasher of the row
+fn hash_values<A, F>(hashers: &mut [DefaultHasher], column: &Array, write: F)
+where
+    A: Array + 'static,
+    F: Fn(&A, usize, &mut DefaultHasher),
+{
+    let array = column.as_any().downcast_ref::<A>().unwrap();
+    for (i, hasher) in hashers.iter_mut().enumerate() {
+        let valid = array.is_valid(i);
+        valid.hash(hasher);
+        if valid {
+            write(array, i, hasher);
+        }
+    }
+}
+
+fn hash_primitive<T>(hashers: &mut [DefaultHasher], column: &Array)
+where
+    T: ArrowNumericType,
+    T::Native: Hash,
+{
+    hash_values(hashers, column, |a: &PrimitiveArray<T>, i, h| {
+        a.value(i).hash(h)
+    })
+}
+
+/// Returns the bits of a float, with the same bits for values that compare as equal
+fn float_bits(value: f64) -> u64 {
+    if value.is_nan() {
+        std::f64::NAN.to_bits()
+    } else if value == 0.0 {
+        0
+    } else {
+        value.to_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_hash_equal_rows() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(2),
+            Some(1),
+            None,
+            None,
+        ]));
+        let b: ArrayRef = Arc::new(BinaryArray::from(vec!["x", "y", "x", "z", "z"]));
+        let hashes = hash(&[a, b]).unwrap();
+        assert_eq!(5, hashes.len());
+        assert_eq!(hashes.value(0), hashes.value(2));
+        assert_ne!(hashes.value(0), hashes.value(1));
+        assert_eq!(hashes.value(3), hashes.value(4));
+    }
+
+    #[test]
+    fn test_hash_string_array() {
+        let values = vec![Some("x"), None, Some("y")];
+        let a: ArrayRef = Arc::new(StringArray::from(values.clone()));
+        let b: ArrayRef = Arc::new(BinaryArray::try_from(values).unwrap());
+        let (a, b) = (hash(&[a]).unwrap(), hash(&[b]).unwrap());
+        for i in 0..3 {
+            assert_eq!(a.value(i), b.value(i));
+        }
+    }
+
+    #[test]
+    fn test_hash_floats() {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![
+            0.0,
+            -0.0,
+            std::f64::NAN,
+            -std::f64::NAN,
+        ]));
+        let hashes = hash(&[a]).unwrap();
+        assert_eq!(hashes.value(0), hashes.value(1));
+        assert_eq!(hashes.value(2), hashes.value(3));
+    }
+
+    #[test]
+    fn test_hash_different_lengths() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2]));
+        let b: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        assert!(hash(&[a, b]).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines histogram kernels, which assign the values of an array to buckets.
+
+use std::cmp::Ordering;
+
+use crate::array::*;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// Returns the index of the bucket that each value of `array` falls into, given the
+/// `boundaries` between the buckets in ascending order.
+///
+/// `n` boundaries define `n + 1` buckets. Each boundary is the inclusive lower bound of
+/// the bucket after it and the exclusive upper bound of the bucket before it, so a value
+/// `v` falls into bucket `i` where `boundaries[i - 1] <= v < boundaries[i]`. Values below
+/// the first boundary fall into bucket `0` and values greater than or equal to the last
+/// boundary fall into bucket `n`. Values that don't compare with the boundaries, such as
+/// `NaN`, fall into bucket `0`.
+///
+/// Null values are mapped to null buckets. Returns an error if `boundaries` are not
+/// sorted in ascending order.
+pub fn bucketize<T>(
+    array: &PrimitiveArray<T>,
+    boundaries: &[T::Native],
+) -> Result<UInt32Array>
+where
+    T: ArrowNumericType,
+{
+    let unsorted = boundaries.windows(2).any(|w| {
+        w[0].partial_cmp(&w[1])
+            .map_or(true, |order| order == Ordering::Greater)
+    });
+    if unsorted {
+        return Err(ArrowError::ComputeError(
+            "Bucket boundaries must be sorted in ascending order".to_string(),
+        ));
+    }
+
+    let mut b = UInt32Builder::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else {
+            b.append_value(bucket_index(boundaries, array.value(i)) as u32)?;
+        }
+    }
+
+    Ok(b.finish())
+}
+
+/// Binary search for the number of `boundaries` that are less than or equal to `value`
+fn bucket_index<T: PartialOrd>(boundaries: &[T], value: T) -> usize {
+    let mut low = 0;
+    let mut high = boundaries.len();
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if boundaries[mid] <= value {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucketize() {
+        let a =
+            Int32Array::from(vec![Some(-5), Some(0), Some(5), None, Some(10), Some(42)]);
+        let b = bucketize(&a, &[0, 10, 20]).unwrap();
+        assert_eq!(6, b.len());
+        // below the first boundary
+        assert_eq!(0, b.value(0));
+        // exactly on a boundary falls into the bucket it starts
+        assert_eq!(1, b.value(1));
+        assert_eq!(1, b.value(2));
+        assert!(b.is_null(3));
+        assert_eq!(2, b.value(4));
+        // above the last boundary
+        assert_eq!(3, b.value(5));
+    }
+
+    #[test]
+    fn test_bucketize_float() {
+        let a = Float64Array::from(vec![0.5, 1.0, 1.5, 2.5]);
+        let b = bucketize(&a, &[1.0, 2.0]).unwrap();
+        assert_eq!(&[0, 1, 1, 2], b.value_slice(0, 4));
+    }
+
+    #[test]
+    fn test_bucketize_no_boundaries() {
+        let a = Int32Array::from(vec![1, 2]);
+        let b = bucketize(&a, &[]).unwrap();
+        assert_eq!(&[0, 0], b.value_slice(0, 2));
+    }
+
+    #[test]
+    fn test_bucketize_unsorted_boundaries() {
+        let a = Int32Array::from(vec![1, 2]);
+        assert!(bucketize(&a, &[10, 0]).is_err());
+    }
+}
@@ -0,0 +1,459 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines join kernels that combine the rows of two `RecordBatch`es on equal keys.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::compute::kernels::hash::hash;
+use crate::compute::kernels::partition::lexicographical_partition_ranges;
+use crate::compute::kernels::sort::{array_comparator, ValueComparator};
+use crate::compute::kernels::take::take;
+use crate::datatypes::{Field, Schema};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+
+/// The kind of join to perform
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinType {
+    /// Only rows with a match on the other side
+    Inner,
+    /// All rows of the left side, with nulls for the right columns of unmatched rows
+    Left,
+    /// All rows of the right side, with nulls for the left columns of unmatched rows
+    Right,
+    /// All rows of both sides, with nulls for the columns of the missing side
+    Full,
+}
+
+impl JoinType {
+    fn keeps_unmatched_left(self) -> bool {
+        self == JoinType::Left || self == JoinType::Full
+    }
+
+    fn keeps_unmatched_right(self) -> bool {
+        self == JoinType::Right || self == JoinType::Full
+    }
+}
+
+/// Joins `left` and `right` on the columns at `left_keys` and `right_keys`, which both
+/// inputs have to be sorted on in ascending order.
+///
+/// The output has the columns of `left` followed by the columns of `right`, in the
+/// order of the keys. Rows are matched if all their keys are equal; as in SQL, a row
+/// with a null key never matches, although outer joins keep it with nulls for the
+/// other side. Rows with equal keys on both sides produce every combination of them.
+pub fn sort_merge_join(
+    left: &RecordBatch,
+    right: &RecordBatch,
+    left_keys: &[usize],
+    right_keys: &[usize],
+    join_type: JoinType,
+) -> Result<RecordBatch> {
+    let left_columns = key_columns(left, left_keys)?;
+    let right_columns = key_columns(right, right_keys)?;
+    let comparators = key_comparators(&left_columns, &right_columns)?;
+    let left_ranges = lexicographical_partition_ranges(&left_columns)?;
+    let right_ranges = lexicographical_partition_ranges(&right_columns)?;
+
+    let mut indices = JoinIndices::default();
+    let (mut l, mut r) = (0, 0);
+    loop {
+        let ordering = match (left_ranges.get(l), right_ranges.get(r)) {
+            (Some(left_range), Some(right_range)) => {
+                if has_null_key(&left_columns, left_range.start) {
+                    Ordering::Less
+                } else if has_null_key(&right_columns, right_range.start) {
+                    Ordering::Greater
+                } else {
+                    compare_keys(&comparators, left_range.start, right_range.start)
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        match ordering {
+            Ordering::Less => {
+                if join_type.keeps_unmatched_left() {
+                    indices.push_left_unmatched(left_ranges[l].clone());
+                }
+                l += 1;
+            }
+            Ordering::Greater => {
+                if join_type.keeps_unmatched_right() {
+                    indices.push_right_unmatched(right_ranges[r].clone());
+                }
+                r += 1;
+            }
+            Ordering::Equal => {
+                for i in left_ranges[l].clone() {
+                    for j in right_ranges[r].clone() {
+                        indices.push(Some(i), Some(j));
+                    }
+                }
+                l += 1;
+                r += 1;
+            }
+        }
+    }
+
+    indices.build_batch(left, right, join_type)
+}
+
+/// Joins `build` and `probe` on the columns at `build_keys` and `probe_keys` by hashing.
+///
+/// The output has the columns of `build` followed by the columns of `probe`. As with
+/// `sort_merge_join`, rows are matched if all their keys are equal, null keys never
+/// match and rows with equal keys on both sides produce every combination of them, but
+/// the inputs do not have to be sorted. `JoinType::Left` keeps every row of `build`.
+///
+/// The hash table is built on whichever input has fewer rows, and the other input is
+/// probed against it; rows whose keys hash alike are compared to rule out collisions.
+/// The order of the output rows is unspecified. Only inner and left joins are
+/// supported so far.
+pub fn hash_join(
+    build: &RecordBatch,
+    probe: &RecordBatch,
+    build_keys: &[usize],
+    probe_keys: &[usize],
+    join_type: JoinType,
+) -> Result<RecordBatch> {
+    if join_type != JoinType::Inner && join_type != JoinType::Left {
+        return Err(ArrowError::ComputeError(format!(
+            "Hash join does not support {:?} joins",
+            join_type
+        )));
+    }
+    let left_columns = key_columns(build, build_keys)?;
+    let right_columns = key_columns(probe, probe_keys)?;
+    let comparators = key_comparators(&left_columns, &right_columns)?;
+    let keys_equal =
+        |i: usize, j: usize| compare_keys(&comparators, i, j) == Ordering::Equal;
+
+    let mut indices = JoinIndices::default();
+    if probe.num_rows() < build.num_rows() {
+        // hash the right side and look up the left rows in order
+        let table = hash_table(&right_columns)?;
+        let left_hashes = hash(&left_columns)?;
+        for i in 0..build.num_rows() {
+            let mut matched = false;
+            if !has_null_key(&left_columns, i) {
+                if let Some(rows) = table.get(&left_hashes.value(i)) {
+                    for &j in rows.iter().filter(|&&j| keys_equal(i, j)) {
+                        indices.push(Some(i), Some(j));
+                        matched = true;
+                    }
+                }
+            }
+            if !matched && join_type.keeps_unmatched_left() {
+                indices.push(Some(i), None);
+            }
+        }
+    } else {
+        // hash the left side and look up the right rows, then add unmatched left rows
+        let table = hash_table(&left_columns)?;
+        let right_hashes = hash(&right_columns)?;
+        let mut left_matched = vec![false; build.num_rows()];
+        for j in 0..probe.num_rows() {
+            if has_null_key(&right_columns, j) {
+                continue;
+            }
+            if let Some(rows) = table.get(&right_hashes.value(j)) {
+                for &i in rows.iter().filter(|&&i| keys_equal(i, j)) {
+                    indices.push(Some(i), Some(j));
+                    left_matched[i] = true;
+                }
+            }
+        }
+        if join_type.keeps_unmatched_left() {
+            for (i, _) in left_matched.iter().enumerate().filter(|(_, m)| !**m) {
+                indices.push(Some(i), None);
+            }
+        }
+    }
+
+    indices.build_batch(build, probe, join_type)
+}
+
+/// Maps the key hash of each row without null keys to the rows with that hash
+fn hash_table(columns: &[ArrayRef]) -> Result<HashMap<u64, Vec<usize>>> {
+    let hashes = hash(columns)?;
+    let mut table: HashMap<u64, Vec<usize>> = HashMap::new();
+    for i in (0..hashes.len()).filter(|&i| !has_null_key(columns, i)) {
+        table
+            .entry(hashes.value(i))
+            .or_insert_with(Vec::new)
+            .push(i);
+    }
+    Ok(table)
+}
+
+/// The matching row indices of both sides of a join, where `None` stands for a missing
+/// row on that side
+#[derive(Default)]
+struct JoinIndices {
+    left: Vec<Option<u32>>,
+    right: Vec<Option<u32>>,
+}
+
+impl JoinIndices {
+    fn push(&mut self, left: Option<usize>, right: Option<usize>) {
+        self.left.push(left.map(|i| i as u32));
+        self.right.push(right.map(|i| i as u32));
+    }
+
+    fn push_left_unmatched(&mut self, rows: Range<usize>) {
+        for i in rows {
+            self.push(Some(i), None);
+        }
+    }
+
+    fn push_right_unmatched(&mut self, rows: Range<usize>) {
+        for j in rows {
+            self.push(None, Some(j));
+        }
+    }
+
+    /// Takes the rows at the indices from both sides and combines them into a batch
+    fn build_batch(
+        self,
+        left: &RecordBatch,
+        right: &RecordBatch,
+        join_type: JoinType,
+    ) -> Result<RecordBatch> {
+        let mut fields = vec![];
+        let mut columns = vec![];
+        for (batch, indices, nullable) in vec![
+            (left, self.left, join_type.keeps_unmatched_right()),
+            (right, self.right, join_type.keeps_unmatched_left()),
+        ] {
+            let indices = UInt32Array::from(indices);
+            for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+                fields.push(Field::new(
+                    field.name(),
+                    field.data_type().clone(),
+                    field.is_nullable() || nullable,
+                ));
+                columns.push(take(column, &indices, None)?);
+            }
+        }
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+    }
+}
+
+/// Returns the key columns of `batch` at `keys`
+fn key_columns(batch: &RecordBatch, keys: &[usize]) -> Result<Vec<ArrayRef>> {
+    keys.iter()
+        .map(|&i| {
+            if i < batch.num_columns() {
+                Ok(batch.column(i).clone())
+            } else {
+                Err(ArrowError::InvalidArgumentError(format!(
+                    "Join key {} is out of bounds for a batch with {} columns",
+                    i,
+                    batch.num_columns()
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Returns comparators of each left key column against the matching right key column
+fn key_comparators<'a>(
+    left: &'a [ArrayRef],
+    right: &'a [ArrayRef],
+) -> Result<Vec<ValueComparator<'a>>> {
+    if left.is_empty() || left.len() != right.len() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "A join requires the same non-zero number of keys on both sides, got {} and {}",
+            left.len(),
+            right.len()
+        )));
+    }
+    left.iter()
+        .zip(right.iter())
+        .map(|(l, r)| array_comparator(l.as_ref(), r.as_ref()))
+        .collect()
+}
+
+fn has_null_key(columns: &[ArrayRef], row: usize) -> bool {
+    columns.iter().any(|c| c.is_null(row))
+}
+
+/// Compares the keys of a left row with the keys of a right row
+fn compare_keys(comparators: &[ValueComparator], left: usize, right: usize) -> Ordering {
+    comparators
+        .iter()
+        .map(|cmp| cmp(left, right))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::datatypes::DataType;
+
+    fn batch(keys: Vec<Option<i32>>, values: Vec<&str>) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("key", DataType::Int32, true),
+            Field::new("value", DataType::Utf8, false),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(keys)),
+                Arc::new(StringArray::from(values)),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn int32_values(array: &ArrayRef) -> Vec<Option<i32>> {
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        (0..array.len())
+            .map(|i| {
+                if array.is_valid(i) {
+                    Some(array.value(i))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_merge_join_inner_one_to_many() {
+        let left = batch(vec![Some(1), Some(2), Some(3)], vec!["a", "b", "c"]);
+        let right = batch(
+            vec![Some(2), Some(2), Some(3), Some(4)],
+            vec!["w", "x", "y", "z"],
+        );
+        let joined = sort_merge_join(&left, &right, &[0], &[0], JoinType::Inner).unwrap();
+
+        assert_eq!(4, joined.num_columns());
+        assert_eq!(3, joined.num_rows());
+        assert_eq!(
+            vec![Some(2), Some(2), Some(3)],
+            int32_values(joined.column(0))
+        );
+        assert_eq!(
+            vec![Some(2), Some(2), Some(3)],
+            int32_values(joined.column(2))
+        );
+        let right_values = as_binary_array(joined.column(3));
+        assert_eq!(b"w", right_values.value(0));
+        assert_eq!(b"x", right_values.value(1));
+        assert_eq!(b"y", right_values.value(2));
+    }
+
+    #[test]
+    fn test_sort_merge_join_outer_with_null_keys() {
+        let left = batch(vec![None, Some(1), Some(2)], vec!["a", "b", "c"]);
+        let right = batch(vec![None, Some(2), Some(3)], vec!["x", "y", "z"]);
+
+        let joined = sort_merge_join(&left, &right, &[0], &[0], JoinType::Left).unwrap();
+        assert_eq!(vec![None, Some(1), Some(2)], int32_values(joined.column(0)));
+        assert_eq!(vec![None, None, Some(2)], int32_values(joined.column(2)));
+        assert!(joined.schema().field(3).is_nullable());
+        assert!(!joined.schema().field(1).is_nullable());
+
+        let joined = sort_merge_join(&left, &right, &[0], &[0], JoinType::Right).unwrap();
+        assert_eq!(vec![None, Some(2), None], int32_values(joined.column(0)));
+        assert_eq!(vec![None, Some(2), Some(3)], int32_values(joined.column(2)));
+
+        let joined = sort_merge_join(&left, &right, &[0], &[0], JoinType::Full).unwrap();
+        assert_eq!(5, joined.num_rows());
+        assert_eq!(
+            vec![None, None, Some(1), Some(2), None],
+            int32_values(joined.column(0))
+        );
+        assert_eq!(
+            vec![None, None, None, Some(2), Some(3)],
+            int32_values(joined.column(2))
+        );
+        assert_eq!(2, joined.column(1).null_count());
+        assert_eq!(2, joined.column(3).null_count());
+    }
+
+    #[test]
+    fn test_hash_join_inner_duplicate_keys() {
+        let build = batch(vec![Some(2), Some(1), Some(2)], vec!["a", "b", "c"]);
+        let probe = batch(
+            vec![Some(2), None, Some(3), Some(2), Some(1)],
+            vec!["v", "w", "x", "y", "z"],
+        );
+        for (build, probe) in vec![(&build, &probe), (&probe, &build)] {
+            let joined = hash_join(build, probe, &[0], &[0], JoinType::Inner).unwrap();
+            // key 2 matches 2 x 2 rows and key 1 matches 1 x 1 rows
+            assert_eq!(5, joined.num_rows());
+            assert_eq!(4, joined.num_columns());
+            assert_eq!(
+                int32_values(joined.column(0)),
+                int32_values(joined.column(2))
+            );
+            let mut keys = int32_values(joined.column(0));
+            keys.sort();
+            assert_eq!(vec![Some(1), Some(2), Some(2), Some(2), Some(2)], keys);
+        }
+    }
+
+    #[test]
+    fn test_hash_join_left() {
+        let build = batch(
+            vec![Some(1), None, Some(2), Some(4)],
+            vec!["a", "b", "c", "d"],
+        );
+        let probe = batch(vec![Some(2), Some(2), None], vec!["x", "y", "z"]);
+        let joined = hash_join(&build, &probe, &[0], &[0], JoinType::Left).unwrap();
+
+        assert_eq!(5, joined.num_rows());
+        assert!(joined.schema().field(2).is_nullable());
+        let mut rows: Vec<(Option<i32>, Option<i32>)> = int32_values(joined.column(0))
+            .into_iter()
+            .zip(int32_values(joined.column(2)))
+            .collect();
+        rows.sort();
+        assert_eq!(
+            vec![
+                (None, None),
+                (Some(1), None),
+                (Some(2), Some(2)),
+                (Some(2), Some(2)),
+                (Some(4), None)
+            ],
+            rows
+        );
+
+        assert!(hash_join(&build, &probe, &[0], &[0], JoinType::Full).is_err());
+    }
+
+    #[test]
+    fn test_sort_merge_join_invalid_keys() {
+        let left = batch(vec![Some(1)], vec!["a"]);
+        let right = batch(vec![Some(1)], vec!["x"]);
+        assert!(sort_merge_join(&left, &right, &[2], &[0], JoinType::Inner).is_err());
+        assert!(sort_merge_join(&left, &right, &[0], &[1], JoinType::Inner).is_err());
+        assert!(sort_merge_join(&left, &right, &[], &[], JoinType::Inner).is_err());
+    }
+}
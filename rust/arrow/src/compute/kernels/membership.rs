@@ -0,0 +1,122 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that test the membership of values in a set of values.
+
+use std::cmp::Ordering;
+
+use crate::array::*;
+use crate::compute::kernels::sort::array_comparator;
+use crate::error::Result;
+
+/// Returns whether each value of `values` is in `sorted_set`, found by a binary search
+/// of `sorted_set`, whose values must be sorted ascending.
+///
+/// This is faster than building a hash set of a large set that is already sorted. Nulls
+/// follow SQL `IN`: a null value is null, and so is a value that isn't found in a set
+/// that contains nulls, as the null could be equal to it. The nulls of `sorted_set`
+/// must all be at its start or all at its end, as `sort_to_indices` places them.
+///
+/// Returns an error if `values` and `sorted_set` are of different data types, or if
+/// their values can not be compared.
+pub fn in_sorted(values: &Array, sorted_set: &Array) -> Result<BooleanArray> {
+    let compare = array_comparator(values, sorted_set)?;
+    let set_nulls = sorted_set.null_count();
+    let set_values = if set_nulls > 0 && sorted_set.is_null(0) {
+        set_nulls..sorted_set.len()
+    } else {
+        0..sorted_set.len() - set_nulls
+    };
+
+    let mut builder = BooleanBuilder::new(values.len());
+    for i in 0..values.len() {
+        if values.is_null(i) {
+            builder.append_null()?;
+            continue;
+        }
+        let (mut low, mut high) = (set_values.start, set_values.end);
+        let mut found = false;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match compare(i, mid) {
+                Ordering::Less => high = mid,
+                Ordering::Greater => low = mid + 1,
+                Ordering::Equal => {
+                    found = true;
+                    break;
+                }
+            }
+        }
+        if found {
+            builder.append_value(true)?;
+        } else if set_nulls > 0 {
+            builder.append_null()?;
+        } else {
+            builder.append_value(false)?;
+        }
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_sorted() {
+        let values = Int32Array::from(vec![1, 5, 9]);
+        let set = Int32Array::from(vec![1, 2, 3, 9]);
+        let result = in_sorted(&values, &set).unwrap();
+        assert_eq!(3, result.len());
+        assert_eq!(0, result.null_count());
+        assert!(result.value(0));
+        assert!(!result.value(1));
+        assert!(result.value(2));
+    }
+
+    #[test]
+    fn test_in_sorted_nulls() {
+        let values = Int32Array::from(vec![Some(2), None, Some(4)]);
+        let set = Int32Array::from(vec![None, Some(1), Some(2)]);
+        let result = in_sorted(&values, &set).unwrap();
+        assert!(result.value(0));
+        assert!(result.is_null(1));
+        assert!(result.is_null(2));
+
+        let set = Int32Array::from(vec![Some(1), Some(2), None]);
+        let result = in_sorted(&values, &set).unwrap();
+        assert!(result.value(0));
+        assert!(result.is_null(2));
+    }
+
+    #[test]
+    fn test_in_sorted_strings() {
+        let values = BinaryArray::from(vec!["apple", "kiwi", "pear"]);
+        let set = BinaryArray::from(vec!["apple", "banana", "pear"]);
+        let result = in_sorted(&values, &set).unwrap();
+        assert!(result.value(0));
+        assert!(!result.value(1));
+        assert!(result.value(2));
+    }
+
+    #[test]
+    fn test_in_sorted_different_types() {
+        let values = Int32Array::from(vec![1]);
+        let set = Int64Array::from(vec![1]);
+        assert!(in_sorted(&values, &set).is_err());
+    }
+}
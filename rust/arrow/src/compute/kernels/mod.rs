@@ -17,9 +17,32 @@
 
 //! Computation kernels on Arrow Arrays
 
+pub mod aggregate;
 pub mod arithmetic;
+pub mod batch;
+pub mod bitwise;
 pub mod boolean;
 pub mod cast;
+pub mod compact;
 pub mod comparison;
+pub mod concat;
+pub mod explode;
+pub mod fill;
+pub mod filter;
+pub mod hash;
+pub mod histogram;
+pub mod join;
+pub mod membership;
+pub mod normalize;
+pub mod nullif;
+pub mod partition;
+pub mod profile;
+pub mod repeat;
+pub mod replace;
+pub mod row;
+pub mod sample;
+pub mod set_ops;
+pub mod sort;
 pub mod take;
 pub mod temporal;
+pub mod window;
@@ -0,0 +1,124 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that normalize the representation of values.
+
+use num::Float;
+
+use crate::array::*;
+use crate::buffer::{Buffer, MutableBuffer};
+use crate::datatypes::{ArrowNumericType, ToByteSlice};
+use crate::util::bit_util;
+
+/// Returns a copy of the floating point `array` in which all `NaN` values have the
+/// same bit pattern, and `-0.0` is replaced by `0.0`.
+///
+/// IEEE 754 defines `NaN != NaN` and `-0.0 == 0.0`, while the bit patterns of these
+/// values differ, so hashing or grouping the raw bits of floats puts equal values in
+/// different groups. Canonical floats can be hashed and compared by their bits. Nulls
+/// are preserved.
+///
+/// Only the values of `array` are read, so a slice of a large array is canonicalized
+/// into buffers the size of the slice. The result has an offset of zero, and shares
+/// the null buffer of `array` unless `array` is sliced.
+pub fn canonicalize_floats<T>(array: &PrimitiveArray<T>) -> PrimitiveArray<T>
+where
+    T: ArrowNumericType,
+    T::Native: Float,
+{
+    let len = array.len();
+    let values: Vec<T::Native> = array
+        .value_slice(0, len)
+        .iter()
+        .map(|v| canonicalize_float(*v))
+        .collect();
+    let mut builder = ArrayData::builder(array.data_type().clone())
+        .len(len)
+        .null_count(array.null_count())
+        .add_buffer(Buffer::from(values.to_byte_slice()));
+    if let Some(bitmap) = array.data_ref().null_bitmap() {
+        if array.offset() == 0 {
+            builder = builder.null_bit_buffer(bitmap.bits.clone());
+        } else {
+            let num_bytes = bit_util::ceil(len, 8);
+            let mut null_buf =
+                MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+            {
+                let null_slice = null_buf.data_mut();
+                for i in (0..len).filter(|i| array.is_valid(*i)) {
+                    bit_util::set_bit(null_slice, i);
+                }
+            }
+            builder = builder.null_bit_buffer(null_buf.freeze());
+        }
+    }
+    PrimitiveArray::<T>::from(builder.build())
+}
+
+fn canonicalize_float<F: Float>(value: F) -> F {
+    if value.is_nan() {
+        F::nan()
+    } else if value == F::zero() {
+        F::zero()
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_floats() {
+        let nan = std::f64::NAN;
+        let other_nan = f64::from_bits(0xFFF8_0000_0000_0001);
+        assert!(other_nan.is_nan());
+        assert_ne!(nan.to_bits(), other_nan.to_bits());
+
+        let a = Float64Array::from(vec![
+            Some(nan),
+            Some(other_nan),
+            Some(-0.0),
+            None,
+            Some(1.5),
+        ]);
+        let b = canonicalize_floats(&a);
+        assert_eq!(5, b.len());
+        assert_eq!(1, b.null_count());
+        assert_eq!(b.value(0).to_bits(), b.value(1).to_bits());
+        assert!(b.value(0).is_nan());
+        assert_eq!(0.0f64.to_bits(), b.value(2).to_bits());
+        assert!(b.is_null(3));
+        assert_eq!(1.5, b.value(4));
+    }
+
+    #[test]
+    fn test_canonicalize_floats_sliced() {
+        let a = Float32Array::from(vec![Some(-1.0), None, Some(-0.0), Some(2.0)]);
+        let a = a.slice(1, 3);
+        let a = a.as_any().downcast_ref::<Float32Array>().unwrap();
+        let b = canonicalize_floats(a);
+        assert_eq!(3, b.len());
+        assert_eq!(0, b.offset());
+        assert_eq!(3 * 4, b.data_ref().buffers()[0].len());
+        assert_eq!(1, b.null_count());
+        assert!(b.is_null(0));
+        assert_eq!(0.0f32.to_bits(), b.value(1).to_bits());
+        assert_eq!(2.0, b.value(2));
+    }
+}
@@ -0,0 +1,156 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that turn values of an array into nulls.
+
+use crate::array::*;
+use crate::buffer::MutableBuffer;
+use crate::datatypes::ArrowNumericType;
+use crate::util::bit_util;
+
+/// Returns a copy of `array` in which every slot equal to `sentinel` is null.
+///
+/// This converts nulls that legacy data encodes as magic numbers (e.g. `-999`) into
+/// Arrow nulls. Slots that are already null stay null. Only a new validity buffer is
+/// allocated; the values buffer is shared with `array`.
+pub fn nullify_sentinel<T>(
+    array: &PrimitiveArray<T>,
+    sentinel: T::Native,
+) -> PrimitiveArray<T>
+where
+    T: ArrowNumericType,
+{
+    let offset = array.offset();
+    let num_bytes = bit_util::ceil(offset + array.len(), 8);
+    let mut null_buf = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+    let mut null_count = 0;
+    {
+        let null_slice = null_buf.data_mut();
+        for i in 0..array.len() {
+            if array.is_valid(i) && array.value(i) != sentinel {
+                bit_util::set_bit(null_slice, offset + i);
+            } else {
+                null_count += 1;
+            }
+        }
+    }
+    let data = ArrayData::builder(array.data_type().clone())
+        .len(array.len())
+        .offset(offset)
+        .null_count(null_count)
+        .null_bit_buffer(null_buf.freeze())
+        .add_buffer(array.values())
+        .build();
+    PrimitiveArray::<T>::from(data)
+}
+
+/// Returns a copy of `array` in which every row whose children are all null is null.
+///
+/// Some sources encode a null struct implicitly as a struct of null fields. This
+/// resolves the ambiguity between the two by collapsing such rows into struct-level
+/// nulls, using `StructArray::validity_from_children`. Rows that are already null stay
+/// null, and the children are shared with `array`.
+pub fn collapse_all_null_structs(array: &StructArray) -> StructArray {
+    let from_children = array.validity_from_children();
+    let from_children = from_children.data();
+    let num_bytes = bit_util::ceil(array.len(), 8);
+    let mut null_buf = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+    let mut null_count = 0;
+    {
+        let null_slice = null_buf.data_mut();
+        for i in 0..array.len() {
+            if array.is_valid(i) && bit_util::get_bit(from_children, i) {
+                bit_util::set_bit(null_slice, i);
+            } else {
+                null_count += 1;
+            }
+        }
+    }
+    let data = ArrayData::builder(array.data_type().clone())
+        .len(array.len())
+        .null_count(null_count)
+        .null_bit_buffer(null_buf.freeze())
+        .child_data(array.columns().iter().map(|c| c.data()).collect())
+        .build();
+    StructArray::from(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::datatypes::{DataType, Field};
+
+    #[test]
+    fn test_nullify_sentinel() {
+        let a = Int32Array::from(vec![1, -1, 3, -1]);
+        let b = nullify_sentinel(&a, -1);
+        assert_eq!(4, b.len());
+        assert_eq!(2, b.null_count());
+        assert_eq!(1, b.value(0));
+        assert!(b.is_null(1));
+        assert_eq!(3, b.value(2));
+        assert!(b.is_null(3));
+    }
+
+    #[test]
+    fn test_nullify_sentinel_keeps_nulls_and_offset() {
+        let a = Float64Array::from(vec![Some(-999.0), None, Some(2.5), Some(-999.0)]);
+        let a = a.slice(1, 3);
+        let a = a.as_any().downcast_ref::<Float64Array>().unwrap();
+        let b = nullify_sentinel(a, -999.0);
+        assert_eq!(3, b.len());
+        assert_eq!(2, b.null_count());
+        assert!(b.is_null(0));
+        assert_eq!(2.5, b.value(1));
+        assert!(b.is_null(2));
+    }
+
+    #[test]
+    fn test_collapse_all_null_structs() {
+        let structs = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(1), None, None, None])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Float64, true),
+                Arc::new(Float64Array::from(vec![None, Some(2.0), None, None]))
+                    as ArrayRef,
+            ),
+        ]);
+        assert_eq!(0, structs.null_count());
+
+        let collapsed = collapse_all_null_structs(&structs);
+        assert_eq!(4, collapsed.len());
+        assert_eq!(2, collapsed.null_count());
+        assert!(collapsed.is_valid(0));
+        assert!(collapsed.is_valid(1));
+        assert!(collapsed.is_null(2));
+        assert!(collapsed.is_null(3));
+        assert_eq!(structs.column(0).data(), collapsed.column(0).data());
+
+        let sliced = collapsed.slice(1, 2);
+        let sliced = sliced.as_any().downcast_ref::<StructArray>().unwrap();
+        let collapsed = collapse_all_null_structs(sliced);
+        assert_eq!(1, collapsed.null_count());
+        assert!(collapsed.is_valid(0));
+        assert!(collapsed.is_null(1));
+    }
+}
@@ -0,0 +1,108 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines partition kernels that split sorted arrays into ranges of equal values.
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use crate::array::*;
+use crate::compute::kernels::sort::value_comparator;
+use crate::error::{ArrowError, Result};
+
+/// Splits the rows of `columns` into consecutive ranges of rows that are equal in all
+/// columns.
+///
+/// The columns are expected to be sorted lexicographically, so that equal rows are
+/// next to each other; otherwise equal rows that are apart end up in different ranges.
+/// Null values are equal to each other. For example, the ranges of the column
+/// `[1, 1, 2, null, null]` are `[0..2, 2..3, 3..5]`.
+pub fn lexicographical_partition_ranges(
+    columns: &[ArrayRef],
+) -> Result<Vec<Range<usize>>> {
+    if columns.is_empty() {
+        return Err(ArrowError::InvalidArgumentError(
+            "Partitioning requires at least one column".to_string(),
+        ));
+    }
+    let len = columns[0].len();
+    if columns.iter().any(|c| c.len() != len) {
+        return Err(ArrowError::InvalidArgumentError(
+            "Cannot partition columns of different length".to_string(),
+        ));
+    }
+    let comparators = columns
+        .iter()
+        .map(|c| value_comparator(c.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    let rows_equal = |i: usize, j: usize| {
+        columns.iter().zip(comparators.iter()).all(|(column, cmp)| {
+            match (column.is_valid(i), column.is_valid(j)) {
+                (true, true) => cmp(i, j) == Ordering::Equal,
+                (a, b) => a == b,
+            }
+        })
+    };
+
+    let mut ranges = vec![];
+    let mut start = 0;
+    for i in 1..len {
+        if !rows_equal(i - 1, i) {
+            ranges.push(start..i);
+            start = i;
+        }
+    }
+    if len > 0 {
+        ranges.push(start..len);
+    }
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    #[test]
+    fn test_partition_ranges_single_column() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(1),
+            Some(2),
+            None,
+            None,
+        ]));
+        let ranges = lexicographical_partition_ranges(&[a]).unwrap();
+        assert_eq!(vec![0..2, 2..3, 3..5], ranges);
+    }
+
+    #[test]
+    fn test_partition_ranges_multiple_columns() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 1, 1, 2]));
+        let b: ArrayRef = Arc::new(BinaryArray::from(vec!["x", "x", "y", "y"]));
+        let ranges = lexicographical_partition_ranges(&[a, b]).unwrap();
+        assert_eq!(vec![0..2, 2..3, 3..4], ranges);
+    }
+
+    #[test]
+    fn test_partition_ranges_empty() {
+        let a: ArrayRef = Arc::new(Int32Array::from(Vec::<i32>::new()));
+        assert!(lexicographical_partition_ranges(&[a]).unwrap().is_empty());
+        assert!(lexicographical_partition_ranges(&[]).is_err());
+    }
+}
@@ -0,0 +1,273 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that estimate the size of the rows of a `RecordBatch`.
+//!
+//! Sizes approximate the bytes a row occupies once serialized: the fixed width of
+//! each value, the bytes of variable length values and their offsets, and a validity
+//! bit for each value of a column with a null bitmap. Nested columns contribute the
+//! bytes of the child values of each row, and dictionary encoded columns the bytes of
+//! the key and of the value it refers to.
+
+use crate::array::*;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::util::bit_util;
+
+/// Returns the approximate serialized size in bytes of each row of `batch`.
+///
+/// This is useful to partition or sample batches by size rather than by row count.
+/// The size of a row is the sum of the sizes of its values across all columns, with
+/// the bits of booleans and validity rounded up to whole bytes.
+///
+/// Returns a `ComputeError` if a column has a type whose size cannot be estimated, or
+/// if a dictionary encoded column has a key that is negative or out of bounds of its
+/// dictionary.
+pub fn row_byte_sizes(batch: &RecordBatch) -> Result<UInt64Array> {
+    let sizes = (0..batch.num_rows())
+        .map(|row| {
+            let bits = batch
+                .columns()
+                .iter()
+                .map(|column| slots_bit_size(column.data_ref(), row, row + 1))
+                .sum::<Result<usize>>()?;
+            Ok(bit_util::ceil(bits, 8) as u64)
+        })
+        .collect::<Result<Vec<u64>>>()?;
+    Ok(UInt64Array::from(sizes))
+}
+
+/// Returns the size in bits of the slots `start..end` of `data`, which are relative to
+/// the offset of `data`
+fn slots_bit_size(data: &ArrayData, start: usize, end: usize) -> Result<usize> {
+    let len = end - start;
+    let start = start + data.offset();
+    let end = end + data.offset();
+    let validity_bits = if data.null_bitmap().is_some() { len } else { 0 };
+    let value_bits = match data.data_type() {
+        DataType::Utf8 | DataType::Binary => {
+            let offsets = data.buffers()[0].typed_data::<i32>();
+            len * 32 + (offsets[end] - offsets[start]) as usize * 8
+        }
+        DataType::FixedSizeBinary(width) => len * *width as usize * 8,
+        DataType::List(_) => {
+            let offsets = data.buffers()[0].typed_data::<i32>();
+            let child = &data.child_data()[0];
+            len * 32
+                + slots_bit_size(child, offsets[start] as usize, offsets[end] as usize)?
+        }
+        DataType::LargeList(_) => {
+            let offsets = data.buffers()[0].typed_data::<i64>();
+            let child = &data.child_data()[0];
+            len * 64
+                + slots_bit_size(child, offsets[start] as usize, offsets[end] as usize)?
+        }
+        DataType::FixedSizeList((_, size)) => {
+            let size = *size as usize;
+            slots_bit_size(&data.child_data()[0], start * size, end * size)?
+        }
+        DataType::Struct(_) => data
+            .child_data()
+            .iter()
+            .map(|child| slots_bit_size(child, start, end))
+            .sum::<Result<usize>>()?,
+        DataType::Dictionary(key_type, _) => {
+            let values = &data.child_data()[0];
+            let value_bits = (start..end)
+                .filter(|i| data.is_valid(*i))
+                .map(|i| {
+                    let key = dictionary_key(data, key_type, i)?;
+                    slots_bit_size(values, key, key + 1)
+                })
+                .sum::<Result<usize>>()?;
+            len * bit_width(key_type)? + value_bits
+        }
+        data_type => len * bit_width(data_type)?,
+    };
+    Ok(validity_bits + value_bits)
+}
+
+/// Returns the key at the physical index `i` of the dictionary encoded `data`,
+/// checking that it is a valid index into the dictionary
+fn dictionary_key(data: &ArrayData, key_type: &DataType, i: usize) -> Result<usize> {
+    let keys = &data.buffers()[0];
+    let key = match key_type {
+        DataType::Int8 => keys.typed_data::<i8>()[i] as i128,
+        DataType::Int16 => keys.typed_data::<i16>()[i] as i128,
+        DataType::Int32 => keys.typed_data::<i32>()[i] as i128,
+        DataType::Int64 => keys.typed_data::<i64>()[i] as i128,
+        DataType::UInt8 => keys.typed_data::<u8>()[i] as i128,
+        DataType::UInt16 => keys.typed_data::<u16>()[i] as i128,
+        DataType::UInt32 => keys.typed_data::<u32>()[i] as i128,
+        DataType::UInt64 => keys.typed_data::<u64>()[i] as i128,
+        t => {
+            return Err(ArrowError::ComputeError(format!(
+                "Dictionary key type {:?} is not an integer type",
+                t
+            )))
+        }
+    };
+    let dictionary_len = data.child_data()[0].len();
+    if key < 0 || key >= dictionary_len as i128 {
+        return Err(ArrowError::ComputeError(format!(
+            "Dictionary key {} is out of bounds of the dictionary of length {}",
+            key, dictionary_len
+        )));
+    }
+    Ok(key as usize)
+}
+
+/// Returns the width in bits of a value of the fixed width `data_type`
+fn bit_width(data_type: &DataType) -> Result<usize> {
+    let width = match data_type {
+        DataType::Null => 0,
+        DataType::Boolean => 1,
+        DataType::Int8 | DataType::UInt8 => 8,
+        DataType::Int16 | DataType::UInt16 | DataType::Float16 => 16,
+        DataType::Int32
+        | DataType::UInt32
+        | DataType::Float32
+        | DataType::Date32(_)
+        | DataType::Time32(_) => 32,
+        DataType::Int64
+        | DataType::UInt64
+        | DataType::Float64
+        | DataType::Timestamp(_)
+        | DataType::Date64(_)
+        | DataType::Time64(_)
+        | DataType::Interval(_) => 64,
+        DataType::Decimal(_, _) => 128,
+        t => {
+            return Err(ArrowError::ComputeError(format!(
+                "Estimating the size of {:?} values is not supported",
+                t
+            )))
+        }
+    };
+    Ok(width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::buffer::Buffer;
+
+    #[test]
+    fn test_row_byte_sizes() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let a = Int32Array::from(vec![1, 2, 3]);
+        let b = StringArray::from(vec![
+            Some("a"),
+            Some("a much longer string than the first one"),
+            None,
+        ]);
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)])
+                .unwrap();
+
+        let sizes = row_byte_sizes(&batch).unwrap();
+        assert_eq!(3, sizes.len());
+        assert!(sizes.value(1) > sizes.value(0));
+        // 4 bytes of the integer, 4 bytes of the offset and a validity bit
+        assert_eq!(9, sizes.value(2));
+        assert_eq!(10, sizes.value(0));
+    }
+
+    #[test]
+    fn test_row_byte_sizes_nested() {
+        let values = Int64Array::from(vec![1, 2, 3, 4, 5, 6]);
+        let data = ArrayData::builder(DataType::List(Box::new(DataType::Int64)))
+            .len(3)
+            .add_buffer(Buffer::from(&[0, 1, 1, 6].to_byte_slice()))
+            .add_child_data(values.data())
+            .build();
+        let list = ListArray::from(data);
+        let schema = Schema::new(vec![Field::new(
+            "l",
+            DataType::List(Box::new(DataType::Int64)),
+            false,
+        )]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(list)]).unwrap();
+
+        let sizes = row_byte_sizes(&batch).unwrap();
+        assert_eq!(vec![12, 4, 44], sizes.values_slice().to_vec());
+    }
+
+    #[test]
+    fn test_row_byte_sizes_interval() {
+        let values = Buffer::from(&[1i64, 2].to_byte_slice());
+        let year_month = ArrayData::builder(DataType::Interval(IntervalUnit::YearMonth))
+            .len(2)
+            .add_buffer(values.clone())
+            .build();
+        let day_time = ArrayData::builder(DataType::Interval(IntervalUnit::DayTime))
+            .len(2)
+            .add_buffer(values)
+            .build();
+        let schema = Schema::new(vec![
+            Field::new("y", DataType::Interval(IntervalUnit::YearMonth), false),
+            Field::new("d", DataType::Interval(IntervalUnit::DayTime), false),
+        ]);
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(PrimitiveArray::<IntervalYearMonthType>::from(year_month)),
+            Arc::new(PrimitiveArray::<IntervalDayTimeType>::from(day_time)),
+        ];
+        let batch = RecordBatch::try_new(Arc::new(schema), columns).unwrap();
+
+        // both interval types hold 8 byte values
+        let sizes = row_byte_sizes(&batch).unwrap();
+        assert_eq!(16, sizes.value(0));
+        assert_eq!(16, sizes.value(1));
+    }
+
+    #[test]
+    fn test_row_byte_sizes_dictionary() {
+        let values = StringArray::from(vec!["a", "bcd"]);
+        let data_type =
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8));
+        let dictionary = |keys: &[i8]| {
+            let data = ArrayData::builder(data_type.clone())
+                .len(keys.len())
+                .add_buffer(Buffer::from(keys.to_byte_slice()))
+                .add_child_data(values.data())
+                .build();
+            let schema = Schema::new(vec![Field::new("d", data_type.clone(), false)]);
+            let column: ArrayRef = Arc::new(DictionaryArray::<Int8Type>::from(data));
+            RecordBatch::try_new(Arc::new(schema), vec![column]).unwrap()
+        };
+
+        // the key byte, and the offset and bytes of the value
+        let sizes = row_byte_sizes(&dictionary(&[0, 1])).unwrap();
+        assert_eq!(vec![6, 8], sizes.values_slice().to_vec());
+
+        for keys in &[[0i8, -1], [0, 2]] {
+            match row_byte_sizes(&dictionary(keys)) {
+                Err(ArrowError::ComputeError(message)) => {
+                    assert!(message.contains("out of bounds"), "{}", message)
+                }
+                _ => panic!("expected an invalid dictionary key error"),
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines a kernel that repeats a scalar value into an array.
+
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::scalar::ScalarValue;
+
+/// Builds a primitive array of `$LEN` copies of `$VALUE`, which are nulls if it is
+/// `ScalarValue::Null`
+macro_rules! repeat_primitive {
+    ($VALUE:expr, $LEN:expr, $ARRAY_TYPE:ident, $VARIANT:ident) => {{
+        let mut builder = $ARRAY_TYPE::builder($LEN);
+        match $VALUE {
+            ScalarValue::$VARIANT(v) => {
+                for _ in 0..$LEN {
+                    builder.append_value(*v)?;
+                }
+            }
+            ScalarValue::Null => {
+                for _ in 0..$LEN {
+                    builder.append_null()?;
+                }
+            }
+            other => return Err(mismatched_value(other, &DataType::$VARIANT)),
+        }
+        Ok(Arc::new(builder.finish()) as ArrayRef)
+    }};
+}
+
+/// Returns an array of type `data_type` that holds `len` copies of `value`.
+///
+/// A `ScalarValue::Null` value results in an array of nulls. Otherwise the value has to
+/// be of `data_type`. Primitive and string (`Utf8`) data types are supported.
+pub fn repeat(value: &ScalarValue, data_type: &DataType, len: usize) -> Result<ArrayRef> {
+    match data_type {
+        DataType::Boolean => repeat_primitive!(value, len, BooleanArray, Boolean),
+        DataType::Int8 => repeat_primitive!(value, len, Int8Array, Int8),
+        DataType::Int16 => repeat_primitive!(value, len, Int16Array, Int16),
+        DataType::Int32 => repeat_primitive!(value, len, Int32Array, Int32),
+        DataType::Int64 => repeat_primitive!(value, len, Int64Array, Int64),
+        DataType::UInt8 => repeat_primitive!(value, len, UInt8Array, UInt8),
+        DataType::UInt16 => repeat_primitive!(value, len, UInt16Array, UInt16),
+        DataType::UInt32 => repeat_primitive!(value, len, UInt32Array, UInt32),
+        DataType::UInt64 => repeat_primitive!(value, len, UInt64Array, UInt64),
+        DataType::Float32 => repeat_primitive!(value, len, Float32Array, Float32),
+        DataType::Float64 => repeat_primitive!(value, len, Float64Array, Float64),
+        DataType::Utf8 => {
+            let mut builder = BinaryBuilder::new(len);
+            match value {
+                ScalarValue::Utf8(v) => {
+                    for _ in 0..len {
+                        builder.append_string(v)?;
+                    }
+                }
+                ScalarValue::Null => {
+                    for _ in 0..len {
+                        builder.append_null()?;
+                    }
+                }
+                other => return Err(mismatched_value(other, data_type)),
+            }
+            Ok(Arc::new(builder.finish()) as ArrayRef)
+        }
+        t => Err(ArrowError::ComputeError(format!(
+            "repeat not supported for data type {:?}",
+            t
+        ))),
+    }
+}
+
+fn mismatched_value(value: &ScalarValue, data_type: &DataType) -> ArrowError {
+    ArrowError::ComputeError(format!(
+        "Cannot repeat {:?} as an array of type {:?}",
+        value, data_type
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeat() {
+        let a = repeat(&ScalarValue::Int32(7), &DataType::Int32, 3).unwrap();
+        let a = a.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(&[7, 7, 7], a.value_slice(0, 3));
+        assert_eq!(0, a.null_count());
+
+        let a = repeat(&ScalarValue::Utf8("x".to_string()), &DataType::Utf8, 2).unwrap();
+        let a = as_binary_array(&a);
+        assert_eq!(2, a.len());
+        assert_eq!("x", a.get_string(1));
+
+        let a = repeat(&ScalarValue::Null, &DataType::Float64, 4).unwrap();
+        assert_eq!(&DataType::Float64, a.data_type());
+        assert_eq!(4, a.null_count());
+
+        let a = repeat(&ScalarValue::Boolean(true), &DataType::Boolean, 0).unwrap();
+        assert_eq!(0, a.len());
+    }
+
+    #[test]
+    fn test_repeat_mismatched_type() {
+        assert!(repeat(&ScalarValue::Int64(1), &DataType::Int32, 2).is_err());
+        assert!(repeat(&ScalarValue::Int32(1), &DataType::Utf8, 2).is_err());
+        assert!(repeat(&ScalarValue::Null, &DataType::Date32(DateUnit::Day), 2).is_err());
+    }
+}
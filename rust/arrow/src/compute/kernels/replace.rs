@@ -0,0 +1,219 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that replace the values of an array by other values.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::scalar::ScalarValue;
+
+/// Maps the values of a primitive array through `mapping`
+macro_rules! map_primitive_values {
+    ($ARRAY:expr, $MAPPING:expr, $DEFAULT:expr, $ARRAY_TYPE:ident, $VARIANT:ident) => {{
+        let array = $ARRAY.as_any().downcast_ref::<$ARRAY_TYPE>().unwrap();
+        let mut builder = $ARRAY_TYPE::builder(array.len());
+        for i in 0..array.len() {
+            if array.is_null(i) {
+                builder.append_null()?;
+                continue;
+            }
+            let value = array.value(i);
+            match $MAPPING
+                .get(&ScalarValue::$VARIANT(value))
+                .or($DEFAULT.as_ref())
+            {
+                Some(ScalarValue::$VARIANT(v)) => builder.append_value(*v)?,
+                Some(ScalarValue::Null) => builder.append_null()?,
+                Some(other) => {
+                    return Err(mismatched_replacement(other, array.data_type()))
+                }
+                None => builder.append_value(value)?,
+            }
+        }
+        Ok(Arc::new(builder.finish()) as ArrayRef)
+    }};
+}
+
+/// Returns a copy of `array` in which every value found in `mapping` is replaced by the
+/// value it maps to.
+///
+/// This is the equivalent of a SQL `CASE` (or `DECODE`) over discrete values. Values
+/// that are not in `mapping` are replaced by `default` if given, and kept otherwise.
+/// Nulls are preserved, and a value mapped to `ScalarValue::Null` becomes null.
+///
+/// The keys of `mapping` must be of the array's type for them to match, and the
+/// replacement values must be of the array's type too, since the result has the same
+/// data type as `array`. Primitive and string (`Utf8`) arrays are supported.
+pub fn map_values(
+    array: &Array,
+    mapping: &HashMap<ScalarValue, ScalarValue>,
+    default: Option<ScalarValue>,
+) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::Boolean => {
+            map_primitive_values!(array, mapping, default, BooleanArray, Boolean)
+        }
+        DataType::Int8 => map_primitive_values!(array, mapping, default, Int8Array, Int8),
+        DataType::Int16 => {
+            map_primitive_values!(array, mapping, default, Int16Array, Int16)
+        }
+        DataType::Int32 => {
+            map_primitive_values!(array, mapping, default, Int32Array, Int32)
+        }
+        DataType::Int64 => {
+            map_primitive_values!(array, mapping, default, Int64Array, Int64)
+        }
+        DataType::UInt8 => {
+            map_primitive_values!(array, mapping, default, UInt8Array, UInt8)
+        }
+        DataType::UInt16 => {
+            map_primitive_values!(array, mapping, default, UInt16Array, UInt16)
+        }
+        DataType::UInt32 => {
+            map_primitive_values!(array, mapping, default, UInt32Array, UInt32)
+        }
+        DataType::UInt64 => {
+            map_primitive_values!(array, mapping, default, UInt64Array, UInt64)
+        }
+        DataType::Float32 => {
+            map_primitive_values!(array, mapping, default, Float32Array, Float32)
+        }
+        DataType::Float64 => {
+            map_primitive_values!(array, mapping, default, Float64Array, Float64)
+        }
+        DataType::Utf8 => map_string_values(array, mapping, default),
+        t => Err(ArrowError::ComputeError(format!(
+            "map_values not supported for data type {:?}",
+            t
+        ))),
+    }
+}
+
+/// Maps the values of a string array through `mapping`
+fn map_string_values(
+    array: &Array,
+    mapping: &HashMap<ScalarValue, ScalarValue>,
+    default: Option<ScalarValue>,
+) -> Result<ArrayRef> {
+    let array = as_binary_array(array);
+    let mut builder = BinaryBuilder::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            builder.append_null()?;
+            continue;
+        }
+        let value = String::from_utf8(array.value(i).to_vec())?;
+        match mapping
+            .get(&ScalarValue::Utf8(value.clone()))
+            .or(default.as_ref())
+        {
+            Some(ScalarValue::Utf8(v)) => builder.append_string(v)?,
+            Some(ScalarValue::Null) => builder.append_null()?,
+            Some(other) => return Err(mismatched_replacement(other, array.data_type())),
+            None => builder.append_string(&value)?,
+        }
+    }
+    Ok(Arc::new(builder.finish()) as ArrayRef)
+}
+
+fn mismatched_replacement(value: &ScalarValue, data_type: &DataType) -> ArrowError {
+    ArrowError::ComputeError(format!(
+        "Cannot replace a value of type {:?} by {:?}",
+        data_type, value
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_values_int32() {
+        let a = Int32Array::from(vec![Some(1), Some(2), Some(3), None, Some(1)]);
+        let mut mapping = HashMap::new();
+        mapping.insert(ScalarValue::Int32(1), ScalarValue::Int32(10));
+        mapping.insert(ScalarValue::Int32(2), ScalarValue::Int32(20));
+        let b = map_values(&a, &mapping, None).unwrap();
+        let b = b.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(5, b.len());
+        assert_eq!(1, b.null_count());
+        assert_eq!(10, b.value(0));
+        assert_eq!(20, b.value(1));
+        assert_eq!(3, b.value(2));
+        assert!(b.is_null(3));
+        assert_eq!(10, b.value(4));
+    }
+
+    #[test]
+    fn test_map_values_with_default() {
+        let a = Int64Array::from(vec![Some(1), Some(2), None, Some(3)]);
+        let mut mapping = HashMap::new();
+        mapping.insert(ScalarValue::Int64(1), ScalarValue::Int64(10));
+        mapping.insert(ScalarValue::Int64(2), ScalarValue::Null);
+        let b = map_values(&a, &mapping, Some(ScalarValue::Int64(0))).unwrap();
+        let b = b.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(2, b.null_count());
+        assert_eq!(10, b.value(0));
+        assert!(b.is_null(1));
+        assert!(b.is_null(2));
+        assert_eq!(0, b.value(3));
+    }
+
+    #[test]
+    fn test_map_values_string() {
+        let mut builder = BinaryBuilder::new(4);
+        builder.append_string("M").unwrap();
+        builder.append_null().unwrap();
+        builder.append_string("F").unwrap();
+        builder.append_string("X").unwrap();
+        let a = builder.finish();
+        let mut mapping = HashMap::new();
+        mapping.insert(
+            ScalarValue::Utf8("M".to_string()),
+            ScalarValue::Utf8("male".to_string()),
+        );
+        mapping.insert(
+            ScalarValue::Utf8("F".to_string()),
+            ScalarValue::Utf8("female".to_string()),
+        );
+        let b = map_values(&a, &mapping, None).unwrap();
+        let b = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("male", b.get_string(0));
+        assert!(b.is_null(1));
+        assert_eq!("female", b.get_string(2));
+        assert_eq!("X", b.get_string(3));
+
+        let a = StringArray::from(vec![Some("F"), None, Some("X")]);
+        let b = map_values(&a, &mapping, None).unwrap();
+        let b = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("female", b.get_string(0));
+        assert!(b.is_null(1));
+        assert_eq!("X", b.get_string(2));
+    }
+
+    #[test]
+    fn test_map_values_mismatched_type() {
+        let a = Int32Array::from(vec![1, 2]);
+        let mut mapping = HashMap::new();
+        mapping.insert(ScalarValue::Int32(1), ScalarValue::Float64(1.5));
+        assert!(map_values(&a, &mapping, None).is_err());
+    }
+}
@@ -0,0 +1,637 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines `RowConverter`, which encodes columns into rows of bytes that compare like
+//! the values they encode.
+//!
+//! Comparing two encoded rows with a plain byte comparison gives the same result as
+//! comparing the values of all the encoded columns one after the other, which makes
+//! rows a fast key for multi-column sorts and for join key equality.
+//!
+//! Each value is encoded as a byte for its validity followed by the bytes of the
+//! value, if any. Integers are written big-endian, with the sign bit of signed
+//! integers flipped, and floating point numbers are written as integers ordered like
+//! the numbers, with `NaN` after all other values. Strings are written with every zero
+//! byte escaped as `0x00 0xFF` and followed by the terminator `0x00 0x00`, so that no
+//! string encoding is a prefix of another. The bytes of a value sorted in descending
+//! order are inverted.
+
+use std::mem;
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::compute::kernels::sort::SortOptions;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+
+/// The validity byte of a non-null value
+const VALID: u8 = 0x01;
+
+/// A column to encode in rows, with the order its values are encoded in
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortField {
+    pub data_type: DataType,
+    pub options: SortOptions,
+}
+
+impl SortField {
+    /// Creates a field of `data_type` sorted with the default `SortOptions`
+    pub fn new(data_type: DataType) -> Self {
+        Self::new_with_options(data_type, SortOptions::default())
+    }
+
+    pub fn new_with_options(data_type: DataType, options: SortOptions) -> Self {
+        SortField { data_type, options }
+    }
+
+    fn null_byte(&self) -> u8 {
+        if self.options.nulls_first {
+            0x00
+        } else {
+            0xFF
+        }
+    }
+}
+
+/// Rows of bytes encoded by a `RowConverter`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rows {
+    data: Vec<u8>,
+    /// The start of each row in `data`, followed by the end of the last row
+    offsets: Vec<usize>,
+}
+
+impl Rows {
+    /// Returns the number of rows
+    pub fn num_rows(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns the bytes of row `i`
+    pub fn row(&self, i: usize) -> &[u8] {
+        &self.data[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Returns an iterator over the bytes of all rows
+    pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
+        (0..self.num_rows()).map(move |i| self.row(i))
+    }
+}
+
+/// Converts columns into comparable rows of bytes and back
+#[derive(Debug, Clone)]
+pub struct RowConverter {
+    fields: Vec<SortField>,
+}
+
+impl RowConverter {
+    /// Creates a converter for columns of the types of `fields`, in that order.
+    ///
+    /// Boolean, integer, floating point and `Utf8` columns are supported.
+    pub fn try_new(fields: Vec<SortField>) -> Result<Self> {
+        for field in &fields {
+            if !is_supported(&field.data_type) {
+                return Err(ArrowError::ComputeError(format!(
+                    "Row encoding not supported for data type {:?}",
+                    field.data_type
+                )));
+            }
+        }
+        Ok(RowConverter { fields })
+    }
+
+    /// Returns the fields of the rows
+    pub fn fields(&self) -> &[SortField] {
+        &self.fields
+    }
+
+    /// Encodes `columns`, which must match the fields of the converter, into rows
+    pub fn convert_columns(&self, columns: &[ArrayRef]) -> Result<Rows> {
+        if columns.len() != self.fields.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Expected {} columns to encode, got {}",
+                self.fields.len(),
+                columns.len()
+            )));
+        }
+        let num_rows = columns.first().map(|c| c.len()).unwrap_or(0);
+        for (column, field) in columns.iter().zip(self.fields.iter()) {
+            if column.data_type() != &field.data_type {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Expected a column of type {:?} to encode, got {:?}",
+                    field.data_type,
+                    column.data_type()
+                )));
+            }
+            if column.len() != num_rows {
+                return Err(ArrowError::InvalidArgumentError(
+                    "Cannot encode columns of different length".to_string(),
+                ));
+            }
+        }
+
+        let mut rows = vec![vec![]; num_rows];
+        for (column, field) in columns.iter().zip(self.fields.iter()) {
+            encode_column(column.as_ref(), field, &mut rows);
+        }
+        let mut data = Vec::with_capacity(rows.iter().map(|r| r.len()).sum());
+        let mut offsets = Vec::with_capacity(num_rows + 1);
+        offsets.push(0);
+        for row in rows {
+            data.extend_from_slice(&row);
+            offsets.push(data.len());
+        }
+        Ok(Rows { data, offsets })
+    }
+
+    /// Encodes the columns of `batch` at `indices`, in that order, into rows
+    pub fn convert_batch(&self, batch: &RecordBatch, indices: &[usize]) -> Result<Rows> {
+        let columns = indices
+            .iter()
+            .map(|i| batch.column(*i).clone())
+            .collect::<Vec<_>>();
+        self.convert_columns(&columns)
+    }
+
+    /// Decodes `rows` encoded by this converter back into columns
+    pub fn convert_rows(&self, rows: &Rows) -> Result<Vec<ArrayRef>> {
+        let mut positions = vec![0; rows.num_rows()];
+        let columns = self
+            .fields
+            .iter()
+            .map(|field| decode_column(field, rows, &mut positions))
+            .collect::<Result<Vec<_>>>()?;
+        if (0..rows.num_rows()).any(|i| positions[i] != rows.row(i).len()) {
+            return Err(invalid_row());
+        }
+        Ok(columns)
+    }
+}
+
+fn is_supported(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Boolean
+        | DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Utf8 => true,
+        _ => false,
+    }
+}
+
+fn invalid_row() -> ArrowError {
+    ArrowError::ComputeError("Invalid row encoding".to_string())
+}
+
+/// A native type encoded with a fixed number of bytes
+trait FixedWidth: Copy {
+    const WIDTH: usize;
+
+    fn encode(self, out: &mut Vec<u8>);
+
+    /// Decodes a value from the first `WIDTH` bytes of `bytes`
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+macro_rules! fixed_width_unsigned {
+    ($T:ty) => {
+        impl FixedWidth for $T {
+            const WIDTH: usize = mem::size_of::<$T>();
+
+            fn encode(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_be_bytes());
+            }
+
+            fn decode(bytes: &[u8]) -> Self {
+                let mut b = [0u8; mem::size_of::<$T>()];
+                b.copy_from_slice(&bytes[..Self::WIDTH]);
+                <$T>::from_be_bytes(b)
+            }
+        }
+    };
+}
+
+macro_rules! fixed_width_signed {
+    ($T:ty, $U:ty) => {
+        impl FixedWidth for $T {
+            const WIDTH: usize = mem::size_of::<$T>();
+
+            fn encode(self, out: &mut Vec<u8>) {
+                let sign = (1 as $U) << (Self::WIDTH * 8 - 1);
+                ((self as $U) ^ sign).encode(out);
+            }
+
+            fn decode(bytes: &[u8]) -> Self {
+                let sign = (1 as $U) << (Self::WIDTH * 8 - 1);
+                (<$U>::decode(bytes) ^ sign) as $T
+            }
+        }
+    };
+}
+
+macro_rules! fixed_width_float {
+    ($T:ty, $U:ty) => {
+        impl FixedWidth for $T {
+            const WIDTH: usize = mem::size_of::<$T>();
+
+            fn encode(self, out: &mut Vec<u8>) {
+                let sign = (1 as $U) << (Self::WIDTH * 8 - 1);
+                let bits = self.to_bits();
+                // negative NaN values order like positive ones, after all numbers
+                let bits = if self.is_nan() { bits & !sign } else { bits };
+                // negative numbers order inversely to their bits
+                let bits = if bits & sign != 0 { !bits } else { bits | sign };
+                bits.encode(out);
+            }
+
+            fn decode(bytes: &[u8]) -> Self {
+                let sign = (1 as $U) << (Self::WIDTH * 8 - 1);
+                let bits = <$U>::decode(bytes);
+                let bits = if bits & sign != 0 { bits ^ sign } else { !bits };
+                <$T>::from_bits(bits)
+            }
+        }
+    };
+}
+
+fixed_width_unsigned!(u8);
+fixed_width_unsigned!(u16);
+fixed_width_unsigned!(u32);
+fixed_width_unsigned!(u64);
+fixed_width_signed!(i8, u8);
+fixed_width_signed!(i16, u16);
+fixed_width_signed!(i32, u32);
+fixed_width_signed!(i64, u64);
+fixed_width_float!(f32, u32);
+fixed_width_float!(f64, u64);
+
+/// Appends the encoding of a value to `row`, where `encode` writes the bytes of the
+/// value if it is valid
+fn encode_value<F>(row: &mut Vec<u8>, field: &SortField, is_valid: bool, encode: F)
+where
+    F: FnOnce(&mut Vec<u8>),
+{
+    if !is_valid {
+        row.push(field.null_byte());
+        return;
+    }
+    row.push(VALID);
+    let start = row.len();
+    encode(row);
+    if field.options.descending {
+        row[start..].iter_mut().for_each(|b| *b = !*b);
+    }
+}
+
+fn encode_primitive<T>(column: &Array, field: &SortField, rows: &mut [Vec<u8>])
+where
+    T: ArrowNumericType,
+    T::Native: FixedWidth,
+{
+    let array = column.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    for (i, row) in rows.iter_mut().enumerate() {
+        encode_value(row, field, array.is_valid(i), |out| {
+            array.value(i).encode(out)
+        });
+    }
+}
+
+fn encode_column(column: &Array, field: &SortField, rows: &mut [Vec<u8>]) {
+    match column.data_type() {
+        DataType::Boolean => {
+            let array = column.as_any().downcast_ref::<BooleanArray>().unwrap();
+            for (i, row) in rows.iter_mut().enumerate() {
+                encode_value(row, field, array.is_valid(i), |out| {
+                    out.push(array.value(i) as u8)
+                });
+            }
+        }
+        DataType::Int8 => encode_primitive::<Int8Type>(column, field, rows),
+        DataType::Int16 => encode_primitive::<Int16Type>(column, field, rows),
+        DataType::Int32 => encode_primitive::<Int32Type>(column, field, rows),
+        DataType::Int64 => encode_primitive::<Int64Type>(column, field, rows),
+        DataType::UInt8 => encode_primitive::<UInt8Type>(column, field, rows),
+        DataType::UInt16 => encode_primitive::<UInt16Type>(column, field, rows),
+        DataType::UInt32 => encode_primitive::<UInt32Type>(column, field, rows),
+        DataType::UInt64 => encode_primitive::<UInt64Type>(column, field, rows),
+        DataType::Float32 => encode_primitive::<Float32Type>(column, field, rows),
+        DataType::Float64 => encode_primitive::<Float64Type>(column, field, rows),
+        DataType::Utf8 => {
+            let array = as_binary_array(column);
+            for (i, row) in rows.iter_mut().enumerate() {
+                encode_value(row, field, array.is_valid(i), |out| {
+                    for b in array.value(i) {
+                        out.push(*b);
+                        if *b == 0x00 {
+                            out.push(0xFF);
+                        }
+                    }
+                    out.extend_from_slice(&[0x00, 0x00]);
+                });
+            }
+        }
+        t => unreachable!("Row encoding not supported for data type {:?}", t),
+    }
+}
+
+/// Reads the validity byte of the next value of `row` at `position`, returning
+/// whether the value is valid
+fn decode_validity(row: &[u8], position: &mut usize, field: &SortField) -> Result<bool> {
+    let byte = *row.get(*position).ok_or_else(invalid_row)?;
+    *position += 1;
+    if byte == VALID {
+        Ok(true)
+    } else if byte == field.null_byte() {
+        Ok(false)
+    } else {
+        Err(invalid_row())
+    }
+}
+
+/// Reads the next byte of a value of `row` at `position`
+fn decode_byte(row: &[u8], position: &mut usize, field: &SortField) -> Result<u8> {
+    let byte = *row.get(*position).ok_or_else(invalid_row)?;
+    *position += 1;
+    Ok(if field.options.descending {
+        !byte
+    } else {
+        byte
+    })
+}
+
+fn decode_primitive<T>(
+    field: &SortField,
+    rows: &Rows,
+    positions: &mut [usize],
+) -> Result<ArrayRef>
+where
+    T: ArrowNumericType,
+    T::Native: FixedWidth,
+{
+    let width = <T::Native as FixedWidth>::WIDTH;
+    let mut builder = PrimitiveBuilder::<T>::new(rows.num_rows());
+    let mut bytes = [0u8; 8];
+    for (i, position) in positions.iter_mut().enumerate() {
+        let row = rows.row(i);
+        if decode_validity(row, position, field)? {
+            for b in bytes.iter_mut().take(width) {
+                *b = decode_byte(row, position, field)?;
+            }
+            builder.append_value(<T::Native as FixedWidth>::decode(&bytes))?;
+        } else {
+            builder.append_null()?;
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+fn decode_column(
+    field: &SortField,
+    rows: &Rows,
+    positions: &mut [usize],
+) -> Result<ArrayRef> {
+    match field.data_type {
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::new(rows.num_rows());
+            for (i, position) in positions.iter_mut().enumerate() {
+                let row = rows.row(i);
+                if decode_validity(row, position, field)? {
+                    builder.append_value(decode_byte(row, position, field)? != 0)?;
+                } else {
+                    builder.append_null()?;
+                }
+            }
+            Ok(Arc::new(builder.finish()))
+        }
+        DataType::Int8 => decode_primitive::<Int8Type>(field, rows, positions),
+        DataType::Int16 => decode_primitive::<Int16Type>(field, rows, positions),
+        DataType::Int32 => decode_primitive::<Int32Type>(field, rows, positions),
+        DataType::Int64 => decode_primitive::<Int64Type>(field, rows, positions),
+        DataType::UInt8 => decode_primitive::<UInt8Type>(field, rows, positions),
+        DataType::UInt16 => decode_primitive::<UInt16Type>(field, rows, positions),
+        DataType::UInt32 => decode_primitive::<UInt32Type>(field, rows, positions),
+        DataType::UInt64 => decode_primitive::<UInt64Type>(field, rows, positions),
+        DataType::Float32 => decode_primitive::<Float32Type>(field, rows, positions),
+        DataType::Float64 => decode_primitive::<Float64Type>(field, rows, positions),
+        DataType::Utf8 => {
+            let mut builder = BinaryBuilder::new(rows.num_rows());
+            let mut value = vec![];
+            for (i, position) in positions.iter_mut().enumerate() {
+                let row = rows.row(i);
+                if !decode_validity(row, position, field)? {
+                    builder.append_null()?;
+                    continue;
+                }
+                value.clear();
+                loop {
+                    let b = decode_byte(row, position, field)?;
+                    if b != 0x00 {
+                        value.push(b);
+                    } else if decode_byte(row, position, field)? == 0xFF {
+                        value.push(0x00);
+                    } else {
+                        break;
+                    }
+                }
+                builder.append_value(&value)?;
+            }
+            Ok(Arc::new(builder.finish()))
+        }
+        ref t => Err(ArrowError::ComputeError(format!(
+            "Row encoding not supported for data type {:?}",
+            t
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cmp::Ordering;
+    use std::convert::TryFrom;
+
+    fn compare_options<T: Ord>(
+        a: &Option<T>,
+        b: &Option<T>,
+        options: SortOptions,
+    ) -> Ordering {
+        match (a, b) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) if options.nulls_first => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) if options.nulls_first => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) if options.descending => b.cmp(a),
+            (Some(a), Some(b)) => a.cmp(b),
+        }
+    }
+
+    #[test]
+    fn test_row_order_matches_logical_order() {
+        let ints = vec![Some(2), None, Some(-1), Some(2), Some(2), None, Some(-1)];
+        let strings = vec![
+            Some("b"),
+            Some("a"),
+            None,
+            Some("ba"),
+            Some("b\0"),
+            None,
+            Some(""),
+        ];
+        let int_options = SortOptions {
+            descending: false,
+            nulls_first: true,
+        };
+        let string_options = SortOptions {
+            descending: true,
+            nulls_first: false,
+        };
+        let converter = RowConverter::try_new(vec![
+            SortField::new_with_options(DataType::Int32, int_options),
+            SortField::new_with_options(DataType::Utf8, string_options),
+        ])
+        .unwrap();
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(ints.clone())),
+            Arc::new(BinaryArray::try_from(strings.clone()).unwrap()),
+        ];
+        let rows = converter.convert_columns(&columns).unwrap();
+        assert_eq!(7, rows.num_rows());
+
+        for i in 0..ints.len() {
+            for j in 0..ints.len() {
+                let expected = compare_options(&ints[i], &ints[j], int_options)
+                    .then(compare_options(&strings[i], &strings[j], string_options));
+                assert_eq!(
+                    expected,
+                    rows.row(i).cmp(rows.row(j)),
+                    "comparing rows {} and {}",
+                    i,
+                    j
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_row_order() {
+        let values = vec![
+            std::f64::NAN,
+            1.5,
+            -0.5,
+            std::f64::INFINITY,
+            -std::f64::NAN,
+            -3.0,
+            std::f64::NEG_INFINITY,
+            0.0,
+        ];
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Float64)]).unwrap();
+        let rows = converter
+            .convert_columns(&[Arc::new(Float64Array::from(values.clone())) as ArrayRef])
+            .unwrap();
+        let mut indices: Vec<usize> = (0..values.len()).collect();
+        indices.sort_by(|a, b| rows.row(*a).cmp(rows.row(*b)));
+        let sorted: Vec<f64> = indices.iter().map(|i| values[*i]).collect();
+        assert_eq!(
+            vec![
+                std::f64::NEG_INFINITY,
+                -3.0,
+                -0.5,
+                0.0,
+                1.5,
+                std::f64::INFINITY
+            ],
+            sorted[..6].to_vec()
+        );
+        assert!(sorted[6].is_nan() && sorted[7].is_nan());
+    }
+
+    #[test]
+    fn test_row_roundtrip() {
+        let descending = SortOptions {
+            descending: true,
+            nulls_first: false,
+        };
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)])),
+            Arc::new(Int8Array::from(vec![Some(-128), Some(127), None])),
+            Arc::new(UInt64Array::from(vec![0, std::u64::MAX, 7])),
+            Arc::new(Float32Array::from(vec![Some(-1.25), None, Some(0.0)])),
+            Arc::new(Int64Array::from(vec![Some(std::i64::MIN), None, Some(-1)])),
+            Arc::new(BinaryArray::try_from(vec![Some("a\0b"), Some(""), None]).unwrap()),
+        ];
+        let converter = RowConverter::try_new(vec![
+            SortField::new(DataType::Boolean),
+            SortField::new_with_options(DataType::Int8, descending),
+            SortField::new(DataType::UInt64),
+            SortField::new_with_options(DataType::Float32, descending),
+            SortField::new(DataType::Int64),
+            SortField::new_with_options(DataType::Utf8, descending),
+        ])
+        .unwrap();
+        let rows = converter.convert_columns(&columns).unwrap();
+        let decoded = converter.convert_rows(&rows).unwrap();
+        assert_eq!(columns.len(), decoded.len());
+        for (expected, actual) in columns.iter().zip(decoded.iter()) {
+            assert!(expected.equals(actual.as_ref()));
+        }
+    }
+
+    #[test]
+    fn test_row_string_array() {
+        let strings = vec![Some("b\0"), None, Some("")];
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Utf8)]).unwrap();
+        let columns: Vec<ArrayRef> = vec![Arc::new(StringArray::from(strings.clone()))];
+        let rows = converter.convert_columns(&columns).unwrap();
+        let binary_rows = converter
+            .convert_columns(&[
+                Arc::new(BinaryArray::try_from(strings).unwrap()) as ArrayRef
+            ])
+            .unwrap();
+        for i in 0..rows.num_rows() {
+            assert_eq!(Ordering::Equal, rows.row(i).cmp(binary_rows.row(i)));
+        }
+        let decoded = converter.convert_rows(&rows).unwrap();
+        assert!(columns[0].equals(decoded[0].as_ref()));
+    }
+
+    #[test]
+    fn test_row_converter_errors() {
+        assert!(
+            RowConverter::try_new(vec![SortField::new(DataType::List(Box::new(
+                DataType::Int32
+            )))])
+            .is_err()
+        );
+
+        let converter =
+            RowConverter::try_new(vec![SortField::new(DataType::Int32)]).unwrap();
+        let columns: Vec<ArrayRef> = vec![Arc::new(Int64Array::from(vec![1]))];
+        assert!(converter.convert_columns(&columns).is_err());
+    }
+}
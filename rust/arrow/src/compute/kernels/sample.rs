@@ -0,0 +1,135 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels that randomly select or reorder the rows of a `RecordBatch`.
+//!
+//! Both kernels are seeded, so that the same seed always selects the same rows.
+
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::SeedableRng;
+
+use crate::array::*;
+use crate::compute::kernels::take::take;
+use crate::error::Result;
+use crate::record_batch::{RecordBatch, RecordBatchOptions};
+
+/// Returns `n` rows of `batch` selected at random without replacement, using `seed`
+/// to make the selection reproducible.
+///
+/// The selected rows keep their relative order from `batch`. If `n` is not less than
+/// the number of rows, all rows are returned.
+pub fn sample(batch: &RecordBatch, n: usize, seed: u64) -> Result<RecordBatch> {
+    let num_rows = batch.num_rows();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut indices: Vec<u32> = index::sample(&mut rng, num_rows, n.min(num_rows))
+        .into_iter()
+        .map(|i| i as u32)
+        .collect();
+    indices.sort();
+    take_rows(batch, indices)
+}
+
+/// Returns all rows of `batch` in a random order, using `seed` to make the order
+/// reproducible.
+pub fn shuffle(batch: &RecordBatch, seed: u64) -> Result<RecordBatch> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut indices: Vec<u32> = (0..batch.num_rows() as u32).collect();
+    indices.shuffle(&mut rng);
+    take_rows(batch, indices)
+}
+
+fn take_rows(batch: &RecordBatch, indices: Vec<u32>) -> Result<RecordBatch> {
+    let row_count = indices.len();
+    let indices = UInt32Array::from(indices);
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| take(column, &indices, None))
+        .collect::<Result<Vec<ArrayRef>>>()?;
+    RecordBatch::try_new_with_options(
+        batch.schema().clone(),
+        columns,
+        &RecordBatchOptions {
+            match_field_names: true,
+            row_count: Some(row_count),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::datatypes::{DataType, Field, Schema};
+
+    fn create_batch(num_rows: i32) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, false),
+        ]);
+        let a = Int32Array::from((0..num_rows).collect::<Vec<i32>>());
+        let b: Vec<String> = (0..num_rows).map(|i| format!("row {}", i)).collect();
+        let b = StringArray::from(b.iter().map(|s| s.as_str()).collect::<Vec<&str>>());
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)]).unwrap()
+    }
+
+    fn column_a(batch: &RecordBatch) -> Vec<i32> {
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        a.value_slice(0, a.len()).to_vec()
+    }
+
+    #[test]
+    fn test_sample_is_reproducible() {
+        let batch = create_batch(100);
+        let first = sample(&batch, 10, 42).unwrap();
+        let second = sample(&batch, 10, 42).unwrap();
+        assert_eq!(10, first.num_rows());
+        assert_eq!(column_a(&first), column_a(&second));
+
+        let rows = column_a(&first);
+        assert!(rows.windows(2).all(|w| w[0] < w[1]));
+        let b = as_binary_array(first.column(1));
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(format!("row {}", row), b.get_string(i));
+        }
+    }
+
+    #[test]
+    fn test_sample_more_than_num_rows() {
+        let batch = create_batch(5);
+        let sampled = sample(&batch, 10, 7).unwrap();
+        assert_eq!(vec![0, 1, 2, 3, 4], column_a(&sampled));
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let batch = create_batch(20);
+        let shuffled = shuffle(&batch, 3).unwrap();
+        assert_eq!(column_a(&shuffled), column_a(&shuffle(&batch, 3).unwrap()));
+
+        let mut rows = column_a(&shuffled);
+        rows.sort();
+        assert_eq!(column_a(&batch), rows);
+    }
+}
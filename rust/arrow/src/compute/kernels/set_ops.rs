@@ -0,0 +1,251 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines set operations on the rows of `RecordBatch`es with the same schema.
+//!
+//! Rows are compared by value in all columns, so unlike SQL predicates, where
+//! `NULL = NULL` is not true, a null value is equal to another null value. This matches
+//! how SQL set operations such as `INTERSECT` treat nulls.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::array::*;
+use crate::compute::kernels::concat::concat_batches;
+use crate::compute::kernels::hash::hash;
+use crate::compute::kernels::sort::{value_comparator, ValueComparator};
+use crate::compute::kernels::take::take;
+use crate::error::Result;
+use crate::record_batch::RecordBatch;
+
+/// Appends the rows of `batches`, which must all have the same schema, into a single
+/// batch.
+///
+/// This is SQL `UNION ALL` or, if `distinct` is true, SQL `UNION`, which only keeps the
+/// first of each group of equal rows. The rows keep their order.
+pub fn union(batches: &[RecordBatch], distinct: bool) -> Result<RecordBatch> {
+    let batch = concat_batches(batches)?;
+    if !distinct {
+        return Ok(batch);
+    }
+    let rows = RowSet::try_new(batch.columns())?;
+    let indices: Vec<u32> = (0..batch.num_rows())
+        .filter(|&i| rows.first_equal(i) == i)
+        .map(|i| i as u32)
+        .collect();
+    take_rows(&batch, indices)
+}
+
+/// Returns the distinct rows of `left` that are also in `right`, in the order of
+/// `left`. Both batches must have the same schema.
+///
+/// This is SQL `INTERSECT`.
+pub fn intersect(left: &RecordBatch, right: &RecordBatch) -> Result<RecordBatch> {
+    filter_distinct_left(left, right, true)
+}
+
+/// Returns the distinct rows of `left` that are not in `right`, in the order of
+/// `left`. Both batches must have the same schema.
+///
+/// This is SQL `EXCEPT`.
+pub fn except(left: &RecordBatch, right: &RecordBatch) -> Result<RecordBatch> {
+    filter_distinct_left(left, right, false)
+}
+
+/// Returns the distinct rows of `left` whose presence in `right` is `in_right`
+fn filter_distinct_left(
+    left: &RecordBatch,
+    right: &RecordBatch,
+    in_right: bool,
+) -> Result<RecordBatch> {
+    let batch = concat_batches(&[left.clone(), right.clone()])?;
+    let rows = RowSet::try_new(batch.columns())?;
+    let right_rows = left.num_rows()..batch.num_rows();
+    let indices: Vec<u32> = (0..left.num_rows())
+        .filter(|&i| {
+            rows.first_equal(i) == i && rows.has_equal_in(i, &right_rows) == in_right
+        })
+        .map(|i| i as u32)
+        .collect();
+    take_rows(&batch, indices)
+}
+
+/// The rows of a set of columns, grouped by the hash of their values
+struct RowSet<'a> {
+    columns: &'a [ArrayRef],
+    comparators: Vec<ValueComparator<'a>>,
+    hashes: UInt64Array,
+    table: HashMap<u64, Vec<usize>>,
+}
+
+impl<'a> RowSet<'a> {
+    fn try_new(columns: &'a [ArrayRef]) -> Result<Self> {
+        let comparators = columns
+            .iter()
+            .map(|c| value_comparator(c.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        let hashes = hash(columns)?;
+        let mut table: HashMap<u64, Vec<usize>> = HashMap::new();
+        for i in 0..hashes.len() {
+            table
+                .entry(hashes.value(i))
+                .or_insert_with(Vec::new)
+                .push(i);
+        }
+        Ok(Self {
+            columns,
+            comparators,
+            hashes,
+            table,
+        })
+    }
+
+    /// Returns the index of the first row that is equal to row `i`
+    fn first_equal(&self, i: usize) -> usize {
+        self.table[&self.hashes.value(i)]
+            .iter()
+            .cloned()
+            .find(|&j| self.rows_equal(i, j))
+            .unwrap_or(i)
+    }
+
+    /// Returns whether any row in `range` is equal to row `i`
+    fn has_equal_in(&self, i: usize, range: &Range<usize>) -> bool {
+        self.table[&self.hashes.value(i)]
+            .iter()
+            .any(|j| range.contains(j) && self.rows_equal(i, *j))
+    }
+
+    fn rows_equal(&self, i: usize, j: usize) -> bool {
+        self.columns
+            .iter()
+            .zip(self.comparators.iter())
+            .all(
+                |(column, cmp)| match (column.is_valid(i), column.is_valid(j)) {
+                    (true, true) => cmp(i, j) == Ordering::Equal,
+                    (a, b) => a == b,
+                },
+            )
+    }
+}
+
+/// Takes the rows at `indices` from all columns of `batch`
+fn take_rows(batch: &RecordBatch, indices: Vec<u32>) -> Result<RecordBatch> {
+    let indices = UInt32Array::from(indices);
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|c| take(c, &indices, None))
+        .collect::<Result<Vec<_>>>()?;
+    RecordBatch::try_new(batch.schema().clone(), columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::datatypes::{DataType, Field, Schema};
+
+    fn batch(a: Vec<Option<i32>>, b: Vec<&str>) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(a)),
+                Arc::new(StringArray::from(b)),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn rows(batch: &RecordBatch) -> Vec<(Option<i32>, String)> {
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        let b = as_binary_array(batch.column(1));
+        (0..batch.num_rows())
+            .map(|i| {
+                let a = if a.is_valid(i) {
+                    Some(a.value(i))
+                } else {
+                    None
+                };
+                (a, String::from_utf8(b.value(i).to_vec()).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_union_all_keeps_duplicates() {
+        let first = batch(vec![Some(1), Some(2)], vec!["x", "y"]);
+        let second = batch(vec![Some(1), None], vec!["x", "z"]);
+        let result = union(&[first, second], false).unwrap();
+        assert_eq!(
+            vec![
+                (Some(1), "x".to_string()),
+                (Some(2), "y".to_string()),
+                (Some(1), "x".to_string()),
+                (None, "z".to_string()),
+            ],
+            rows(&result)
+        );
+    }
+
+    #[test]
+    fn test_union_distinct_removes_duplicates() {
+        let first = batch(vec![Some(1), Some(1), None], vec!["x", "y", "z"]);
+        let second = batch(vec![Some(1), None, Some(1)], vec!["x", "z", "y"]);
+        let result = union(&[first, second], true).unwrap();
+        assert_eq!(
+            vec![
+                (Some(1), "x".to_string()),
+                (Some(1), "y".to_string()),
+                (None, "z".to_string()),
+            ],
+            rows(&result)
+        );
+    }
+
+    #[test]
+    fn test_intersect_and_except() {
+        let left = batch(
+            vec![Some(1), Some(2), None, Some(1), Some(3)],
+            vec!["x", "y", "z", "x", "w"],
+        );
+        let right = batch(vec![Some(1), None, Some(2)], vec!["x", "z", "x"]);
+
+        let result = intersect(&left, &right).unwrap();
+        assert_eq!(
+            vec![(Some(1), "x".to_string()), (None, "z".to_string())],
+            rows(&result)
+        );
+
+        let result = except(&left, &right).unwrap();
+        assert_eq!(
+            vec![(Some(2), "y".to_string()), (Some(3), "w".to_string())],
+            rows(&result)
+        );
+    }
+}
@@ -0,0 +1,278 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines sort kernels for `ArrayRef`
+
+use std::cmp::Ordering;
+
+use crate::array::*;
+use crate::compute::kernels::take::take;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// Options that define how sort kernels order their input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortOptions {
+    /// Whether to sort in descending order
+    pub descending: bool,
+    /// Whether to place null values before all other values
+    pub nulls_first: bool,
+}
+
+impl Default for SortOptions {
+    fn default() -> Self {
+        Self {
+            descending: false,
+            nulls_first: true,
+        }
+    }
+}
+
+/// Returns a copy of `values` with its values sorted, by taking the values at the
+/// indices returned by `sort_to_indices`.
+pub fn sort(values: &ArrayRef, options: SortOptions) -> Result<ArrayRef> {
+    let indices = sort_to_indices(values.as_ref(), options)?;
+    take(values, &indices, None)
+}
+
+/// Returns the indices that would sort `values`, as a `UInt32Array`.
+///
+/// Floating point `NaN` values are ordered after all other values. Null values are
+/// placed before or after the non-null values according to `options.nulls_first`,
+/// regardless of `options.descending`. The relative order of equal values is not
+/// specified.
+///
+/// Supports boolean, integer, floating point, date, string and binary arrays.
+pub fn sort_to_indices(values: &Array, options: SortOptions) -> Result<UInt32Array> {
+    let cmp = value_comparator(values)?;
+    let (mut valid, nulls): (Vec<u32>, Vec<u32>) =
+        (0..values.len() as u32).partition(|i| values.is_valid(*i as usize));
+    valid.sort_by(|a, b| {
+        let ordering = cmp(*a as usize, *b as usize);
+        if options.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    let indices: Vec<u32> = if options.nulls_first {
+        nulls.into_iter().chain(valid.into_iter()).collect()
+    } else {
+        valid.into_iter().chain(nulls.into_iter()).collect()
+    };
+    Ok(UInt32Array::from(indices))
+}
+
+/// Compares the non-null values at two indices of an array
+pub(crate) type ValueComparator<'a> = Box<Fn(usize, usize) -> Ordering + 'a>;
+
+/// Returns a comparator that orders the non-null values of `values` ascending
+pub(crate) fn value_comparator<'a>(values: &'a Array) -> Result<ValueComparator<'a>> {
+    array_comparator(values, values)
+}
+
+/// Returns a comparator that orders a non-null value of `left`, given by the first
+/// index, against a non-null value of `right`, given by the second index, ascending.
+///
+/// Both arrays must have the same data type.
+pub(crate) fn array_comparator<'a>(
+    left: &'a Array,
+    right: &'a Array,
+) -> Result<ValueComparator<'a>> {
+    if left.data_type() != right.data_type() {
+        return Err(ArrowError::ComputeError(format!(
+            "Cannot compare values of type {:?} with values of type {:?}",
+            left.data_type(),
+            right.data_type()
+        )));
+    }
+    match left.data_type() {
+        DataType::Boolean => {
+            let left = left.as_any().downcast_ref::<BooleanArray>().unwrap();
+            let right = right.as_any().downcast_ref::<BooleanArray>().unwrap();
+            Ok(Box::new(move |i, j| left.value(i).cmp(&right.value(j))))
+        }
+        DataType::Int8 => Ok(primitive_comparator::<Int8Type>(left, right)),
+        DataType::Int16 => Ok(primitive_comparator::<Int16Type>(left, right)),
+        DataType::Int32 => Ok(primitive_comparator::<Int32Type>(left, right)),
+        DataType::Int64 => Ok(primitive_comparator::<Int64Type>(left, right)),
+        DataType::UInt8 => Ok(primitive_comparator::<UInt8Type>(left, right)),
+        DataType::UInt16 => Ok(primitive_comparator::<UInt16Type>(left, right)),
+        DataType::UInt32 => Ok(primitive_comparator::<UInt32Type>(left, right)),
+        DataType::UInt64 => Ok(primitive_comparator::<UInt64Type>(left, right)),
+        DataType::Float32 => Ok(primitive_comparator::<Float32Type>(left, right)),
+        DataType::Float64 => Ok(primitive_comparator::<Float64Type>(left, right)),
+        DataType::Date32(_) => Ok(primitive_comparator::<Date32Type>(left, right)),
+        DataType::Date64(_) => Ok(primitive_comparator::<Date64Type>(left, right)),
+        DataType::Utf8 | DataType::Binary => {
+            let left = as_binary_array(left);
+            let right = as_binary_array(right);
+            Ok(Box::new(move |i, j| left.value(i).cmp(right.value(j))))
+        }
+        t => Err(ArrowError::ComputeError(format!(
+            "Sort not supported for data type {:?}",
+            t
+        ))),
+    }
+}
+
+fn primitive_comparator<'a, T: ArrowNumericType>(
+    left: &'a Array,
+    right: &'a Array,
+) -> ValueComparator<'a> {
+    let left = left.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    let right = right.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    Box::new(move |i, j| total_cmp(&left.value(i), &right.value(j)))
+}
+
+/// Orders two values, placing values that are not comparable with themselves (`NaN`)
+/// after all other values.
+pub(crate) fn total_cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or_else(|| {
+        let a_nan = a.partial_cmp(a).is_none();
+        let b_nan = b.partial_cmp(b).is_none();
+        a_nan.cmp(&b_nan)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    fn test_sort_to_indices_primitive<T>(
+        data: Vec<Option<T::Native>>,
+        options: SortOptions,
+        expected: Vec<u32>,
+    ) where
+        T: ArrowNumericType,
+        PrimitiveArray<T>: From<Vec<Option<T::Native>>>,
+    {
+        let values = PrimitiveArray::<T>::from(data);
+        let indices = sort_to_indices(&values, options).unwrap();
+        let indices: Vec<u32> = (0..indices.len()).map(|i| indices.value(i)).collect();
+        assert_eq!(expected, indices);
+    }
+
+    #[test]
+    fn test_sort_to_indices_int32() {
+        let data = vec![Some(3), None, Some(1), Some(2), None];
+        test_sort_to_indices_primitive::<Int32Type>(
+            data.clone(),
+            SortOptions::default(),
+            vec![1, 4, 2, 3, 0],
+        );
+        test_sort_to_indices_primitive::<Int32Type>(
+            data,
+            SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+            vec![0, 3, 2, 1, 4],
+        );
+    }
+
+    #[test]
+    fn test_sort_to_indices_float64_nan() {
+        test_sort_to_indices_primitive::<Float64Type>(
+            vec![Some(std::f64::NAN), Some(1.5), None, Some(-2.0)],
+            SortOptions::default(),
+            vec![2, 3, 1, 0],
+        );
+    }
+
+    #[test]
+    fn test_sort_to_indices_strings() {
+        let values = BinaryArray::from(vec!["pear", "apple", "fig"]);
+        let indices = sort_to_indices(&values, SortOptions::default()).unwrap();
+        assert_eq!(&[1, 2, 0], indices.value_slice(0, 3));
+
+        let values = StringArray::from(vec![Some("pear"), None, Some("apple")]);
+        let options = SortOptions {
+            descending: true,
+            nulls_first: false,
+        };
+        let indices = sort_to_indices(&values, options).unwrap();
+        assert_eq!(&[0, 2, 1], indices.value_slice(0, 3));
+    }
+
+    #[test]
+    fn test_sort_to_indices_boolean() {
+        let values = BooleanArray::from(vec![Some(true), None, Some(false)]);
+        let indices = sort_to_indices(&values, SortOptions::default()).unwrap();
+        assert_eq!(&[1, 2, 0], indices.value_slice(0, 3));
+    }
+
+    #[test]
+    fn test_sort_to_indices_sliced() {
+        let values = Int32Array::from(vec![Some(5), Some(3), None, Some(4), Some(1)]);
+        let values = values.slice(1, 3);
+        let indices = sort_to_indices(values.as_ref(), SortOptions::default()).unwrap();
+        assert_eq!(&[1, 0, 2], indices.value_slice(0, 3));
+    }
+
+    #[test]
+    fn test_sort() {
+        // equal values may be in any order, so only the sorted values are compared
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![
+            Some(2),
+            None,
+            Some(7),
+            Some(2),
+            None,
+            Some(-1),
+            Some(7),
+        ]));
+        let options = SortOptions {
+            descending: false,
+            nulls_first: false,
+        };
+        let sorted = sort(&values, options).unwrap();
+        let expected = Int64Array::from(vec![
+            Some(-1),
+            Some(2),
+            Some(2),
+            Some(7),
+            Some(7),
+            None,
+            None,
+        ]);
+        assert!(array_equals(&expected, sorted.as_ref()));
+
+        let values: ArrayRef = Arc::new(Float32Array::from(vec![
+            Some(1.0),
+            Some(std::f32::NAN),
+            None,
+            Some(-3.5),
+        ]));
+        let options = SortOptions {
+            descending: true,
+            nulls_first: true,
+        };
+        let sorted = sort(&values, options).unwrap();
+        let expected =
+            Float32Array::from(vec![None, Some(std::f32::NAN), Some(1.0), Some(-3.5)]);
+        assert!(array_equals(&expected, sorted.as_ref()));
+    }
+
+    #[test]
+    fn test_sort_unsupported_type() {
+        let values = NullArray::new(2);
+        assert!(sort_to_indices(&values, SortOptions::default()).is_err());
+    }
+}
@@ -21,9 +21,12 @@ use std::sync::Arc;
 
 use crate::array::*;
 use crate::buffer::{Buffer, MutableBuffer};
-use crate::compute::util::take_value_indices_from_list;
+use crate::compute::util::{
+    dictionary_from_keys, dictionary_keys, take_value_indices_from_list,
+};
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
+use crate::record_batch::{RecordBatch, RecordBatchOptions};
 use crate::util::bit_util;
 
 use TimeUnit::*;
@@ -32,25 +35,45 @@ use TimeUnit::*;
 ///
 /// Supports:
 ///  * null indices, returning a null value for the index
-///  * checking for overflowing indices
+///  * indices that are out of bounds, returning either an error or a null value for the
+///    index depending on `TakeOptions::check_bounds`
+///
+/// Lists are taken by copying the child values of each taken list.
+///
+/// If `indices` is `0..values.len()` without nulls, `values` is returned unchanged.
 pub fn take(
     values: &ArrayRef,
     indices: &UInt32Array,
     options: Option<TakeOptions>,
 ) -> Result<ArrayRef> {
     let options = options.unwrap_or(Default::default());
-    if options.check_bounds {
-        let len = values.len();
-        for i in 0..indices.len() {
-            if indices.is_valid(i) {
-                let ix = indices.value(i) as usize;
-                if ix >= len {
-                    return Err(ArrowError::ComputeError(
-                    format!("Array index out of bounds, cannot get item at index {} from {} entries", ix, len))
-                );
-                }
-            }
+    if is_identity(indices, values.len()) {
+        return Ok(values.clone());
+    }
+    let len = values.len();
+    let out_of_bounds = (0..indices.len())
+        .find(|i| indices.is_valid(*i) && indices.value(*i) as usize >= len);
+    if let Some(i) = out_of_bounds {
+        if options.check_bounds {
+            return Err(ArrowError::ComputeError(format!(
+                "Array index out of bounds, cannot get item at index {} from {} entries",
+                indices.value(i),
+                len
+            )));
         }
+        // take nulls for the indices that are out of bounds
+        let indices = UInt32Array::from(
+            (0..indices.len())
+                .map(|i| {
+                    if indices.is_valid(i) && (indices.value(i) as usize) < len {
+                        Some(indices.value(i))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<Option<u32>>>(),
+        );
+        return take(values, &indices, Some(options));
     }
     match values.data_type() {
         DataType::Boolean => take_primitive::<BooleanType>(values, indices),
@@ -88,8 +111,13 @@ pub fn take(
         DataType::Timestamp(Nanosecond) => {
             take_primitive::<TimestampNanosecondType>(values, indices)
         }
-        DataType::Utf8 => take_binary(values, indices),
+        DataType::Utf8 | DataType::Binary => take_binary(values, indices),
         DataType::List(_) => take_list(values, indices),
+        DataType::Dictionary(_, _) => {
+            let keys = dictionary_keys(values.data_ref());
+            let keys = take(&keys, indices, Some(options.clone()))?;
+            Ok(dictionary_from_keys(values.data_ref(), &keys))
+        }
         DataType::Struct(fields) => {
             let struct_: &StructArray =
                 values.as_any().downcast_ref::<StructArray>().unwrap();
@@ -107,12 +135,43 @@ pub fn take(
     }
 }
 
+/// Takes the rows of `batch` at `indices` by applying `take` to each of its columns.
+///
+/// Dictionary encoded columns stay encoded and share their dictionary values with the
+/// columns of `batch`.
+pub fn take_record_batch(
+    batch: &RecordBatch,
+    indices: &UInt32Array,
+) -> Result<RecordBatch> {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| take(column, indices, None))
+        .collect::<Result<Vec<ArrayRef>>>()?;
+    RecordBatch::try_new_with_options(
+        batch.schema().clone(),
+        columns,
+        &RecordBatchOptions {
+            match_field_names: true,
+            row_count: Some(indices.len()),
+        },
+    )
+}
+
+/// Returns whether `indices` takes every one of `len` values in order, i.e. is
+/// `0..len` without nulls, in which case `take` can return the values unchanged
+fn is_identity(indices: &UInt32Array, len: usize) -> bool {
+    indices.len() == len
+        && indices.null_count() == 0
+        && (0..len).all(|i| indices.value(i) as usize == i)
+}
+
 /// Options that define how `take` should behave
 #[derive(Clone)]
 pub struct TakeOptions {
     /// Perform bounds check before taking indices from values.
     /// If enabled, an `ArrowError` is returned if the indices are out of bounds.
-    /// If not enabled, and indices exceed bounds, the kernel will panic.
+    /// If not enabled, indices that exceed bounds take a null value.
     pub check_bounds: bool,
 }
 
@@ -156,10 +215,10 @@ where
     Ok(Arc::new(builder.finish()) as ArrayRef)
 }
 
-/// `take` implementation for binary arrays
+/// `take` implementation for binary and string arrays
 fn take_binary(values: &ArrayRef, indices: &UInt32Array) -> Result<ArrayRef> {
     let mut builder = BinaryBuilder::new(indices.len());
-    let a = values.as_any().downcast_ref::<BinaryArray>().unwrap();
+    let a = as_binary_array(values);
     for i in 0..indices.len() {
         if indices.is_null(i) {
             builder.append(false)?;
@@ -172,7 +231,16 @@ fn take_binary(values: &ArrayRef, indices: &UInt32Array) -> Result<ArrayRef> {
             }
         }
     }
-    Ok(Arc::new(builder.finish()) as ArrayRef)
+    let taken = builder.finish().data();
+    Ok(make_array(Arc::new(ArrayData::new(
+        values.data_type().clone(),
+        taken.len(),
+        Some(taken.null_count()),
+        taken.null_bitmap().clone().map(|bitmap| bitmap.bits),
+        taken.offset(),
+        taken.buffers().to_vec(),
+        vec![],
+    ))))
 }
 
 /// `take` implementation for list arrays
@@ -255,6 +323,18 @@ mod tests {
         Arc::new(struct_array) as ArrayRef
     }
 
+    #[test]
+    fn test_take_identity_shares_array() {
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]));
+        let index = UInt32Array::from(vec![0, 1, 2]);
+        let output = take(&values, &index, None).unwrap();
+        assert!(Arc::ptr_eq(&values, &output));
+
+        let index = UInt32Array::from(vec![0, 2, 1]);
+        let output = take(&values, &index, None).unwrap();
+        assert!(!Arc::ptr_eq(&values, &output));
+    }
+
     #[test]
     fn test_take_primitive() {
         let index = UInt32Array::from(vec![Some(3), None, Some(1), Some(3), Some(2)]);
@@ -338,6 +418,44 @@ mod tests {
         assert_eq!(a.data(), b.data());
     }
 
+    #[test]
+    fn test_take_string() {
+        let index = UInt32Array::from(vec![Some(2), None, Some(0)]);
+        let array = Arc::new(StringArray::from(vec![Some("one"), None, Some("three")]))
+            as ArrayRef;
+        let a = take(&array, &index, None).unwrap();
+        assert_eq!(&DataType::Utf8, a.data_type());
+        let a = StringArray::from(a.data());
+        assert_eq!(3, a.len());
+        assert_eq!("three", a.value(0));
+        assert!(a.is_null(1));
+        assert_eq!("one", a.value(2));
+    }
+
+    #[test]
+    fn test_take_empty_and_null_indices() {
+        let array = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef;
+
+        let a = take(&array, &UInt32Array::from(Vec::<u32>::new()), None).unwrap();
+        assert_eq!(0, a.len());
+        assert_eq!(&DataType::Int32, a.data_type());
+
+        let index = UInt32Array::from(vec![None, None]);
+        let a = take(&array, &index, None).unwrap();
+        assert_eq!(2, a.len());
+        assert_eq!(2, a.null_count());
+    }
+
+    #[test]
+    fn test_take_out_of_bounds_without_check_is_null() {
+        test_take_primitive_arrays::<Int32Type>(
+            vec![Some(0), None, Some(2)],
+            &UInt32Array::from(vec![Some(2), Some(5), Some(1), Some(0)]),
+            None,
+            vec![Some(2), None, None, Some(0)],
+        );
+    }
+
     #[test]
     fn test_take_list() {
         // Construct a value array, [[0,0,0], [-1,-2,-1], [2,3]]
@@ -592,4 +710,48 @@ mod tests {
             vec![None],
         );
     }
+
+    #[test]
+    fn test_take_record_batch_keeps_dictionary() {
+        let dictionary: DictionaryArray<Int8Type> =
+            vec![Some("a"), None, Some("b"), Some("a")]
+                .into_iter()
+                .collect();
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new(
+                "b",
+                DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+                true,
+            ),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+                Arc::new(dictionary),
+            ],
+        )
+        .unwrap();
+
+        let index = UInt32Array::from(vec![Some(2), Some(1), None, Some(0)]);
+        let taken = take_record_batch(&batch, &index).unwrap();
+        assert_eq!(4, taken.num_rows());
+        assert_eq!(batch.schema(), taken.schema());
+
+        let a = taken.column(0).downcast::<Int32Array>().unwrap();
+        assert_eq!(3, a.value(0));
+        assert!(a.is_null(2));
+        let b = taken
+            .column(1)
+            .downcast::<DictionaryArray<Int8Type>>()
+            .unwrap();
+        assert_eq!(Some(1), b.key(0));
+        assert_eq!(None, b.key(1));
+        assert_eq!(None, b.key(2));
+        assert_eq!(Some(0), b.key(3));
+        let original = batch.column(1).data();
+        let values = &b.data_ref().child_data()[0];
+        assert!(Arc::ptr_eq(&original.child_data()[0], values));
+    }
 }
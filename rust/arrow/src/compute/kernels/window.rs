@@ -0,0 +1,188 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines window kernels that compute a value for each element of an array from the
+//! elements around it.
+
+use std::cmp::Ordering;
+use std::ops::Add;
+
+use num::Zero;
+
+use crate::array::*;
+use crate::compute::kernels::aggregate::{AggregateOptions, PolicyAdd};
+use crate::compute::kernels::sort::{sort_to_indices, value_comparator, SortOptions};
+use crate::datatypes::ArrowNumericType;
+use crate::error::Result;
+
+/// Returns the running sum of `array`, where each output value is the sum of all
+/// non-null values up to and including the same position.
+///
+/// Null values are null in the output, but they do not reset the running sum, so the
+/// cumulative sum of `[1, null, 2, 3]` is `[1, null, 3, 6]`.
+///
+/// An overflowing integer sum returns an error, as with the default `AggregateOptions`.
+/// Use `cumulative_sum_with_options` to wrap or saturate instead.
+pub fn cumulative_sum<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: Add<Output = T::Native> + Zero,
+{
+    cumulative_sum_with_options(array, AggregateOptions::default())
+}
+
+/// Returns the running sum of `array` like `cumulative_sum`, handling an overflow of an
+/// integer sum according to `options.overflow`. Float values are added with `+`.
+pub fn cumulative_sum_with_options<T>(
+    array: &PrimitiveArray<T>,
+    options: AggregateOptions,
+) -> Result<PrimitiveArray<T>>
+where
+    T: ArrowNumericType,
+    T::Native: Add<Output = T::Native> + Zero,
+{
+    let mut builder = PrimitiveBuilder::<T>::new(array.len());
+    let mut sum = T::Native::zero();
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            builder.append_null()?;
+        } else {
+            sum = sum.add_with_policy(array.value(i), options.overflow)?;
+            builder.append_value(sum)?;
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Returns the rank of each element of `array` in the order given by `options`,
+/// starting from 1.
+///
+/// Equal values share the rank of the first of them, leaving a gap after each group of
+/// ties, so the ranks of `[10, 20, 20, 30]` are `[1, 2, 2, 4]`. Null values are ranked
+/// as equal to each other, before or after the other values according to
+/// `options.nulls_first`.
+pub fn rank(array: &Array, options: SortOptions) -> Result<UInt32Array> {
+    assign_ranks(array, options, false)
+}
+
+/// Returns the dense rank of each element of `array` in the order given by `options`,
+/// starting from 1.
+///
+/// Like `rank`, but without gaps after ties, so the dense ranks of `[10, 20, 20, 30]`
+/// are `[1, 2, 2, 3]`.
+pub fn dense_rank(array: &Array, options: SortOptions) -> Result<UInt32Array> {
+    assign_ranks(array, options, true)
+}
+
+fn assign_ranks(array: &Array, options: SortOptions, dense: bool) -> Result<UInt32Array> {
+    let indices = sort_to_indices(array, options)?;
+    let cmp = value_comparator(array)?;
+    let is_tie = |i: usize, j: usize| match (array.is_null(i), array.is_null(j)) {
+        (true, true) => true,
+        (false, false) => cmp(i, j) == Ordering::Equal,
+        _ => false,
+    };
+
+    let mut ranks = vec![0u32; array.len()];
+    let mut current_rank = 0;
+    for position in 0..indices.len() {
+        let index = indices.value(position) as usize;
+        if position == 0 || !is_tie(indices.value(position - 1) as usize, index) {
+            current_rank = if dense {
+                current_rank + 1
+            } else {
+                position as u32 + 1
+            };
+        }
+        ranks[index] = current_rank;
+    }
+    Ok(UInt32Array::from(ranks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cumulative_sum() {
+        let a = Int32Array::from(vec![Some(1), None, Some(2), Some(3)]);
+        let c = cumulative_sum(&a).unwrap();
+        assert_eq!(4, c.len());
+        assert_eq!(1, c.null_count());
+        assert_eq!(1, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(3, c.value(2));
+        assert_eq!(6, c.value(3));
+    }
+
+    #[test]
+    fn test_cumulative_sum_overflow_policy() {
+        use crate::compute::kernels::aggregate::OverflowPolicy;
+
+        let a = Int64Array::from(vec![Some(std::i64::MAX), None, Some(1)]);
+        assert!(cumulative_sum_with_options(&a, AggregateOptions::default()).is_err());
+        assert!(cumulative_sum(&a).is_err());
+
+        let options = AggregateOptions {
+            overflow: OverflowPolicy::Wrapping,
+        };
+        let c = cumulative_sum_with_options(&a, options).unwrap();
+        assert_eq!(std::i64::MAX, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(std::i64::MIN, c.value(2));
+
+        let options = AggregateOptions {
+            overflow: OverflowPolicy::Saturating,
+        };
+        let c = cumulative_sum_with_options(&a, options).unwrap();
+        assert_eq!(std::i64::MAX, c.value(2));
+    }
+
+    #[test]
+    fn test_cumulative_sum_sliced() {
+        let a = Float64Array::from(vec![Some(5.0), Some(1.5), None, Some(2.5)]);
+        let a = a.slice(1, 3);
+        let a = a.as_any().downcast_ref::<Float64Array>().unwrap();
+        let c = cumulative_sum(a).unwrap();
+        assert_eq!(3, c.len());
+        assert_eq!(1.5, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(4.0, c.value(2));
+    }
+
+    #[test]
+    fn test_rank() {
+        let a = Int32Array::from(vec![10, 20, 20, 30]);
+        let r = rank(&a, SortOptions::default()).unwrap();
+        assert_eq!(&[1, 2, 2, 4], r.value_slice(0, 4));
+        let r = dense_rank(&a, SortOptions::default()).unwrap();
+        assert_eq!(&[1, 2, 2, 3], r.value_slice(0, 4));
+    }
+
+    #[test]
+    fn test_rank_descending_with_nulls() {
+        let a = Float64Array::from(vec![Some(1.0), None, Some(3.0), None, Some(1.0)]);
+        let options = SortOptions {
+            descending: true,
+            nulls_first: false,
+        };
+        let r = rank(&a, options).unwrap();
+        assert_eq!(&[2, 4, 1, 4, 2], r.value_slice(0, 5));
+        let r = dense_rank(&a, options).unwrap();
+        assert_eq!(&[2, 3, 1, 3, 2], r.value_slice(0, 5));
+    }
+}
@@ -23,9 +23,42 @@ pub mod kernels;
 mod util;
 
 pub use self::array_ops::*;
+pub use self::kernels::aggregate;
+pub use self::kernels::aggregate::*;
+pub use self::kernels::arithmetic as arith;
 pub use self::kernels::arithmetic::*;
+pub use self::kernels::batch;
+pub use self::kernels::batch::*;
+pub use self::kernels::bitwise::*;
 pub use self::kernels::boolean::*;
 pub use self::kernels::cast::*;
+pub use self::kernels::compact::*;
 pub use self::kernels::comparison::*;
+pub use self::kernels::concat;
+pub use self::kernels::concat::*;
+pub use self::kernels::explode;
+pub use self::kernels::explode::*;
+pub use self::kernels::fill::*;
+pub use self::kernels::filter;
+pub use self::kernels::filter::*;
+pub use self::kernels::hash::*;
+pub use self::kernels::histogram::*;
+pub use self::kernels::join;
+pub use self::kernels::join::*;
+pub use self::kernels::membership::*;
+pub use self::kernels::normalize::*;
+pub use self::kernels::nullif::*;
+pub use self::kernels::partition::*;
+pub use self::kernels::profile;
+pub use self::kernels::profile::*;
+pub use self::kernels::repeat::*;
+pub use self::kernels::replace::*;
+pub use self::kernels::row;
+pub use self::kernels::sample;
+pub use self::kernels::sample::*;
+pub use self::kernels::set_ops;
+pub use self::kernels::set_ops::*;
+pub use self::kernels::sort::*;
 pub use self::kernels::take::*;
 pub use self::kernels::temporal::*;
+pub use self::kernels::window::*;
@@ -20,7 +20,6 @@
 use crate::array::*;
 use crate::bitmap::Bitmap;
 use crate::buffer::Buffer;
-#[cfg(feature = "simd")]
 use crate::datatypes::*;
 use crate::error::Result;
 #[cfg(feature = "simd")]
@@ -51,6 +50,76 @@ where
     }
 }
 
+/// Returns the validity bitmaps of `left` and `right`, which both have `len` slots,
+/// ANDed together into a bitmap whose first bit is the validity of their first slots.
+///
+/// The bitmaps are combined a byte at a time if neither array is a slice, and a bit at a
+/// time otherwise, as the first slot of a slice may be at any bit of its bitmap.
+#[cfg(feature = "simd")]
+pub(super) fn combine_option_bitmaps(
+    left: &ArrayDataRef,
+    right: &ArrayDataRef,
+    len: usize,
+) -> Result<Option<Buffer>> {
+    if left.offset() == 0 && right.offset() == 0 {
+        return apply_bin_op_to_option_bitmap(
+            left.null_bitmap(),
+            right.null_bitmap(),
+            |a, b| a & b,
+        );
+    }
+    if left.null_bitmap().is_none() && right.null_bitmap().is_none() {
+        return Ok(None);
+    }
+    let mut builder = BooleanBufferBuilder::new(len);
+    for i in 0..len {
+        builder.append(
+            left.is_valid(left.offset() + i) && right.is_valid(right.offset() + i),
+        )?;
+    }
+    Ok(Some(builder.finish()))
+}
+
+/// Returns the keys of the dictionary array `data` as an array of its key type, sharing
+/// the buffers of `data`.
+///
+/// Kernels that select slots, such as take and filter, can select the keys instead of
+/// the decoded values and rebuild the dictionary array with `dictionary_from_keys`, so
+/// that the result stays dictionary encoded.
+pub(super) fn dictionary_keys(data: &ArrayDataRef) -> ArrayRef {
+    let key_type = match data.data_type() {
+        DataType::Dictionary(key_type, _) => key_type.as_ref().clone(),
+        t => panic!("Expected a dictionary array, got {:?}", t),
+    };
+    let mut keys = ArrayData::builder(key_type)
+        .len(data.len())
+        .offset(data.offset())
+        .add_buffer(data.buffers()[0].clone());
+    if let Some(bitmap) = data.null_bitmap() {
+        keys = keys
+            .null_count(data.null_count())
+            .null_bit_buffer(bitmap.bits.clone());
+    }
+    make_array(keys.build())
+}
+
+/// Creates a dictionary array with the data type and dictionary values of the
+/// dictionary array `data`, whose keys are `keys`. The values are shared, not copied.
+pub(super) fn dictionary_from_keys(data: &ArrayDataRef, keys: &ArrayRef) -> ArrayRef {
+    let keys = keys.data();
+    let mut builder = ArrayData::builder(data.data_type().clone())
+        .len(keys.len())
+        .offset(keys.offset())
+        .add_buffer(keys.buffers()[0].clone())
+        .add_child_data(data.child_data()[0].clone());
+    if let Some(bitmap) = keys.null_bitmap() {
+        builder = builder
+            .null_count(keys.null_count())
+            .null_bit_buffer(bitmap.bits.clone());
+    }
+    make_array(builder.build())
+}
+
 /// Takes/filters a list array's inner data using the offsets of the list array.
 ///
 /// Where a list array has indices `[0,2,5,10]`, taking indices of `[2,0]` returns
@@ -131,8 +200,9 @@ where
 
 /// Performs a SIMD load but sets all 'invalid' lanes to a constant value.
 ///
-/// 'invalid' lanes are lanes where the corresponding array slots are either `NULL` or between the
-/// length and capacity of the array, i.e. in the padded region.
+/// 'invalid' lanes are lanes where the corresponding array slots are `NULL`. All lanes must be
+/// within the array, i.e. `i + simd_width <= array.len()`, as the values are read from the
+/// buffer of `array` starting at its offset, which may not be padded past the array's end.
 ///
 /// Note that `array` below has it's own `Bitmap` separate from the `bitmap` argument.  This
 /// function is used to prepare `array`'s for binary operations.  The `bitmap` argument is the
@@ -149,6 +219,10 @@ where
     T: ArrowNumericType,
     T::Native: One,
 {
+    debug_assert!(
+        i + simd_width <= array.len(),
+        "SIMD lanes must not extend past the end of the array"
+    );
     let simd_with_zeros = T::load(array.value_slice(i, simd_width));
     T::mask_select(
         is_valid::<T>(bitmap, i, simd_width, array.len()),
@@ -256,7 +330,16 @@ mod tests {
     #[test]
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "simd"))]
     fn test_simd_load_set_invalid() {
-        let a = Int64Array::from(vec![None, Some(15), Some(5), Some(0)]);
+        let a = Int64Array::from(vec![
+            None,
+            Some(15),
+            Some(5),
+            Some(0),
+            Some(1),
+            None,
+            Some(3),
+            Some(4),
+        ]);
         let new_bitmap = &Some(Bitmap::from(Buffer::from([0b00001010])));
         let simd_lanes = 8;
         let result = unsafe {
@@ -16,10 +16,14 @@
 // under the License.
 
 //! Transfer data between the Arrow memory format and CSV (comma-separated values).
+//!
+//! Compressed input can be read by wrapping it in a
+//! `util::compression::CompressedReader`.
 
 pub mod reader;
 pub mod writer;
 
+pub use self::reader::infer_schema;
 pub use self::reader::Reader;
 pub use self::reader::ReaderBuilder;
 pub use self::writer::Writer;
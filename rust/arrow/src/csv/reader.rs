@@ -40,9 +40,10 @@
 //! let batch = csv.next().unwrap().unwrap();
 //! ```
 
+use chrono::{NaiveDate, NaiveDateTime};
 use lazy_static::lazy_static;
 use regex::{Regex, RegexBuilder};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::sync::Arc;
 
@@ -51,10 +52,15 @@ use csv as csv_crate;
 use crate::array::{ArrayRef, BinaryBuilder, PrimitiveBuilder};
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
-use crate::record_batch::RecordBatch;
+use crate::record_batch::{RecordBatch, RecordBatchReader};
 
 use self::csv_crate::{StringRecord, StringRecordsIntoIter};
 
+/// The format of dates in date columns that have no format of their own
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// The format of timestamps in timestamp columns that have no format of their own
+const DEFAULT_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
 lazy_static! {
     static ref DECIMAL_RE: Regex = Regex::new(r"^-?(\d+\.\d+)$").unwrap();
     static ref INTEGER_RE: Regex = Regex::new(r"^-?(\d*.)$").unwrap();
@@ -86,12 +92,14 @@ fn infer_field_schema(string: &str) -> DataType {
 /// Infer the schema of a CSV file by reading through the first n records of the file,
 /// with `max_read_records` controlling the maximum number of records to read.
 ///
-/// If `max_read_records` is not set, the whole file is read to infer its schema.
+/// If `max_read_records` is not set, the whole file is read to infer its schema. Empty
+/// values and values equal to one of `null_tokens` are treated as nulls.
 fn infer_file_schema<R: Read + Seek>(
     reader: &mut BufReader<R>,
     delimiter: u8,
     max_read_records: Option<usize>,
     has_headers: bool,
+    null_tokens: &[String],
 ) -> Result<Schema> {
     let mut csv_reader = csv::ReaderBuilder::new()
         .delimiter(delimiter)
@@ -134,7 +142,7 @@ fn infer_file_schema<R: Read + Seek>(
             let string: Option<&str> = record.get(i);
             match string {
                 Some(s) => {
-                    if s == "" {
+                    if s == "" || null_tokens.iter().any(|t| t == s) {
                         nulls[i] = true;
                     } else {
                         column_types[i].insert(infer_field_schema(s));
@@ -180,6 +188,20 @@ fn infer_file_schema<R: Read + Seek>(
     Ok(Schema::new(fields))
 }
 
+/// Infers the schema of CSV data with a header row and comma delimited values, reading
+/// through the first `max_records` records, or all records if `max_records` is `None`.
+///
+/// The type of each column is the narrowest of `Boolean`, `Int64`, `Float64` and `Utf8`
+/// that all its values parse as, and columns with empty values are nullable. The reader
+/// is returned to its start afterwards. Use `ReaderBuilder::infer_schema` to infer the
+/// schema of other CSV dialects.
+pub fn infer_schema<R: Read + Seek>(
+    reader: &mut R,
+    max_records: Option<usize>,
+) -> Result<Schema> {
+    infer_file_schema(&mut BufReader::new(reader), b',', max_records, true, &[])
+}
+
 /// CSV file reader
 pub struct Reader<R: Read> {
     /// Explicit schema for the CSV file
@@ -192,6 +214,14 @@ pub struct Reader<R: Read> {
     batch_size: usize,
     /// Current line number, used in error reporting
     line_number: usize,
+    /// Line number at which to stop reading, used to read only part of a file
+    end: usize,
+    /// Values that are read as nulls
+    null_tokens: Vec<String>,
+    /// Parse formats of temporal columns, by field name
+    temporal_formats: HashMap<String, String>,
+    /// Whether a temporal value that does not match its format is an error or a null
+    strict_temporal_parsing: bool,
 }
 
 impl<R: Read> Reader<R> {
@@ -252,14 +282,23 @@ impl<R: Read> Reader<R> {
             record_iter,
             batch_size,
             line_number: if has_headers { 1 } else { 0 },
+            end: usize::max_value(),
+            null_tokens: vec![],
+            temporal_formats: HashMap::new(),
+            strict_temporal_parsing: true,
         }
     }
 
     /// Read the next batch of rows
+    ///
+    /// Each batch has `batch_size` rows, except for the last batch, which holds the
+    /// remaining rows.
     pub fn next(&mut self) -> Result<Option<RecordBatch>> {
+        let batch_size =
+            ::std::cmp::min(self.batch_size, self.end.saturating_sub(self.line_number));
         // read a batch of rows into memory
-        let mut rows: Vec<StringRecord> = Vec::with_capacity(self.batch_size);
-        for i in 0..self.batch_size {
+        let mut rows: Vec<StringRecord> = Vec::with_capacity(batch_size);
+        for i in 0..batch_size {
             match self.record_iter.next() {
                 Some(Ok(r)) => {
                     rows.push(r);
@@ -320,11 +359,68 @@ impl<R: Read> Reader<R> {
                     &DataType::Float64 => {
                         self.build_primitive_array::<Float64Type>(rows, i)
                     }
+                    &DataType::Date32(DateUnit::Day) => self
+                        .build_temporal_array::<Date32Type, _>(
+                            rows,
+                            i,
+                            DEFAULT_DATE_FORMAT,
+                            |s, format| parse_days(s, format).map(|d| d as i32),
+                        ),
+                    &DataType::Date64(DateUnit::Millisecond) => self
+                        .build_temporal_array::<Date64Type, _>(
+                            rows,
+                            i,
+                            DEFAULT_DATE_FORMAT,
+                            |s, format| {
+                                parse_days(s, format).map(|d| d * MILLISECONDS_IN_DAY)
+                            },
+                        ),
+                    &DataType::Timestamp(TimeUnit::Second) => self
+                        .build_temporal_array::<TimestampSecondType, _>(
+                            rows,
+                            i,
+                            DEFAULT_TIMESTAMP_FORMAT,
+                            |s, format| parse_timestamp(s, format).map(|t| t.timestamp()),
+                        ),
+                    &DataType::Timestamp(TimeUnit::Millisecond) => {
+                        self.build_temporal_array::<TimestampMillisecondType, _>(
+                            rows,
+                            i,
+                            DEFAULT_TIMESTAMP_FORMAT,
+                            |s, format| {
+                                parse_timestamp(s, format).map(|t| t.timestamp_millis())
+                            },
+                        )
+                    }
+                    &DataType::Timestamp(TimeUnit::Microsecond) => {
+                        self.build_temporal_array::<TimestampMicrosecondType, _>(
+                            rows,
+                            i,
+                            DEFAULT_TIMESTAMP_FORMAT,
+                            |s, format| {
+                                parse_timestamp(s, format).map(|t| {
+                                    t.timestamp() * 1_000_000
+                                        + t.timestamp_subsec_micros() as i64
+                                })
+                            },
+                        )
+                    }
+                    &DataType::Timestamp(TimeUnit::Nanosecond) => self
+                        .build_temporal_array::<TimestampNanosecondType, _>(
+                        rows,
+                        i,
+                        DEFAULT_TIMESTAMP_FORMAT,
+                        |s, format| {
+                            parse_timestamp(s, format).map(|t| t.timestamp_nanos())
+                        },
+                    ),
                     &DataType::Utf8 => {
                         let mut builder = BinaryBuilder::new(rows.len());
                         for row_index in 0..rows.len() {
                             match rows[row_index].get(*i) {
-                                Some(s) => builder.append_string(s).unwrap(),
+                                Some(s) if !self.is_null_token(s) => {
+                                    builder.append_string(s).unwrap()
+                                }
                                 _ => builder.append(false).unwrap(),
                             }
                         }
@@ -368,7 +464,7 @@ impl<R: Read> Reader<R> {
             *self.schema.field(*col_idx).data_type() == DataType::Boolean;
         for row_index in 0..rows.len() {
             match rows[row_index].get(*col_idx) {
-                Some(s) if s.len() > 0 => {
+                Some(s) if s.len() > 0 && !self.is_null_token(s) => {
                     let t = if is_boolean_type {
                         s.to_lowercase().parse::<T::Native>()
                     } else {
@@ -391,6 +487,79 @@ impl<R: Read> Reader<R> {
         }
         Ok(Arc::new(builder.finish()))
     }
+
+    /// Builds a temporal array from the values of column `col_idx`, parsing each value
+    /// with `parse` and the format of the column, or `default_format` if the column has
+    /// no format of its own
+    fn build_temporal_array<T, F>(
+        &self,
+        rows: &[StringRecord],
+        col_idx: &usize,
+        default_format: &str,
+        parse: F,
+    ) -> Result<ArrayRef>
+    where
+        T: ArrowPrimitiveType,
+        F: Fn(&str, &str) -> Option<T::Native>,
+    {
+        let field = self.schema.field(*col_idx);
+        let format = self
+            .temporal_formats
+            .get(field.name())
+            .map(|f| f.as_str())
+            .unwrap_or(default_format);
+        let mut builder = PrimitiveBuilder::<T>::new(rows.len());
+        for row_index in 0..rows.len() {
+            match rows[row_index].get(*col_idx) {
+                Some(s) if s.len() > 0 && !self.is_null_token(s) => {
+                    match parse(s, format) {
+                        Some(v) => builder.append_value(v)?,
+                        None if self.strict_temporal_parsing => {
+                            return Err(ArrowError::ParseError(format!(
+                                "Error while parsing value {} with format {} at line {}",
+                                s,
+                                format,
+                                self.line_number + row_index
+                            )));
+                        }
+                        None => builder.append_null()?,
+                    }
+                }
+                _ => builder.append_null()?,
+            }
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+
+    /// Returns true if `value` is one of the configured null tokens
+    fn is_null_token(&self, value: &str) -> bool {
+        self.null_tokens.iter().any(|t| t == value)
+    }
+}
+
+impl<R: Read> RecordBatchReader for Reader<R> {
+    fn schema(&mut self) -> SchemaRef {
+        Reader::schema(self)
+    }
+
+    fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        self.next()
+    }
+}
+
+/// Number of milliseconds in a day
+const MILLISECONDS_IN_DAY: i64 = 86_400_000;
+
+/// Parses a date with `format` into the number of days since the UNIX epoch
+fn parse_days(s: &str, format: &str) -> Option<i64> {
+    NaiveDate::parse_from_str(s, format).ok().map(|date| {
+        date.signed_duration_since(NaiveDate::from_ymd(1970, 1, 1))
+            .num_days()
+    })
+}
+
+fn parse_timestamp(s: &str, format: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, format).ok()
 }
 
 /// CSV file reader builder
@@ -417,6 +586,14 @@ pub struct ReaderBuilder {
     batch_size: usize,
     /// Optional projection for which columns to load (zero-based column indices)
     projection: Option<Vec<usize>>,
+    /// Optional range of records to read, excluding the header
+    bounds: Option<(usize, usize)>,
+    /// Values that are read as nulls, in addition to empty values of non-string types
+    null_tokens: Vec<String>,
+    /// Parse formats of temporal columns, by field name
+    temporal_formats: HashMap<String, String>,
+    /// Whether a temporal value that does not match its format is an error or a null
+    strict_temporal_parsing: bool,
 }
 
 impl Default for ReaderBuilder {
@@ -428,6 +605,10 @@ impl Default for ReaderBuilder {
             max_records: None,
             batch_size: 1024,
             projection: None,
+            bounds: None,
+            null_tokens: vec![],
+            temporal_formats: HashMap::new(),
+            strict_temporal_parsing: true,
         }
     }
 }
@@ -498,6 +679,45 @@ impl ReaderBuilder {
         self
     }
 
+    /// Set the reader to only read the records from `start` up to, but excluding, `end`
+    ///
+    /// Records are counted from zero and exclude the header, which is skipped whenever
+    /// the file has headers. This allows splitting a file by line range across several
+    /// readers, e.g. to read it in parallel. The reader still has to parse the records
+    /// before `start` to find where they end, but does not convert them.
+    pub fn with_bounds(mut self, start: usize, end: usize) -> Self {
+        self.bounds = Some((start, end));
+        self
+    }
+
+    /// Set values that are read as nulls, e.g. `&["NA", "NULL", "\\N"]`
+    ///
+    /// A value is null if it is exactly equal to one of the tokens, in columns of any
+    /// type. Empty values are always nulls in non-string columns, and are also nulls in
+    /// string columns if `""` is one of the tokens. Null tokens are also treated as
+    /// nulls when inferring the schema.
+    pub fn with_null_tokens(mut self, null_tokens: &[&str]) -> Self {
+        self.null_tokens = null_tokens.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Set the parse formats of temporal columns, by field name, e.g. `"%m/%d/%Y"`
+    ///
+    /// The formats use the `strftime` syntax of `chrono`. Columns without a format are
+    /// parsed as `%Y-%m-%d` for dates, and as `%Y-%m-%dT%H:%M:%S%.f` for timestamps.
+    /// `Date32(Day)`, `Date64(Millisecond)` and `Timestamp` columns are supported.
+    pub fn with_temporal_formats(mut self, formats: HashMap<String, String>) -> Self {
+        self.temporal_formats = formats;
+        self
+    }
+
+    /// Set whether a temporal value that does not match the format of its column is an
+    /// error, which is the default, or is read as a null
+    pub fn with_strict_temporal_parsing(mut self, strict: bool) -> Self {
+        self.strict_temporal_parsing = strict;
+        self
+    }
+
     /// Create a new `Reader` from the `ReaderBuilder`
     pub fn build<R: Read + Seek>(self, reader: R) -> Result<Reader<R>> {
         // check if schema should be inferred
@@ -510,6 +730,7 @@ impl ReaderBuilder {
                     self.delimiter.unwrap_or(b','),
                     self.max_records,
                     self.has_headers,
+                    &self.null_tokens,
                 )?;
 
                 Arc::new(inferred_schema)
@@ -519,13 +740,35 @@ impl ReaderBuilder {
             .delimiter(self.delimiter.unwrap_or(b','))
             .has_headers(self.has_headers)
             .from_reader(buf_reader);
-        let record_iter = csv_reader.into_records();
+        let mut record_iter = csv_reader.into_records();
+        let mut line_number = if self.has_headers { 1 } else { 0 };
+        let mut end = usize::max_value();
+        if let Some((start, bounds_end)) = self.bounds {
+            end = line_number + ::std::cmp::max(start, bounds_end);
+            // skip the records before the start of the bounds
+            for _ in 0..start {
+                match record_iter.next() {
+                    Some(Ok(_)) => line_number += 1,
+                    Some(Err(e)) => {
+                        return Err(ArrowError::ParseError(format!(
+                            "Error parsing line {}: {:?}",
+                            line_number, e
+                        )));
+                    }
+                    None => break,
+                }
+            }
+        }
         Ok(Reader {
             schema,
             projection: self.projection.clone(),
             record_iter,
             batch_size: self.batch_size,
-            line_number: if self.has_headers { 1 } else { 0 },
+            line_number,
+            end,
+            null_tokens: self.null_tokens,
+            temporal_formats: self.temporal_formats,
+            strict_temporal_parsing: self.strict_temporal_parsing,
         })
     }
 }
@@ -534,10 +777,12 @@ impl ReaderBuilder {
 mod tests {
     use super::*;
 
+    use std::convert::TryFrom;
     use std::fs::File;
     use std::io::Cursor;
 
     use crate::array::*;
+    use crate::compute::concat_batches;
     use crate::datatypes::Field;
 
     #[test]
@@ -576,6 +821,32 @@ mod tests {
         assert_eq!("Aberdeen, Aberdeen City, UK", city_name);
     }
 
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_csv_gzip_compressed() {
+        use crate::util::compression::{CompressedReader, CompressionCodec};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("city", DataType::Utf8, false),
+            Field::new("lat", DataType::Float64, false),
+            Field::new("lng", DataType::Float64, false),
+        ]));
+
+        let file = File::open("test/data/uk_cities.csv.gz").unwrap();
+        let input = CompressedReader::new(file, CompressionCodec::Gzip).unwrap();
+        let mut csv = Reader::new(input, schema.clone(), false, 1024, None);
+        let batch = csv.next().unwrap().unwrap();
+
+        let file = File::open("test/data/uk_cities.csv").unwrap();
+        let mut csv = Reader::new(file, schema, false, 1024, None);
+        let expected = csv.next().unwrap().unwrap();
+
+        assert_eq!(expected.num_rows(), batch.num_rows());
+        for i in 0..expected.num_columns() {
+            assert!(expected.column(i).equals(batch.column(i).as_ref()));
+        }
+    }
+
     #[test]
     fn test_csv_from_buf_reader() {
         let schema = Schema::new(vec![
@@ -602,6 +873,157 @@ mod tests {
         assert_eq!(3, batch.num_columns());
     }
 
+    #[test]
+    fn test_csv_with_bounds() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("city", DataType::Utf8, false),
+            Field::new("lat", DataType::Float64, false),
+            Field::new("lng", DataType::Float64, false),
+        ]));
+        let read_all = |builder: ReaderBuilder| {
+            let file = File::open("test/data/uk_cities_with_headers.csv").unwrap();
+            let mut csv = builder
+                .with_schema(schema.clone())
+                .has_headers(true)
+                .with_batch_size(5)
+                .build(file)
+                .unwrap();
+            let mut batches = vec![];
+            while let Some(batch) = csv.next().unwrap() {
+                batches.push(batch);
+            }
+            batches
+        };
+
+        let expected = read_all(ReaderBuilder::new());
+        let first = read_all(ReaderBuilder::new().with_bounds(0, 12));
+        let second = read_all(ReaderBuilder::new().with_bounds(12, 100));
+
+        // batches have exactly `batch_size` rows, except for the last one
+        let num_rows = |batches: &[RecordBatch]| -> Vec<usize> {
+            batches.iter().map(|b| b.num_rows()).collect()
+        };
+        assert_eq!(vec![5, 5, 2], num_rows(&first));
+        assert_eq!(vec![5, 5, 5, 5, 5], num_rows(&second));
+
+        let expected = concat_batches(&expected).unwrap();
+        let mut batches = first;
+        batches.extend(second);
+        let actual = concat_batches(&batches).unwrap();
+        assert_eq!(37, actual.num_rows());
+        for i in 0..expected.num_columns() {
+            assert!(expected.column(i).equals(actual.column(i).as_ref()));
+        }
+
+        let empty = read_all(ReaderBuilder::new().with_bounds(40, 50));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_csv_with_null_tokens() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]));
+        let file = File::open("test/data/null_tokens.csv").unwrap();
+        let mut csv = ReaderBuilder::new()
+            .with_schema(schema.clone())
+            .has_headers(true)
+            .with_null_tokens(&["NA", "\\N"])
+            .build(file)
+            .unwrap();
+        let batch = csv.next().unwrap().unwrap();
+
+        let expected_ids = Int64Array::from(vec![Some(1), None, Some(3), None, Some(5)]);
+        assert!(batch.column(0).equals(&expected_ids));
+        let expected_names =
+            BinaryArray::try_from(vec![Some("alice"), Some("bob"), None, None, Some("")])
+                .unwrap();
+        assert!(batch.column(1).equals(&expected_names));
+        let expected_scores =
+            Float64Array::from(vec![Some(1.5), None, Some(2.5), None, Some(3.0)]);
+        assert!(batch.column(2).equals(&expected_scores));
+
+        // the tokens are not parsed as values when inferring the schema
+        let file = File::open("test/data/null_tokens.csv").unwrap();
+        let csv = ReaderBuilder::new()
+            .has_headers(true)
+            .infer_schema(None)
+            .with_null_tokens(&["NA", "\\N"])
+            .build(file)
+            .unwrap();
+        assert_eq!(schema, csv.schema());
+
+        // without null tokens, they fail to parse as numbers
+        let file = File::open("test/data/null_tokens.csv").unwrap();
+        let mut csv = ReaderBuilder::new()
+            .with_schema(schema)
+            .has_headers(true)
+            .build(file)
+            .unwrap();
+        assert!(csv.next().is_err());
+    }
+
+    #[test]
+    fn test_csv_with_temporal_formats() {
+        let data = "id,day\n1,05-Mar-2019\n2,31-Dec-1969\n3,\n4,2019-03-05\n";
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("day", DataType::Date32(DateUnit::Day), true),
+        ]));
+        let mut formats = HashMap::new();
+        formats.insert("day".to_string(), "%d-%b-%Y".to_string());
+        let builder = || {
+            ReaderBuilder::new()
+                .with_schema(schema.clone())
+                .has_headers(true)
+                .with_temporal_formats(formats.clone())
+        };
+
+        // the last value does not match the format
+        let mut csv = builder().build(Cursor::new(data)).unwrap();
+        assert!(csv.next().is_err());
+
+        let mut csv = builder()
+            .with_strict_temporal_parsing(false)
+            .build(Cursor::new(data))
+            .unwrap();
+        let batch = csv.next().unwrap().unwrap();
+        let days = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap();
+        assert_eq!(4, days.len());
+        assert_eq!(17960, days.value(0));
+        assert_eq!(-1, days.value(1));
+        assert!(days.is_null(2));
+        assert!(days.is_null(3));
+    }
+
+    #[test]
+    fn test_csv_with_default_timestamp_format() {
+        let data = "2019-03-05T10:20:30.5\n1970-01-01T00:00:01\n";
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Millisecond),
+            false,
+        )]));
+        let mut csv = ReaderBuilder::new()
+            .with_schema(schema)
+            .build(Cursor::new(data))
+            .unwrap();
+        let batch = csv.next().unwrap().unwrap();
+        let ts = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(1_551_781_230_500, ts.value(0));
+        assert_eq!(1_000, ts.value(1));
+    }
+
     #[test]
     fn test_csv_with_schema_inference() {
         let file = File::open("test/data/uk_cities_with_headers.csv").unwrap();
@@ -639,6 +1061,55 @@ mod tests {
         assert_eq!("Aberdeen, Aberdeen City, UK", city_name);
     }
 
+    #[test]
+    fn test_infer_schema() {
+        let mut file = File::open("test/data/uk_cities_with_headers.csv").unwrap();
+        let schema = infer_schema(&mut file, Some(10)).unwrap();
+        let expected_schema = Schema::new(vec![
+            Field::new("city", DataType::Utf8, false),
+            Field::new("lat", DataType::Float64, false),
+            Field::new("lng", DataType::Float64, false),
+        ]);
+        assert_eq!(expected_schema, schema);
+
+        // the file can be read from its start with the inferred schema
+        let mut csv: Box<RecordBatchReader> =
+            Box::new(Reader::new(file, Arc::new(schema), true, 1024, None));
+        assert_eq!(3, csv.schema().fields().len());
+        let batch = csv.next_batch().unwrap().unwrap();
+        assert_eq!(37, batch.num_rows());
+        assert!(csv.next_batch().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_infer_schema_narrowest_type() {
+        let data = "a,b,c,d\n1,1,true,x\n2,2.5,false,\n,3,true,1\n";
+        let schema = infer_schema(&mut Cursor::new(data), None).unwrap();
+        let expected_schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Float64, false),
+            Field::new("c", DataType::Boolean, false),
+            Field::new("d", DataType::Utf8, true),
+        ]);
+        assert_eq!(expected_schema, schema);
+    }
+
+    #[test]
+    fn test_wrong_column_count() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let data = "a,b\n1,2\n3\n";
+        let mut csv = Reader::new(Cursor::new(data), Arc::new(schema), true, 1024, None);
+        match csv.next() {
+            Err(ArrowError::ParseError(message)) => {
+                assert!(message.starts_with("Error parsing line 2:"), message)
+            }
+            _ => panic!("should have failed"),
+        }
+    }
+
     #[test]
     fn test_csv_with_schema_inference_no_headers() {
         let file = File::open("test/data/uk_cities.csv").unwrap();
@@ -705,8 +1176,8 @@ mod tests {
     fn test_nulls() {
         let schema = Schema::new(vec![
             Field::new("c_int", DataType::UInt64, false),
-            Field::new("c_float", DataType::Float32, false),
-            Field::new("c_string", DataType::Utf8, false),
+            Field::new("c_float", DataType::Float32, true),
+            Field::new("c_string", DataType::Utf8, true),
         ]);
 
         let file = File::open("test/data/null_test.csv").unwrap();
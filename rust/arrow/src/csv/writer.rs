@@ -149,7 +149,7 @@ where
                     c.value(row_index).to_string()
                 }
                 DataType::Utf8 => {
-                    let c = col.as_any().downcast_ref::<BinaryArray>().unwrap();
+                    let c = as_binary_array(col);
                     String::from_utf8(c.value(row_index).to_vec())?
                 }
                 DataType::Date32(DateUnit::Day) => {
@@ -465,7 +465,7 @@ sed do eiusmod tempor,-556132.25,1,,2019-04-18T02:45:55.555000000,23:46:03
             Field::new("c6", DataType::Time32(TimeUnit::Second), false),
         ]);
 
-        let c1 = BinaryArray::from(vec![
+        let c1 = StringArray::from(vec![
             "Lorem ipsum dolor sit amet",
             "consectetur adipiscing elit",
             "sed do eiusmod tempor",
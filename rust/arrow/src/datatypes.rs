@@ -21,6 +21,8 @@
 //! information regarding data-types and memory layouts see
 //! [here](https://arrow.apache.org/docs/memory_layout.html).
 
+use std::cmp::max;
+use std::convert::TryFrom;
 use std::fmt;
 use std::mem::size_of;
 #[cfg(feature = "simd")]
@@ -44,11 +46,19 @@ use std::sync::Arc;
 ///  - `List<T>`
 ///  - `Struct<T, U, V, ...>`
 ///
+/// `Dictionary(K, V)` represents values of type `V` that are encoded as integer keys of
+/// type `K` into a dictionary of distinct values.
+///
+/// `Decimal(precision, scale)` represents exact decimal numbers with `precision` digits
+/// in total, `scale` of which are after the decimal point.
+///
 /// Nested types can themselves be nested within other arrays.
 /// For more information on these types please see
 /// [here](https://arrow.apache.org/docs/memory_layout.html).
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum DataType {
+    /// A type whose every value is null, which needs no memory for its values
+    Null,
     Boolean,
     Int8,
     Int16,
@@ -68,11 +78,19 @@ pub enum DataType {
     Time64(TimeUnit),
     Interval(IntervalUnit),
     Utf8,
+    Binary,
+    FixedSizeBinary(i32),
     List(Box<DataType>),
+    LargeList(Box<DataType>),
     FixedSizeList((Box<DataType>, i32)),
     Struct(Vec<Field>),
+    Dictionary(Box<DataType>, Box<DataType>),
+    Decimal(usize, usize),
 }
 
+/// The maximum precision of a `DataType::Decimal`, which is stored in 128 bits
+pub const DECIMAL_MAX_PRECISION: usize = 38;
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum DateUnit {
     Day,
@@ -101,12 +119,24 @@ pub struct Field {
     name: String,
     data_type: DataType,
     nullable: bool,
+    #[serde(default)]
+    dict_is_ordered: bool,
 }
 
 pub trait ArrowNativeType:
-    fmt::Debug + Send + Sync + Copy + PartialOrd + FromStr + 'static
+    fmt::Debug + Send + Sync + Copy + PartialOrd + FromStr + NumCast + 'static
 {
     fn into_json_value(self) -> Option<Value>;
+
+    /// Convert native type from usize.
+    fn from_usize(_: usize) -> Option<Self> {
+        None
+    }
+
+    /// Convert native type to usize.
+    fn to_usize(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Trait indicating a primitive fixed-width type (bool, ints and floats).
@@ -136,48 +166,112 @@ impl ArrowNativeType for i8 {
     fn into_json_value(self) -> Option<Value> {
         Some(VNumber(Number::from(self)))
     }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        num::FromPrimitive::from_usize(v)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        num::ToPrimitive::to_usize(self)
+    }
 }
 
 impl ArrowNativeType for i16 {
     fn into_json_value(self) -> Option<Value> {
         Some(VNumber(Number::from(self)))
     }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        num::FromPrimitive::from_usize(v)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        num::ToPrimitive::to_usize(self)
+    }
 }
 
 impl ArrowNativeType for i32 {
     fn into_json_value(self) -> Option<Value> {
         Some(VNumber(Number::from(self)))
     }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        num::FromPrimitive::from_usize(v)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        num::ToPrimitive::to_usize(self)
+    }
 }
 
 impl ArrowNativeType for i64 {
     fn into_json_value(self) -> Option<Value> {
         Some(VNumber(Number::from(self)))
     }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        num::FromPrimitive::from_usize(v)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        num::ToPrimitive::to_usize(self)
+    }
 }
 
 impl ArrowNativeType for u8 {
     fn into_json_value(self) -> Option<Value> {
         Some(VNumber(Number::from(self)))
     }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        num::FromPrimitive::from_usize(v)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        num::ToPrimitive::to_usize(self)
+    }
 }
 
 impl ArrowNativeType for u16 {
     fn into_json_value(self) -> Option<Value> {
         Some(VNumber(Number::from(self)))
     }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        num::FromPrimitive::from_usize(v)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        num::ToPrimitive::to_usize(self)
+    }
 }
 
 impl ArrowNativeType for u32 {
     fn into_json_value(self) -> Option<Value> {
         Some(VNumber(Number::from(self)))
     }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        num::FromPrimitive::from_usize(v)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        num::ToPrimitive::to_usize(self)
+    }
 }
 
 impl ArrowNativeType for u64 {
     fn into_json_value(self) -> Option<Value> {
         Some(VNumber(Number::from(self)))
     }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        num::FromPrimitive::from_usize(v)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        num::ToPrimitive::to_usize(self)
+    }
 }
 
 impl ArrowNativeType for f32 {
@@ -192,6 +286,86 @@ impl ArrowNativeType for f64 {
     }
 }
 
+/// Checked conversions of native values to and from `f64` and `i64`.
+///
+/// Unlike `as` casts, which silently truncate or saturate, these return `None` if the
+/// value can not be represented in the target type.
+///
+/// Every `ArrowNativeType` implements it, so kernels can convert the values of any
+/// primitive array without further bounds.
+pub trait NumCast: Sized {
+    /// Converts an `f64` to this type, returning `None` if it is out of range.
+    fn from_f64(v: f64) -> Option<Self>;
+
+    /// Converts this value to an `f64`.
+    fn to_f64(&self) -> Option<f64>;
+
+    /// Converts an `i64` to this type, returning `None` if it is out of range.
+    fn from_i64(v: i64) -> Option<Self>;
+
+    /// Converts this value to an `i64`, returning `None` if it is out of range.
+    fn to_i64(&self) -> Option<i64>;
+}
+
+macro_rules! make_num_cast {
+    ($native_ty:ty) => {
+        impl NumCast for $native_ty {
+            fn from_f64(v: f64) -> Option<Self> {
+                num::FromPrimitive::from_f64(v)
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                num::ToPrimitive::to_f64(self)
+            }
+
+            fn from_i64(v: i64) -> Option<Self> {
+                num::FromPrimitive::from_i64(v)
+            }
+
+            fn to_i64(&self) -> Option<i64> {
+                num::ToPrimitive::to_i64(self)
+            }
+        }
+    };
+}
+
+make_num_cast!(i8);
+make_num_cast!(i16);
+make_num_cast!(i32);
+make_num_cast!(i64);
+make_num_cast!(u8);
+make_num_cast!(u16);
+make_num_cast!(u32);
+make_num_cast!(u64);
+make_num_cast!(f32);
+make_num_cast!(f64);
+
+/// Booleans convert to and from `0` and `1`; any other value is out of range.
+impl NumCast for bool {
+    fn from_f64(v: f64) -> Option<Self> {
+        if v.fract() != 0.0 {
+            return None;
+        }
+        num::ToPrimitive::to_i64(&v).and_then(Self::from_i64)
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(if *self { 1.0 } else { 0.0 })
+    }
+
+    fn from_i64(v: i64) -> Option<Self> {
+        match v {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        Some(*self as i64)
+    }
+}
+
 macro_rules! make_type {
     ($name:ident, $native_ty:ty, $data_ty:expr, $bit_width:expr, $default_val:expr) => {
         pub struct $name {}
@@ -509,6 +683,47 @@ impl ArrowTemporalType for Time64NanosecondType {}
 impl ArrowTemporalType for IntervalYearMonthType {}
 impl ArrowTemporalType for IntervalDayTimeType {}
 
+/// A subtype of temporal type that represents timestamps in a fixed time unit.
+pub trait ArrowTimestampType:
+    ArrowTemporalType + ArrowPrimitiveType<Native = i64>
+{
+    /// Returns the time unit of the timestamps.
+    fn get_time_unit() -> TimeUnit;
+}
+
+impl ArrowTimestampType for TimestampSecondType {
+    fn get_time_unit() -> TimeUnit {
+        TimeUnit::Second
+    }
+}
+impl ArrowTimestampType for TimestampMillisecondType {
+    fn get_time_unit() -> TimeUnit {
+        TimeUnit::Millisecond
+    }
+}
+impl ArrowTimestampType for TimestampMicrosecondType {
+    fn get_time_unit() -> TimeUnit {
+        TimeUnit::Microsecond
+    }
+}
+impl ArrowTimestampType for TimestampNanosecondType {
+    fn get_time_unit() -> TimeUnit {
+        TimeUnit::Nanosecond
+    }
+}
+
+/// A subtype of primitive type that represents the keys of a dictionary array.
+pub trait ArrowDictionaryKeyType: ArrowNumericType {}
+
+impl ArrowDictionaryKeyType for Int8Type {}
+impl ArrowDictionaryKeyType for Int16Type {}
+impl ArrowDictionaryKeyType for Int32Type {}
+impl ArrowDictionaryKeyType for Int64Type {}
+impl ArrowDictionaryKeyType for UInt8Type {}
+impl ArrowDictionaryKeyType for UInt16Type {}
+impl ArrowDictionaryKeyType for UInt32Type {}
+impl ArrowDictionaryKeyType for UInt64Type {}
+
 /// Allows conversion from supported Arrow types to a byte slice.
 pub trait ToByteSlice {
     /// Converts this instance into a byte slice
@@ -534,8 +749,10 @@ impl DataType {
     fn from(json: &Value) -> Result<DataType> {
         match *json {
             Value::Object(ref map) => match map.get("name") {
+                Some(s) if s == "null" => Ok(DataType::Null),
                 Some(s) if s == "bool" => Ok(DataType::Boolean),
                 Some(s) if s == "utf8" => Ok(DataType::Utf8),
+                Some(s) if s == "binary" => Ok(DataType::Binary),
                 Some(s) if s == "floatingpoint" => match map.get("precision") {
                     Some(p) if p == "HALF" => Ok(DataType::Float16),
                     Some(p) if p == "SINGLE" => Ok(DataType::Float32),
@@ -634,6 +851,10 @@ impl DataType {
                     // return a list with any type as its child isn't defined in the map
                     Ok(DataType::List(Box::new(DataType::Boolean)))
                 }
+                Some(s) if s == "largelist" => {
+                    // return a list with any type as its child isn't defined in the map
+                    Ok(DataType::LargeList(Box::new(DataType::Boolean)))
+                }
                 Some(s) if s == "fixedsizelist" => {
                     // return a list with any type as its child isn't defined in the map
                     if let Some(Value::Number(size)) = map.get("listSize") {
@@ -647,10 +868,43 @@ impl DataType {
                         )))
                     }
                 }
+                Some(s) if s == "fixedsizebinary" => match map.get("byteWidth") {
+                    Some(Value::Number(size)) => size
+                        .as_u64()
+                        .and_then(|size| i32::try_from(size).ok())
+                        .map(DataType::FixedSizeBinary)
+                        .ok_or_else(|| {
+                            ArrowError::ParseError(format!(
+                                "byteWidth {} of fixedsizebinary is invalid",
+                                size
+                            ))
+                        }),
+                    _ => Err(ArrowError::ParseError(format!(
+                        "Expecting a byteWidth for fixedsizebinary",
+                    ))),
+                },
                 Some(s) if s == "struct" => {
                     // return an empty `struct` type as its children aren't defined in the map
                     Ok(DataType::Struct(vec![]))
                 }
+                Some(s) if s == "decimal" => {
+                    match (map.get("precision"), map.get("scale")) {
+                        (Some(Value::Number(precision)), Some(Value::Number(scale))) => {
+                            match (precision.as_u64(), scale.as_u64()) {
+                                (Some(precision), Some(scale)) => Ok(DataType::Decimal(
+                                    precision as usize,
+                                    scale as usize,
+                                )),
+                                _ => Err(ArrowError::ParseError(
+                                    "decimal precision or scale invalid".to_string(),
+                                )),
+                            }
+                        }
+                        _ => Err(ArrowError::ParseError(
+                            "decimal precision or scale missing".to_string(),
+                        )),
+                    }
+                }
                 Some(other) => Err(ArrowError::ParseError(format!(
                     "invalid or unsupported type name: {} in {:?}",
                     other, json
@@ -663,9 +917,176 @@ impl DataType {
         }
     }
 
+    /// Returns the width in bytes of each value of a fixed-width primitive type, or
+    /// `None` for types whose values are not stored as fixed-width bytes (booleans are
+    /// stored as bits).
+    pub fn size_of(&self) -> Option<usize> {
+        match self {
+            DataType::Int8 | DataType::UInt8 => Some(1),
+            DataType::Int16 | DataType::UInt16 | DataType::Float16 => Some(2),
+            DataType::Int32
+            | DataType::UInt32
+            | DataType::Float32
+            | DataType::Date32(_)
+            | DataType::Time32(_) => Some(4),
+            DataType::Int64
+            | DataType::UInt64
+            | DataType::Float64
+            | DataType::Date64(_)
+            | DataType::Time64(_)
+            | DataType::Timestamp(_)
+            | DataType::Interval(_) => Some(8),
+            DataType::Decimal(_, _) => Some(16),
+            _ => None,
+        }
+    }
+
+    /// Returns the narrowest type that both `a` and `b` can be converted to, e.g. to
+    /// align the inputs of arithmetic or set operations.
+    ///
+    /// Numeric types are promoted as follows:
+    ///  - integers of the same signedness widen to the wider type, e.g. `Int32` and
+    ///    `Int64` to `Int64`
+    ///  - signed and unsigned integers promote to a signed type wide enough for both,
+    ///    e.g. `Int8` and `UInt8` to `Int16`, or to `Float64` if that would need more
+    ///    than 64 bits
+    ///  - floating point types widen to the wider type, and integers with floating
+    ///    point types promote to `Float64`
+    ///  - decimals widen to keep the largest scale and the most integer digits, e.g.
+    ///    `Decimal(10, 2)` and `Decimal(8, 4)` to `Decimal(12, 4)`. Integers are treated
+    ///    as decimals with a scale of 0 and enough digits for all their values, and
+    ///    decimals with floating point types promote to `Float64`.
+    ///
+    /// Other types only have a supertype with themselves. Returns a `ComputeError` if
+    /// the types have no common supertype, e.g. for `Int32` and `Utf8`, or if it would
+    /// be a decimal with more than `DECIMAL_MAX_PRECISION` digits.
+    pub fn try_supertype(a: &DataType, b: &DataType) -> Result<DataType> {
+        if a == b {
+            return Ok(a.clone());
+        }
+        let supertype = match (a, b) {
+            (DataType::Decimal(p1, s1), DataType::Decimal(p2, s2)) => {
+                let scale = max(*s1, *s2);
+                let precision =
+                    max(p1.saturating_sub(*s1), p2.saturating_sub(*s2)) + scale;
+                if precision > DECIMAL_MAX_PRECISION {
+                    return Err(ArrowError::ComputeError(format!(
+                        "The supertype of {:?} and {:?} would need a precision of {}, \
+                         more than the maximum of {}",
+                        a, b, precision, DECIMAL_MAX_PRECISION
+                    )));
+                }
+                Some(DataType::Decimal(precision, scale))
+            }
+            (DataType::Decimal(_, _), t) | (t, DataType::Decimal(_, _))
+                if t.is_floating() =>
+            {
+                Some(DataType::Float64)
+            }
+            (decimal @ DataType::Decimal(_, _), t)
+            | (t, decimal @ DataType::Decimal(_, _)) => {
+                match t.integer_decimal_digits() {
+                    Some(digits) => {
+                        return DataType::try_supertype(
+                            decimal,
+                            &DataType::Decimal(digits, 0),
+                        );
+                    }
+                    None => None,
+                }
+            }
+            (l, r) if l.is_floating() && r.is_floating() => {
+                Some(if l.size_of() >= r.size_of() { l } else { r }.clone())
+            }
+            (l, r) if l.is_floating() && r.is_integer() => Some(DataType::Float64),
+            (l, r) if l.is_integer() && r.is_floating() => Some(DataType::Float64),
+            (l, r) if l.is_integer() && r.is_integer() => Some(integer_supertype(l, r)),
+            _ => None,
+        };
+        supertype.ok_or_else(|| {
+            ArrowError::ComputeError(format!(
+                "Cannot find a common supertype of {:?} and {:?}",
+                a, b
+            ))
+        })
+    }
+
+    /// Returns true if this is a signed or unsigned integer type
+    fn is_integer(&self) -> bool {
+        self.is_signed_integer() || self.is_unsigned_integer()
+    }
+
+    fn is_signed_integer(&self) -> bool {
+        match self {
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => true,
+            _ => false,
+        }
+    }
+
+    fn is_unsigned_integer(&self) -> bool {
+        match self {
+            DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_floating(&self) -> bool {
+        match self {
+            DataType::Float16 | DataType::Float32 | DataType::Float64 => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the number of decimal digits needed to represent all values of an
+    /// integer type
+    fn integer_decimal_digits(&self) -> Option<usize> {
+        match self {
+            DataType::Int8 | DataType::UInt8 => Some(3),
+            DataType::Int16 | DataType::UInt16 => Some(5),
+            DataType::Int32 | DataType::UInt32 => Some(10),
+            DataType::Int64 => Some(19),
+            DataType::UInt64 => Some(20),
+            _ => None,
+        }
+    }
+
+    /// Compares two data types structurally, ignoring the names of nested fields.
+    ///
+    /// Struct fields are compared by position, using only their data types and
+    /// nullability. This is useful where name differences should not prevent an
+    /// operation, e.g. when the children of equivalent structs were named differently.
+    /// Use `==` to also compare the names.
+    pub fn equals_ignore_names(&self, other: &DataType) -> bool {
+        match (self, other) {
+            (DataType::List(left), DataType::List(right))
+            | (DataType::LargeList(left), DataType::LargeList(right)) => {
+                left.equals_ignore_names(right)
+            }
+            (
+                DataType::FixedSizeList((left, left_size)),
+                DataType::FixedSizeList((right, right_size)),
+            ) => left_size == right_size && left.equals_ignore_names(right),
+            (DataType::Struct(left), DataType::Struct(right)) => {
+                left.len() == right.len()
+                    && left.iter().zip(right.iter()).all(|(l, r)| {
+                        l.is_nullable() == r.is_nullable()
+                            && l.data_type().equals_ignore_names(r.data_type())
+                    })
+            }
+            (
+                DataType::Dictionary(left_key, left_value),
+                DataType::Dictionary(right_key, right_value),
+            ) => left_key == right_key && left_value.equals_ignore_names(right_value),
+            _ => self == other,
+        }
+    }
+
     /// Generate a JSON representation of the data type
     pub fn to_json(&self) -> Value {
         match self {
+            DataType::Null => json!({"name": "null"}),
             DataType::Boolean => json!({"name": "bool"}),
             DataType::Int8 => json!({"name": "int", "bitWidth": 8, "isSigned": true}),
             DataType::Int16 => json!({"name": "int", "bitWidth": 16, "isSigned": true}),
@@ -679,9 +1100,16 @@ impl DataType {
             DataType::Float32 => json!({"name": "floatingpoint", "precision": "SINGLE"}),
             DataType::Float64 => json!({"name": "floatingpoint", "precision": "DOUBLE"}),
             DataType::Utf8 => json!({"name": "utf8"}),
+            DataType::Binary => json!({"name": "binary"}),
+            DataType::FixedSizeBinary(byte_width) => {
+                json!({"name": "fixedsizebinary", "byteWidth": byte_width})
+            }
             DataType::Struct(_) => json!({"name": "struct"}),
             DataType::List(_) => json!({ "name": "list"}),
-            DataType::FixedSizeList((_, length)) => json!({"name":"fixedsizelist", "listSize": length}),
+            DataType::LargeList(_) => json!({ "name": "largelist"}),
+            DataType::FixedSizeList((_, length)) => {
+                json!({"name":"fixedsizelist", "listSize": length})
+            }
             DataType::Time32(unit) => {
                 json!({"name": "time", "bitWidth": 32, "unit": match unit {
                     TimeUnit::Second => "SECOND",
@@ -714,6 +1142,21 @@ impl DataType {
                 IntervalUnit::YearMonth => "YEAR_MONTH",
                 IntervalUnit::DayTime => "DAY_TIME",
             }}),
+            // dictionary encoding is a property of the field, the type is the value type
+            DataType::Dictionary(_, value_type) => value_type.to_json(),
+            DataType::Decimal(precision, scale) => {
+                json!({"name": "decimal", "precision": precision, "scale": scale})
+            }
+        }
+    }
+}
+
+impl DateUnit {
+    /// Returns the number of milliseconds in one unit of this date unit
+    pub fn to_millis_factor(&self) -> i64 {
+        match self {
+            DateUnit::Day => 86_400_000,
+            DateUnit::Millisecond => 1,
         }
     }
 }
@@ -725,6 +1168,23 @@ impl Field {
             name: name.to_string(),
             data_type,
             nullable,
+            dict_is_ordered: false,
+        }
+    }
+
+    /// Creates a new dictionary encoded field, whose dictionary values are ordered if
+    /// `dict_is_ordered` is true
+    pub fn new_dict(
+        name: &str,
+        data_type: DataType,
+        nullable: bool,
+        dict_is_ordered: bool,
+    ) -> Self {
+        Field {
+            name: name.to_string(),
+            data_type,
+            nullable,
+            dict_is_ordered,
         }
     }
 
@@ -743,6 +1203,12 @@ impl Field {
         self.nullable
     }
 
+    /// Indicates whether the dictionary values of this dictionary encoded `Field` are
+    /// ordered, which is always false for a field that is not dictionary encoded
+    pub fn dict_is_ordered(&self) -> bool {
+        self.dict_is_ordered
+    }
+
     /// Parse a `Field` definition from a JSON representation
     pub fn from(json: &Value) -> Result<Self> {
         match *json {
@@ -773,41 +1239,44 @@ impl Field {
                 };
                 // if data_type is a struct or list, get its children
                 let data_type = match data_type {
-                    DataType::List(_) | DataType::FixedSizeList(_) => {
-                        match map.get("children") {
-                            Some(Value::Array(values)) => {
-                                if values.len() != 1 {
-                                    return Err(ArrowError::ParseError(
+                    DataType::List(_)
+                    | DataType::LargeList(_)
+                    | DataType::FixedSizeList(_) => match map.get("children") {
+                        Some(Value::Array(values)) => {
+                            if values.len() != 1 {
+                                return Err(ArrowError::ParseError(
                                     "Field 'children' must have one element for a list data type".to_string(),
                                 ));
-                                }
-                                match data_type {
-                                    DataType::List(_) => DataType::List(Box::new(
-                                        Self::from(&values[0])?.data_type,
-                                    )),
-                                    DataType::FixedSizeList((_, int)) => {
-                                        DataType::FixedSizeList((
-                                            Box::new(Self::from(&values[0])?.data_type),
-                                            int,
-                                        ))
-                                    }
-                                    _ => unreachable!(
-                                        "Data type should be a list or fixedsizelist"
-                                    ),
-                                }
-                            }
-                            Some(_) => {
-                                return Err(ArrowError::ParseError(
-                                    "Field 'children' must be an array".to_string(),
-                                ))
                             }
-                            None => {
-                                return Err(ArrowError::ParseError(
-                                    "Field missing 'children' attribute".to_string(),
-                                ));
+                            match data_type {
+                                DataType::List(_) => DataType::List(Box::new(
+                                    Self::from(&values[0])?.data_type,
+                                )),
+                                DataType::LargeList(_) => DataType::LargeList(Box::new(
+                                    Self::from(&values[0])?.data_type,
+                                )),
+                                DataType::FixedSizeList((_, int)) => {
+                                    DataType::FixedSizeList((
+                                        Box::new(Self::from(&values[0])?.data_type),
+                                        int,
+                                    ))
+                                }
+                                _ => unreachable!(
+                                    "Data type should be a list or fixedsizelist"
+                                ),
                             }
                         }
-                    }
+                        Some(_) => {
+                            return Err(ArrowError::ParseError(
+                                "Field 'children' must be an array".to_string(),
+                            ))
+                        }
+                        None => {
+                            return Err(ArrowError::ParseError(
+                                "Field missing 'children' attribute".to_string(),
+                            ));
+                        }
+                    },
                     DataType::Struct(mut fields) => match map.get("children") {
                         Some(Value::Array(values)) => {
                             let struct_fields: Result<Vec<Field>> =
@@ -828,10 +1297,50 @@ impl Field {
                     },
                     _ => data_type,
                 };
+                // if the field is dictionary encoded, its type is the value type and
+                // the key type is given by the dictionary's index type
+                let (data_type, dict_is_ordered) = match map.get("dictionary") {
+                    Some(Value::Object(dictionary)) => {
+                        let index_type = match dictionary.get("indexType") {
+                            Some(t) => DataType::from(t)?,
+                            _ => {
+                                return Err(ArrowError::ParseError(
+                                    "Field 'dictionary' missing 'indexType' attribute"
+                                        .to_string(),
+                                ));
+                            }
+                        };
+                        let is_ordered = match dictionary.get("isOrdered") {
+                            Some(&Value::Bool(b)) => b,
+                            None => false,
+                            _ => {
+                                return Err(ArrowError::ParseError(
+                                    "Field 'dictionary' 'isOrdered' attribute must be a \
+                                     boolean"
+                                        .to_string(),
+                                ));
+                            }
+                        };
+                        (
+                            DataType::Dictionary(
+                                Box::new(index_type),
+                                Box::new(data_type),
+                            ),
+                            is_ordered,
+                        )
+                    }
+                    Some(_) => {
+                        return Err(ArrowError::ParseError(
+                            "Field 'dictionary' must be an object".to_string(),
+                        ))
+                    }
+                    None => (data_type, false),
+                };
                 Ok(Field {
                     name,
                     nullable,
                     data_type,
+                    dict_is_ordered,
                 })
             }
             _ => Err(ArrowError::ParseError(
@@ -841,10 +1350,17 @@ impl Field {
     }
 
     /// Generate a JSON representation of the `Field`
+    ///
+    /// The children of nested types are emitted in the order of the nested fields, so
+    /// equal fields always produce identical JSON.
     pub fn to_json(&self) -> Value {
-        let children: Vec<Value> = match self.data_type() {
+        let value_type = match self.data_type() {
+            DataType::Dictionary(_, value_type) => value_type.as_ref(),
+            data_type => data_type,
+        };
+        let children: Vec<Value> = match value_type {
             DataType::Struct(fields) => fields.iter().map(|f| f.to_json()).collect(),
-            DataType::List(dtype) => {
+            DataType::List(dtype) | DataType::LargeList(dtype) => {
                 let item = Field::new("item", *dtype.clone(), self.nullable);
                 vec![item.to_json()]
             }
@@ -854,12 +1370,19 @@ impl Field {
             }
             _ => vec![],
         };
-        json!({
+        let mut json = json!({
             "name": self.name,
             "nullable": self.nullable,
             "type": self.data_type.to_json(),
             "children": children
-        })
+        });
+        if let DataType::Dictionary(index_type, _) = self.data_type() {
+            json["dictionary"] = json!({
+                "indexType": index_type.to_json(),
+                "isOrdered": self.dict_is_ordered
+            });
+        }
+        json
     }
 
     /// Converts to a `String` representation of the `Field`
@@ -932,6 +1455,18 @@ impl Schema {
         })
     }
 
+    /// Generate a JSON Schema (draft-07) document describing the rows of this schema
+    ///
+    /// Each row is described as an object with a property per field. Nested structs
+    /// are described as nested objects and lists as arrays, while temporal values are
+    /// described as strings with a `format` annotation (e.g. `date` or `date-time`).
+    /// Non-nullable fields are required and nullable ones also accept `null`.
+    pub fn to_json_schema(&self) -> Value {
+        let mut schema = object_json_schema(&self.fields);
+        schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+        schema
+    }
+
     /// Parse a `Schema` definition from a JSON representation
     pub fn from(json: &Value) -> Result<Self> {
         match *json {
@@ -953,6 +1488,100 @@ impl Schema {
     }
 }
 
+/// Returns the JSON Schema of an object with a property for each of `fields`
+fn object_json_schema(fields: &[Field]) -> Value {
+    let properties: serde_json::Map<String, Value> = fields
+        .iter()
+        .map(|field| {
+            let mut schema = data_type_json_schema(field.data_type());
+            if field.is_nullable() {
+                schema = nullable_json_schema(schema);
+            }
+            (field.name().clone(), schema)
+        })
+        .collect();
+    let required: Vec<Value> = fields
+        .iter()
+        .filter(|field| !field.is_nullable())
+        .map(|field| json!(field.name()))
+        .collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Returns the supertype of two different integer types
+fn integer_supertype(a: &DataType, b: &DataType) -> DataType {
+    let (a_width, b_width) = (a.size_of().unwrap(), b.size_of().unwrap());
+    if a.is_signed_integer() == b.is_signed_integer() {
+        return if a_width >= b_width { a } else { b }.clone();
+    }
+    let (signed_width, unsigned_width) = if a.is_signed_integer() {
+        (a_width, b_width)
+    } else {
+        (b_width, a_width)
+    };
+    // a signed type must be wider than an unsigned one to hold all its values
+    match max(signed_width, unsigned_width * 2) {
+        1 => DataType::Int8,
+        2 => DataType::Int16,
+        4 => DataType::Int32,
+        8 => DataType::Int64,
+        _ => DataType::Float64,
+    }
+}
+
+/// Returns the JSON Schema of a non-null value of `data_type`
+fn data_type_json_schema(data_type: &DataType) -> Value {
+    match data_type {
+        DataType::Null => json!({"type": "null"}),
+        DataType::Boolean => json!({"type": "boolean"}),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            json!({"type": "integer"})
+        }
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            json!({"type": "integer", "minimum": 0})
+        }
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => {
+            json!({"type": "number"})
+        }
+        DataType::Utf8 | DataType::Binary | DataType::FixedSizeBinary(_) => {
+            json!({"type": "string"})
+        }
+        DataType::Date32(_) | DataType::Date64(_) => {
+            json!({"type": "string", "format": "date"})
+        }
+        DataType::Time32(_) | DataType::Time64(_) => {
+            json!({"type": "string", "format": "time"})
+        }
+        DataType::Timestamp(_) => json!({"type": "string", "format": "date-time"}),
+        DataType::Interval(_) => json!({"type": "string", "format": "duration"}),
+        DataType::List(value_type) | DataType::LargeList(value_type) => json!({
+            "type": "array",
+            "items": nullable_json_schema(data_type_json_schema(value_type)),
+        }),
+        DataType::FixedSizeList((value_type, length)) => json!({
+            "type": "array",
+            "items": nullable_json_schema(data_type_json_schema(value_type)),
+            "minItems": length,
+            "maxItems": length,
+        }),
+        DataType::Struct(fields) => object_json_schema(fields),
+        DataType::Dictionary(_, value_type) => data_type_json_schema(value_type),
+        DataType::Decimal(_, _) => json!({"type": "number"}),
+    }
+}
+
+/// Extends a JSON Schema to also accept `null`
+fn nullable_json_schema(mut schema: Value) -> Value {
+    if let Some(value_type) = schema.get("type").cloned() {
+        schema["type"] = json!([value_type, "null"]);
+    }
+    schema
+}
+
 impl fmt::Display for Schema {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(
@@ -1141,6 +1770,34 @@ mod tests {
         assert_eq!(DataType::Utf8, dt);
     }
 
+    #[test]
+    fn parse_fixed_size_binary_from_json() {
+        let value = json!({"name": "fixedsizebinary", "byteWidth": 16});
+        assert_eq!(
+            DataType::FixedSizeBinary(16),
+            DataType::from(&value).unwrap()
+        );
+
+        for width in &[json!(-1), json!(1u64 << 32), json!(1.5), json!("16")] {
+            let value = json!({"name": "fixedsizebinary", "byteWidth": width});
+            assert!(DataType::from(&value).is_err());
+        }
+    }
+
+    #[test]
+    fn null_field_json_round_trip() {
+        let value = json!({
+            "name": "a",
+            "nullable": true,
+            "type": {"name": "null"},
+            "children": []
+        });
+        let field = Field::from(&value).unwrap();
+        assert_eq!(Field::new("a", DataType::Null, true), field);
+        assert_eq!(json!({"name": "null"}), field.data_type().to_json());
+        assert_eq!(field, Field::from(&field.to_json()).unwrap());
+    }
+
     #[test]
     fn parse_int32_from_json() {
         let json = "{\"name\": \"int\", \"isSigned\": true, \"bitWidth\": 32}";
@@ -1464,6 +2121,70 @@ mod tests {
         assert_eq!(schema, schema2);
     }
 
+    #[test]
+    fn struct_field_to_json_is_deterministic() {
+        let make_field = || {
+            Field::new(
+                "s",
+                DataType::Struct(vec![
+                    Field::new("z", DataType::Int32, false),
+                    Field::new("a", DataType::Utf8, true),
+                    Field::new("m", DataType::List(Box::new(DataType::Boolean)), true),
+                ]),
+                false,
+            )
+        };
+        let first = serde_json::to_string(&make_field().to_json()).unwrap();
+        let second = serde_json::to_string(&make_field().to_json()).unwrap();
+        assert_eq!(first, second);
+
+        let json = make_field().to_json();
+        let names: Vec<&str> = json["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|child| child["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(vec!["z", "a", "m"], names);
+    }
+
+    #[test]
+    fn dictionary_field_json_roundtrip() {
+        let field = Field::new(
+            "dict",
+            DataType::Dictionary(Box::new(DataType::Int16), Box::new(DataType::Utf8)),
+            true,
+        );
+        let json = field.to_json();
+        assert_eq!(json["type"], json!({"name": "utf8"}));
+        assert_eq!(
+            json["dictionary"]["indexType"],
+            json!({"name": "int", "bitWidth": 16, "isSigned": true})
+        );
+        assert_eq!(json["dictionary"]["isOrdered"], json!(false));
+        assert_eq!(field, Field::from(&json).unwrap());
+
+        let ordered = Field::new_dict("dict", field.data_type().clone(), true, true);
+        let json = ordered.to_json();
+        assert_eq!(json["dictionary"]["isOrdered"], json!(true));
+        let parsed = Field::from(&json).unwrap();
+        assert!(parsed.dict_is_ordered());
+        assert_eq!(ordered, parsed);
+        assert_ne!(field, parsed);
+    }
+
+    #[test]
+    fn large_list_field_json_roundtrip() {
+        let field = Field::new(
+            "large",
+            DataType::LargeList(Box::new(DataType::Int64)),
+            true,
+        );
+        let json = field.to_json();
+        assert_eq!(json["type"], json!({"name": "largelist"}));
+        assert_eq!(field, Field::from(&json).unwrap());
+    }
+
     #[test]
     fn create_schema_string() {
         let _person = Schema::new(vec![
@@ -1555,4 +2276,166 @@ mod tests {
         );
         assert_eq!(None, NAN.into_json_value());
     }
+
+    #[test]
+    fn num_cast_checked() {
+        assert_eq!(None, <i32 as NumCast>::from_f64(1e300));
+        assert_eq!(Some(-3), <i32 as NumCast>::from_f64(-3.0));
+        assert_eq!(None, <u8 as NumCast>::from_i64(-1));
+        assert_eq!(Some(255), <u8 as NumCast>::from_i64(255));
+        assert_eq!(None, NumCast::to_i64(&std::u64::MAX));
+        assert_eq!(Some(2.5), NumCast::to_f64(&2.5f32));
+        assert_eq!(Some(true), <bool as NumCast>::from_f64(1.0));
+        assert_eq!(None, <bool as NumCast>::from_f64(0.5));
+        assert_eq!(None, <bool as NumCast>::from_i64(2));
+        assert_eq!(Some(1), NumCast::to_i64(&true));
+
+        // available for any native type through `ArrowNativeType`
+        fn native_to_f64<T: ArrowPrimitiveType>(v: T::Native) -> Option<f64> {
+            v.to_f64()
+        }
+        assert_eq!(Some(3.0), native_to_f64::<UInt8Type>(3));
+        assert_eq!(Some(1.0), native_to_f64::<BooleanType>(true));
+    }
+
+    #[test]
+    fn schema_to_json_schema() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("scores", DataType::List(Box::new(DataType::Float64)), false),
+            Field::new(
+                "address",
+                DataType::Struct(vec![
+                    Field::new("street", DataType::Utf8, false),
+                    Field::new("since", DataType::Date32(DateUnit::Day), true),
+                ]),
+                true,
+            ),
+        ]);
+        let expected = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "name": {"type": ["string", "null"]},
+                "scores": {
+                    "type": "array",
+                    "items": {"type": ["number", "null"]}
+                },
+                "address": {
+                    "type": ["object", "null"],
+                    "properties": {
+                        "street": {"type": "string"},
+                        "since": {"type": ["string", "null"], "format": "date"}
+                    },
+                    "required": ["street"]
+                }
+            },
+            "required": ["id", "scores"]
+        });
+        assert_eq!(expected, schema.to_json_schema());
+    }
+
+    #[test]
+    fn data_type_equals_ignore_names() {
+        let a = DataType::Struct(vec![Field::new("a", DataType::Int32, true)]);
+        let b = DataType::Struct(vec![Field::new("b", DataType::Int32, true)]);
+        assert_ne!(a, b);
+        assert!(a.equals_ignore_names(&b));
+
+        let list_a = DataType::List(Box::new(a.clone()));
+        let list_b = DataType::List(Box::new(b));
+        assert!(list_a.equals_ignore_names(&list_b));
+
+        let c = DataType::Struct(vec![Field::new("a", DataType::Int32, false)]);
+        assert!(!a.equals_ignore_names(&c));
+        let d = DataType::Struct(vec![Field::new("a", DataType::Int64, true)]);
+        assert!(!a.equals_ignore_names(&d));
+    }
+
+    #[test]
+    fn data_type_supertype() {
+        let supertype = |a: DataType, b: DataType| {
+            let ab = DataType::try_supertype(&a, &b).unwrap();
+            assert_eq!(ab, DataType::try_supertype(&b, &a).unwrap());
+            ab
+        };
+        assert_eq!(DataType::Int64, supertype(DataType::Int32, DataType::Int64));
+        assert_eq!(
+            DataType::UInt16,
+            supertype(DataType::UInt8, DataType::UInt16)
+        );
+        assert_eq!(DataType::Int16, supertype(DataType::Int8, DataType::UInt8));
+        assert_eq!(
+            DataType::Int32,
+            supertype(DataType::Int32, DataType::UInt16)
+        );
+        assert_eq!(
+            DataType::Float64,
+            supertype(DataType::Int64, DataType::UInt64)
+        );
+        assert_eq!(
+            DataType::Float64,
+            supertype(DataType::Int8, DataType::Float32)
+        );
+        assert_eq!(
+            DataType::Float32,
+            supertype(DataType::Float16, DataType::Float32)
+        );
+        assert_eq!(DataType::Utf8, supertype(DataType::Utf8, DataType::Utf8));
+        assert_eq!(
+            DataType::Decimal(12, 4),
+            supertype(DataType::Decimal(10, 2), DataType::Decimal(8, 4))
+        );
+        assert_eq!(
+            DataType::Decimal(12, 2),
+            supertype(DataType::Decimal(5, 2), DataType::Int32)
+        );
+        assert_eq!(
+            DataType::Float64,
+            supertype(DataType::Decimal(5, 2), DataType::Float32)
+        );
+
+        assert!(DataType::try_supertype(&DataType::Int32, &DataType::Utf8).is_err());
+        assert!(DataType::try_supertype(&DataType::Utf8, &DataType::Float64).is_err());
+        assert!(DataType::try_supertype(&DataType::Boolean, &DataType::Int8).is_err());
+        assert!(DataType::try_supertype(
+            &DataType::Decimal(38, 0),
+            &DataType::Decimal(38, 10)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn decimal_json_roundtrip() {
+        let data_type = DataType::Decimal(12, 4);
+        let json = data_type.to_json();
+        assert_eq!(
+            json!({"name": "decimal", "precision": 12, "scale": 4}),
+            json
+        );
+        assert_eq!(data_type, DataType::from(&json).unwrap());
+    }
+
+    #[test]
+    fn floating_point_precision_json_roundtrip() {
+        for (precision, data_type) in &[
+            ("HALF", DataType::Float16),
+            ("SINGLE", DataType::Float32),
+            ("DOUBLE", DataType::Float64),
+        ] {
+            let json = json!({"name": "floatingpoint", "precision": precision});
+            assert_eq!(json, data_type.to_json());
+            assert_eq!(data_type, &DataType::from(&json).unwrap());
+
+            let field = Field::new("f", data_type.clone(), true);
+            assert_eq!(field, Field::from(&field.to_json()).unwrap());
+        }
+
+        let json = json!({"name": "floatingpoint", "precision": "QUAD"});
+        assert!(DataType::from(&json).is_err());
+        let json = json!({"name": "floatingpoint"});
+        assert!(DataType::from(&json).is_err());
+    }
 }
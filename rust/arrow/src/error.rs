@@ -32,6 +32,9 @@ pub enum ArrowError {
     IoError(String),
     InvalidArgumentError(String),
     ParquetError(String),
+    DictionaryKeyOverflowError,
+    CastError(String),
+    NotYetImplemented(String),
 }
 
 impl From<::std::io::Error> for ArrowError {
@@ -17,60 +17,381 @@
 
 //! Utilities for converting between IPC types and native Arrow types
 
-use crate::datatypes::DataType::*;
-use crate::datatypes::Schema;
+use crate::datatypes::{DataType, DateUnit, Field, IntervalUnit, Schema, TimeUnit};
+use crate::error::{ArrowError, Result};
 use crate::ipc;
 
-use flatbuffers::FlatBufferBuilder;
+use flatbuffers::{FlatBufferBuilder, UnionWIPOffset, WIPOffset};
 
 /// Serialize a schema in IPC format
-fn schema_to_fb(schema: &Schema) -> FlatBufferBuilder {
+pub fn schema_to_fb(schema: &Schema) -> Result<FlatBufferBuilder> {
     let mut fbb = FlatBufferBuilder::new();
+    let root = schema_to_fb_offset(&mut fbb, schema)?;
+    fbb.finish(root, None);
+    Ok(fbb)
+}
 
-    let mut fields = vec![];
-    for field in schema.fields() {
-        let fb_field_name = fbb.create_string(field.name().as_str());
-        let mut field_builder = ipc::FieldBuilder::new(&mut fbb);
-        field_builder.add_name(fb_field_name);
-        let ipc_type = match field.data_type() {
-            Boolean => ipc::Type::Bool,
-            UInt8 | UInt16 | UInt32 | UInt64 => ipc::Type::Int,
-            Int8 | Int16 | Int32 | Int64 => ipc::Type::Int,
-            Float32 | Float64 => ipc::Type::FloatingPoint,
-            Utf8 => ipc::Type::Utf8,
-            Date32(_) | Date64(_) => ipc::Type::Date,
-            Time32(_) | Time64(_) => ipc::Type::Time,
-            Timestamp(_) => ipc::Type::Timestamp,
-            _ => ipc::Type::NONE,
+/// Serialize a schema into `fbb`, returning the offset of the schema table so that it
+/// can be embedded in a message
+pub(crate) fn schema_to_fb_offset<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    schema: &Schema,
+) -> Result<WIPOffset<ipc::Schema<'a>>> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| field_to_fb(fbb, field))
+        .collect::<Result<Vec<_>>>()?;
+    let fb_field_list = fbb.create_vector(&fields);
+
+    let mut builder = ipc::SchemaBuilder::new(fbb);
+    builder.add_fields(fb_field_list);
+    Ok(builder.finish())
+}
+
+fn field_to_fb<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    field: &Field,
+) -> Result<WIPOffset<ipc::Field<'a>>> {
+    let fb_field_name = fbb.create_string(field.name().as_str());
+    let (ipc_type, fb_type, children) = data_type_to_fb(fbb, field.data_type())?;
+    let fb_children = fbb.create_vector(&children);
+
+    let mut field_builder = ipc::FieldBuilder::new(fbb);
+    field_builder.add_name(fb_field_name);
+    field_builder.add_type_type(ipc_type);
+    field_builder.add_type_(fb_type);
+    field_builder.add_nullable(field.is_nullable());
+    field_builder.add_children(fb_children);
+    Ok(field_builder.finish())
+}
+
+/// Serialize a data type, returning its IPC type, the offset of its type table and
+/// the child fields of nested types
+fn data_type_to_fb<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    data_type: &DataType,
+) -> Result<(
+    ipc::Type,
+    WIPOffset<UnionWIPOffset>,
+    Vec<WIPOffset<ipc::Field<'a>>>,
+)> {
+    use DataType::*;
+    let int = |fbb: &mut FlatBufferBuilder<'a>, bit_width, is_signed| {
+        let args = ipc::IntArgs {
+            bitWidth: bit_width,
+            is_signed,
         };
-        field_builder.add_type_type(ipc_type);
-        field_builder.add_nullable(field.is_nullable());
-        fields.push(field_builder.finish());
+        ipc::Int::create(fbb, &args).as_union_value()
+    };
+    let float = |fbb: &mut FlatBufferBuilder<'a>, precision| {
+        let args = ipc::FloatingPointArgs { precision };
+        ipc::FloatingPoint::create(fbb, &args).as_union_value()
+    };
+    let time = |fbb: &mut FlatBufferBuilder<'a>, unit: &TimeUnit, bit_width| {
+        let args = ipc::TimeArgs {
+            unit: time_unit_to_fb(unit),
+            bitWidth: bit_width,
+        };
+        ipc::Time::create(fbb, &args).as_union_value()
+    };
+    let (ipc_type, fb_type) = match data_type {
+        Boolean => (
+            ipc::Type::Bool,
+            ipc::Bool::create(fbb, &ipc::BoolArgs {}).as_union_value(),
+        ),
+        Int8 => (ipc::Type::Int, int(fbb, 8, true)),
+        Int16 => (ipc::Type::Int, int(fbb, 16, true)),
+        Int32 => (ipc::Type::Int, int(fbb, 32, true)),
+        Int64 => (ipc::Type::Int, int(fbb, 64, true)),
+        UInt8 => (ipc::Type::Int, int(fbb, 8, false)),
+        UInt16 => (ipc::Type::Int, int(fbb, 16, false)),
+        UInt32 => (ipc::Type::Int, int(fbb, 32, false)),
+        UInt64 => (ipc::Type::Int, int(fbb, 64, false)),
+        Float16 => (ipc::Type::FloatingPoint, float(fbb, ipc::Precision::HALF)),
+        Float32 => (ipc::Type::FloatingPoint, float(fbb, ipc::Precision::SINGLE)),
+        Float64 => (ipc::Type::FloatingPoint, float(fbb, ipc::Precision::DOUBLE)),
+        Utf8 => (
+            ipc::Type::Utf8,
+            ipc::Utf8::create(fbb, &ipc::Utf8Args {}).as_union_value(),
+        ),
+        Binary => (
+            ipc::Type::Binary,
+            ipc::Binary::create(fbb, &ipc::BinaryArgs {}).as_union_value(),
+        ),
+        Date32(unit) | Date64(unit) => {
+            let unit = match unit {
+                DateUnit::Day => ipc::DateUnit::DAY,
+                DateUnit::Millisecond => ipc::DateUnit::MILLISECOND,
+            };
+            let args = ipc::DateArgs { unit };
+            (
+                ipc::Type::Date,
+                ipc::Date::create(fbb, &args).as_union_value(),
+            )
+        }
+        Time32(unit) => (ipc::Type::Time, time(fbb, unit, 32)),
+        Time64(unit) => (ipc::Type::Time, time(fbb, unit, 64)),
+        Timestamp(unit) => {
+            let args = ipc::TimestampArgs {
+                unit: time_unit_to_fb(unit),
+                timezone: None,
+            };
+            (
+                ipc::Type::Timestamp,
+                ipc::Timestamp::create(fbb, &args).as_union_value(),
+            )
+        }
+        Interval(unit) => {
+            let unit = match unit {
+                IntervalUnit::YearMonth => ipc::IntervalUnit::YEAR_MONTH,
+                IntervalUnit::DayTime => ipc::IntervalUnit::DAY_TIME,
+            };
+            let args = ipc::IntervalArgs { unit };
+            (
+                ipc::Type::Interval,
+                ipc::Interval::create(fbb, &args).as_union_value(),
+            )
+        }
+        List(value_type) => {
+            let child = Field::new("item", value_type.as_ref().clone(), true);
+            let children = vec![field_to_fb(fbb, &child)?];
+            let fb_type = ipc::List::create(fbb, &ipc::ListArgs {}).as_union_value();
+            return Ok((ipc::Type::List, fb_type, children));
+        }
+        FixedSizeList((value_type, size)) => {
+            let child = Field::new("item", value_type.as_ref().clone(), true);
+            let children = vec![field_to_fb(fbb, &child)?];
+            let args = ipc::FixedSizeListArgs { listSize: *size };
+            let fb_type = ipc::FixedSizeList::create(fbb, &args).as_union_value();
+            return Ok((ipc::Type::FixedSizeList, fb_type, children));
+        }
+        Struct(fields) => {
+            let children = fields
+                .iter()
+                .map(|field| field_to_fb(fbb, field))
+                .collect::<Result<Vec<_>>>()?;
+            let fb_type =
+                ipc::Struct_::create(fbb, &ipc::Struct_Args {}).as_union_value();
+            return Ok((ipc::Type::Struct_, fb_type, children));
+        }
+        t => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Data type {:?} is not supported in IPC messages yet",
+                t
+            )))
+        }
+    };
+    Ok((ipc_type, fb_type, vec![]))
+}
+
+fn time_unit_to_fb(unit: &TimeUnit) -> ipc::TimeUnit {
+    match unit {
+        TimeUnit::Second => ipc::TimeUnit::SECOND,
+        TimeUnit::Millisecond => ipc::TimeUnit::MILLISECOND,
+        TimeUnit::Microsecond => ipc::TimeUnit::MICROSECOND,
+        TimeUnit::Nanosecond => ipc::TimeUnit::NANOSECOND,
     }
+}
 
-    let fb_field_list = fbb.create_vector(&fields);
+/// Deserialize a schema from IPC format
+pub fn fb_to_schema(fb: ipc::Schema) -> Result<Schema> {
+    let mut fields = vec![];
+    if let Some(fb_fields) = fb.fields() {
+        for i in 0..fb_fields.len() {
+            fields.push(fb_to_field(fb_fields.get(i))?);
+        }
+    }
+    Ok(Schema::new(fields))
+}
 
-    let root = {
-        let mut builder = ipc::SchemaBuilder::new(&mut fbb);
-        builder.add_fields(fb_field_list);
-        builder.finish()
+fn fb_to_field(field: ipc::Field) -> Result<Field> {
+    let name = field.name().unwrap_or("");
+    let mut children = vec![];
+    if let Some(fb_children) = field.children() {
+        for i in 0..fb_children.len() {
+            children.push(fb_to_field(fb_children.get(i))?);
+        }
+    }
+    if field.dictionary().is_some() {
+        return Err(ArrowError::NotYetImplemented(format!(
+            "Dictionary-encoded IPC field {} is not supported yet",
+            name
+        )));
+    }
+    let data_type = match field.type_type() {
+        ipc::Type::Bool => DataType::Boolean,
+        ipc::Type::Int => {
+            let int = field
+                .type__as_int()
+                .ok_or_else(|| missing_type_table(name, ipc::Type::Int))?;
+            match (int.bitWidth(), int.is_signed()) {
+                (8, true) => DataType::Int8,
+                (16, true) => DataType::Int16,
+                (32, true) => DataType::Int32,
+                (64, true) => DataType::Int64,
+                (8, false) => DataType::UInt8,
+                (16, false) => DataType::UInt16,
+                (32, false) => DataType::UInt32,
+                (64, false) => DataType::UInt64,
+                (bit_width, _) => {
+                    return Err(ArrowError::ParseError(format!(
+                        "Invalid IPC integer bit width {}",
+                        bit_width
+                    )))
+                }
+            }
+        }
+        ipc::Type::FloatingPoint => {
+            match field
+                .type__as_floating_point()
+                .ok_or_else(|| missing_type_table(name, ipc::Type::FloatingPoint))?
+                .precision()
+            {
+                ipc::Precision::HALF => DataType::Float16,
+                ipc::Precision::SINGLE => DataType::Float32,
+                ipc::Precision::DOUBLE => DataType::Float64,
+            }
+        }
+        ipc::Type::Utf8 => DataType::Utf8,
+        ipc::Type::Binary => DataType::Binary,
+        ipc::Type::Date => match field
+            .type__as_date()
+            .ok_or_else(|| missing_type_table(name, ipc::Type::Date))?
+            .unit()
+        {
+            ipc::DateUnit::DAY => DataType::Date32(DateUnit::Day),
+            ipc::DateUnit::MILLISECOND => DataType::Date64(DateUnit::Millisecond),
+        },
+        ipc::Type::Time => {
+            let time = field
+                .type__as_time()
+                .ok_or_else(|| missing_type_table(name, ipc::Type::Time))?;
+            let unit = fb_to_time_unit(time.unit());
+            if time.bitWidth() == 32 {
+                DataType::Time32(unit)
+            } else {
+                DataType::Time64(unit)
+            }
+        }
+        ipc::Type::Timestamp => DataType::Timestamp(fb_to_time_unit(
+            field
+                .type__as_timestamp()
+                .ok_or_else(|| missing_type_table(name, ipc::Type::Timestamp))?
+                .unit(),
+        )),
+        ipc::Type::Interval => match field
+            .type__as_interval()
+            .ok_or_else(|| missing_type_table(name, ipc::Type::Interval))?
+            .unit()
+        {
+            ipc::IntervalUnit::YEAR_MONTH => DataType::Interval(IntervalUnit::YearMonth),
+            ipc::IntervalUnit::DAY_TIME => DataType::Interval(IntervalUnit::DayTime),
+        },
+        ipc::Type::List if children.len() == 1 => {
+            DataType::List(Box::new(children[0].data_type().clone()))
+        }
+        ipc::Type::FixedSizeList if children.len() == 1 => {
+            let size = field
+                .type__as_fixed_size_list()
+                .ok_or_else(|| missing_type_table(name, ipc::Type::FixedSizeList))?
+                .listSize();
+            DataType::FixedSizeList((Box::new(children[0].data_type().clone()), size))
+        }
+        ipc::Type::Struct_ => DataType::Struct(children),
+        t => {
+            return Err(ArrowError::ParseError(format!(
+                "IPC type {:?} of field {} is not supported yet",
+                t, name
+            )))
+        }
     };
+    Ok(Field::new(name, data_type, field.nullable()))
+}
 
-    fbb.finish(root, None);
+/// Returns the error for a field whose type table is missing, which the generated
+/// union accessors return as `None`
+fn missing_type_table(name: &str, type_type: ipc::Type) -> ArrowError {
+    ArrowError::IoError(format!(
+        "IPC field {} of type {:?} has no type table",
+        name, type_type
+    ))
+}
 
-    fbb
+fn fb_to_time_unit(unit: ipc::TimeUnit) -> TimeUnit {
+    match unit {
+        ipc::TimeUnit::SECOND => TimeUnit::Second,
+        ipc::TimeUnit::MILLISECOND => TimeUnit::Millisecond,
+        ipc::TimeUnit::MICROSECOND => TimeUnit::Microsecond,
+        ipc::TimeUnit::NANOSECOND => TimeUnit::Nanosecond,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::datatypes::{DataType, Field, Schema};
 
     #[test]
     fn convert_schema() {
-        let schema = Schema::new(vec![Field::new("a", DataType::UInt32, false)]);
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::UInt32, false),
+            Field::new("b", DataType::Float64, true),
+            Field::new("c", DataType::Utf8, true),
+            Field::new("d", DataType::Date32(DateUnit::Day), false),
+            Field::new("e", DataType::Time64(TimeUnit::Nanosecond), false),
+            Field::new("f", DataType::Timestamp(TimeUnit::Millisecond), true),
+            Field::new("g", DataType::List(Box::new(DataType::Int16)), true),
+            Field::new(
+                "h",
+                DataType::FixedSizeList((Box::new(DataType::Boolean), 3)),
+                false,
+            ),
+            Field::new(
+                "i",
+                DataType::Struct(vec![
+                    Field::new("j", DataType::Int8, false),
+                    Field::new("k", DataType::Interval(IntervalUnit::DayTime), true),
+                ]),
+                true,
+            ),
+        ]);
+
+        let fbb = schema_to_fb(&schema).unwrap();
+        let ipc = ipc::get_root_as_schema(fbb.finished_data());
+        assert_eq!(schema, fb_to_schema(ipc).unwrap());
+    }
 
-        let ipc = schema_to_fb(&schema);
-        assert_eq!(60, ipc.finished_data().len());
+    #[test]
+    fn convert_unsupported_schema() {
+        let dictionary =
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8));
+        let schema = Schema::new(vec![Field::new("a", dictionary, false)]);
+        assert!(schema_to_fb(&schema).is_err());
+    }
+
+    #[test]
+    fn convert_field_without_type_table() {
+        let mut fbb = FlatBufferBuilder::new();
+        let name = fbb.create_string("a");
+        let field = {
+            let mut builder = ipc::FieldBuilder::new(&mut fbb);
+            builder.add_name(name);
+            builder.add_type_type(ipc::Type::Int);
+            builder.finish()
+        };
+        let fields = fbb.create_vector(&[field]);
+        let schema = {
+            let mut builder = ipc::SchemaBuilder::new(&mut fbb);
+            builder.add_fields(fields);
+            builder.finish()
+        };
+        fbb.finish(schema, None);
+        let ipc = ipc::get_root_as_schema(fbb.finished_data());
+        match fb_to_schema(ipc) {
+            Err(ArrowError::IoError(message)) => {
+                assert_eq!("IPC field a of type Int has no type table", message)
+            }
+            _ => panic!("expected a missing type table error"),
+        }
     }
 }
@@ -0,0 +1,153 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Exchanges record batches through an in-memory buffer in the IPC streaming format.
+//!
+//! This is useful to hand batches to another process or runtime, e.g. over a socket or
+//! through shared memory, without going through a file.
+
+use std::io::Cursor;
+
+use crate::datatypes::Schema;
+use crate::error::Result;
+use crate::ipc::reader::StreamReader;
+use crate::ipc::writer::StreamWriter;
+use crate::record_batch::RecordBatch;
+
+/// Record batches serialized to bytes in the IPC streaming format
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchExchange {
+    data: Vec<u8>,
+}
+
+impl BatchExchange {
+    /// Serializes `batches`, which must all have `schema`, into a new exchange
+    pub fn try_new(schema: &Schema, batches: &[RecordBatch]) -> Result<Self> {
+        let mut writer = StreamWriter::try_new(vec![], schema)?;
+        for batch in batches {
+            writer.write(batch)?;
+        }
+        Ok(Self {
+            data: writer.into_inner()?,
+        })
+    }
+
+    /// Creates an exchange from bytes in the IPC streaming format, e.g. received from
+    /// another process. The bytes are validated when they are read.
+    pub fn from_bytes(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    /// Returns the serialized batches
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consumes the exchange, returning the serialized batches
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Returns a reader of the batches in the exchange
+    pub fn reader(&self) -> Result<StreamReader<Cursor<&[u8]>>> {
+        StreamReader::try_new(Cursor::new(self.data.as_slice()))
+    }
+
+    /// Deserializes all batches in the exchange
+    pub fn read_batches(&self) -> Result<Vec<RecordBatch>> {
+        let mut reader = self.reader()?;
+        let mut batches = vec![];
+        while let Some(batch) = reader.next()? {
+            batches.push(batch);
+        }
+        Ok(batches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+    use std::sync::Arc;
+
+    use crate::array::*;
+    use crate::datatypes::{DataType, Field};
+
+    #[test]
+    fn test_exchange_round_trip() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("scores", DataType::List(Box::new(DataType::Float32)), true),
+        ]));
+
+        let mut scores = ListBuilder::new(Float32Builder::new(8));
+        scores.values().append_slice(&[1.0, 2.0]).unwrap();
+        scores.append(true).unwrap();
+        scores.append(false).unwrap();
+        scores.values().append_value(3.0).unwrap();
+        scores.append(true).unwrap();
+        let batch1 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])),
+                Arc::new(
+                    BinaryArray::try_from(vec![Some("a"), Some("b"), None]).unwrap(),
+                ),
+                Arc::new(scores.finish()),
+            ],
+        )
+        .unwrap();
+
+        let mut scores = ListBuilder::new(Float32Builder::new(8));
+        for i in 0..4 {
+            scores.values().append_value(i as f32).unwrap();
+            scores.append(true).unwrap();
+        }
+        let batch2 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![4, 5, 6, 7])),
+                Arc::new(BinaryArray::from(vec!["four", "five", "six", "seven"])),
+                Arc::new(scores.finish()),
+            ],
+        )
+        .unwrap();
+        let batches = vec![batch1.clone(), batch2.slice(1, 2), batch2];
+
+        let exchange = BatchExchange::try_new(&schema, &batches).unwrap();
+        let exchange = BatchExchange::from_bytes(exchange.into_bytes());
+        assert_eq!(schema, exchange.reader().unwrap().schema());
+
+        let read = exchange.read_batches().unwrap();
+        assert_eq!(batches.len(), read.len());
+        for (expected, actual) in batches.iter().zip(read.iter()) {
+            assert_eq!(expected.schema(), actual.schema());
+            assert_eq!(expected.num_rows(), actual.num_rows());
+            for i in 0..expected.num_columns() {
+                assert!(expected.column(i).equals(actual.column(i).as_ref()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_exchange_invalid_bytes() {
+        let exchange = BatchExchange::from_bytes(vec![1, 2, 3]);
+        assert!(exchange.read_batches().is_err());
+    }
+}
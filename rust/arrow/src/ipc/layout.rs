@@ -0,0 +1,284 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Describes the buffers that an array contributes to the body of an IPC record batch
+//! message.
+//!
+//! Buffers are listed in the order in which they are laid out in the message: for each
+//! array, its validity bitmap, its value offsets if it has any, and its values if it has
+//! any, followed by the buffers of its children, depth first. Each buffer is padded to
+//! a multiple of `IPC_ALIGNMENT` bytes.
+//!
+//! The order is defined once, by `visit_array`, which both `buffer_layout` and the IPC
+//! writer use to walk the arrays of a message body.
+
+use crate::array::ArrayData;
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+use crate::util::bit_util;
+
+/// The alignment in bytes of the buffers in an IPC message body
+pub const IPC_ALIGNMENT: usize = 8;
+
+/// The role of a buffer in the layout of an array
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BufferType {
+    /// The validity bitmap, which is empty for arrays without nulls
+    Validity,
+    /// The value offsets of variable-size types
+    Offsets,
+    /// The values, as bits for booleans and as bytes otherwise
+    Values,
+}
+
+/// The size of a buffer in an IPC message body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BufferLayout {
+    /// The role of the buffer
+    pub buffer_type: BufferType,
+    /// The length of the buffer in bytes, without padding
+    pub len: usize,
+    /// The number of padding bytes written after the buffer
+    pub padding: usize,
+}
+
+impl BufferLayout {
+    fn new(buffer_type: BufferType, len: usize) -> Self {
+        Self {
+            buffer_type,
+            len,
+            padding: padding(len),
+        }
+    }
+
+    /// Returns the length of the buffer in bytes, including padding
+    pub fn padded_len(&self) -> usize {
+        self.len + self.padding
+    }
+}
+
+/// Returns the number of bytes needed to pad `len` bytes to `IPC_ALIGNMENT`
+pub fn padding(len: usize) -> usize {
+    (IPC_ALIGNMENT - len % IPC_ALIGNMENT) % IPC_ALIGNMENT
+}
+
+/// Returns the buffers that `data` contributes to an IPC message body, in order.
+///
+/// Only the values within the offset and length of `data` are counted, so a sliced
+/// array contributes the buffers of its slice, and its validity bitmap is empty if
+/// none of the values of the slice are null. Returns an `InvalidArgumentError` for
+/// types that cannot be written to IPC messages yet, such as dictionaries.
+pub fn buffer_layout(data: &ArrayData) -> Result<Vec<BufferLayout>> {
+    let mut layout = LayoutVisitor(vec![]);
+    visit_array(data, data.offset(), data.len(), &mut layout)?;
+    Ok(layout.0)
+}
+
+/// The contents of a buffer of an IPC message body, borrowed from the array
+pub(crate) enum BodyBuffer<'a> {
+    /// A bitmap of `len` bits, where bit `i` is set if `bit(i)` is true
+    Bits {
+        len: usize,
+        bit: &'a dyn Fn(usize) -> bool,
+    },
+    /// Value offsets, which are written rebased to start at zero
+    Offsets(&'a [i32]),
+    /// Bytes that are written as they are
+    Bytes(&'a [u8]),
+}
+
+impl<'a> BodyBuffer<'a> {
+    /// Returns the length in bytes of the buffer, without padding
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            BodyBuffer::Bits { len, .. } => bit_util::ceil(*len, 8),
+            BodyBuffer::Offsets(offsets) => offsets.len() * 4,
+            BodyBuffer::Bytes(bytes) => bytes.len(),
+        }
+    }
+}
+
+/// Receives the field nodes and buffers of arrays in the order in which they are laid
+/// out in an IPC message body
+pub(crate) trait BodyVisitor {
+    /// Visits the field node of an array of `len` values, `null_count` of them null
+    fn visit_node(&mut self, len: usize, null_count: usize) -> Result<()>;
+
+    /// Visits the next buffer of the array of the last visited field node
+    fn visit_buffer(&mut self, buffer_type: BufferType, buffer: BodyBuffer)
+        -> Result<()>;
+}
+
+/// Collects the sizes of the buffers of a message body
+struct LayoutVisitor(Vec<BufferLayout>);
+
+impl BodyVisitor for LayoutVisitor {
+    fn visit_node(&mut self, _len: usize, _null_count: usize) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_buffer(
+        &mut self,
+        buffer_type: BufferType,
+        buffer: BodyBuffer,
+    ) -> Result<()> {
+        self.0.push(BufferLayout::new(buffer_type, buffer.len()));
+        Ok(())
+    }
+}
+
+/// Visits the field nodes and buffers of the `len` values of `data` starting at index
+/// `offset`, which includes the offset of `data` itself, followed by its children
+pub(crate) fn visit_array<V: BodyVisitor>(
+    data: &ArrayData,
+    offset: usize,
+    len: usize,
+    visitor: &mut V,
+) -> Result<()> {
+    let null_count = (offset..offset + len).filter(|&i| data.is_null(i)).count();
+    visitor.visit_node(len, null_count)?;
+    if null_count > 0 {
+        let bit = |i: usize| data.is_valid(offset + i);
+        visitor
+            .visit_buffer(BufferType::Validity, BodyBuffer::Bits { len, bit: &bit })?;
+    } else {
+        visitor.visit_buffer(BufferType::Validity, BodyBuffer::Bytes(&[]))?;
+    }
+
+    match data.data_type() {
+        DataType::Boolean => {
+            let values = data.buffers()[0].data();
+            let bit = |i: usize| bit_util::get_bit(values, offset + i);
+            visitor
+                .visit_buffer(BufferType::Values, BodyBuffer::Bits { len, bit: &bit })?;
+        }
+        DataType::Utf8 | DataType::Binary => {
+            let (start, end) = visit_offsets(data, offset, len, visitor)?;
+            let values = &data.buffers()[1].data()[start..end];
+            visitor.visit_buffer(BufferType::Values, BodyBuffer::Bytes(values))?;
+        }
+        DataType::List(_) => {
+            let (start, end) = visit_offsets(data, offset, len, visitor)?;
+            let child = &data.child_data()[0];
+            visit_array(child, child.offset() + start, end - start, visitor)?;
+        }
+        DataType::FixedSizeList((_, size)) => {
+            let size = *size as usize;
+            let child = &data.child_data()[0];
+            visit_array(child, child.offset() + offset * size, len * size, visitor)?;
+        }
+        DataType::Struct(_) => {
+            for child in data.child_data() {
+                visit_array(child, child.offset() + offset, len, visitor)?;
+            }
+        }
+        t => match t.size_of() {
+            Some(width) => {
+                let values =
+                    &data.buffers()[0].data()[offset * width..(offset + len) * width];
+                visitor.visit_buffer(BufferType::Values, BodyBuffer::Bytes(values))?;
+            }
+            None => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Data type {:?} is not supported in IPC messages yet",
+                    t
+                )))
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Visits the value offsets of `len` values starting at `offset`, and returns the
+/// range of values that they reference
+fn visit_offsets<V: BodyVisitor>(
+    data: &ArrayData,
+    offset: usize,
+    len: usize,
+    visitor: &mut V,
+) -> Result<(usize, usize)> {
+    let offsets = &data.buffers()[0].typed_data::<i32>()[offset..=offset + len];
+    visitor.visit_buffer(BufferType::Offsets, BodyBuffer::Offsets(offsets))?;
+    Ok((offsets[0] as usize, offsets[len] as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::array::*;
+    use crate::buffer::Buffer;
+    use crate::datatypes::ToByteSlice;
+
+    #[test]
+    fn test_list_buffer_layout() {
+        let values = Int32Array::from(vec![Some(0), None, Some(2), Some(3), Some(4)]);
+        let offsets = Buffer::from(&[0, 2, 2, 5].to_byte_slice());
+        let list_data = ArrayData::builder(DataType::List(Box::new(DataType::Int32)))
+            .len(3)
+            .add_buffer(offsets)
+            .add_child_data(values.data())
+            .build();
+
+        let layout = buffer_layout(&list_data).unwrap();
+        let expected = vec![
+            (BufferType::Validity, 0, 0),
+            (BufferType::Offsets, 16, 0),
+            (BufferType::Validity, 1, 7),
+            (BufferType::Values, 20, 4),
+        ];
+        let actual: Vec<(BufferType, usize, usize)> = layout
+            .iter()
+            .map(|b| (b.buffer_type, b.len, b.padding))
+            .collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_sliced_buffer_layout() {
+        let array = BinaryArray::from(vec!["a", "bc", "def"]);
+        let sliced = array.slice(1, 2);
+        let layout = buffer_layout(&sliced.data()).unwrap();
+        let actual: Vec<(BufferType, usize)> =
+            layout.iter().map(|b| (b.buffer_type, b.len)).collect();
+        assert_eq!(
+            vec![
+                (BufferType::Validity, 0),
+                (BufferType::Offsets, 12),
+                (BufferType::Values, 5)
+            ],
+            actual
+        );
+        assert_eq!(16, layout[1].padded_len());
+    }
+
+    #[test]
+    fn test_sliced_validity_buffer_layout() {
+        let array = Int64Array::from(vec![None, Some(1), Some(2), None]);
+        let layout = buffer_layout(&array.slice(1, 2).data()).unwrap();
+        assert_eq!(0, layout[0].len);
+        let layout = buffer_layout(&array.slice(2, 2).data()).unwrap();
+        assert_eq!(1, layout[0].len);
+        assert_eq!(16, layout[1].len);
+    }
+
+    #[test]
+    fn test_unsupported_buffer_layout() {
+        let array: Int8DictionaryArray = vec!["a", "b"].into_iter().collect();
+        assert!(buffer_layout(&array.data()).is_err());
+    }
+}
@@ -16,6 +16,11 @@
 // under the License.
 
 pub mod convert;
+pub mod exchange;
+pub mod layout;
+pub mod reader;
+mod verify;
+pub mod writer;
 
 pub mod gen;
 
@@ -24,3 +29,5 @@ pub use self::gen::Message::*;
 pub use self::gen::Schema::*;
 pub use self::gen::SparseTensor::*;
 pub use self::gen::Tensor::*;
+
+pub use self::exchange::BatchExchange;
@@ -0,0 +1,738 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reads record batches written in the Arrow IPC streaming and file formats.
+
+use std::io::{BufReader, ErrorKind, Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::buffer::Buffer;
+use crate::datatypes::{DataType, SchemaRef};
+use crate::error::{ArrowError, Result};
+use crate::ipc;
+use crate::ipc::convert::fb_to_schema;
+use crate::ipc::verify::{verify_footer, verify_message};
+use crate::ipc::writer::{ARROW_MAGIC, CONTINUATION_MARKER};
+use crate::record_batch::{RecordBatch, RecordBatchOptions, RecordBatchReader};
+
+/// Reads record batches from a stream in the IPC format
+pub struct StreamReader<R: Read> {
+    reader: BufReader<R>,
+    schema: SchemaRef,
+    finished: bool,
+}
+
+impl<R: Read> StreamReader<R> {
+    /// Creates a reader of the stream in `reader`, reading its schema message
+    pub fn try_new(reader: R) -> Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let message = read_message(&mut reader)?.ok_or_else(|| {
+            ArrowError::IoError(
+                "Expected a schema message, found end of stream".to_string(),
+            )
+        })?;
+        let fb_message = ipc::get_root_as_message(&message.metadata);
+        let schema = match fb_message.header_as_schema() {
+            Some(fb_schema) => fb_to_schema(fb_schema)?,
+            None => {
+                return Err(ArrowError::IoError(format!(
+                    "Expected a schema message, found {:?}",
+                    fb_message.header_type()
+                )))
+            }
+        };
+        Ok(Self {
+            reader,
+            schema: Arc::new(schema),
+            finished: false,
+        })
+    }
+
+    /// Returns the schema of the batches in the stream
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Reads the next record batch, returning `None` at the end of the stream
+    pub fn next(&mut self) -> Result<Option<RecordBatch>> {
+        if self.finished {
+            return Ok(None);
+        }
+        let message = match read_message(&mut self.reader)? {
+            Some(message) => message,
+            None => {
+                self.finished = true;
+                return Ok(None);
+            }
+        };
+        read_batch_message(&message, self.schema.clone()).map(Some)
+    }
+}
+
+impl<R: Read> RecordBatchReader for StreamReader<R> {
+    fn schema(&mut self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        self.next()
+    }
+}
+
+/// Reads record batches from a file in the IPC format, in order or by their index.
+///
+/// A file starts and ends with the `ARROW1` magic bytes. The messages of the file are
+/// followed by a footer with the schema and the locations of the record batches.
+pub struct FileReader<R: Read + Seek> {
+    reader: BufReader<R>,
+    schema: SchemaRef,
+    blocks: Vec<ipc::Block>,
+    current_block: usize,
+}
+
+impl<R: Read + Seek> FileReader<R> {
+    /// Creates a reader of the file in `reader`, reading its footer
+    pub fn try_new(reader: R) -> Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let file_len = reader.seek(SeekFrom::End(0))?;
+        // the leading magic bytes are padded to 8 bytes, and the trailing ones follow
+        // the footer length
+        if file_len < (2 * ARROW_MAGIC.len() + 6) as u64 {
+            return Err(ArrowError::IoError(format!(
+                "Arrow file of {} bytes is too short",
+                file_len
+            )));
+        }
+        let mut magic = [0; 6];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut magic)?;
+        if magic != ARROW_MAGIC {
+            return Err(ArrowError::IoError(
+                "Arrow file does not start with the ARROW1 magic bytes".to_string(),
+            ));
+        }
+        let mut footer_len = [0; 4];
+        reader.seek(SeekFrom::End(-10))?;
+        reader.read_exact(&mut footer_len)?;
+        reader.read_exact(&mut magic)?;
+        if magic != ARROW_MAGIC {
+            return Err(ArrowError::IoError(
+                "Arrow file does not end with the ARROW1 magic bytes".to_string(),
+            ));
+        }
+        let footer_len = i32::from_le_bytes(footer_len);
+        if footer_len < 0 || footer_len as u64 + 18 > file_len {
+            return Err(ArrowError::IoError(format!(
+                "Invalid Arrow file footer length {}",
+                footer_len
+            )));
+        }
+
+        let mut footer_data = vec![0; footer_len as usize];
+        reader.seek(SeekFrom::End(-10 - footer_len as i64))?;
+        reader.read_exact(&mut footer_data)?;
+        verify_footer(&footer_data)?;
+        let footer = ipc::get_root_as_footer(&footer_data);
+        let schema = match footer.schema() {
+            Some(fb_schema) => fb_to_schema(fb_schema)?,
+            None => {
+                return Err(ArrowError::IoError(
+                    "Arrow file footer has no schema".to_string(),
+                ))
+            }
+        };
+        if footer.dictionaries().map_or(false, |d| !d.is_empty()) {
+            return Err(ArrowError::NotYetImplemented(
+                "Reading dictionary batches from IPC files is not supported yet"
+                    .to_string(),
+            ));
+        }
+        Ok(Self {
+            reader,
+            schema: Arc::new(schema),
+            blocks: footer.recordBatches().unwrap_or(&[]).to_vec(),
+            current_block: 0,
+        })
+    }
+
+    /// Returns the schema of the batches in the file
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Returns the number of record batches in the file
+    pub fn num_batches(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Reads the record batch at `index`, without changing the position of the
+    /// iterator over the batches
+    pub fn read_batch(&mut self, index: usize) -> Result<RecordBatch> {
+        let block = self.blocks.get(index).ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!(
+                "Record batch index {} is out of bounds of the {} batches in the file",
+                index,
+                self.blocks.len()
+            ))
+        })?;
+        if block.offset() < 0 {
+            return Err(ArrowError::IoError(format!(
+                "Invalid IPC record batch offset {}",
+                block.offset()
+            )));
+        }
+        self.reader.seek(SeekFrom::Start(block.offset() as u64))?;
+        let message = read_message(&mut self.reader)?.ok_or_else(|| {
+            ArrowError::IoError(format!(
+                "Expected a record batch message at offset {}, found end of stream",
+                block.offset()
+            ))
+        })?;
+        read_batch_message(&message, self.schema.clone())
+    }
+}
+
+impl<R: Read + Seek> Iterator for FileReader<R> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_block < self.blocks.len() {
+            self.current_block += 1;
+            Some(self.read_batch(self.current_block - 1))
+        } else {
+            None
+        }
+    }
+}
+
+impl<R: Read + Seek> RecordBatchReader for FileReader<R> {
+    fn schema(&mut self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        self.next().transpose()
+    }
+}
+
+/// The flatbuffer metadata and the body of a message
+struct Message {
+    metadata: Vec<u8>,
+    body: Vec<u8>,
+}
+
+/// Reads the next message, returning `None` at the end-of-stream marker or if the
+/// stream ends before a message
+fn read_message<R: Read>(reader: &mut R) -> Result<Option<Message>> {
+    let mut word = [0; 4];
+    match reader.read_exact(&mut word) {
+        Err(ref e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        result => result?,
+    }
+    // streams written before the continuation marker was introduced start with the
+    // metadata length
+    if u32::from_le_bytes(word) == CONTINUATION_MARKER {
+        reader.read_exact(&mut word)?;
+    }
+    let metadata_len = i32::from_le_bytes(word);
+    if metadata_len == 0 {
+        return Ok(None);
+    }
+    if metadata_len < 0 {
+        return Err(ArrowError::IoError(format!(
+            "Invalid IPC message metadata length {}",
+            metadata_len
+        )));
+    }
+
+    let mut metadata = vec![0; metadata_len as usize];
+    reader.read_exact(&mut metadata)?;
+    verify_message(&metadata)?;
+    let body_len = ipc::get_root_as_message(&metadata).bodyLength();
+    if body_len < 0 {
+        return Err(ArrowError::IoError(format!(
+            "Invalid IPC message body length {}",
+            body_len
+        )));
+    }
+    let mut body = vec![0; body_len as usize];
+    reader.read_exact(&mut body)?;
+    Ok(Some(Message { metadata, body }))
+}
+
+/// Creates a record batch with `schema` from a message, which must be a record batch
+fn read_batch_message(message: &Message, schema: SchemaRef) -> Result<RecordBatch> {
+    let fb_message = ipc::get_root_as_message(&message.metadata);
+    if let Some(fb_batch) = fb_message.header_as_record_batch() {
+        return read_record_batch(fb_batch, &message.body, schema);
+    }
+    match fb_message.header_type() {
+        ipc::MessageHeader::DictionaryBatch => Err(ArrowError::NotYetImplemented(
+            "Reading dictionary batches from IPC messages is not supported yet"
+                .to_string(),
+        )),
+        t => Err(ArrowError::IoError(format!(
+            "Reading {:?} messages is not supported yet",
+            t
+        ))),
+    }
+}
+
+/// Creates a record batch with `schema` from a record batch message
+pub(crate) fn read_record_batch(
+    batch: ipc::RecordBatch,
+    body: &[u8],
+    schema: SchemaRef,
+) -> Result<RecordBatch> {
+    let mut decoder = ArrayDecoder {
+        nodes: batch.nodes().unwrap_or(&[]),
+        buffers: batch.buffers().unwrap_or(&[]),
+        body,
+    };
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| decoder.next_array(field.data_type()).map(make_array))
+        .collect::<Result<Vec<_>>>()?;
+    let options = RecordBatchOptions {
+        row_count: Some(batch.length() as usize),
+        ..Default::default()
+    };
+    RecordBatch::try_new_with_options(schema, columns, &options)
+}
+
+/// Creates arrays from the field nodes and buffers of a record batch message, which
+/// are consumed in order
+struct ArrayDecoder<'a> {
+    nodes: &'a [ipc::FieldNode],
+    buffers: &'a [ipc::Buffer],
+    body: &'a [u8],
+}
+
+impl<'a> ArrayDecoder<'a> {
+    fn next_array(&mut self, data_type: &DataType) -> Result<ArrayDataRef> {
+        let (node, nodes) = self.nodes.split_first().ok_or_else(|| {
+            ArrowError::IoError(
+                "IPC message has fewer field nodes than fields".to_string(),
+            )
+        })?;
+        self.nodes = nodes;
+        let len = node.length() as usize;
+        let null_count = node.null_count() as usize;
+
+        let validity = self.next_buffer()?;
+        let mut builder = ArrayData::builder(data_type.clone())
+            .len(len)
+            .null_count(null_count);
+        if null_count > 0 {
+            builder = builder.null_bit_buffer(validity);
+        }
+        builder = match data_type {
+            DataType::Utf8 | DataType::Binary => builder
+                .add_buffer(self.next_buffer()?)
+                .add_buffer(self.next_buffer()?),
+            DataType::List(value_type) => builder
+                .add_buffer(self.next_buffer()?)
+                .add_child_data(self.next_array(value_type)?),
+            DataType::FixedSizeList((value_type, _)) => {
+                builder.add_child_data(self.next_array(value_type)?)
+            }
+            DataType::Struct(fields) => {
+                for field in fields {
+                    builder = builder.add_child_data(self.next_array(field.data_type())?);
+                }
+                builder
+            }
+            DataType::Boolean => builder.add_buffer(self.next_buffer()?),
+            t if t.size_of().is_some() => builder.add_buffer(self.next_buffer()?),
+            t => {
+                return Err(ArrowError::IoError(format!(
+                    "Reading arrays of type {:?} from IPC messages is not supported yet",
+                    t
+                )))
+            }
+        };
+        builder.build_checked()
+    }
+
+    fn next_buffer(&mut self) -> Result<Buffer> {
+        let (buffer, buffers) = self.buffers.split_first().ok_or_else(|| {
+            ArrowError::IoError(
+                "IPC message has fewer buffers than its fields need".to_string(),
+            )
+        })?;
+        self.buffers = buffers;
+        let start = buffer.offset() as usize;
+        let end = start + buffer.length() as usize;
+        if buffer.offset() < 0 || buffer.length() < 0 || end > self.body.len() {
+            return Err(ArrowError::IoError(format!(
+                "IPC buffer at offset {} with length {} is out of bounds of the message body",
+                buffer.offset(),
+                buffer.length()
+            )));
+        }
+        Ok(Buffer::from(&self.body[start..end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::File;
+    use std::io::Cursor;
+
+    use flatbuffers::FlatBufferBuilder;
+
+    use crate::datatypes::{DateUnit, Field, Schema};
+    use crate::ipc::convert::schema_to_fb_offset;
+    use crate::ipc::writer::{FileWriter, StreamWriter};
+
+    fn test_batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+            Field::new("c", DataType::Boolean, true),
+            Field::new(
+                "d",
+                DataType::List(Box::new(DataType::Date32(DateUnit::Day))),
+                true,
+            ),
+            Field::new(
+                "e",
+                DataType::Struct(vec![Field::new("f", DataType::Float64, true)]),
+                false,
+            ),
+        ]);
+
+        let mut list = ListBuilder::new(Date32Builder::new(8));
+        list.values().append_value(1).unwrap();
+        list.values().append_null().unwrap();
+        list.append(true).unwrap();
+        list.append(false).unwrap();
+        list.values().append_value(3).unwrap();
+        list.append(true).unwrap();
+        list.append(true).unwrap();
+        list.values().append_value(5).unwrap();
+        list.append(true).unwrap();
+
+        let f: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.5),
+            None,
+            Some(2.5),
+            Some(3.5),
+            Some(4.5),
+        ]));
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![
+                    Some(1),
+                    None,
+                    Some(3),
+                    Some(4),
+                    None,
+                ])),
+                Arc::new(BinaryArray::from(vec!["a", "bc", "", "def", "g"])),
+                Arc::new(BooleanArray::from(vec![
+                    Some(true),
+                    Some(false),
+                    None,
+                    Some(true),
+                    None,
+                ])),
+                Arc::new(list.finish()),
+                Arc::new(StructArray::from(vec![(
+                    Field::new("f", DataType::Float64, true),
+                    f,
+                )])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn assert_batches_equal(expected: &RecordBatch, actual: &RecordBatch) {
+        assert_eq!(expected.schema(), actual.schema());
+        assert_eq!(expected.num_rows(), actual.num_rows());
+        for i in 0..expected.num_columns() {
+            assert!(
+                expected.column(i).equals(actual.column(i).as_ref()),
+                "column {} differs",
+                i
+            );
+        }
+    }
+
+    /// Lays out the messages of a stream as a file, with a footer locating its batches
+    fn stream_to_file(stream: &[u8], schema: &Schema) -> Vec<u8> {
+        let mut file = ARROW_MAGIC.to_vec();
+        file.extend_from_slice(&[0, 0]);
+        let mut blocks = vec![];
+        let mut position = 0;
+        loop {
+            let mut word = [0; 4];
+            word.copy_from_slice(&stream[position + 4..position + 8]);
+            let metadata_len = i32::from_le_bytes(word) as usize;
+            if metadata_len == 0 {
+                break;
+            }
+            let message = ipc::get_root_as_message(
+                &stream[position + 8..position + 8 + metadata_len],
+            );
+            if message.header_type() == ipc::MessageHeader::RecordBatch {
+                blocks.push(ipc::Block::new(
+                    (file.len() + position) as i64,
+                    (8 + metadata_len) as i32,
+                    message.bodyLength(),
+                ));
+            }
+            position += 8 + metadata_len + message.bodyLength() as usize;
+        }
+        file.extend_from_slice(&stream[..position]);
+
+        let mut fbb = FlatBufferBuilder::new();
+        let fb_schema = schema_to_fb_offset(&mut fbb, schema).unwrap();
+        let fb_blocks = fbb.create_vector(&blocks);
+        let footer = {
+            let mut builder = ipc::FooterBuilder::new(&mut fbb);
+            builder.add_version(ipc::MetadataVersion::V4);
+            builder.add_schema(fb_schema);
+            builder.add_recordBatches(fb_blocks);
+            builder.finish()
+        };
+        fbb.finish(footer, None);
+        file.extend_from_slice(fbb.finished_data());
+        file.extend_from_slice(&(fbb.finished_data().len() as i32).to_le_bytes());
+        file.extend_from_slice(&ARROW_MAGIC);
+        file
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let batch = test_batch();
+        let mut writer = StreamWriter::try_new(vec![], &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch.slice(1, 3)).unwrap();
+        let data = stream_to_file(&writer.into_inner().unwrap(), &batch.schema());
+
+        let mut reader = FileReader::try_new(Cursor::new(data)).unwrap();
+        assert_eq!(batch.schema(), &reader.schema());
+        assert_eq!(2, reader.num_batches());
+        assert_batches_equal(&batch.slice(1, 3), &reader.read_batch(1).unwrap());
+
+        let batches = reader.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(2, batches.len());
+        assert_batches_equal(&batch, &batches[0]);
+        assert_batches_equal(&batch.slice(1, 3), &batches[1]);
+        for i in 0..batch.num_columns() {
+            assert!(array_equals(
+                batch.column(i).as_ref(),
+                batches[0].column(i).as_ref()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_file_writer_round_trip() {
+        let batch = test_batch();
+        let mut writer = FileWriter::try_new(vec![], &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch.slice(1, 3)).unwrap();
+        writer.write(&batch.slice(4, 1)).unwrap();
+        let data = writer.into_inner().unwrap();
+
+        let mut reader = FileReader::try_new(Cursor::new(data)).unwrap();
+        assert_eq!(batch.schema(), &reader.schema());
+        assert_eq!(3, reader.num_batches());
+        assert_batches_equal(&batch.slice(4, 1), &reader.read_batch(2).unwrap());
+        assert_batches_equal(&batch, &reader.next().unwrap().unwrap());
+        assert_batches_equal(&batch.slice(1, 3), &reader.next().unwrap().unwrap());
+        assert_batches_equal(&batch.slice(4, 1), &reader.next().unwrap().unwrap());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_file_batch_index_out_of_bounds() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, false)]);
+        let writer = StreamWriter::try_new(vec![], &schema).unwrap();
+        let data = stream_to_file(&writer.into_inner().unwrap(), &schema);
+
+        let mut reader = FileReader::try_new(Cursor::new(data)).unwrap();
+        assert_eq!(schema, *reader.schema());
+        assert_eq!(0, reader.num_batches());
+        match reader.read_batch(0) {
+            Err(ArrowError::InvalidArgumentError(message)) => assert_eq!(
+                "Record batch index 0 is out of bounds of the 0 batches in the file",
+                message
+            ),
+            _ => panic!("expected an out of bounds error"),
+        }
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_file_invalid_magic() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, false)]);
+        let writer = StreamWriter::try_new(vec![], &schema).unwrap();
+        let stream = writer.into_inner().unwrap();
+        // a stream is not a file
+        assert!(FileReader::try_new(Cursor::new(stream.clone())).is_err());
+
+        let mut data = stream_to_file(&stream, &schema);
+        let len = data.len();
+        data[len - 1] = b'2';
+        assert!(FileReader::try_new(Cursor::new(data)).is_err());
+    }
+
+    #[test]
+    fn test_file_reference() {
+        // written independently of `FileWriter`, in the layout of other
+        // implementations: the body buffers are 8-byte aligned and the column without
+        // nulls has an empty validity buffer
+        let file = File::open("test/data/primitive_and_utf8.arrow").unwrap();
+        let mut reader = FileReader::try_new(file).unwrap();
+        let schema = Schema::new(vec![
+            Field::new("ints", DataType::Int32, true),
+            Field::new("floats", DataType::Float64, true),
+            Field::new("strs", DataType::Utf8, true),
+            Field::new("bools", DataType::Boolean, true),
+        ]);
+        assert_eq!(schema, *reader.schema());
+        assert_eq!(1, reader.num_batches());
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(3, batch.num_rows());
+        let ints = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(1, ints.value(0));
+        assert!(ints.is_null(1));
+        assert_eq!(3, ints.value(2));
+        let floats = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(0, floats.null_count());
+        assert_eq!(&[1.5, 2.5, -0.5], floats.value_slice(0, 3));
+        let strs = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!("foo", strs.get_string(0));
+        assert!(strs.is_null(1));
+        assert_eq!("bar", strs.get_string(2));
+        let bools = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert!(bools.value(0));
+        assert!(!bools.value(1));
+        assert!(bools.is_null(2));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_file_corrupt_footer() {
+        let batch = test_batch();
+        let mut writer = FileWriter::try_new(vec![], &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        let data = writer.into_inner().unwrap();
+        let len = data.len();
+        let mut word = [0; 4];
+        word.copy_from_slice(&data[len - 10..len - 6]);
+        let footer_start = len - 10 - i32::from_le_bytes(word) as usize;
+
+        // the root offset of the footer points past its end
+        let mut corrupt = data.clone();
+        corrupt[footer_start..footer_start + 4].copy_from_slice(&[0xFF; 4]);
+        match FileReader::try_new(Cursor::new(corrupt)) {
+            Err(ArrowError::IoError(_)) => {}
+            _ => panic!("expected an invalid footer error"),
+        }
+
+        // the footer is all zeros, so its table has no valid vtable
+        let mut corrupt = data.clone();
+        for byte in &mut corrupt[footer_start..len - 10] {
+            *byte = 0;
+        }
+        match FileReader::try_new(Cursor::new(corrupt)) {
+            Err(ArrowError::IoError(_)) => {}
+            _ => panic!("expected an invalid footer error"),
+        }
+
+        // flipping any byte of the footer may or may not leave a valid footer, but
+        // never panics
+        for i in footer_start..len - 10 {
+            let mut corrupt = data.clone();
+            corrupt[i] ^= 0xFF;
+            let _ = FileReader::try_new(Cursor::new(corrupt));
+        }
+    }
+
+    #[test]
+    fn test_stream_corrupt_message() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, false)]);
+        let writer = StreamWriter::try_new(vec![], &schema).unwrap();
+        let mut data = writer.into_inner().unwrap();
+        // the root offset of the schema message follows the continuation marker and
+        // the metadata length
+        data[8..12].copy_from_slice(&[0xFF; 4]);
+        match StreamReader::try_new(data.as_slice()) {
+            Err(ArrowError::IoError(_)) => {}
+            _ => panic!("expected an invalid message error"),
+        }
+    }
+
+    #[test]
+    fn test_stream_round_trip() {
+        let batch = test_batch();
+        let mut writer = StreamWriter::try_new(vec![], &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch.slice(1, 3)).unwrap();
+        let data = writer.into_inner().unwrap();
+        assert_eq!(0, data.len() % 8);
+
+        let mut reader = StreamReader::try_new(data.as_slice()).unwrap();
+        assert_eq!(batch.schema(), &reader.schema());
+        assert_batches_equal(&batch, &reader.next().unwrap().unwrap());
+        assert_batches_equal(&batch.slice(1, 3), &reader.next().unwrap().unwrap());
+        assert!(reader.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stream_without_batches() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, false)]);
+        let writer = StreamWriter::try_new(vec![], &schema).unwrap();
+        let data = writer.into_inner().unwrap();
+
+        let mut reader = StreamReader::try_new(data.as_slice()).unwrap();
+        assert_eq!(schema, *reader.schema());
+        assert!(reader.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stream_invalid_start() {
+        let data: Vec<u8> = vec![];
+        assert!(StreamReader::try_new(data.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,426 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Bounds checks for the flatbuffers read from IPC files and streams.
+//!
+//! The generated flatbuffer accessors trust the offsets they follow, so a truncated
+//! or corrupt buffer makes them panic or read out of bounds. The functions here
+//! walk every table, vector, string and enum that the readers access and return an
+//! `IoError` for any that does not fit in the buffer.
+
+use crate::error::{ArrowError, Result};
+use crate::ipc;
+
+/// The deepest nesting of fields that is accepted, which bounds the recursion
+const MAX_DEPTH: usize = 64;
+
+/// Size in bytes of the `Block` struct of a file footer
+const BLOCK_SIZE: usize = 24;
+
+/// Size in bytes of the `FieldNode` and `Buffer` structs of a record batch
+const NODE_SIZE: usize = 16;
+
+/// Checks that the flatbuffer of an Arrow file footer can be read
+pub(crate) fn verify_footer(buf: &[u8]) -> Result<()> {
+    let verifier = Verifier {
+        buf,
+        name: "Arrow file footer",
+    };
+    let footer = verifier.root()?;
+    verifier.enum_field(&footer, 0, 2, ipc::MetadataVersion::V4 as i64)?;
+    if let Some(schema) = verifier.table_field(&footer, 1)? {
+        verifier.schema(&schema)?;
+    }
+    verifier.vector_field(&footer, 2, BLOCK_SIZE)?;
+    verifier.vector_field(&footer, 3, BLOCK_SIZE)?;
+    Ok(())
+}
+
+/// Checks that the flatbuffer metadata of an IPC message can be read
+pub(crate) fn verify_message(buf: &[u8]) -> Result<()> {
+    let verifier = Verifier {
+        buf,
+        name: "IPC message",
+    };
+    let message = verifier.root()?;
+    verifier.enum_field(&message, 0, 2, ipc::MetadataVersion::V4 as i64)?;
+    let header_type =
+        verifier.enum_field(&message, 1, 1, ipc::MessageHeader::SparseTensor as i64)?;
+    verifier.scalar_field(&message, 3, 8)?;
+    verifier.key_values(&message, 4)?;
+    let header = match verifier.table_field(&message, 2)? {
+        Some(header) => header,
+        None => return Ok(()),
+    };
+    if header_type == ipc::MessageHeader::Schema as i64 {
+        verifier.schema(&header)
+    } else if header_type == ipc::MessageHeader::RecordBatch as i64 {
+        verifier.record_batch(&header)
+    } else if header_type == ipc::MessageHeader::DictionaryBatch as i64 {
+        verifier.scalar_field(&header, 0, 8)?;
+        if let Some(batch) = verifier.table_field(&header, 1)? {
+            verifier.record_batch(&batch)?;
+        }
+        verifier.bool_field(&header, 2)
+    } else {
+        Ok(())
+    }
+}
+
+/// A table located in the buffer, whose fields are within `len` bytes of `pos`
+struct Table {
+    pos: usize,
+    vtable: usize,
+    vtable_len: usize,
+    len: usize,
+}
+
+struct Verifier<'a> {
+    buf: &'a [u8],
+    name: &'static str,
+}
+
+impl<'a> Verifier<'a> {
+    fn error(&self, message: String) -> ArrowError {
+        ArrowError::IoError(format!("Invalid {}: {}", self.name, message))
+    }
+
+    /// Returns the `len` bytes at `pos`, if they are all in the buffer
+    fn bytes(&self, pos: usize, len: usize) -> Result<&'a [u8]> {
+        match pos.checked_add(len) {
+            Some(end) if end <= self.buf.len() => Ok(&self.buf[pos..end]),
+            _ => Err(self.error(format!(
+                "{} bytes at offset {} are out of bounds of the {} byte buffer",
+                len,
+                pos,
+                self.buf.len()
+            ))),
+        }
+    }
+
+    fn read_u16(&self, pos: usize) -> Result<u16> {
+        let bytes = self.bytes(pos, 2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&self, pos: usize) -> Result<u32> {
+        let bytes = self.bytes(pos, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Returns the position that the unsigned offset at `pos` points to
+    fn follow(&self, pos: usize) -> Result<usize> {
+        let target = pos.checked_add(self.read_u32(pos)? as usize);
+        match target {
+            Some(target) if target < self.buf.len() => Ok(target),
+            _ => Err(self.error(format!(
+                "offset at {} points out of bounds of the {} byte buffer",
+                pos,
+                self.buf.len()
+            ))),
+        }
+    }
+
+    fn root(&self) -> Result<Table> {
+        let pos = self.follow(0)?;
+        self.table(pos)
+    }
+
+    /// Checks the table at `pos` and its vtable
+    fn table(&self, pos: usize) -> Result<Table> {
+        let soffset = self.read_u32(pos)? as i32 as i64;
+        let vtable = pos as i64 - soffset;
+        if vtable < 0 || vtable as usize >= self.buf.len() {
+            return Err(
+                self.error(format!("vtable of the table at {} is out of bounds", pos))
+            );
+        }
+        let vtable = vtable as usize;
+        let vtable_len = self.read_u16(vtable)? as usize;
+        let len = self.read_u16(vtable + 2)? as usize;
+        if vtable_len < 4 || vtable_len % 2 != 0 || len < 4 {
+            return Err(self.error(format!(
+                "vtable of the table at {} has invalid lengths",
+                pos
+            )));
+        }
+        self.bytes(vtable, vtable_len)?;
+        self.bytes(pos, len)?;
+        Ok(Table {
+            pos,
+            vtable,
+            vtable_len,
+            len,
+        })
+    }
+
+    /// Returns the position of the `size` byte field `slot` of `table`, or `None` if
+    /// the field is not set
+    fn scalar_field(
+        &self,
+        table: &Table,
+        slot: usize,
+        size: usize,
+    ) -> Result<Option<usize>> {
+        let entry = 4 + 2 * slot;
+        if entry + 2 > table.vtable_len {
+            return Ok(None);
+        }
+        let offset = self.read_u16(table.vtable + entry)? as usize;
+        if offset == 0 {
+            return Ok(None);
+        }
+        if offset + size > table.len {
+            return Err(self.error(format!(
+                "field {} of the table at {} is out of bounds of the table",
+                slot, table.pos
+            )));
+        }
+        Ok(Some(table.pos + offset))
+    }
+
+    /// Checks that the enum field `slot` of `table` is at most `max`, returning its
+    /// value or zero, the default of every enum in the format, if it is not set
+    fn enum_field(
+        &self,
+        table: &Table,
+        slot: usize,
+        size: usize,
+        max: i64,
+    ) -> Result<i64> {
+        let value = match self.scalar_field(table, slot, size)? {
+            Some(pos) if size == 1 => self.buf[pos] as i64,
+            Some(pos) => self.read_u16(pos)? as i16 as i64,
+            None => 0,
+        };
+        if value < 0 || value > max {
+            return Err(self.error(format!(
+                "field {} of the table at {} has invalid enum value {}",
+                slot, table.pos, value
+            )));
+        }
+        Ok(value)
+    }
+
+    fn bool_field(&self, table: &Table, slot: usize) -> Result<()> {
+        self.enum_field(table, slot, 1, 1).map(|_| ())
+    }
+
+    /// Returns the position that the offset field `slot` of `table` points to
+    fn offset_field(&self, table: &Table, slot: usize) -> Result<Option<usize>> {
+        match self.scalar_field(table, slot, 4)? {
+            Some(pos) => self.follow(pos).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn table_field(&self, table: &Table, slot: usize) -> Result<Option<Table>> {
+        match self.offset_field(table, slot)? {
+            Some(pos) => self.table(pos).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Checks the vector at `pos` of elements of `size` bytes, returning its length
+    fn vector(&self, pos: usize, size: usize) -> Result<usize> {
+        let len = self.read_u32(pos)? as usize;
+        let bytes = len.checked_mul(size).ok_or_else(|| {
+            self.error(format!("vector at {} of length {} is too long", pos, len))
+        })?;
+        self.bytes(pos + 4, bytes)?;
+        Ok(len)
+    }
+
+    fn vector_field(&self, table: &Table, slot: usize, size: usize) -> Result<()> {
+        if let Some(pos) = self.offset_field(table, slot)? {
+            self.vector(pos, size)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the tables of the vector of tables in field `slot` of `table`
+    fn tables_field(&self, table: &Table, slot: usize) -> Result<Vec<Table>> {
+        let pos = match self.offset_field(table, slot)? {
+            Some(pos) => pos,
+            None => return Ok(vec![]),
+        };
+        let len = self.vector(pos, 4)?;
+        (0..len)
+            .map(|i| self.follow(pos + 4 + 4 * i).and_then(|t| self.table(t)))
+            .collect()
+    }
+
+    fn string_field(&self, table: &Table, slot: usize) -> Result<()> {
+        if let Some(pos) = self.offset_field(table, slot)? {
+            let len = self.read_u32(pos)? as usize;
+            let bytes = self.bytes(pos + 4, len)?;
+            if std::str::from_utf8(bytes).is_err() {
+                return Err(self.error(format!("string at {} is not valid UTF-8", pos)));
+            }
+        }
+        Ok(())
+    }
+
+    fn key_values(&self, table: &Table, slot: usize) -> Result<()> {
+        for key_value in self.tables_field(table, slot)? {
+            self.string_field(&key_value, 0)?;
+            self.string_field(&key_value, 1)?;
+        }
+        Ok(())
+    }
+
+    fn schema(&self, schema: &Table) -> Result<()> {
+        self.enum_field(schema, 0, 2, ipc::Endianness::Big as i64)?;
+        for field in self.tables_field(schema, 1)? {
+            self.field(&field, 0)?;
+        }
+        self.key_values(schema, 2)
+    }
+
+    fn field(&self, field: &Table, depth: usize) -> Result<()> {
+        if depth > MAX_DEPTH {
+            return Err(
+                self.error(format!("fields are nested more than {} deep", MAX_DEPTH))
+            );
+        }
+        self.string_field(field, 0)?;
+        self.bool_field(field, 1)?;
+        let type_type = self.enum_field(field, 2, 1, ipc::Type::Map as i64)?;
+        if let Some(type_) = self.table_field(field, 3)? {
+            self.type_(&type_, type_type)?;
+        }
+        if let Some(dictionary) = self.table_field(field, 4)? {
+            self.scalar_field(&dictionary, 0, 8)?;
+            if let Some(index_type) = self.table_field(&dictionary, 1)? {
+                self.type_(&index_type, ipc::Type::Int as i64)?;
+            }
+            self.bool_field(&dictionary, 2)?;
+        }
+        for child in self.tables_field(field, 5)? {
+            self.field(&child, depth + 1)?;
+        }
+        self.key_values(field, 6)
+    }
+
+    /// Checks the fields of a type table of the union member `type_type`
+    fn type_(&self, type_: &Table, type_type: i64) -> Result<()> {
+        let time_unit = ipc::TimeUnit::NANOSECOND as i64;
+        match type_type {
+            t if t == ipc::Type::Int as i64 => {
+                self.scalar_field(type_, 0, 4)?;
+                self.bool_field(type_, 1)
+            }
+            t if t == ipc::Type::FloatingPoint as i64 => self
+                .enum_field(type_, 0, 2, ipc::Precision::DOUBLE as i64)
+                .map(|_| ()),
+            t if t == ipc::Type::Decimal as i64 => {
+                self.scalar_field(type_, 0, 4)?;
+                self.scalar_field(type_, 1, 4).map(|_| ())
+            }
+            t if t == ipc::Type::Date as i64 => self
+                .enum_field(type_, 0, 2, ipc::DateUnit::MILLISECOND as i64)
+                .map(|_| ()),
+            t if t == ipc::Type::Time as i64 => {
+                self.enum_field(type_, 0, 2, time_unit)?;
+                self.scalar_field(type_, 1, 4).map(|_| ())
+            }
+            t if t == ipc::Type::Timestamp as i64 => {
+                self.enum_field(type_, 0, 2, time_unit)?;
+                self.string_field(type_, 1)
+            }
+            t if t == ipc::Type::Interval as i64 => self
+                .enum_field(type_, 0, 2, ipc::IntervalUnit::DAY_TIME as i64)
+                .map(|_| ()),
+            t if t == ipc::Type::Union as i64 => {
+                self.enum_field(type_, 0, 2, ipc::UnionMode::Dense as i64)?;
+                self.vector_field(type_, 1, 4)
+            }
+            t if t == ipc::Type::FixedSizeBinary as i64
+                || t == ipc::Type::FixedSizeList as i64 =>
+            {
+                self.scalar_field(type_, 0, 4).map(|_| ())
+            }
+            t if t == ipc::Type::Map as i64 => self.bool_field(type_, 0),
+            _ => Ok(()),
+        }
+    }
+
+    fn record_batch(&self, batch: &Table) -> Result<()> {
+        self.scalar_field(batch, 0, 8)?;
+        self.vector_field(batch, 1, NODE_SIZE)?;
+        self.vector_field(batch, 2, NODE_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use flatbuffers::FlatBufferBuilder;
+
+    use crate::datatypes::{DataType, Field, Schema, TimeUnit};
+    use crate::ipc::convert::schema_to_fb_offset;
+
+    fn footer() -> Vec<u8> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Timestamp(TimeUnit::Second), false),
+            Field::new("c", DataType::List(Box::new(DataType::Float64)), true),
+        ]);
+        let mut fbb = FlatBufferBuilder::new();
+        let fb_schema = schema_to_fb_offset(&mut fbb, &schema).unwrap();
+        let fb_blocks = fbb.create_vector(&[ipc::Block::new(8, 16, 24)]);
+        let footer = {
+            let mut builder = ipc::FooterBuilder::new(&mut fbb);
+            builder.add_version(ipc::MetadataVersion::V4);
+            builder.add_schema(fb_schema);
+            builder.add_recordBatches(fb_blocks);
+            builder.finish()
+        };
+        fbb.finish(footer, None);
+        fbb.finished_data().to_vec()
+    }
+
+    #[test]
+    fn test_verify_valid_footer() {
+        let data = footer();
+        verify_footer(&data).unwrap();
+        let footer = ipc::get_root_as_footer(&data);
+        assert_eq!(3, footer.schema().unwrap().fields().unwrap().len());
+        assert_eq!(1, footer.recordBatches().unwrap().len());
+    }
+
+    #[test]
+    fn test_verify_truncated_footer() {
+        let data = footer();
+        for &len in &[0, 3, 8, data.len() / 2, data.len() - 8] {
+            match verify_footer(&data[..len]) {
+                Err(ArrowError::IoError(_)) => {}
+                _ => panic!("expected a footer truncated to {} bytes to be invalid", len),
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_corrupt_root_offset() {
+        let mut data = footer();
+        data[..4].copy_from_slice(&(data.len() as u32).to_le_bytes());
+        assert!(verify_footer(&data).is_err());
+        data[..4].copy_from_slice(&u32::max_value().to_le_bytes());
+        assert!(verify_footer(&data).is_err());
+    }
+}
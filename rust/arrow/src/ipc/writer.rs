@@ -0,0 +1,390 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Writes record batches in the Arrow IPC streaming and file formats.
+//!
+//! A stream starts with a schema message, followed by one record batch message per
+//! batch and an end-of-stream marker. Each message is written as a continuation marker,
+//! the length of its flatbuffer metadata, the metadata and the message body, with the
+//! metadata and each buffer of the body padded to 8 bytes.
+//!
+//! A file holds the same messages between the `ARROW1` magic bytes, followed by a
+//! footer with the schema and the locations of the record batch messages.
+
+use std::io::{BufWriter, Write};
+
+use flatbuffers::{FlatBufferBuilder, UnionWIPOffset, WIPOffset};
+
+use crate::datatypes::Schema;
+use crate::error::{ArrowError, Result};
+use crate::ipc;
+use crate::ipc::convert::schema_to_fb_offset;
+use crate::ipc::layout::{padding, visit_array, BodyBuffer, BodyVisitor, BufferType};
+use crate::record_batch::RecordBatch;
+use crate::util::bit_util;
+
+/// The marker that precedes the metadata length of each message
+pub(crate) const CONTINUATION_MARKER: u32 = 0xFFFF_FFFF;
+
+/// The magic bytes at the start and the end of IPC files
+pub(crate) const ARROW_MAGIC: [u8; 6] = *b"ARROW1";
+
+/// Writes record batches with the same schema to a stream in the IPC format
+pub struct StreamWriter<W: Write> {
+    writer: BufWriter<W>,
+    schema: Schema,
+    finished: bool,
+}
+
+impl<W: Write> StreamWriter<W> {
+    /// Creates a writer of batches with `schema`, writing the schema message to `writer`
+    pub fn try_new(writer: W, schema: &Schema) -> Result<Self> {
+        let mut writer = BufWriter::new(writer);
+        let mut fbb = FlatBufferBuilder::new();
+        let fb_schema = schema_to_fb_offset(&mut fbb, schema)?;
+        finish_message(
+            &mut fbb,
+            ipc::MessageHeader::Schema,
+            fb_schema.as_union_value(),
+            0,
+        );
+        write_message(&mut writer, fbb.finished_data(), &[])?;
+        Ok(Self {
+            writer,
+            schema: schema.clone(),
+            finished: false,
+        })
+    }
+
+    /// Writes a record batch, which must have the schema of the writer
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if self.finished {
+            return Err(ArrowError::IoError(
+                "Cannot write a record batch to a finished stream".to_string(),
+            ));
+        }
+        if batch.schema().as_ref() != &self.schema {
+            return Err(ArrowError::InvalidArgumentError(
+                "Cannot write a record batch with a different schema to a stream"
+                    .to_string(),
+            ));
+        }
+        let (metadata, body) = record_batch_to_bytes(batch)?;
+        write_message(&mut self.writer, &metadata, &body)?;
+        Ok(())
+    }
+
+    /// Writes the end-of-stream marker and flushes the stream. No batches can be
+    /// written afterwards.
+    pub fn finish(&mut self) -> Result<()> {
+        if !self.finished {
+            self.writer.write_all(&CONTINUATION_MARKER.to_le_bytes())?;
+            self.writer.write_all(&0u32.to_le_bytes())?;
+            self.writer.flush()?;
+            self.finished = true;
+        }
+        Ok(())
+    }
+
+    /// Finishes the stream if needed and returns the underlying writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.finish()?;
+        self.writer
+            .into_inner()
+            .map_err(|e| ArrowError::IoError(e.to_string()))
+    }
+}
+
+/// Writes record batches with the same schema to a file in the IPC format
+pub struct FileWriter<W: Write> {
+    writer: BufWriter<W>,
+    schema: Schema,
+    /// The number of bytes written so far
+    position: usize,
+    /// The locations of the record batch messages
+    blocks: Vec<ipc::Block>,
+    finished: bool,
+}
+
+impl<W: Write> FileWriter<W> {
+    /// Creates a writer of batches with `schema`, writing the leading magic bytes and
+    /// the schema message to `writer`
+    pub fn try_new(writer: W, schema: &Schema) -> Result<Self> {
+        let mut writer = BufWriter::new(writer);
+        let mut position = write_buffer_aligned(&mut writer, &ARROW_MAGIC)?;
+        let mut fbb = FlatBufferBuilder::new();
+        let fb_schema = schema_to_fb_offset(&mut fbb, schema)?;
+        finish_message(
+            &mut fbb,
+            ipc::MessageHeader::Schema,
+            fb_schema.as_union_value(),
+            0,
+        );
+        let (metadata_len, _) = write_message(&mut writer, fbb.finished_data(), &[])?;
+        position += metadata_len;
+        Ok(Self {
+            writer,
+            schema: schema.clone(),
+            position,
+            blocks: vec![],
+            finished: false,
+        })
+    }
+
+    /// Writes a record batch, which must have the schema of the writer
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if self.finished {
+            return Err(ArrowError::IoError(
+                "Cannot write a record batch to a finished file".to_string(),
+            ));
+        }
+        if batch.schema().as_ref() != &self.schema {
+            return Err(ArrowError::InvalidArgumentError(
+                "Cannot write a record batch with a different schema to a file"
+                    .to_string(),
+            ));
+        }
+        let (metadata, body) = record_batch_to_bytes(batch)?;
+        let (metadata_len, body_len) = write_message(&mut self.writer, &metadata, &body)?;
+        self.blocks.push(ipc::Block::new(
+            self.position as i64,
+            metadata_len as i32,
+            body_len as i64,
+        ));
+        self.position += metadata_len + body_len;
+        Ok(())
+    }
+
+    /// Writes the footer and the trailing magic bytes and flushes the file. No batches
+    /// can be written afterwards.
+    pub fn finish(&mut self) -> Result<()> {
+        if !self.finished {
+            let mut fbb = FlatBufferBuilder::new();
+            let fb_schema = schema_to_fb_offset(&mut fbb, &self.schema)?;
+            let fb_dictionaries = fbb.create_vector::<ipc::Block>(&[]);
+            let fb_blocks = fbb.create_vector(&self.blocks);
+            let footer = {
+                let mut builder = ipc::FooterBuilder::new(&mut fbb);
+                builder.add_version(ipc::MetadataVersion::V4);
+                builder.add_schema(fb_schema);
+                builder.add_dictionaries(fb_dictionaries);
+                builder.add_recordBatches(fb_blocks);
+                builder.finish()
+            };
+            fbb.finish(footer, None);
+            let footer_data = fbb.finished_data();
+            self.writer.write_all(footer_data)?;
+            self.writer
+                .write_all(&(footer_data.len() as i32).to_le_bytes())?;
+            self.writer.write_all(&ARROW_MAGIC)?;
+            self.writer.flush()?;
+            self.finished = true;
+        }
+        Ok(())
+    }
+
+    /// Finishes the file if needed and returns the underlying writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.finish()?;
+        self.writer
+            .into_inner()
+            .map_err(|e| ArrowError::IoError(e.to_string()))
+    }
+}
+
+/// Finishes `fbb` with a message of the given header
+fn finish_message<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    header_type: ipc::MessageHeader,
+    header: WIPOffset<UnionWIPOffset>,
+    body_length: usize,
+) {
+    let mut builder = ipc::MessageBuilder::new(fbb);
+    builder.add_version(ipc::MetadataVersion::V4);
+    builder.add_header_type(header_type);
+    builder.add_header(header);
+    builder.add_bodyLength(body_length as i64);
+    let root = builder.finish();
+    fbb.finish(root, None);
+}
+
+/// Writes a message with its padded metadata followed by its body, and returns the
+/// number of bytes written for the metadata, including its prefix, and for the body
+fn write_message<W: Write>(
+    writer: &mut W,
+    metadata: &[u8],
+    body: &[u8],
+) -> Result<(usize, usize)> {
+    let padded_len = metadata.len() + padding(metadata.len());
+    writer.write_all(&CONTINUATION_MARKER.to_le_bytes())?;
+    writer.write_all(&(padded_len as i32).to_le_bytes())?;
+    write_buffer_aligned(writer, metadata)?;
+    writer.write_all(body)?;
+    Ok((8 + padded_len, body.len()))
+}
+
+/// Writes the bytes of `buffer` followed by zeros up to the next multiple of the IPC
+/// alignment of 8 bytes, and returns the number of bytes written.
+///
+/// All buffers of IPC messages are written this way, so that every buffer starts at an
+/// aligned position of the stream.
+pub fn write_buffer_aligned<W: Write>(out: &mut W, buffer: &[u8]) -> Result<usize> {
+    let padding = padding(buffer.len());
+    out.write_all(buffer)?;
+    out.write_all(&vec![0; padding])?;
+    Ok(buffer.len() + padding)
+}
+
+/// Serializes a record batch into the flatbuffer metadata and the body of its message
+fn record_batch_to_bytes(batch: &RecordBatch) -> Result<(Vec<u8>, Vec<u8>)> {
+    let mut body = MessageBody::default();
+    for column in batch.columns() {
+        let data = column.data();
+        visit_array(&data, data.offset(), data.len(), &mut body)?;
+    }
+
+    let mut fbb = FlatBufferBuilder::new();
+    let fb_nodes = fbb.create_vector(&body.nodes);
+    let fb_buffers = fbb.create_vector(&body.buffers);
+    let fb_batch = {
+        let mut builder = ipc::RecordBatchBuilder::new(&mut fbb);
+        builder.add_length(batch.num_rows() as i64);
+        builder.add_nodes(fb_nodes);
+        builder.add_buffers(fb_buffers);
+        builder.finish()
+    };
+    finish_message(
+        &mut fbb,
+        ipc::MessageHeader::RecordBatch,
+        fb_batch.as_union_value(),
+        body.data.len(),
+    );
+    Ok((fbb.finished_data().to_vec(), body.data))
+}
+
+/// The field nodes, buffer locations and bytes of a record batch message body
+#[derive(Default)]
+struct MessageBody {
+    nodes: Vec<ipc::FieldNode>,
+    buffers: Vec<ipc::Buffer>,
+    data: Vec<u8>,
+}
+
+impl BodyVisitor for MessageBody {
+    fn visit_node(&mut self, len: usize, null_count: usize) -> Result<()> {
+        self.nodes
+            .push(ipc::FieldNode::new(len as i64, null_count as i64));
+        Ok(())
+    }
+
+    /// Appends a buffer followed by padding to the body
+    fn visit_buffer(
+        &mut self,
+        _buffer_type: BufferType,
+        buffer: BodyBuffer,
+    ) -> Result<()> {
+        self.buffers.push(ipc::Buffer::new(
+            self.data.len() as i64,
+            buffer.len() as i64,
+        ));
+        match buffer {
+            BodyBuffer::Bits { len, bit } => {
+                let mut bits = vec![0; bit_util::ceil(len, 8)];
+                for i in (0..len).filter(|&i| bit(i)) {
+                    bit_util::set_bit(&mut bits, i);
+                }
+                write_buffer_aligned(&mut self.data, &bits)?;
+            }
+            BodyBuffer::Offsets(offsets) => {
+                // rebased to start at zero, as the values are written from the first one
+                // that is referenced
+                let rebased: Vec<u8> = offsets
+                    .iter()
+                    .flat_map(|o| (o - offsets[0]).to_le_bytes().to_vec())
+                    .collect();
+                write_buffer_aligned(&mut self.data, &rebased)?;
+            }
+            BodyBuffer::Bytes(bytes) => {
+                write_buffer_aligned(&mut self.data, bytes)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::array::Int32Array;
+    use crate::datatypes::{DataType, Field};
+
+    #[test]
+    fn test_file_layout() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]))],
+        )
+        .unwrap();
+        let mut writer = FileWriter::try_new(vec![], &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch.slice(1, 2)).unwrap();
+        assert_eq!(2, writer.blocks.len());
+        for block in &writer.blocks {
+            assert_eq!(0, block.offset() % 8);
+            assert_eq!(0, block.metaDataLength() % 8);
+            assert_eq!(0, block.bodyLength() % 8);
+        }
+        assert_eq!(
+            writer.blocks[0].offset()
+                + writer.blocks[0].metaDataLength() as i64
+                + writer.blocks[0].bodyLength(),
+            writer.blocks[1].offset()
+        );
+
+        let data = writer.into_inner().unwrap();
+        assert_eq!(b"ARROW1\0\0", &data[..8]);
+        assert_eq!(b"ARROW1", &data[data.len() - 6..]);
+    }
+
+    #[test]
+    fn test_write_to_finished_file() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![Arc::new(Int32Array::from(vec![1, 2]))],
+        )
+        .unwrap();
+        let mut writer = FileWriter::try_new(vec![], &schema).unwrap();
+        writer.finish().unwrap();
+        assert!(writer.write(&batch).is_err());
+    }
+
+    #[test]
+    fn test_write_buffer_aligned() {
+        let mut out = vec![];
+        assert_eq!(8, write_buffer_aligned(&mut out, &[1, 2, 3, 4, 5]).unwrap());
+        assert_eq!(vec![1, 2, 3, 4, 5, 0, 0, 0], out);
+
+        assert_eq!(8, write_buffer_aligned(&mut out, &[6; 8]).unwrap());
+        assert_eq!(0, write_buffer_aligned(&mut out, &[]).unwrap());
+        assert_eq!(16, out.len());
+    }
+}
@@ -16,6 +16,9 @@
 // under the License.
 
 //! Transfer data between the Arrow memory format and JSON line-delimited records.
+//!
+//! Compressed input can be read by wrapping it in a
+//! `util::compression::CompressedReader`.
 
 pub mod reader;
 
@@ -31,9 +31,9 @@
 //! use std::sync::Arc;
 //!
 //! let schema = Schema::new(vec![
-//!     Field::new("a", DataType::Float64, false),
-//!     Field::new("b", DataType::Float64, false),
-//!     Field::new("c", DataType::Float64, false),
+//!     Field::new("a", DataType::Float64, true),
+//!     Field::new("b", DataType::Float64, true),
+//!     Field::new("c", DataType::Float64, true),
 //! ]);
 //!
 //! let file = File::open("test/data/basic.json").unwrap();
@@ -51,9 +51,11 @@ use std::sync::Arc;
 use serde_json::Value;
 
 use crate::array::*;
+use crate::buffer::MutableBuffer;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
 use crate::record_batch::RecordBatch;
+use crate::util::bit_util;
 
 /// Coerce data type during inference
 ///
@@ -170,7 +172,8 @@ fn infer_json_schema(file: File, max_read_records: Option<usize>) -> Result<Arc<
         if line.is_empty() {
             break;
         }
-        let record: Value = serde_json::from_str(&line.trim()).expect("Not valid JSON");
+        let record: Value = serde_json::from_str(&line.trim())
+            .map_err(|e| ArrowError::JsonError(format!("Not valid JSON: {}", e)))?;
 
         line = String::new();
 
@@ -319,10 +322,14 @@ pub struct Reader<R: Read> {
     schema: Arc<Schema>,
     /// Optional projection for which columns to load (case-sensitive names)
     projection: Option<Vec<String>>,
+    /// The fields of `schema` that are in the projection, the only columns that are built
+    projected_schema: Arc<Schema>,
     /// File reader
     reader: BufReader<R>,
     /// Batch size (number of records to load each time)
     batch_size: usize,
+    /// Optional limit on the number of input bytes to load each time
+    batch_bytes: Option<usize>,
 }
 
 impl<R: Read> Reader<R> {
@@ -330,318 +337,365 @@ impl<R: Read> Reader<R> {
     ///
     /// If reading a `File`, you can customise the Reader, such as to enable schema
     /// inference, use `ReaderBuilder`.
+    ///
+    /// An empty `projection` loads all columns, like `None`.
     pub fn new(
         reader: BufReader<R>,
         schema: Arc<Schema>,
         batch_size: usize,
         projection: Option<Vec<String>>,
     ) -> Self {
+        let projection = projection.filter(|projection| !projection.is_empty());
+        let projected_schema = match &projection {
+            Some(projection) => Arc::new(Schema::new(
+                schema
+                    .fields()
+                    .iter()
+                    .filter(|field| projection.contains(field.name()))
+                    .cloned()
+                    .collect(),
+            )),
+            None => schema.clone(),
+        };
         Self {
             schema,
             projection,
+            projected_schema,
             reader,
             batch_size,
+            batch_bytes: None,
         }
     }
 
+    /// Limits each batch to roughly `batch_bytes` bytes of input, in addition to the
+    /// `batch_size` record limit.
+    ///
+    /// A batch is closed once the lines read for it reach `batch_bytes`, so memory use
+    /// stays bounded regardless of the size of the input. Every batch holds at least one
+    /// record.
+    pub fn with_batch_bytes(mut self, batch_bytes: usize) -> Self {
+        self.batch_bytes = Some(batch_bytes);
+        self
+    }
+
     /// Returns the schema of the reader, useful for getting the schema without reading
     /// record batches
     pub fn schema(&self) -> Arc<Schema> {
-        match &self.projection {
-            Some(projection) => {
-                let fields = self.schema.fields();
-                let projected_fields: Vec<Field> = fields
-                    .iter()
-                    .filter_map(|field| {
-                        if projection.contains(field.name()) {
-                            Some(field.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-
-                Arc::new(Schema::new(projected_fields))
-            }
-            None => self.schema.clone(),
-        }
+        self.projected_schema.clone()
     }
 
     /// Read the next batch of records
+    ///
+    /// Returns `None` once the input is exhausted. Fields that are not part of the
+    /// projection are dropped from each record as soon as it is parsed.
     pub fn next(&mut self) -> Result<Option<RecordBatch>> {
         let mut rows: Vec<Value> = Vec::with_capacity(self.batch_size);
+        let mut bytes_read = 0;
         let mut line = String::new();
-        for _ in 0..self.batch_size {
-            self.reader.read_line(&mut line)?;
-            if !line.is_empty() {
-                rows.push(serde_json::from_str(&line).expect("Not valid JSON"));
-                line = String::new();
-            } else {
+        while rows.len() < self.batch_size {
+            if let Some(batch_bytes) = self.batch_bytes {
+                if !rows.is_empty() && bytes_read >= batch_bytes {
+                    break;
+                }
+            }
+            line.clear();
+            let n = self.reader.read_line(&mut line)?;
+            if n == 0 {
                 break;
             }
+            bytes_read += n;
+            let value: Value = serde_json::from_str(&line)
+                .map_err(|e| ArrowError::JsonError(format!("Not valid JSON: {}", e)))?;
+            let value = match (&self.projection, value) {
+                (Some(projection), Value::Object(map)) => Value::Object(
+                    map.into_iter()
+                        .filter(|(name, _)| projection.contains(name))
+                        .collect(),
+                ),
+                (_, value) => value,
+            };
+            rows.push(value);
+        }
+
+        if rows.is_empty() {
+            return Ok(None);
         }
 
         let rows = &rows[..];
-        let projection = self.projection.clone().unwrap_or(vec![]);
-        let arrays: Result<Vec<ArrayRef>> = self
-            .schema
-            .clone()
-            .fields()
-            .iter()
-            .filter(|field| {
-                if projection.is_empty() {
-                    return true;
-                }
-                projection.contains(field.name())
-            })
-            .map(|field| {
-                match field.data_type().clone() {
-                    DataType::Boolean => self.build_boolean_array(rows, field.name()),
-                    DataType::Float64 => {
-                        self.build_primitive_array::<Float64Type>(rows, field.name())
-                    }
-                    DataType::Float32 => {
-                        self.build_primitive_array::<Float32Type>(rows, field.name())
-                    }
-                    DataType::Int64 => self.build_primitive_array::<Int64Type>(rows, field.name()),
-                    DataType::Int32 => self.build_primitive_array::<Int32Type>(rows, field.name()),
-                    DataType::Int16 => self.build_primitive_array::<Int16Type>(rows, field.name()),
-                    DataType::Int8 => self.build_primitive_array::<Int8Type>(rows, field.name()),
-                    DataType::UInt64 => {
-                        self.build_primitive_array::<UInt64Type>(rows, field.name())
-                    }
-                    DataType::UInt32 => {
-                        self.build_primitive_array::<UInt32Type>(rows, field.name())
-                    }
-                    DataType::UInt16 => {
-                        self.build_primitive_array::<UInt16Type>(rows, field.name())
-                    }
-                    DataType::UInt8 => self.build_primitive_array::<UInt8Type>(rows, field.name()),
-                    DataType::Utf8 => {
-                        let mut builder = BinaryBuilder::new(rows.len());
-                        for row_index in 0..rows.len() {
-                            match rows[row_index].get(field.name()) {
-                                Some(value) => {
-                                    match value.as_str() {
-                                        Some(v) => builder.append_string(v)?,
-                                        // TODO: value might exist as something else, coerce so we don't lose it
-                                        None => builder.append(false)?,
-                                    }
-                                }
-                                None => builder.append(false)?,
-                            }
+        let projected_schema = self.schema();
+        // only the projected columns are built
+        let arrays = build_columns(rows, projected_schema.fields());
+
+        match arrays {
+            Ok(arr) => match RecordBatch::try_new(projected_schema, arr) {
+                Ok(batch) => Ok(Some(batch)),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Builds the arrays of `fields` from the values of the fields of the JSON objects
+/// `rows`, where missing values are nulls
+pub(crate) fn build_columns(rows: &[Value], fields: &[Field]) -> Result<Vec<ArrayRef>> {
+    fields
+        .iter()
+        .map(|field| build_array(rows, field))
+        .collect()
+}
+
+fn build_array(rows: &[Value], field: &Field) -> Result<ArrayRef> {
+    match field.data_type().clone() {
+        DataType::Boolean => build_boolean_array(rows, field.name()),
+        DataType::Float64 => build_primitive_array::<Float64Type>(rows, field.name()),
+        DataType::Float32 => build_primitive_array::<Float32Type>(rows, field.name()),
+        DataType::Int64 => build_primitive_array::<Int64Type>(rows, field.name()),
+        DataType::Int32 => build_primitive_array::<Int32Type>(rows, field.name()),
+        DataType::Int16 => build_primitive_array::<Int16Type>(rows, field.name()),
+        DataType::Int8 => build_primitive_array::<Int8Type>(rows, field.name()),
+        DataType::UInt64 => build_primitive_array::<UInt64Type>(rows, field.name()),
+        DataType::UInt32 => build_primitive_array::<UInt32Type>(rows, field.name()),
+        DataType::UInt16 => build_primitive_array::<UInt16Type>(rows, field.name()),
+        DataType::UInt8 => build_primitive_array::<UInt8Type>(rows, field.name()),
+        DataType::Utf8 => {
+            let mut builder = BinaryBuilder::new(rows.len());
+            for row_index in 0..rows.len() {
+                match rows[row_index].get(field.name()) {
+                    Some(value) => {
+                        match value.as_str() {
+                            Some(v) => builder.append_string(v)?,
+                            // TODO: value might exist as something else, coerce so we don't lose it
+                            None => builder.append(false)?,
                         }
-                        Ok(Arc::new(builder.finish()) as ArrayRef)
                     }
-                    DataType::List(ref t) => match **t {
-                        DataType::Int8 => self.build_list_array::<Int8Type>(rows, field.name()),
-                        DataType::Int16 => self.build_list_array::<Int16Type>(rows, field.name()),
-                        DataType::Int32 => self.build_list_array::<Int32Type>(rows, field.name()),
-                        DataType::Int64 => self.build_list_array::<Int64Type>(rows, field.name()),
-                        DataType::UInt8 => self.build_list_array::<UInt8Type>(rows, field.name()),
-                        DataType::UInt16 => self.build_list_array::<UInt16Type>(rows, field.name()),
-                        DataType::UInt32 => self.build_list_array::<UInt32Type>(rows, field.name()),
-                        DataType::UInt64 => self.build_list_array::<UInt64Type>(rows, field.name()),
-                        DataType::Float32 => self.build_list_array::<Float32Type>(rows, field.name()),
-                        DataType::Float64 => self.build_list_array::<Float64Type>(rows, field.name()),
-                        DataType::Boolean => self.build_boolean_list_array(rows, field.name()),
-                        DataType::Utf8 => {
-                            let values_builder = BinaryBuilder::new(rows.len() * 5);
-                            let mut builder = ListBuilder::new(values_builder);
-                            for row_index in 0..rows.len() {
-                                match rows[row_index].get(field.name()) {
-                                    Some(value) => {
-                                        // value can be an array or a scalar
-                                        let vals: Vec<Option<String>> = if let Value::String(v) = value {
-                                            vec![Some(v.to_string())]
-                                        } else if let Value::Array(n) = value {
-                                            n.iter().map(|v: &Value| {
-                                                if v.is_string() {
-                                                    Some(v.as_str().unwrap().to_string())
-                                                } else if v.is_array() || v.is_object() {
-                                                    // implicitly drop nested values
-                                                    // TODO support deep-nesting
-                                                    None
-                                                } else {
-                                                    Some(v.to_string())
-                                                }
-                                            }).collect()
-                                        } else if let Value::Null = value {
-                                            vec![None]
+                    None => builder.append(false)?,
+                }
+            }
+            Ok(Arc::new(builder.finish()) as ArrayRef)
+        }
+        DataType::List(ref t) => match **t {
+            DataType::Int8 => build_list_array::<Int8Type>(rows, field.name()),
+            DataType::Int16 => build_list_array::<Int16Type>(rows, field.name()),
+            DataType::Int32 => build_list_array::<Int32Type>(rows, field.name()),
+            DataType::Int64 => build_list_array::<Int64Type>(rows, field.name()),
+            DataType::UInt8 => build_list_array::<UInt8Type>(rows, field.name()),
+            DataType::UInt16 => build_list_array::<UInt16Type>(rows, field.name()),
+            DataType::UInt32 => build_list_array::<UInt32Type>(rows, field.name()),
+            DataType::UInt64 => build_list_array::<UInt64Type>(rows, field.name()),
+            DataType::Float32 => build_list_array::<Float32Type>(rows, field.name()),
+            DataType::Float64 => build_list_array::<Float64Type>(rows, field.name()),
+            DataType::Boolean => build_boolean_list_array(rows, field.name()),
+            DataType::Utf8 => {
+                let values_builder = BinaryBuilder::new(rows.len() * 5);
+                let mut builder = ListBuilder::new(values_builder);
+                for row_index in 0..rows.len() {
+                    match rows[row_index].get(field.name()) {
+                        Some(value) => {
+                            // value can be an array or a scalar
+                            let vals: Vec<Option<String>> = if let Value::String(v) =
+                                value
+                            {
+                                vec![Some(v.to_string())]
+                            } else if let Value::Array(n) = value {
+                                n.iter()
+                                    .map(|v: &Value| {
+                                        if v.is_string() {
+                                            Some(v.as_str().unwrap().to_string())
+                                        } else if v.is_array() || v.is_object() {
+                                            // implicitly drop nested values
+                                            // TODO support deep-nesting
+                                            None
                                         } else {
-                                            if !value.is_object() {
-                                                vec![Some(value.to_string())]
-                                            } else {
-                                                return Err(ArrowError::JsonError("1Only scalars are currently supported in JSON arrays".to_string()))
-                                            }
-                                        };
-                                        for i in 0..vals.len() {
-                                            match &vals[i] {
-                                                Some(v) => builder.values().append_string(&v)?,
-                                                None => builder.values().append_null()?,
-                                            };
+                                            Some(v.to_string())
                                         }
-                                    }
-                                    None => {}
+                                    })
+                                    .collect()
+                            } else if let Value::Null = value {
+                                vec![None]
+                            } else {
+                                if !value.is_object() {
+                                    vec![Some(value.to_string())]
+                                } else {
+                                    return Err(ArrowError::JsonError("1Only scalars are currently supported in JSON arrays".to_string()));
                                 }
-                                builder.append(true)?
+                            };
+                            for i in 0..vals.len() {
+                                match &vals[i] {
+                                    Some(v) => builder.values().append_string(&v)?,
+                                    None => builder.values().append_null()?,
+                                };
                             }
-                            Ok(Arc::new(builder.finish()) as ArrayRef)
                         }
-                        _ => return Err(ArrowError::JsonError("Data type is currently not supported in a list".to_string())),
-                    },
-                    _ => return Err(ArrowError::JsonError("struct types are not yet supported".to_string())),
+                        None => {}
+                    }
+                    builder.append(true)?
                 }
-            })
-            .collect();
-
-        let projected_fields: Vec<Field> = if projection.is_empty() {
-            self.schema.fields().to_vec()
-        } else {
-            projection
-                .iter()
-                .map(|name| self.schema.column_with_name(name))
-                .filter_map(|c| c)
-                .map(|(_, field)| field.clone())
-                .collect()
-        };
-
-        let projected_schema = Arc::new(Schema::new(projected_fields));
+                Ok(Arc::new(builder.finish()) as ArrayRef)
+            }
+            _ => {
+                return Err(ArrowError::JsonError(
+                    "Data type is currently not supported in a list".to_string(),
+                ))
+            }
+        },
+        DataType::Struct(fields) => build_struct_array(rows, field.name(), &fields),
+        _ => {
+            return Err(ArrowError::JsonError(
+                "Data type is currently not supported".to_string(),
+            ))
+        }
+    }
+}
 
-        match arrays {
-            Ok(arr) => match RecordBatch::try_new(projected_schema, arr) {
-                Ok(batch) => Ok(Some(batch)),
-                Err(e) => Err(e),
+fn build_boolean_array(rows: &[Value], col_name: &str) -> Result<ArrayRef> {
+    let mut builder = BooleanBuilder::new(rows.len());
+    for row_index in 0..rows.len() {
+        match rows[row_index].get(col_name) {
+            Some(value) => match value.as_bool() {
+                Some(v) => builder.append_value(v)?,
+                None => builder.append_null()?,
             },
-            Err(e) => Err(e),
+            None => {
+                builder.append_null()?;
+            }
         }
     }
+    Ok(Arc::new(builder.finish()))
+}
 
-    fn build_boolean_array(&self, rows: &[Value], col_name: &str) -> Result<ArrayRef> {
-        let mut builder = BooleanBuilder::new(rows.len());
-        for row_index in 0..rows.len() {
-            match rows[row_index].get(col_name) {
-                Some(value) => match value.as_bool() {
-                    Some(v) => builder.append_value(v)?,
-                    None => builder.append_null()?,
-                },
-                None => {
-                    builder.append_null()?;
+fn build_boolean_list_array(rows: &[Value], col_name: &str) -> Result<ArrayRef> {
+    let values_builder = BooleanBuilder::new(rows.len() * 5);
+    let mut builder = ListBuilder::new(values_builder);
+    for row_index in 0..rows.len() {
+        match rows[row_index].get(col_name) {
+            Some(value) => {
+                // value can be an array or a scalar
+                let vals: Vec<Option<bool>> = if let Value::Bool(v) = value {
+                    vec![Some(*v)]
+                } else if let Value::Array(n) = value {
+                    n.iter().map(|v: &Value| v.as_bool()).collect()
+                } else if let Value::Null = value {
+                    vec![None]
+                } else {
+                    return Err(ArrowError::JsonError(
+                        "2Only scalars are currently supported in JSON arrays"
+                            .to_string(),
+                    ));
+                };
+                for i in 0..vals.len() {
+                    match vals[i] {
+                        Some(v) => builder.values().append_value(v)?,
+                        None => builder.values().append_null()?,
+                    };
                 }
             }
+            None => {}
         }
-        Ok(Arc::new(builder.finish()))
+        builder.append(true)?
     }
+    Ok(Arc::new(builder.finish()))
+}
 
-    fn build_boolean_list_array(
-        &self,
-        rows: &[Value],
-        col_name: &str,
-    ) -> Result<ArrayRef> {
-        let values_builder = BooleanBuilder::new(rows.len() * 5);
-        let mut builder = ListBuilder::new(values_builder);
-        for row_index in 0..rows.len() {
-            match rows[row_index].get(col_name) {
-                Some(value) => {
-                    // value can be an array or a scalar
-                    let vals: Vec<Option<bool>> = if let Value::Bool(v) = value {
-                        vec![Some(*v)]
-                    } else if let Value::Array(n) = value {
-                        n.iter().map(|v: &Value| v.as_bool()).collect()
-                    } else if let Value::Null = value {
-                        vec![None]
-                    } else {
-                        return Err(ArrowError::JsonError(
-                            "2Only scalars are currently supported in JSON arrays"
-                                .to_string(),
-                        ));
-                    };
-                    for i in 0..vals.len() {
-                        match vals[i] {
-                            Some(v) => builder.values().append_value(v)?,
-                            None => builder.values().append_null()?,
-                        };
-                    }
+fn build_primitive_array<T: ArrowPrimitiveType>(
+    rows: &[Value],
+    col_name: &str,
+) -> Result<ArrayRef>
+where
+    T: ArrowNumericType,
+    T::Native: num::NumCast,
+{
+    let mut builder = PrimitiveBuilder::<T>::new(rows.len());
+    for row_index in 0..rows.len() {
+        match rows[row_index].get(col_name) {
+            Some(value) => {
+                // check that value is of expected datatype
+                match value.as_f64() {
+                    Some(v) => match num::cast::cast(v) {
+                        Some(v) => builder.append_value(v)?,
+                        None => builder.append_null()?,
+                    },
+                    None => builder.append_null()?,
                 }
-                None => {}
             }
-            builder.append(true)?
+            None => {
+                builder.append_null()?;
+            }
         }
-        Ok(Arc::new(builder.finish()))
     }
+    Ok(Arc::new(builder.finish()))
+}
 
-    fn build_primitive_array<T: ArrowPrimitiveType>(
-        &self,
-        rows: &[Value],
-        col_name: &str,
-    ) -> Result<ArrayRef>
-    where
-        T: ArrowNumericType,
-        T::Native: num::NumCast,
-    {
-        let mut builder = PrimitiveBuilder::<T>::new(rows.len());
-        for row_index in 0..rows.len() {
-            match rows[row_index].get(col_name) {
-                Some(value) => {
-                    // check that value is of expected datatype
-                    match value.as_f64() {
+fn build_list_array<T: ArrowPrimitiveType>(
+    rows: &[Value],
+    col_name: &str,
+) -> Result<ArrayRef>
+where
+    T::Native: num::NumCast,
+{
+    let values_builder: PrimitiveBuilder<T> = PrimitiveBuilder::new(rows.len());
+    let mut builder = ListBuilder::new(values_builder);
+    for row_index in 0..rows.len() {
+        match rows[row_index].get(col_name) {
+            Some(value) => {
+                // value can be an array or a scalar
+                let vals: Vec<Option<f64>> = if let Value::Number(value) = value {
+                    vec![value.as_f64()]
+                } else if let Value::Array(n) = value {
+                    n.iter().map(|v: &Value| v.as_f64()).collect()
+                } else if let Value::Null = value {
+                    vec![None]
+                } else {
+                    return Err(ArrowError::JsonError(
+                        "3Only scalars are currently supported in JSON arrays"
+                            .to_string(),
+                    ));
+                };
+                for i in 0..vals.len() {
+                    match vals[i] {
                         Some(v) => match num::cast::cast(v) {
-                            Some(v) => builder.append_value(v)?,
-                            None => builder.append_null()?,
+                            Some(v) => builder.values().append_value(v)?,
+                            None => builder.values().append_null()?,
                         },
-                        None => builder.append_null()?,
-                    }
-                }
-                None => {
-                    builder.append_null()?;
+                        None => builder.values().append_null()?,
+                    };
                 }
             }
+            None => {}
         }
-        Ok(Arc::new(builder.finish()))
+        builder.append(true)?
     }
+    Ok(Arc::new(builder.finish()))
+}
 
-    fn build_list_array<T: ArrowPrimitiveType>(
-        &self,
-        rows: &[Value],
-        col_name: &str,
-    ) -> Result<ArrayRef>
-    where
-        T::Native: num::NumCast,
+/// Builds a struct array from the objects under `col_name`, where values that are not
+/// objects are nulls
+fn build_struct_array(
+    rows: &[Value],
+    col_name: &str,
+    fields: &[Field],
+) -> Result<ArrayRef> {
+    let values: Vec<Value> = rows
+        .iter()
+        .map(|row| row.get(col_name).cloned().unwrap_or(Value::Null))
+        .collect();
+    let num_bytes = bit_util::ceil(values.len(), 8);
+    let mut null_buf = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
     {
-        let values_builder: PrimitiveBuilder<T> = PrimitiveBuilder::new(rows.len());
-        let mut builder = ListBuilder::new(values_builder);
-        for row_index in 0..rows.len() {
-            match rows[row_index].get(col_name) {
-                Some(value) => {
-                    // value can be an array or a scalar
-                    let vals: Vec<Option<f64>> = if let Value::Number(value) = value {
-                        vec![value.as_f64()]
-                    } else if let Value::Array(n) = value {
-                        n.iter().map(|v: &Value| v.as_f64()).collect()
-                    } else if let Value::Null = value {
-                        vec![None]
-                    } else {
-                        return Err(ArrowError::JsonError(
-                            "3Only scalars are currently supported in JSON arrays"
-                                .to_string(),
-                        ));
-                    };
-                    for i in 0..vals.len() {
-                        match vals[i] {
-                            Some(v) => match num::cast::cast(v) {
-                                Some(v) => builder.values().append_value(v)?,
-                                None => builder.values().append_null()?,
-                            },
-                            None => builder.values().append_null()?,
-                        };
-                    }
-                }
-                None => {}
+        let null_slice = null_buf.data_mut();
+        for (i, value) in values.iter().enumerate() {
+            if value.is_object() {
+                bit_util::set_bit(null_slice, i);
             }
-            builder.append(true)?
         }
-        Ok(Arc::new(builder.finish()))
     }
+    let children = build_columns(&values, fields)?;
+    let data = ArrayData::builder(DataType::Struct(fields.to_vec()))
+        .len(values.len())
+        .null_bit_buffer(null_buf.freeze())
+        .child_data(children.iter().map(|c| c.data()).collect())
+        .build();
+    Ok(Arc::new(StructArray::from(data)))
 }
 
 /// JSON file reader builder
@@ -659,8 +713,10 @@ pub struct ReaderBuilder {
     ///
     /// The default batch size when using the `ReaderBuilder` is 1024 records
     batch_size: usize,
-    /// Optional projection for which columns to load (zero-based column indices)
+    /// Optional projection for which columns to load (case-sensitive names)
     projection: Option<Vec<String>>,
+    /// Optional limit on the number of input bytes to load each time
+    batch_bytes: Option<usize>,
 }
 
 impl Default for ReaderBuilder {
@@ -670,6 +726,7 @@ impl Default for ReaderBuilder {
             max_records: None,
             batch_size: 1024,
             projection: None,
+            batch_bytes: None,
         }
     }
 }
@@ -722,12 +779,19 @@ impl ReaderBuilder {
         self
     }
 
-    /// Set the reader's column projection
+    /// Set the reader's column projection to the columns named in `projection`, in the
+    /// order of the schema. An empty projection loads all columns.
     pub fn with_projection(mut self, projection: Vec<String>) -> Self {
         self.projection = Some(projection);
         self
     }
 
+    /// Set the approximate number of input bytes to load at one time
+    pub fn with_batch_bytes(mut self, batch_bytes: usize) -> Self {
+        self.batch_bytes = Some(batch_bytes);
+        self
+    }
+
     /// Create a new `Reader` from the `ReaderBuilder`
     pub fn build<R: Read>(self, file: File) -> Result<Reader<File>> {
         // check if schema should be inferred
@@ -740,12 +804,10 @@ impl ReaderBuilder {
             }
         };
         let buf_reader = BufReader::new(file);
-        Ok(Reader::new(
-            buf_reader,
-            schema,
-            self.batch_size,
-            self.projection,
-        ))
+        let mut reader =
+            Reader::new(buf_reader, schema, self.batch_size, self.projection);
+        reader.batch_bytes = self.batch_bytes;
+        Ok(reader)
     }
 }
 
@@ -873,7 +935,7 @@ mod tests {
     #[test]
     fn test_json_basic_schema() {
         let schema = Schema::new(vec![
-            Field::new("a", DataType::Int32, false),
+            Field::new("a", DataType::Int32, true),
             Field::new("b", DataType::Float32, false),
             Field::new("c", DataType::Boolean, false),
             Field::new("d", DataType::Utf8, false),
@@ -926,7 +988,7 @@ mod tests {
         // Implicit: omitting fields from a schema
         // Explicit: supplying a vec of fields to take
         let schema = Schema::new(vec![
-            Field::new("a", DataType::Int32, false),
+            Field::new("a", DataType::Int32, true),
             Field::new("b", DataType::Float32, false),
             Field::new("c", DataType::Boolean, false),
         ]);
@@ -939,7 +1001,7 @@ mod tests {
         );
         let reader_schema = reader.schema();
         let expected_schema = Arc::new(Schema::new(vec![
-            Field::new("a", DataType::Int32, false),
+            Field::new("a", DataType::Int32, true),
             Field::new("c", DataType::Boolean, false),
         ]));
         assert_eq!(reader_schema.clone(), expected_schema);
@@ -961,6 +1023,55 @@ mod tests {
         assert_eq!(&DataType::Boolean, c.1.data_type());
     }
 
+    #[test]
+    fn test_json_projection_in_batches() {
+        let builder = ReaderBuilder::new()
+            .infer_schema(None)
+            .with_batch_size(5)
+            .with_projection(vec!["d".to_string(), "a".to_string()]);
+        let mut reader: Reader<File> = builder
+            .build::<File>(File::open("test/data/basic.json").unwrap())
+            .unwrap();
+
+        let mut num_rows = vec![];
+        while let Some(batch) = reader.next().unwrap() {
+            assert_eq!(2, batch.num_columns());
+            assert_eq!("a", batch.schema().field(0).name());
+            assert_eq!("d", batch.schema().field(1).name());
+            num_rows.push(batch.num_rows());
+        }
+        assert_eq!(vec![5, 5, 2], num_rows);
+    }
+
+    #[test]
+    fn test_json_empty_projection() {
+        let builder = ReaderBuilder::new()
+            .infer_schema(None)
+            .with_projection(vec![]);
+        let mut reader: Reader<File> = builder
+            .build::<File>(File::open("test/data/basic.json").unwrap())
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(4, batch.num_columns());
+        assert_eq!(reader.schema(), batch.schema());
+    }
+
+    #[test]
+    fn test_json_batch_bytes() {
+        let builder = ReaderBuilder::new().infer_schema(None).with_batch_bytes(1);
+        let mut reader: Reader<File> = builder
+            .build::<File>(File::open("test/data/basic.json").unwrap())
+            .unwrap();
+
+        let mut num_batches = 0;
+        while let Some(batch) = reader.next().unwrap() {
+            assert_eq!(1, batch.num_rows());
+            assert_eq!(4, batch.num_columns());
+            num_batches += 1;
+        }
+        assert_eq!(12, num_batches);
+    }
+
     #[test]
     fn test_json_arrays() {
         let builder = ReaderBuilder::new().infer_schema(None).with_batch_size(64);
@@ -1022,13 +1133,28 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Not valid JSON")]
     fn test_invalid_file() {
         let builder = ReaderBuilder::new().infer_schema(None).with_batch_size(64);
+        match builder
+            .build::<File>(File::open("test/data/uk_cities_with_headers.csv").unwrap())
+        {
+            Err(ArrowError::JsonError(message)) => {
+                assert!(message.starts_with("Not valid JSON"))
+            }
+            _ => panic!("Expected a JSON error while inferring the schema"),
+        }
+
+        let schema = Schema::new(vec![Field::new("city", DataType::Utf8, true)]);
+        let builder = ReaderBuilder::new().with_schema(Arc::new(schema));
         let mut reader: Reader<File> = builder
             .build::<File>(File::open("test/data/uk_cities_with_headers.csv").unwrap())
             .unwrap();
-        let _batch = reader.next().unwrap().unwrap();
+        match reader.next() {
+            Err(ArrowError::JsonError(message)) => {
+                assert!(message.starts_with("Not valid JSON"))
+            }
+            _ => panic!("Expected a JSON error while reading a batch"),
+        }
     }
 
     #[test]
@@ -1120,4 +1246,37 @@ mod tests {
         assert_eq!("array", &String::from_utf8(dd.value(5).to_vec()).unwrap());
         assert_eq!("2.4", &String::from_utf8(dd.value(6).to_vec()).unwrap());
     }
+
+    #[test]
+    fn test_build_struct_columns() {
+        let rows = vec![
+            serde_json::json!({"s": {"a": 1, "b": "x"}}),
+            serde_json::json!({"s": null}),
+            serde_json::json!({}),
+            serde_json::json!({"s": {"a": 4}}),
+        ];
+        let fields = vec![Field::new(
+            "s",
+            DataType::Struct(vec![
+                Field::new("a", DataType::Int64, true),
+                Field::new("b", DataType::Utf8, true),
+            ]),
+            true,
+        )];
+        let columns = build_columns(&rows, &fields).unwrap();
+        let s = columns[0].as_any().downcast_ref::<StructArray>().unwrap();
+        assert_eq!(4, s.len());
+        assert_eq!(2, s.null_count());
+        assert!(s.is_valid(0));
+        assert!(s.is_null(1));
+        assert!(s.is_null(2));
+        assert!(s.is_valid(3));
+
+        let a = s.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(1, a.value(0));
+        assert_eq!(4, a.value(3));
+        let b = s.column(1).as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("x", b.get_string(0));
+        assert!(b.is_null(3));
+    }
 }
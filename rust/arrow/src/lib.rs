@@ -37,5 +37,7 @@ pub mod ipc;
 pub mod json;
 pub mod memory;
 pub mod record_batch;
+pub mod scalar;
+pub mod table;
 pub mod tensor;
 pub mod util;
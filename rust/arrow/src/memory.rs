@@ -21,6 +21,11 @@
 use std::alloc::Layout;
 use std::mem::align_of;
 
+/// The alignment in bytes of the memory allocated for buffers.
+///
+/// SIMD kernels rely on buffers being aligned to this many bytes, so buffers imported
+/// from elsewhere should be checked with `Buffer::is_aligned`, and copied with
+/// `Buffer::to_aligned` when they aren't.
 pub const ALIGNMENT: usize = 64;
 
 pub fn allocate_aligned(size: usize) -> *mut u8 {
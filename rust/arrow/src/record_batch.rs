@@ -24,14 +24,39 @@
 use std::sync::Arc;
 
 use crate::array::*;
+use crate::compute::kernels::cast::{cast_with_options, CastOptions};
+use crate::compute::kernels::repeat::repeat;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
+use crate::scalar::ScalarValue;
 
 /// A batch of column-oriented data
 #[derive(Clone)]
 pub struct RecordBatch {
     schema: Arc<Schema>,
     columns: Vec<Arc<Array>>,
+    row_count: usize,
+}
+
+/// Options that relax the validation performed when creating a `RecordBatch`
+#[derive(Debug, Clone)]
+pub struct RecordBatchOptions {
+    /// Whether nested field names (e.g. those of struct children) in the column data
+    /// types have to match the ones in the schema. When `false`, only the nested types
+    /// and their nullability are compared.
+    pub match_field_names: bool,
+    /// The number of rows in the batch. Required when creating a batch without any
+    /// columns; otherwise it has to match the length of the columns.
+    pub row_count: Option<usize>,
+}
+
+impl Default for RecordBatchOptions {
+    fn default() -> Self {
+        Self {
+            match_field_names: true,
+            row_count: None,
+        }
+    }
 }
 
 impl RecordBatch {
@@ -41,9 +66,23 @@ impl RecordBatch {
     ///  * the vec of columns to not be empty
     ///  * the schema and column data types to have equal lengths and match
     ///  * each array in columns to have the same length
+    ///  * the columns of fields that aren't nullable to have no null values
     pub fn try_new(schema: Arc<Schema>, columns: Vec<ArrayRef>) -> Result<Self> {
-        // check that there are some columns
-        if columns.is_empty() {
+        Self::try_new_with_options(schema, columns, &RecordBatchOptions::default())
+    }
+
+    /// Creates a `RecordBatch` from a schema and columns, with the validation
+    /// relaxed as described by `options`.
+    ///
+    /// Unlike `try_new`, this allows creating a batch without columns as long as
+    /// `options.row_count` is set.
+    pub fn try_new_with_options(
+        schema: Arc<Schema>,
+        columns: Vec<ArrayRef>,
+        options: &RecordBatchOptions,
+    ) -> Result<Self> {
+        // check that there are some columns, or an explicit row count
+        if columns.is_empty() && options.row_count.is_none() {
             return Err(ArrowError::InvalidArgumentError(
                 "at least one column must be defined to create a record batch"
                     .to_string(),
@@ -56,22 +95,41 @@ impl RecordBatch {
             ));
         }
         // check that all columns have the same row count, and match the schema
-        let len = columns[0].data().len();
+        let len = options.row_count.unwrap_or_else(|| columns[0].data().len());
         for i in 0..columns.len() {
             if columns[i].len() != len {
                 return Err(ArrowError::InvalidArgumentError(
                     "all columns in a record batch must have the same length".to_string(),
                 ));
             }
-            if columns[i].data_type() != schema.field(i).data_type() {
+            let types_match = if options.match_field_names {
+                columns[i].data_type() == schema.field(i).data_type()
+            } else {
+                columns[i]
+                    .data_type()
+                    .equals_ignore_names(schema.field(i).data_type())
+            };
+            if !types_match {
                 return Err(ArrowError::InvalidArgumentError(format!(
                     "column types must match schema types, expected {:?} but found {:?} at column index {}", 
                     schema.field(i).data_type(),
                     columns[i].data_type(),
                     i)));
             }
+            if !schema.field(i).is_nullable() && columns[i].null_count() > 0 {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "column \"{}\" at index {} is not nullable, but has {} null values",
+                    schema.field(i).name(),
+                    i,
+                    columns[i].null_count()
+                )));
+            }
         }
-        Ok(RecordBatch { schema, columns })
+        Ok(RecordBatch {
+            schema,
+            columns,
+            row_count: len,
+        })
     }
 
     /// Returns the schema of the record batch
@@ -86,7 +144,7 @@ impl RecordBatch {
 
     /// Number of rows in each column
     pub fn num_rows(&self) -> usize {
-        self.columns[0].data().len()
+        self.row_count
     }
 
     /// Get a reference to a column's array by index
@@ -98,6 +156,216 @@ impl RecordBatch {
     pub fn columns(&self) -> &[ArrayRef] {
         &self.columns[..]
     }
+
+    /// Returns a zero-copy slice of this record batch with the indicated offset and
+    /// length.
+    ///
+    /// Every column is sliced the same way, sharing the buffers of this batch. Slicing a
+    /// slice adds up the offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset + length` is greater than the number of rows.
+    pub fn slice(&self, offset: usize, length: usize) -> RecordBatch {
+        assert!(
+            offset + length <= self.row_count,
+            "the slice {}..{} is out of bounds of a record batch of {} rows",
+            offset,
+            offset + length,
+            self.row_count
+        );
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| column.slice(offset, length))
+            .collect();
+        RecordBatch {
+            schema: self.schema.clone(),
+            columns,
+            row_count: length,
+        }
+    }
+
+    /// Casts the columns of this record batch to the types of the fields of `target`.
+    ///
+    /// The target schema has to have the same number of fields as this batch, with the
+    /// same names in the same order. Columns that already have the target type are
+    /// shared without copying.
+    pub fn cast_to_schema(
+        &self,
+        target: &Schema,
+        options: CastOptions,
+    ) -> Result<RecordBatch> {
+        if target.fields().len() != self.num_columns() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "cannot cast a record batch with {} columns to a schema with {} fields",
+                self.num_columns(),
+                target.fields().len()
+            )));
+        }
+        let mut columns = Vec::with_capacity(self.num_columns());
+        for (i, (column, field)) in self.columns.iter().zip(target.fields()).enumerate() {
+            if self.schema.field(i).name() != field.name() {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "expected field \"{}\" but found \"{}\" at column index {}",
+                    field.name(),
+                    self.schema.field(i).name(),
+                    i
+                )));
+            }
+            if column.data_type() == field.data_type() {
+                columns.push(column.clone());
+            } else {
+                columns.push(cast_with_options(column, field.data_type(), &options)?);
+            }
+        }
+        RecordBatch::try_new_with_options(
+            Arc::new(target.clone()),
+            columns,
+            &RecordBatchOptions {
+                match_field_names: true,
+                row_count: Some(self.row_count),
+            },
+        )
+    }
+
+    /// Returns a copy of this record batch whose columns are in the order of the fields of
+    /// `target`, matching the columns to the fields by name.
+    ///
+    /// The target schema has to have a field for every column of this batch and no
+    /// others, with the same data types, so each column is used exactly once and a name
+    /// can only repeat in `target` as often as it does in this batch. The columns are
+    /// shared without copying.
+    pub fn reorder_to(&self, target: &Schema) -> Result<RecordBatch> {
+        if target.fields().len() != self.num_columns() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "cannot reorder a record batch with {} columns to a schema with {} fields",
+                self.num_columns(),
+                target.fields().len()
+            )));
+        }
+        let mut columns = Vec::with_capacity(self.num_columns());
+        let mut used = vec![false; self.num_columns()];
+        for field in target.fields() {
+            let index = self
+                .schema
+                .fields()
+                .iter()
+                .enumerate()
+                .position(|(i, f)| !used[i] && f.name() == field.name())
+                .ok_or_else(|| {
+                    ArrowError::InvalidArgumentError(format!(
+                        "no unused column named \"{}\" in the record batch",
+                        field.name()
+                    ))
+                })?;
+            used[index] = true;
+            columns.push(self.columns[index].clone());
+        }
+        RecordBatch::try_new_with_options(
+            Arc::new(target.clone()),
+            columns,
+            &RecordBatchOptions {
+                match_field_names: true,
+                row_count: Some(self.row_count),
+            },
+        )
+    }
+
+    /// Splits this record batch into zero-copy slices that are each estimated to fit
+    /// within `max_bytes`.
+    ///
+    /// The estimate is derived from the average number of bytes per row across all
+    /// columns, as reported by `get_array_memory_size`. Each slice holds at least one
+    /// row, so a single row larger than the budget is returned in a batch of its own.
+    /// The last slice may hold fewer rows than the others.
+    pub fn split_to_budget(&self, max_bytes: usize) -> Vec<RecordBatch> {
+        let num_rows = self.num_rows();
+        if num_rows == 0 {
+            return vec![self.clone()];
+        }
+        // the sizes saturate rather than overflow, and the divisions round up without
+        // adding to the dividend, so that no budget can overflow the capacity
+        let total_bytes = self.columns.iter().fold(0usize, |total, column| {
+            total.saturating_add(column.get_array_memory_size())
+        });
+        let bytes_per_row = ::std::cmp::max(1, div_ceil(total_bytes, num_rows));
+        let rows_per_batch = ::std::cmp::max(1, max_bytes / bytes_per_row);
+
+        let mut batches = Vec::with_capacity(div_ceil(num_rows, rows_per_batch));
+        let mut offset = 0;
+        while offset < num_rows {
+            let length = ::std::cmp::min(rows_per_batch, num_rows - offset);
+            batches.push(self.slice(offset, length));
+            offset += length;
+        }
+        batches
+    }
+
+    /// Returns a copy of this record batch with a column appended for `field` that holds
+    /// `value` in every row.
+    ///
+    /// A `ScalarValue::Null` value results in a column of nulls; any other value has to
+    /// be of the data type of `field`.
+    pub fn add_constant_column(
+        &self,
+        field: Field,
+        value: ScalarValue,
+    ) -> Result<RecordBatch> {
+        let column = repeat(&value, field.data_type(), self.num_rows())?;
+        let mut fields = self.schema.fields().clone();
+        fields.push(field);
+        let mut columns = self.columns.clone();
+        columns.push(column);
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+    }
+
+    /// Creates a `RecordBatch` from named columns and whether each may contain nulls,
+    /// returning the first error of the columns if there is one. Used by the
+    /// `record_batch!` macro.
+    #[doc(hidden)]
+    pub fn try_from_named_columns(
+        named_columns: Vec<(&str, Result<(ArrayRef, bool)>)>,
+    ) -> Result<RecordBatch> {
+        let mut fields = Vec::with_capacity(named_columns.len());
+        let mut columns = Vec::with_capacity(named_columns.len());
+        for (name, column) in named_columns {
+            let (column, nullable) = column?;
+            fields.push(Field::new(name, column.data_type().clone(), nullable));
+            columns.push(column);
+        }
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+    }
+}
+
+/// Returns `a / b` rounded up, without overflowing for any `a`
+fn div_ceil(a: usize, b: usize) -> usize {
+    a / b + if a % b == 0 { 0 } else { 1 }
+}
+
+#[cfg(feature = "serde_rows")]
+impl RecordBatch {
+    /// Creates a `RecordBatch` from a slice of serializable rows.
+    ///
+    /// Each row is serialized to a JSON value and its fields are read by name with the
+    /// same rules as the JSON reader, so nested structs map to `Struct` columns and
+    /// sequences to `List` columns. Fields missing from a row are nulls.
+    ///
+    /// Requires the `serde_rows` feature.
+    pub fn from_serde_rows<T: serde::Serialize>(
+        rows: &[T],
+        schema: &Schema,
+    ) -> Result<RecordBatch> {
+        let values = rows
+            .iter()
+            .map(|row| {
+                serde_json::to_value(row)
+                    .map_err(|e| ArrowError::JsonError(e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let columns = crate::json::reader::build_columns(&values, schema.fields())?;
+        RecordBatch::try_new(Arc::new(schema.clone()), columns)
+    }
 }
 
 impl From<&StructArray> for RecordBatch {
@@ -111,6 +379,7 @@ impl From<&StructArray> for RecordBatch {
             RecordBatch {
                 schema: Arc::new(schema),
                 columns,
+                row_count: struct_array.len(),
             }
         } else {
             unreachable!("unable to get datatype as struct")
@@ -144,6 +413,84 @@ pub trait RecordBatchReader {
     fn next_batch(&mut self) -> Result<Option<RecordBatch>>;
 }
 
+/// A vector of values that can be turned into a column of a `RecordBatch`, used by the
+/// `record_batch!` macro to infer the type of each column.
+pub trait IntoColumn {
+    /// Returns the column, and whether it may contain nulls.
+    fn into_column(self) -> Result<(ArrayRef, bool)>;
+}
+
+macro_rules! into_column {
+    ($native_ty:ty, $array_ty:ty) => {
+        impl IntoColumn for Vec<$native_ty> {
+            fn into_column(self) -> Result<(ArrayRef, bool)> {
+                Ok((Arc::new(<$array_ty>::from(self)) as ArrayRef, false))
+            }
+        }
+
+        impl IntoColumn for Vec<Option<$native_ty>> {
+            fn into_column(self) -> Result<(ArrayRef, bool)> {
+                Ok((Arc::new(<$array_ty>::from(self)) as ArrayRef, true))
+            }
+        }
+    };
+}
+
+into_column!(bool, BooleanArray);
+into_column!(i8, Int8Array);
+into_column!(i16, Int16Array);
+into_column!(i32, Int32Array);
+into_column!(i64, Int64Array);
+into_column!(u8, UInt8Array);
+into_column!(u16, UInt16Array);
+into_column!(u32, UInt32Array);
+into_column!(u64, UInt64Array);
+into_column!(f32, Float32Array);
+into_column!(f64, Float64Array);
+
+impl<'a> IntoColumn for Vec<&'a str> {
+    fn into_column(self) -> Result<(ArrayRef, bool)> {
+        Ok((Arc::new(BinaryArray::from(self)) as ArrayRef, false))
+    }
+}
+
+impl<'a> IntoColumn for Vec<Option<&'a str>> {
+    fn into_column(self) -> Result<(ArrayRef, bool)> {
+        use std::convert::TryFrom;
+        Ok((Arc::new(BinaryArray::try_from(self)?) as ArrayRef, true))
+    }
+}
+
+/// Creates a `RecordBatch` from named lists of values, inferring the type of each
+/// column from its values. Columns of `Option` values are nullable, with `None` as a
+/// null.
+///
+/// Evaluates to a `Result<RecordBatch>`, which is an error if the columns have
+/// different lengths.
+///
+/// ```
+/// use arrow::record_batch;
+///
+/// let batch = record_batch!(
+///     "a" => [1, 2, 3],
+///     "b" => [Some("x"), None, Some("z")]
+/// )
+/// .unwrap();
+/// assert_eq!(3, batch.num_rows());
+/// assert!(batch.schema().field(1).is_nullable());
+/// ```
+#[macro_export]
+macro_rules! record_batch {
+    ($($name:expr => [$($value:expr),* $(,)?]),* $(,)?) => {
+        $crate::record_batch::RecordBatch::try_from_named_columns(vec![$(
+            (
+                $name,
+                $crate::record_batch::IntoColumn::into_column(vec![$($value),*]),
+            ),
+        )*])
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,6 +532,79 @@ mod tests {
         assert_eq!(5, record_batch.column(1).data().len());
     }
 
+    #[test]
+    fn create_record_batch_with_nulls_in_non_nullable_column() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+
+        let mut builder =
+            Int32Builder::new(3).with_nullable(schema.field(0).is_nullable());
+        builder.append_value(1).unwrap();
+        assert!(builder.append_null().is_err());
+        builder.append_value(3).unwrap();
+        let a = builder.finish();
+        assert!(RecordBatch::try_new(schema.clone(), vec![Arc::new(a)]).is_ok());
+
+        let a = Int32Array::from(vec![Some(1), None, Some(3)]);
+        match RecordBatch::try_new(schema, vec![Arc::new(a)]) {
+            Err(ArrowError::InvalidArgumentError(message)) => assert_eq!(
+                "column \"a\" at index 0 is not nullable, but has 1 null values",
+                message
+            ),
+            _ => panic!("expected an error for the null in a non-nullable column"),
+        }
+    }
+
+    #[test]
+    fn slice_record_batch() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::List(Box::new(DataType::Int32)), true),
+        ]);
+        let a = Int32Array::from(vec![Some(1), None, Some(3), Some(4), Some(5)]);
+        let mut builder = ListBuilder::new(Int32Builder::new(8));
+        for i in 0..5 {
+            builder.values().append_slice(&vec![i; i as usize]).unwrap();
+            builder.append(i != 2).unwrap();
+        }
+        let b = builder.finish();
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)])
+                .unwrap();
+
+        let sliced = batch.slice(1, 4).slice(1, 2);
+        assert_eq!(2, sliced.num_rows());
+        assert_eq!(batch.schema(), sliced.schema());
+        for column in sliced.columns() {
+            assert_eq!(2, column.offset());
+            assert_eq!(2, column.len());
+        }
+        assert_eq!(
+            batch.column(0).data().buffers(),
+            sliced.column(0).data().buffers()
+        );
+
+        let a = sliced.column(0).downcast::<Int32Array>().unwrap();
+        assert_eq!(&[3, 4], a.value_slice(0, 2));
+        assert_eq!(0, a.null_count());
+        let b = sliced.column(1).downcast::<ListArray>().unwrap();
+        assert_eq!(1, b.null_count());
+        assert!(b.is_null(0));
+        let value = b.value(1);
+        let value = value.downcast::<Int32Array>().unwrap();
+        assert_eq!(&[3, 3, 3], value.value_slice(0, 3));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the slice 3..6 is out of bounds of a record batch of 5 rows"
+    )]
+    fn slice_record_batch_out_of_bounds() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a)]).unwrap();
+        batch.slice(3, 3);
+    }
+
     #[test]
     fn create_record_batch_schema_mismatch() {
         let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
@@ -239,4 +659,377 @@ mod tests {
         assert_eq!(batch.column(0).data(), boolean_data);
         assert_eq!(batch.column(1).data(), int_data);
     }
+
+    #[test]
+    fn create_record_batch_ignoring_field_names() {
+        let struct_array = StructArray::from(vec![(
+            Field::new("producer_name", DataType::Int32, false),
+            Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef,
+        )]);
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(vec![Field::new("consumer_name", DataType::Int32, false)]),
+            false,
+        )]));
+        let columns = vec![Arc::new(struct_array) as ArrayRef];
+
+        assert!(RecordBatch::try_new(schema.clone(), columns.clone()).is_err());
+
+        let options = RecordBatchOptions {
+            match_field_names: false,
+            row_count: None,
+        };
+        let batch = RecordBatch::try_new_with_options(schema, columns, &options).unwrap();
+        assert_eq!(3, batch.num_rows());
+        assert_eq!(1, batch.num_columns());
+    }
+
+    #[test]
+    fn create_record_batch_without_columns() {
+        let schema = Arc::new(Schema::empty());
+        assert!(RecordBatch::try_new(schema.clone(), vec![]).is_err());
+
+        let options = RecordBatchOptions {
+            match_field_names: true,
+            row_count: Some(10),
+        };
+        let batch = RecordBatch::try_new_with_options(schema, vec![], &options).unwrap();
+        assert_eq!(10, batch.num_rows());
+        assert_eq!(0, batch.num_columns());
+    }
+
+    #[test]
+    fn create_record_batch_row_count_mismatch() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let a = Int32Array::from(vec![1, 2, 3]);
+        let options = RecordBatchOptions {
+            match_field_names: true,
+            row_count: Some(4),
+        };
+        let batch =
+            RecordBatch::try_new_with_options(schema, vec![Arc::new(a)], &options);
+        assert!(batch.is_err());
+    }
+
+    #[test]
+    fn cast_record_batch_to_schema() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+            Field::new("c", DataType::Utf8, false),
+        ]);
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let b: ArrayRef = Arc::new(Int32Array::from(vec![Some(4), None, Some(6)]));
+        let c: ArrayRef = Arc::new(BinaryArray::from(vec!["x", "y", "z"]));
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![a, b, c.clone()]).unwrap();
+
+        let target = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, true),
+            Field::new("c", DataType::Utf8, false),
+        ]);
+        let cast_batch = batch
+            .cast_to_schema(&target, CastOptions::default())
+            .unwrap();
+        assert_eq!(&target, cast_batch.schema().as_ref());
+        assert_eq!(3, cast_batch.num_rows());
+        let a = cast_batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(&[1, 2, 3], a.value_slice(0, 3));
+        let b = cast_batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(6, b.value(2));
+        assert!(b.is_null(1));
+        // the matching column is shared
+        assert!(Arc::ptr_eq(&c, cast_batch.column(2)));
+
+        let wrong_names = Schema::new(vec![
+            Field::new("b", DataType::Int64, false),
+            Field::new("a", DataType::Int64, true),
+            Field::new("c", DataType::Utf8, false),
+        ]);
+        assert!(batch
+            .cast_to_schema(&wrong_names, CastOptions::default())
+            .is_err());
+        let too_few = Schema::new(vec![Field::new("a", DataType::Int64, false)]);
+        assert!(batch
+            .cast_to_schema(&too_few, CastOptions::default())
+            .is_err());
+    }
+
+    #[test]
+    fn add_constant_column_to_record_batch() {
+        let schema = Schema::new(vec![
+            Field::new("bools", DataType::Boolean, true),
+            Field::new("int32s", DataType::Int32, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)])),
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])),
+            ],
+        )
+        .unwrap();
+
+        let with_x = batch
+            .add_constant_column(
+                Field::new("x", DataType::Utf8, false),
+                ScalarValue::Utf8("x".to_string()),
+            )
+            .unwrap();
+        assert_eq!(3, with_x.num_columns());
+        assert_eq!("x", with_x.schema().field(2).name());
+        let x = with_x
+            .column(2)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!(3, x.len());
+        assert_eq!(0, x.null_count());
+        for i in 0..x.len() {
+            assert_eq!("x", x.get_string(i));
+        }
+
+        let with_null = batch
+            .add_constant_column(
+                Field::new("n", DataType::Int64, true),
+                ScalarValue::Null,
+            )
+            .unwrap();
+        assert_eq!(3, with_null.column(2).null_count());
+
+        assert!(batch
+            .add_constant_column(
+                Field::new("y", DataType::Utf8, false),
+                ScalarValue::Int32(1),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn reorder_record_batch() {
+        let schema = Schema::new(vec![
+            Field::new("c", DataType::Utf8, false),
+            Field::new("b", DataType::Float64, false),
+            Field::new("a", DataType::Int32, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(BinaryArray::from(vec!["x", "y"])),
+                Arc::new(Float64Array::from(vec![1.5, 2.5])),
+                Arc::new(Int32Array::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+
+        let target = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Float64, false),
+            Field::new("c", DataType::Utf8, false),
+        ]);
+        let reordered = batch.reorder_to(&target).unwrap();
+        assert_eq!(&target, reordered.schema().as_ref());
+        assert_eq!(2, reordered.num_rows());
+        assert_eq!(batch.column(2).data(), reordered.column(0).data());
+        assert_eq!(batch.column(1).data(), reordered.column(1).data());
+        assert_eq!(batch.column(0).data(), reordered.column(2).data());
+
+        let missing = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Float64, false),
+            Field::new("d", DataType::Utf8, false),
+        ]);
+        assert!(batch.reorder_to(&missing).is_err());
+        let fewer = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        assert!(batch.reorder_to(&fewer).is_err());
+        let wrong_type = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Float64, false),
+            Field::new("c", DataType::Utf8, false),
+        ]);
+        assert!(batch.reorder_to(&wrong_type).is_err());
+
+        // every column has to be used once, so a repeated target field is rejected
+        let duplicate = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("a", DataType::Int32, false),
+            Field::new("c", DataType::Utf8, false),
+        ]);
+        match batch.reorder_to(&duplicate) {
+            Err(ArrowError::InvalidArgumentError(message)) => {
+                assert!(message.contains("\"a\""))
+            }
+            _ => panic!("Expected a repeated target field to be rejected"),
+        }
+    }
+
+    #[test]
+    fn split_record_batch_to_budget() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, false),
+        ]);
+        let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
+        let b = BinaryArray::from(vec!["a", "bb", "ccc", "dddd", "eeeee"]);
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)])
+                .unwrap();
+
+        let batches = batch.split_to_budget(1);
+        assert_eq!(5, batches.len());
+        assert!(batches.iter().all(|b| b.num_rows() == 1));
+        assert_eq!(5, batches.iter().map(|b| b.num_rows()).sum::<usize>());
+        for (i, b) in batches.iter().enumerate() {
+            let a = b.column(0).as_any().downcast_ref::<Int32Array>().unwrap();
+            assert_eq!(i as i32 + 1, a.value(0));
+            let s = b.column(1).as_any().downcast_ref::<BinaryArray>().unwrap();
+            assert_eq!(i + 1, s.value_length(0) as usize);
+        }
+
+        let batches = batch.split_to_budget(usize::max_value());
+        assert_eq!(1, batches.len());
+        assert_eq!(5, batches[0].num_rows());
+
+        assert_eq!(1, div_ceil(1, usize::max_value()));
+        assert_eq!(usize::max_value(), div_ceil(usize::max_value(), 1));
+        assert_eq!(3, div_ceil(5, 2));
+    }
+
+    #[test]
+    #[cfg(feature = "serde_rows")]
+    fn create_record_batch_from_serde_rows() {
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Serialize)]
+        struct Row {
+            id: i64,
+            name: Option<String>,
+            point: Point,
+            weights: Vec<f64>,
+        }
+
+        let rows = vec![
+            Row {
+                id: 1,
+                name: Some("one".to_string()),
+                point: Point { x: 1, y: 2 },
+                weights: vec![0.5, 1.5],
+            },
+            Row {
+                id: 2,
+                name: None,
+                point: Point { x: 3, y: 4 },
+                weights: vec![],
+            },
+        ];
+        let point_fields = vec![
+            Field::new("x", DataType::Int32, false),
+            Field::new("y", DataType::Int32, false),
+        ];
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("point", DataType::Struct(point_fields), false),
+            Field::new(
+                "weights",
+                DataType::List(Box::new(DataType::Float64)),
+                false,
+            ),
+        ]);
+
+        let batch = RecordBatch::from_serde_rows(&rows, &schema).unwrap();
+        assert_eq!(2, batch.num_rows());
+        assert_eq!(4, batch.num_columns());
+
+        let id = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(1, id.value(0));
+        assert_eq!(2, id.value(1));
+
+        let name = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!("one", name.get_string(0));
+        assert!(name.is_null(1));
+
+        let point = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .unwrap();
+        assert_eq!(0, point.null_count());
+        let y = point
+            .column_by_name("y")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(2, y.value(0));
+        assert_eq!(4, y.value(1));
+
+        let weights = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+        assert_eq!(2, weights.value_length(0));
+        assert_eq!(0, weights.value_length(1));
+        let values = weights.values();
+        let values = values.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(1.5, values.value(1));
+    }
+
+    #[test]
+    fn create_record_batch_with_macro() {
+        let batch = record_batch!(
+            "a" => [1, 2, 3],
+            "b" => [Some(1.5), None, Some(3.5)],
+            "c" => ["x", "y", "z"],
+            "d" => [Some(true), Some(false), None],
+        )
+        .unwrap();
+
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Float64, true),
+            Field::new("c", DataType::Utf8, false),
+            Field::new("d", DataType::Boolean, true),
+        ]);
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![1, 2, 3])),
+            Arc::new(Float64Array::from(vec![Some(1.5), None, Some(3.5)])),
+            Arc::new(BinaryArray::from(vec!["x", "y", "z"])),
+            Arc::new(BooleanArray::from(vec![Some(true), Some(false), None])),
+        ];
+        let expected = RecordBatch::try_new(Arc::new(schema), columns).unwrap();
+
+        assert_eq!(expected.schema(), batch.schema());
+        for i in 0..expected.num_columns() {
+            assert!(expected.column(i).equals(batch.column(i).as_ref()));
+        }
+        assert_eq!(1, batch.column(1).null_count());
+        assert_eq!(1, batch.column(3).null_count());
+
+        assert!(record_batch!("a" => [1, 2], "b" => [1]).is_err());
+    }
 }
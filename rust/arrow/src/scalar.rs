@@ -0,0 +1,157 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines `ScalarValue`, a single value of one of the Arrow data types, which is used
+//! as an argument or a result of kernels that deal with individual values.
+
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+use crate::datatypes::DataType;
+
+/// A single, possibly null, value
+///
+/// Scalar values can be compared and hashed, e.g. to be used as the keys of a map.
+/// Floating point values are compared by their bits, so that `NaN` equals `NaN`, but
+/// `0.0` does not equal `-0.0`. Values of different types are never equal.
+#[derive(Debug, Clone)]
+pub enum ScalarValue {
+    Null,
+    Boolean(bool),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Float32(f32),
+    Float64(f64),
+    Utf8(String),
+}
+
+impl ScalarValue {
+    /// Returns the data type of the value, or `None` for `ScalarValue::Null`
+    pub fn data_type(&self) -> Option<DataType> {
+        match self {
+            ScalarValue::Null => None,
+            ScalarValue::Boolean(_) => Some(DataType::Boolean),
+            ScalarValue::Int8(_) => Some(DataType::Int8),
+            ScalarValue::Int16(_) => Some(DataType::Int16),
+            ScalarValue::Int32(_) => Some(DataType::Int32),
+            ScalarValue::Int64(_) => Some(DataType::Int64),
+            ScalarValue::UInt8(_) => Some(DataType::UInt8),
+            ScalarValue::UInt16(_) => Some(DataType::UInt16),
+            ScalarValue::UInt32(_) => Some(DataType::UInt32),
+            ScalarValue::UInt64(_) => Some(DataType::UInt64),
+            ScalarValue::Float32(_) => Some(DataType::Float32),
+            ScalarValue::Float64(_) => Some(DataType::Float64),
+            ScalarValue::Utf8(_) => Some(DataType::Utf8),
+        }
+    }
+
+    /// Returns true if the value is `ScalarValue::Null`
+    pub fn is_null(&self) -> bool {
+        match self {
+            ScalarValue::Null => true,
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq for ScalarValue {
+    fn eq(&self, other: &ScalarValue) -> bool {
+        use ScalarValue::*;
+        match (self, other) {
+            (Null, Null) => true,
+            (Boolean(l), Boolean(r)) => l == r,
+            (Int8(l), Int8(r)) => l == r,
+            (Int16(l), Int16(r)) => l == r,
+            (Int32(l), Int32(r)) => l == r,
+            (Int64(l), Int64(r)) => l == r,
+            (UInt8(l), UInt8(r)) => l == r,
+            (UInt16(l), UInt16(r)) => l == r,
+            (UInt32(l), UInt32(r)) => l == r,
+            (UInt64(l), UInt64(r)) => l == r,
+            (Float32(l), Float32(r)) => l.to_bits() == r.to_bits(),
+            (Float64(l), Float64(r)) => l.to_bits() == r.to_bits(),
+            (Utf8(l), Utf8(r)) => l == r,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ScalarValue {}
+
+impl Hash for ScalarValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        use ScalarValue::*;
+        mem::discriminant(self).hash(state);
+        match self {
+            Null => {}
+            Boolean(v) => v.hash(state),
+            Int8(v) => v.hash(state),
+            Int16(v) => v.hash(state),
+            Int32(v) => v.hash(state),
+            Int64(v) => v.hash(state),
+            UInt8(v) => v.hash(state),
+            UInt16(v) => v.hash(state),
+            UInt32(v) => v.hash(state),
+            UInt64(v) => v.hash(state),
+            Float32(v) => v.to_bits().hash(state),
+            Float64(v) => v.to_bits().hash(state),
+            Utf8(v) => v.hash(state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+    use std::f64::NAN;
+
+    #[test]
+    fn test_scalar_value_equality() {
+        assert_eq!(ScalarValue::Int32(1), ScalarValue::Int32(1));
+        assert_ne!(ScalarValue::Int32(1), ScalarValue::Int64(1));
+        assert_eq!(ScalarValue::Float64(NAN), ScalarValue::Float64(NAN));
+        assert_ne!(ScalarValue::Float64(0.0), ScalarValue::Float64(-0.0));
+        assert_eq!(ScalarValue::Null, ScalarValue::Null);
+
+        let set: HashSet<ScalarValue> = vec![
+            ScalarValue::Utf8("a".to_string()),
+            ScalarValue::Utf8("a".to_string()),
+            ScalarValue::Float64(NAN),
+            ScalarValue::Float64(NAN),
+            ScalarValue::Null,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(3, set.len());
+    }
+
+    #[test]
+    fn test_scalar_value_data_type() {
+        assert_eq!(Some(DataType::UInt16), ScalarValue::UInt16(3).data_type());
+        assert_eq!(None, ScalarValue::Null.data_type());
+        assert!(ScalarValue::Null.is_null());
+        assert!(!ScalarValue::Boolean(false).is_null());
+    }
+}
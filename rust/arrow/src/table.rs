@@ -0,0 +1,192 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A `Table` is a collection of record batches, or chunks, that share a schema.
+//!
+//! Tables hold data that is read or produced in several batches as a whole, without
+//! having to concatenate the batches.
+
+use std::slice;
+
+use crate::compute::concat::concat_batches;
+use crate::datatypes::SchemaRef;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::{RecordBatch, RecordBatchReader};
+
+/// A table of column-oriented data, stored as record batches that share a schema
+#[derive(Clone)]
+pub struct Table {
+    schema: SchemaRef,
+    chunks: Vec<RecordBatch>,
+}
+
+impl Table {
+    /// Creates a table from a schema and chunks
+    ///
+    /// Returns an error if a chunk does not have the same schema as the table.
+    pub fn try_new(schema: SchemaRef, chunks: Vec<RecordBatch>) -> Result<Self> {
+        if let Some(i) = chunks.iter().position(|c| c.schema() != &schema) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Chunk {} of the table does not match the table schema, expected {:?} \
+                 but found {:?}",
+                i,
+                schema,
+                chunks[i].schema()
+            )));
+        }
+        Ok(Self { schema, chunks })
+    }
+
+    /// Creates a table from all the remaining batches of `reader`
+    pub fn try_from_reader<R: RecordBatchReader>(reader: &mut R) -> Result<Self> {
+        let schema = reader.schema();
+        let mut chunks = vec![];
+        while let Some(batch) = reader.next_batch()? {
+            chunks.push(batch);
+        }
+        Self::try_new(schema, chunks)
+    }
+
+    /// Returns the schema of the table
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Returns the number of columns in the table
+    pub fn num_columns(&self) -> usize {
+        self.schema.fields().len()
+    }
+
+    /// Returns the number of rows in all chunks of the table
+    pub fn num_rows(&self) -> usize {
+        self.chunks.iter().map(|c| c.num_rows()).sum()
+    }
+
+    /// Returns the number of chunks in the table
+    pub fn num_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the chunk at index `i`
+    pub fn chunk(&self, i: usize) -> &RecordBatch {
+        &self.chunks[i]
+    }
+
+    /// Returns all chunks of the table
+    pub fn chunks(&self) -> &[RecordBatch] {
+        &self.chunks
+    }
+
+    /// Returns an iterator over the chunks of the table
+    pub fn iter_batches(&self) -> slice::Iter<RecordBatch> {
+        self.chunks.iter()
+    }
+
+    /// Concatenates the chunks of the table into a single record batch
+    ///
+    /// Returns an error if the table has no chunks.
+    pub fn concat_to_batch(&self) -> Result<RecordBatch> {
+        if self.chunks.is_empty() {
+            return Err(ArrowError::InvalidArgumentError(
+                "Cannot concat a table without chunks into a record batch".to_string(),
+            ));
+        }
+        concat_batches(&self.chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::array::*;
+    use crate::datatypes::{DataType, Field, Schema};
+
+    fn batch(schema: &SchemaRef, ids: Vec<i32>, names: Vec<&str>) -> RecordBatch {
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(ids)),
+                Arc::new(BinaryArray::from(names)),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_table() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let chunks = vec![
+            batch(&schema, vec![1, 2], vec!["a", "b"]),
+            batch(&schema, vec![3], vec!["c"]),
+            batch(&schema, vec![4, 5, 6], vec!["d", "e", "f"]),
+        ];
+        let table = Table::try_new(schema.clone(), chunks).unwrap();
+        assert_eq!(&schema, table.schema());
+        assert_eq!(2, table.num_columns());
+        assert_eq!(3, table.num_chunks());
+        assert_eq!(6, table.num_rows());
+        assert_eq!(1, table.chunk(1).num_rows());
+        assert_eq!(
+            vec![2, 1, 3],
+            table
+                .iter_batches()
+                .map(|b| b.num_rows())
+                .collect::<Vec<_>>()
+        );
+
+        let batch = table.concat_to_batch().unwrap();
+        assert_eq!(6, batch.num_rows());
+        assert!(batch
+            .column(0)
+            .equals(&Int32Array::from(vec![1, 2, 3, 4, 5, 6])));
+        assert!(batch
+            .column(1)
+            .equals(&BinaryArray::from(vec!["a", "b", "c", "d", "e", "f"])));
+    }
+
+    #[test]
+    fn test_table_schema_mismatch() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let other_schema = Arc::new(Schema::new(vec![
+            Field::new("key", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let chunks = vec![
+            batch(&schema, vec![1], vec!["a"]),
+            batch(&other_schema, vec![2], vec!["b"]),
+        ];
+        assert!(Table::try_new(schema, chunks).is_err());
+    }
+
+    #[test]
+    fn test_empty_table() {
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let table = Table::try_new(schema, vec![]).unwrap();
+        assert_eq!(0, table.num_rows());
+        assert!(table.concat_to_batch().is_err());
+    }
+}
@@ -20,6 +20,8 @@
 #[cfg(feature = "simd")]
 use packed_simd::u8x64;
 
+use crate::buffer::Buffer;
+
 static BIT_MASK: [u8; 8] = [1, 2, 4, 8, 16, 32, 64, 128];
 
 static POPCOUNT_TABLE: [u8; 256] = [
@@ -127,6 +129,32 @@ pub fn ceil(value: usize, divisor: usize) -> usize {
     result
 }
 
+/// Expands the first `len` bits of the bit-packed `packed` buffer into a buffer holding
+/// one byte per value, set to `1` if the bit is set and `0` otherwise.
+///
+/// This is the inverse of `pack_bits`.
+pub fn unpack_bits(packed: &Buffer, len: usize) -> Buffer {
+    let data = packed.data();
+    assert!(len <= (data.len() << 3));
+    let unpacked: Vec<u8> = (0..len).map(|i| get_bit(data, i) as u8).collect();
+    Buffer::from(unpacked)
+}
+
+/// Packs a buffer holding one byte per value into a bit-packed buffer, where bit `i` is
+/// set if byte `i` is non-zero.
+///
+/// This is the inverse of `unpack_bits`.
+pub fn pack_bits(unpacked: &Buffer) -> Buffer {
+    let data = unpacked.data();
+    let mut packed = vec![0u8; ceil(data.len(), 8)];
+    for (i, v) in data.iter().enumerate() {
+        if *v != 0 {
+            set_bit(&mut packed, i);
+        }
+    }
+    Buffer::from(packed)
+}
+
 /// Performs SIMD bitwise binary operations.
 ///
 /// Note that each slice should be 64 bytes and it is the callers responsibility to ensure
@@ -303,6 +331,17 @@ mod tests {
         assert_eq!(ceil(10000000000, 1000000000), 10);
     }
 
+    #[test]
+    fn test_pack_unpack_bits_roundtrip() {
+        let bits: Vec<u8> = vec![1, 0, 1, 1, 0, 0, 0, 1, 1, 0, 1, 0, 1];
+        let packed = pack_bits(&Buffer::from(&bits[..]));
+        assert_eq!(&[0b1000_1101, 0b0001_0101], packed.data());
+
+        let unpacked = unpack_bits(&packed, 13);
+        assert_eq!(&bits[..], unpacked.data());
+        assert_eq!(packed.data(), pack_bits(&unpacked).data());
+    }
+
     #[test]
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "simd"))]
     fn test_bitwise_and_simd() {
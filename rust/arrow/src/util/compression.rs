@@ -0,0 +1,177 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines `CompressedReader`, which decompresses an input stream while it is read.
+//!
+//! Wrapping a file in a `CompressedReader` lets the CSV and JSON readers ingest
+//! compressed files such as `.csv.gz` directly. The codec is always given explicitly,
+//! it is never guessed from a file extension. Apart from `Uncompressed`, each codec
+//! requires a feature of this crate: `gzip`, `zstd` or `bzip2`.
+//!
+//! Example:
+//!
+//! ```no_run
+//! use arrow::csv;
+//! use arrow::datatypes::{DataType, Field, Schema};
+//! use arrow::util::compression::{CompressedReader, CompressionCodec};
+//! use std::fs::File;
+//! use std::sync::Arc;
+//!
+//! let schema = Schema::new(vec![
+//!     Field::new("city", DataType::Utf8, false),
+//!     Field::new("lat", DataType::Float64, false),
+//!     Field::new("lng", DataType::Float64, false),
+//! ]);
+//!
+//! let file = File::open("test/data/uk_cities.csv.gz").unwrap();
+//! let input = CompressedReader::new(file, CompressionCodec::Gzip).unwrap();
+//! let mut csv = csv::Reader::new(input, Arc::new(schema), false, 1024, None);
+//! let batch = csv.next().unwrap().unwrap();
+//! ```
+
+#[cfg(feature = "zstd")]
+use std::io::BufReader;
+use std::io::{self, Read};
+
+use crate::error::{ArrowError, Result};
+
+/// The compression codecs supported by `CompressedReader`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompressionCodec {
+    /// The input is read as is
+    Uncompressed,
+    /// gzip, requires the `gzip` feature
+    Gzip,
+    /// Zstandard, requires the `zstd` feature
+    Zstd,
+    /// bzip2, requires the `bzip2` feature
+    Bzip2,
+}
+
+/// A reader that decompresses the data read from an underlying reader
+pub struct CompressedReader<R: Read> {
+    decoder: Decoder<R>,
+}
+
+enum Decoder<R: Read> {
+    Uncompressed(R),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::read::MultiGzDecoder<R>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::read::Decoder<BufReader<R>>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::read::BzDecoder<R>),
+}
+
+impl<R: Read> CompressedReader<R> {
+    /// Creates a reader that decompresses `reader` with `codec`.
+    ///
+    /// Returns an error if the feature required by `codec` is not enabled.
+    pub fn new(reader: R, codec: CompressionCodec) -> Result<Self> {
+        let decoder = match codec {
+            CompressionCodec::Uncompressed => Decoder::Uncompressed(reader),
+            #[cfg(feature = "gzip")]
+            CompressionCodec::Gzip => {
+                Decoder::Gzip(flate2::read::MultiGzDecoder::new(reader))
+            }
+            #[cfg(feature = "zstd")]
+            CompressionCodec::Zstd => {
+                Decoder::Zstd(zstd::stream::read::Decoder::new(reader)?)
+            }
+            #[cfg(feature = "bzip2")]
+            CompressionCodec::Bzip2 => {
+                Decoder::Bzip2(bzip2::read::BzDecoder::new(reader))
+            }
+            #[allow(unreachable_patterns)]
+            codec => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Reading {:?} compressed input requires the `{}` feature",
+                    codec,
+                    feature_name(codec)
+                )))
+            }
+        };
+        Ok(Self { decoder })
+    }
+}
+
+fn feature_name(codec: CompressionCodec) -> &'static str {
+    match codec {
+        CompressionCodec::Uncompressed => "",
+        CompressionCodec::Gzip => "gzip",
+        CompressionCodec::Zstd => "zstd",
+        CompressionCodec::Bzip2 => "bzip2",
+    }
+}
+
+impl<R: Read> Read for CompressedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.decoder {
+            Decoder::Uncompressed(ref mut reader) => reader.read(buf),
+            #[cfg(feature = "gzip")]
+            Decoder::Gzip(ref mut reader) => reader.read(buf),
+            #[cfg(feature = "zstd")]
+            Decoder::Zstd(ref mut reader) => reader.read(buf),
+            #[cfg(feature = "bzip2")]
+            Decoder::Bzip2(ref mut reader) => reader.read(buf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::File;
+
+    fn read_to_end(reader: &mut Read) -> Vec<u8> {
+        let mut buf = vec![];
+        reader.read_to_end(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_uncompressed_reader() {
+        let file = File::open("test/data/uk_cities.csv").unwrap();
+        let mut reader =
+            CompressedReader::new(file, CompressionCodec::Uncompressed).unwrap();
+        let expected = read_to_end(&mut File::open("test/data/uk_cities.csv").unwrap());
+        assert_eq!(expected, read_to_end(&mut reader));
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_gzip_reader() {
+        let file = File::open("test/data/uk_cities.csv.gz").unwrap();
+        let mut reader = CompressedReader::new(file, CompressionCodec::Gzip).unwrap();
+        let expected = read_to_end(&mut File::open("test/data/uk_cities.csv").unwrap());
+        assert_eq!(expected, read_to_end(&mut reader));
+    }
+
+    #[test]
+    #[cfg(not(feature = "bzip2"))]
+    fn test_codec_requires_feature() {
+        let file = File::open("test/data/uk_cities.csv").unwrap();
+        match CompressedReader::new(file, CompressionCodec::Bzip2) {
+            Err(ArrowError::InvalidArgumentError(msg)) => assert_eq!(
+                "Reading Bzip2 compressed input requires the `bzip2` feature",
+                msg
+            ),
+            _ => panic!("expected bzip2 to be unavailable"),
+        }
+    }
+}
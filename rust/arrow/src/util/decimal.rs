@@ -0,0 +1,245 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Utils for parsing and formatting decimal values
+//!
+//! A value of type `Decimal(precision, scale)` is represented by its unscaled `i128`
+//! value, e.g. `150.00` as `Decimal(6, 2)` is `15000`.
+
+use crate::datatypes::DECIMAL_MAX_PRECISION;
+use crate::error::{ArrowError, Result};
+
+/// How to round digits that do not fit the scale of a decimal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalRounding {
+    /// Drop the extra digits
+    Truncate,
+    /// Round to the nearest value, ties away from zero
+    HalfUp,
+    /// Round to the nearest value, ties to the even value
+    HalfEven,
+}
+
+impl Default for DecimalRounding {
+    fn default() -> Self {
+        DecimalRounding::HalfUp
+    }
+}
+
+/// Checks that `Decimal(precision, scale)` is a valid decimal type, i.e. that
+/// `0 < precision <= DECIMAL_MAX_PRECISION` and `scale <= precision`
+pub fn validate_decimal_type(precision: usize, scale: usize) -> Result<()> {
+    if precision == 0 || precision > DECIMAL_MAX_PRECISION || scale > precision {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Invalid decimal type Decimal({}, {})",
+            precision, scale
+        )));
+    }
+    Ok(())
+}
+
+/// Parses `s` into the unscaled value of a `Decimal(precision, scale)`.
+///
+/// Accepts an optional sign, digits with an optional decimal point, and an optional
+/// exponent, e.g. `-12.5`, `.5`, `1.5e3` or `2E-2`. Surrounding whitespace is ignored.
+/// Fraction digits beyond `scale` are rounded per `rounding`; a value with more than
+/// `precision` significant digits after rounding is an error.
+pub fn parse_decimal(
+    s: &str,
+    precision: usize,
+    scale: usize,
+    rounding: DecimalRounding,
+) -> Result<i128> {
+    validate_decimal_type(precision, scale)?;
+    let error = |reason: &str| {
+        ArrowError::ParseError(format!(
+            "Cannot parse '{}' as Decimal({}, {}): {}",
+            s, precision, scale, reason
+        ))
+    };
+
+    let trimmed = s.trim();
+    let (negative, unsigned) = match trimmed.as_bytes().first() {
+        Some(b'-') => (true, &trimmed[1..]),
+        Some(b'+') => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
+    let (mantissa, exponent) = match unsigned.find(|c| c == 'e' || c == 'E') {
+        Some(pos) => {
+            let exponent = &unsigned[pos + 1..];
+            let digits = exponent.trim_start_matches(|c| c == '+' || c == '-');
+            if digits.is_empty()
+                || exponent.len() - digits.len() > 1
+                || !digits.bytes().all(|b| b.is_ascii_digit())
+            {
+                return Err(error("invalid exponent"));
+            }
+            let exponent = exponent
+                .parse::<i32>()
+                .map_err(|_| error("exponent out of range"))?;
+            (&unsigned[..pos], exponent as i64)
+        }
+        None => (unsigned, 0),
+    };
+    let (integer_part, fraction_part) = match mantissa.find('.') {
+        Some(pos) => (&mantissa[..pos], &mantissa[pos + 1..]),
+        None => (mantissa, ""),
+    };
+    if integer_part.is_empty() && fraction_part.is_empty() {
+        return Err(error("no digits"));
+    }
+    if !integer_part
+        .bytes()
+        .chain(fraction_part.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return Err(error("invalid digit"));
+    }
+
+    // the value is `digits * 10^shift` in units of the scale
+    let digits: Vec<u8> = integer_part
+        .bytes()
+        .chain(fraction_part.bytes())
+        .map(|b| b - b'0')
+        .skip_while(|d| *d == 0)
+        .collect();
+    let shift = exponent - fraction_part.len() as i64 + scale as i64;
+
+    let (kept, round_up) = if shift >= 0 {
+        (digits.len(), false)
+    } else {
+        let dropped = (-shift) as usize;
+        if dropped > digits.len() {
+            (0, false)
+        } else {
+            let kept = digits.len() - dropped;
+            let round_up = match digits.get(kept) {
+                None => false,
+                Some(first) => match rounding {
+                    DecimalRounding::Truncate => false,
+                    DecimalRounding::HalfUp => *first >= 5,
+                    DecimalRounding::HalfEven => {
+                        let rest_is_zero = digits[kept + 1..].iter().all(|d| *d == 0);
+                        let last_is_odd = kept > 0 && digits[kept - 1] % 2 == 1;
+                        *first > 5 || (*first == 5 && (!rest_is_zero || last_is_odd))
+                    }
+                },
+            };
+            (kept, round_up)
+        }
+    };
+    let num_digits = if shift > 0 && kept > 0 {
+        kept + shift as usize
+    } else {
+        kept
+    };
+    if num_digits > precision {
+        return Err(error("value exceeds the precision"));
+    }
+
+    let mut value: i128 = 0;
+    for d in &digits[..kept] {
+        value = value * 10 + *d as i128;
+    }
+    if shift > 0 && kept > 0 {
+        value *= 10_i128.pow(shift as u32);
+    }
+    if round_up {
+        value += 1;
+        if value >= 10_i128.pow(precision as u32) {
+            return Err(error("value exceeds the precision"));
+        }
+    }
+    Ok(if negative { -value } else { value })
+}
+
+/// Formats the unscaled `value` of a decimal with the given `scale`, e.g. `15000` with
+/// scale 2 as `150.00`
+pub fn format_decimal(value: i128, scale: usize) -> String {
+    let digits = value.to_string();
+    let (sign, digits) = if value < 0 {
+        ("-", &digits[1..])
+    } else {
+        ("", &digits[..])
+    };
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer_part, fraction_part) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, integer_part, fraction_part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str, precision: usize, scale: usize) -> Result<i128> {
+        parse_decimal(s, precision, scale, DecimalRounding::HalfUp)
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(15000, parse("1.5e2", 6, 2).unwrap());
+        assert_eq!("150.00", format_decimal(parse("1.5e2", 6, 2).unwrap(), 2));
+        assert_eq!(-1250, parse("-12.5", 6, 2).unwrap());
+        assert_eq!(1250, parse("+12.5", 6, 2).unwrap());
+        assert_eq!(50, parse(".5", 6, 2).unwrap());
+        assert_eq!(500, parse("5.", 6, 2).unwrap());
+        assert_eq!(2, parse("2E-2", 6, 2).unwrap());
+        assert_eq!(0, parse("0.000", 6, 2).unwrap());
+        assert_eq!(42, parse(" 42 ", 6, 0).unwrap());
+        assert_eq!(0, parse("1e-9", 6, 2).unwrap());
+    }
+
+    #[test]
+    fn test_parse_decimal_rounding() {
+        let round = |s, rounding| parse_decimal(s, 6, 2, rounding).unwrap();
+        assert_eq!(1235, round("12.345", DecimalRounding::HalfUp));
+        assert_eq!(1234, round("12.345", DecimalRounding::Truncate));
+        assert_eq!(1234, round("12.345", DecimalRounding::HalfEven));
+        assert_eq!(1236, round("12.355", DecimalRounding::HalfEven));
+        assert_eq!(1235, round("12.3451", DecimalRounding::HalfEven));
+        assert_eq!(-1235, round("-12.345", DecimalRounding::HalfUp));
+        assert_eq!(1, round("0.005", DecimalRounding::HalfUp));
+        assert_eq!(0, round("0.005", DecimalRounding::HalfEven));
+    }
+
+    #[test]
+    fn test_parse_decimal_errors() {
+        for s in &[
+            "", "-", ".", "1.2.3", "abc", "1e", "e5", "1e+-2", "1,5", "0x10",
+        ] {
+            assert!(parse(s, 6, 2).is_err(), "'{}' should not parse", s);
+        }
+        // 5 integer digits do not fit Decimal(6, 2)
+        assert!(parse("12345.6", 6, 2).is_err());
+        assert!(parse("1e4", 6, 2).is_err());
+        // rounding up overflows the precision
+        assert!(parse("9999.995", 6, 2).is_err());
+        assert!(parse("1", 39, 0).is_err());
+        assert!(parse("1", 2, 3).is_err());
+    }
+
+    #[test]
+    fn test_format_decimal() {
+        assert_eq!("0.05", format_decimal(5, 2));
+        assert_eq!("-0.05", format_decimal(-5, 2));
+        assert_eq!("-123", format_decimal(-123, 0));
+        assert_eq!("1.230", format_decimal(1230, 3));
+    }
+}
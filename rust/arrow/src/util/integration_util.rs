@@ -17,188 +17,577 @@
 
 //! Utils for JSON integration testing
 //!
-//! These utilities define structs that read the integration JSON format for integration testing purposes.
+//! These utilities define structs that read and write the integration JSON format for
+//! integration testing purposes.
 
-use serde_derive::Deserialize;
+use std::cmp::max;
+use std::convert::TryFrom;
+
+use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::array::*;
 use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
 use crate::record_batch::RecordBatch;
 
+pub mod reader;
+pub mod writer;
+
 /// A struct that represents an Arrow file with a schema and record batches
-#[derive(Deserialize)]
-struct ArrowJson {
-    schema: ArrowJsonSchema,
-    batches: Vec<ArrowJsonBatch>,
+#[derive(Deserialize, Serialize)]
+pub struct ArrowJson {
+    pub schema: ArrowJsonSchema,
+    pub batches: Vec<ArrowJsonBatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dictionaries: Option<Vec<ArrowJsonDictionaryBatch>>,
 }
 
 /// A struct that partially reads the Arrow JSON schema.
 ///
 /// Fields are left as JSON `Value` as they vary by `DataType`
-#[derive(Deserialize)]
-struct ArrowJsonSchema {
-    fields: Vec<Value>,
+#[derive(Deserialize, Serialize)]
+pub struct ArrowJsonSchema {
+    pub fields: Vec<Value>,
+}
+
+/// A struct that reads an Arrow JSON dictionary batch, which holds the values of the
+/// dictionary with the id `id` in its single column
+#[derive(Deserialize, Serialize)]
+pub struct ArrowJsonDictionaryBatch {
+    pub id: i64,
+    pub data: ArrowJsonBatch,
 }
 
 /// A struct that partially reads the Arrow JSON record batch
-#[derive(Deserialize)]
-struct ArrowJsonBatch {
-    count: usize,
-    columns: Vec<ArrowJsonColumn>,
+#[derive(Deserialize, Serialize)]
+pub struct ArrowJsonBatch {
+    pub count: usize,
+    pub columns: Vec<ArrowJsonColumn>,
 }
 
 /// A struct that partially reads the Arrow JSON column/array
-#[derive(Deserialize, Clone, Debug)]
-struct ArrowJsonColumn {
-    name: String,
-    count: usize,
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ArrowJsonColumn {
+    pub name: String,
+    pub count: usize,
     #[serde(rename = "VALIDITY")]
-    validity: Vec<u8>,
-    #[serde(rename = "DATA")]
-    data: Option<Vec<Value>>,
-    #[serde(rename = "OFFSET")]
-    offset: Option<Vec<Value>>, // leaving as Value as 64-bit offsets are strings
-    children: Option<Vec<ArrowJsonColumn>>,
+    pub validity: Vec<u8>,
+    #[serde(rename = "DATA", skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<Value>>,
+    #[serde(rename = "OFFSET", skip_serializing_if = "Option::is_none")]
+    pub offset: Option<Vec<Value>>, // leaving as Value as 64-bit offsets are strings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<ArrowJsonColumn>>,
+}
+
+impl ArrowJson {
+    /// Creates the Arrow JSON representation of `batches` of `schema`
+    pub fn from_batches(schema: &Schema, batches: &[RecordBatch]) -> Result<Self> {
+        Ok(ArrowJson {
+            schema: ArrowJsonSchema::from_schema(schema),
+            batches: batches
+                .iter()
+                .map(ArrowJsonBatch::from_batch)
+                .collect::<Result<_>>()?,
+            dictionaries: None,
+        })
+    }
+
+    /// Returns the column of values of the dictionary of each field of the schema, or
+    /// `None` for a field that is not dictionary encoded
+    ///
+    /// Returns an error if a field refers to a dictionary that is not in the file.
+    fn dictionary_columns(&self) -> Result<Vec<Option<&ArrowJsonColumn>>> {
+        self.schema
+            .fields
+            .iter()
+            .map(|field| {
+                let id = match field.get("dictionary").and_then(|d| d.get("id")) {
+                    Some(id) => id.as_i64().ok_or_else(|| {
+                        ArrowError::ParseError(format!("Invalid dictionary id {}", id))
+                    })?,
+                    None => return Ok(None),
+                };
+                self.dictionaries
+                    .iter()
+                    .flatten()
+                    .find(|dictionary| dictionary.id == id)
+                    .and_then(|dictionary| dictionary.data.columns.first())
+                    .map(Some)
+                    .ok_or_else(|| {
+                        ArrowError::ParseError(format!(
+                            "Dictionary with id {} not found",
+                            id
+                        ))
+                    })
+            })
+            .collect()
+    }
 }
 
 impl ArrowJsonSchema {
+    /// Creates the Arrow JSON representation of `schema`
+    pub fn from_schema(schema: &Schema) -> Self {
+        ArrowJsonSchema {
+            fields: schema
+                .fields()
+                .iter()
+                .map(|field| field.to_json())
+                .collect(),
+        }
+    }
+
     /// Compare the Arrow JSON schema with the Arrow `Schema`
-    fn equals_schema(&self, schema: &Schema) -> bool {
+    pub fn equals_schema(&self, schema: &Schema) -> bool {
         let field_len = self.fields.len();
         if field_len != schema.fields().len() {
             return false;
         }
-        for i in 0..field_len {
-            let json_field = &self.fields[i];
-            let field = schema.field(i);
-            assert_eq!(json_field, &field.to_json());
+        (0..field_len).all(|i| {
+            canonicalize_json(&self.fields[i])
+                == canonicalize_json(&schema.field(i).to_json())
+        })
+    }
+
+    /// Lists the differences between the Arrow JSON schema and the Arrow `Schema`, with
+    /// one entry per differing field showing the expected JSON of the Arrow JSON schema
+    /// and the actual JSON of `schema`. Returns an empty list if the schemas are equal.
+    pub fn schema_mismatches(&self, schema: &Schema) -> Vec<String> {
+        let mut mismatches = vec![];
+        for i in 0..max(self.fields.len(), schema.fields().len()) {
+            let expected = self.fields.get(i).map(canonicalize_json);
+            let actual = schema
+                .fields()
+                .get(i)
+                .map(|f| canonicalize_json(&f.to_json()));
+            if expected == actual {
+                continue;
+            }
+            let name = actual
+                .as_ref()
+                .or_else(|| expected.as_ref())
+                .and_then(|field| field.get("name"))
+                .and_then(|name| name.as_str())
+                .unwrap_or("")
+                .to_string();
+            let to_string = |field: Option<Value>| match field {
+                Some(field) => field.to_string(),
+                None => "no field".to_string(),
+            };
+            mismatches.push(format!(
+                "field {} '{}': expected {}, actual {}",
+                i,
+                name,
+                to_string(expected),
+                to_string(actual)
+            ));
+        }
+        mismatches
+    }
+}
+
+/// Returns a copy of `value` with the keys of all objects sorted, so that JSON emitted by
+/// serializers with different key ordering compares and serializes identically.
+///
+/// The order of arrays, such as the `children` of a field, is significant and preserved.
+fn canonicalize_json(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let mut canonical = serde_json::map::Map::new();
+            for key in keys {
+                canonical.insert(key.clone(), canonicalize_json(&map[key]));
+            }
+            Value::Object(canonical)
+        }
+        Value::Array(values) => {
+            Value::Array(values.iter().map(canonicalize_json).collect())
         }
-        true
+        _ => value.clone(),
     }
 }
 
 impl ArrowJsonBatch {
+    /// Creates the Arrow JSON representation of `batch`
+    ///
+    /// Returns an error if a column has a data type that can not be represented.
+    pub fn from_batch(batch: &RecordBatch) -> Result<Self> {
+        let columns = batch
+            .columns()
+            .iter()
+            .zip(batch.schema().fields())
+            .map(|(column, field)| json_col_from_array(field.name(), column))
+            .collect::<Result<_>>()?;
+        Ok(ArrowJsonBatch {
+            count: batch.num_rows(),
+            columns,
+        })
+    }
+
     /// Comapre the Arrow JSON record batch with a `RecordBatch`
-    fn equals_batch(&self, batch: &RecordBatch) -> bool {
+    ///
+    /// Returns an error if a column does not have the array type of its field, or if a
+    /// field has a data type that can not be compared.
+    pub fn equals_batch(&self, batch: &RecordBatch) -> Result<bool> {
+        self.equals_batch_with_dictionaries(batch, &[])
+    }
+
+    /// Compare the Arrow JSON record batch with a `RecordBatch` that has dictionary
+    /// encoded columns, where `dictionaries` holds the dictionary values of each column
+    /// as returned by `ArrowJson::dictionary_columns`
+    ///
+    /// Returns an error if a dictionary encoded column has no dictionary, or if one of
+    /// its keys is out of the range of the dictionary values.
+    fn equals_batch_with_dictionaries(
+        &self,
+        batch: &RecordBatch,
+        dictionaries: &[Option<&ArrowJsonColumn>],
+    ) -> Result<bool> {
         if self.count != batch.num_rows() {
-            return false;
+            return Ok(false);
         }
         let num_columns = self.columns.len();
         if num_columns != batch.num_columns() {
-            return false;
+            return Ok(false);
         }
         let schema = batch.schema();
-        self.columns
+        for (i, ((col, arr), field)) in self
+            .columns
             .iter()
             .zip(batch.columns())
             .zip(schema.fields())
-            .all(|((col, arr), field)| {
-                // compare each column based on its type
-                if &col.name != field.name() {
-                    return false;
+            .enumerate()
+        {
+            // compare each column based on its type
+            if &col.name != field.name() {
+                return Ok(false);
+            }
+            let json_array: Vec<Value> = match field.data_type() {
+                DataType::Dictionary(_, value_type) => {
+                    let dictionary =
+                        dictionaries.get(i).and_then(|d| *d).ok_or_else(|| {
+                            ArrowError::ComputeError(format!(
+                                "No dictionary for column {}",
+                                field.name()
+                            ))
+                        })?;
+                    json_from_dictionary_col(&col, value_type, dictionary)?
                 }
-                let json_array: Vec<Value> = json_from_col(&col, field.data_type());
-                match field.data_type() {
-                    DataType::Boolean => {
-                        let arr = arr.as_any().downcast_ref::<BooleanArray>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::Int8 => {
-                        let arr = arr.as_any().downcast_ref::<Int8Array>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::Int16 => {
-                        let arr = arr.as_any().downcast_ref::<Int16Array>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::Int32 | DataType::Date32(_) | DataType::Time32(_) => {
-                        let arr = Int32Array::from(arr.data());
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::Int64
-                    | DataType::Date64(_)
-                    | DataType::Time64(_)
-                    | DataType::Timestamp(_) => {
-                        let arr = Int64Array::from(arr.data());
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::UInt8 => {
-                        let arr = arr.as_any().downcast_ref::<UInt8Array>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::UInt16 => {
-                        let arr = arr.as_any().downcast_ref::<UInt16Array>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::UInt32 => {
-                        let arr = arr.as_any().downcast_ref::<UInt32Array>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::UInt64 => {
-                        let arr = arr.as_any().downcast_ref::<UInt64Array>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::Float32 => {
-                        let arr = arr.as_any().downcast_ref::<Float32Array>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::Float64 => {
-                        let arr = arr.as_any().downcast_ref::<Float64Array>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::Utf8 => {
-                        let arr = arr.as_any().downcast_ref::<BinaryArray>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::List(_) => {
-                        let arr = arr.as_any().downcast_ref::<ListArray>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    DataType::Struct(_) => {
-                        let arr = arr.as_any().downcast_ref::<StructArray>().unwrap();
-                        arr.equals_json(&json_array.iter().collect::<Vec<&Value>>()[..])
-                    }
-                    t @ _ => panic!("Unsupported comparison for {:?}", t),
+                data_type => json_from_col(&col, data_type)?,
+            };
+            let json_array = &json_array.iter().collect::<Vec<&Value>>()[..];
+            let equal = match field.data_type() {
+                DataType::Null => arr.downcast::<NullArray>()?.equals_json(json_array),
+                DataType::Boolean => {
+                    arr.downcast::<BooleanArray>()?.equals_json(json_array)
                 }
-            })
+                DataType::Int8 => arr.downcast::<Int8Array>()?.equals_json(json_array),
+                DataType::Int16 => arr.downcast::<Int16Array>()?.equals_json(json_array),
+                DataType::Int32 | DataType::Date32(_) | DataType::Time32(_) => {
+                    Int32Array::from(arr.data()).equals_json(json_array)
+                }
+                DataType::Int64
+                | DataType::Date64(_)
+                | DataType::Time64(_)
+                | DataType::Timestamp(_) => {
+                    Int64Array::from(arr.data()).equals_json(json_array)
+                }
+                DataType::UInt8 => arr.downcast::<UInt8Array>()?.equals_json(json_array),
+                DataType::UInt16 => {
+                    arr.downcast::<UInt16Array>()?.equals_json(json_array)
+                }
+                DataType::UInt32 => {
+                    arr.downcast::<UInt32Array>()?.equals_json(json_array)
+                }
+                DataType::UInt64 => {
+                    arr.downcast::<UInt64Array>()?.equals_json(json_array)
+                }
+                DataType::Float32 => {
+                    arr.downcast::<Float32Array>()?.equals_json(json_array)
+                }
+                DataType::Float64 => {
+                    arr.downcast::<Float64Array>()?.equals_json(json_array)
+                }
+                DataType::Utf8 => {
+                    StringArray::try_from_data(arr.data())?.equals_json(json_array)
+                }
+                DataType::Binary => {
+                    arr.downcast::<BinaryArray>()?.equals_json(json_array)
+                }
+                DataType::FixedSizeBinary(_) => arr
+                    .downcast::<FixedSizeBinaryArray>()?
+                    .equals_json(json_array),
+                DataType::List(_) => arr.downcast::<ListArray>()?.equals_json(json_array),
+                DataType::LargeList(_) => {
+                    arr.downcast::<LargeListArray>()?.equals_json(json_array)
+                }
+                DataType::FixedSizeList(_) => arr
+                    .downcast::<FixedSizeListArray>()?
+                    .equals_json(json_array),
+                DataType::Struct(_) => {
+                    arr.downcast::<StructArray>()?.equals_json(json_array)
+                }
+                DataType::Dictionary(_, _) => arr.equals_json(json_array),
+                t @ _ => {
+                    return Err(ArrowError::ComputeError(format!(
+                        "Unsupported comparison for {:?}",
+                        t
+                    )))
+                }
+            };
+            if !equal {
+                return Ok(false);
+            }
+        }
+        Ok(true)
     }
 }
 
+/// Convert an array into an Arrow JSON column/array named `name`
+///
+/// The 32-bit offsets of `Utf8`, `Binary` and `List` arrays are written as numbers,
+/// while the 64-bit offsets of `LargeList` arrays are written as strings. Offsets start
+/// from zero even if the array is a slice. Binary values are written in hexadecimal.
+fn json_col_from_array(name: &str, array: &ArrayRef) -> Result<ArrowJsonColumn> {
+    let validity = (0..array.len())
+        .map(|i| if array.is_valid(i) { 1 } else { 0 })
+        .collect();
+    let mut col = ArrowJsonColumn {
+        name: name.to_string(),
+        count: array.len(),
+        validity,
+        data: None,
+        offset: None,
+        children: None,
+    };
+    col.data = match array.data_type() {
+        DataType::Boolean => Some(json_data(array.downcast::<BooleanArray>()?)),
+        DataType::Int8 => Some(json_data(array.downcast::<Int8Array>()?)),
+        DataType::Int16 => Some(json_data(array.downcast::<Int16Array>()?)),
+        DataType::Int32 | DataType::Date32(_) | DataType::Time32(_) => {
+            Some(json_data(&Int32Array::from(array.data())))
+        }
+        DataType::Int64
+        | DataType::Date64(_)
+        | DataType::Time64(_)
+        | DataType::Timestamp(_) => Some(json_data(&Int64Array::from(array.data()))),
+        DataType::UInt8 => Some(json_data(array.downcast::<UInt8Array>()?)),
+        DataType::UInt16 => Some(json_data(array.downcast::<UInt16Array>()?)),
+        DataType::UInt32 => Some(json_data(array.downcast::<UInt32Array>()?)),
+        DataType::UInt64 => Some(json_data(array.downcast::<UInt64Array>()?)),
+        DataType::Float32 => Some(json_data(array.downcast::<Float32Array>()?)),
+        DataType::Float64 => Some(json_data(array.downcast::<Float64Array>()?)),
+        DataType::Utf8 => {
+            let array = StringArray::try_from_data(array.data())?;
+            let start = array.value_offset(0);
+            col.offset = Some(
+                (0..=array.len())
+                    .map(|i| Value::from(array.value_offset(i) - start))
+                    .collect(),
+            );
+            Some(
+                (0..array.len())
+                    .map(|i| Value::from(array.value(i)))
+                    .collect(),
+            )
+        }
+        DataType::Binary => {
+            let array = array.downcast::<BinaryArray>()?;
+            let start = array.value_offset(0);
+            col.offset = Some(
+                (0..=array.len())
+                    .map(|i| Value::from(array.value_offset(i) - start))
+                    .collect(),
+            );
+            Some(
+                (0..array.len())
+                    .map(|i| {
+                        let hex = array.value(i).iter().map(|b| format!("{:02X}", b));
+                        Value::from(hex.collect::<String>())
+                    })
+                    .collect(),
+            )
+        }
+        DataType::List(_) => {
+            let array = array.downcast::<ListArray>()?;
+            let start = array.value_offset(0);
+            let end = array.value_offset(array.len());
+            col.offset = Some(
+                (0..=array.len())
+                    .map(|i| Value::from(array.value_offset(i) - start))
+                    .collect(),
+            );
+            let values = array.values().slice(start as usize, (end - start) as usize);
+            col.children = Some(vec![json_col_from_array("item", &values)?]);
+            None
+        }
+        DataType::LargeList(_) => {
+            let array = array.downcast::<LargeListArray>()?;
+            let start = array.value_offset(0);
+            let end = array.value_offset(array.len());
+            // 64-bit offsets are strings, as JSON numbers may not hold them exactly
+            col.offset = Some(
+                (0..=array.len())
+                    .map(|i| Value::from((array.value_offset(i) - start).to_string()))
+                    .collect(),
+            );
+            let values = array.values().slice(start as usize, (end - start) as usize);
+            col.children = Some(vec![json_col_from_array("item", &values)?]);
+            None
+        }
+        DataType::Struct(fields) => {
+            let array = array.downcast::<StructArray>()?;
+            col.children = Some(
+                fields
+                    .iter()
+                    .zip(array.columns())
+                    .map(|(field, child)| json_col_from_array(field.name(), child))
+                    .collect::<Result<_>>()?,
+            );
+            None
+        }
+        t => {
+            return Err(ArrowError::ComputeError(format!(
+                "Unsupported conversion to JSON for {:?}",
+                t
+            )))
+        }
+    };
+    Ok(col)
+}
+
+/// Convert the values of a primitive array into the DATA of an Arrow JSON column,
+/// including the values of null slots
+fn json_data<T: ArrowPrimitiveType>(array: &PrimitiveArray<T>) -> Vec<Value> {
+    (0..array.len())
+        .map(|i| {
+            PrimitiveArrayOps::value(array, i)
+                .into_json_value()
+                .unwrap_or(Value::Null)
+        })
+        .collect()
+}
+
 /// Convert an Arrow JSON column/array into a vector of `Value`
-fn json_from_col(col: &ArrowJsonColumn, data_type: &DataType) -> Vec<Value> {
+///
+/// Returns an error if a VALIDITY entry is not 0 or 1, if a valid slot has no DATA, or
+/// if a nested column is missing its children or has too few OFFSET entries.
+fn json_from_col(col: &ArrowJsonColumn, data_type: &DataType) -> Result<Vec<Value>> {
     match data_type {
-        DataType::List(dt) => json_from_list_col(col, &**dt),
+        DataType::Null => Ok(vec![Value::Null; col.count]),
+        DataType::List(dt) | DataType::LargeList(dt) => json_from_list_col(col, &**dt),
+        DataType::FixedSizeList((dt, size)) => {
+            json_from_fixed_size_list_col(col, &**dt, *size as usize)
+        }
         DataType::Struct(fields) => json_from_struct_col(col, fields),
-        _ => merge_json_array(&col.validity, &col.data.clone().unwrap()),
+        _ => merge_json_array(&col.validity, col.data.as_ref()),
     }
 }
 
+/// Convert an Arrow JSON column/array of a `DataType::Dictionary`, whose DATA are keys
+/// into the values in `dictionary`, into a vector of the decoded `Value`s
+fn json_from_dictionary_col(
+    col: &ArrowJsonColumn,
+    value_type: &DataType,
+    dictionary: &ArrowJsonColumn,
+) -> Result<Vec<Value>> {
+    let values = json_from_col(dictionary, value_type)?;
+    let keys = merge_json_array(&col.validity, col.data.as_ref())?;
+    keys.iter()
+        .map(|key| match key {
+            Value::Null => Ok(Value::Null),
+            key => key
+                .as_u64()
+                .and_then(|k| values.get(k as usize))
+                .cloned()
+                .ok_or_else(|| {
+                    ArrowError::ComputeError(format!(
+                        "Dictionary key {} is out of range of {} values",
+                        key,
+                        values.len()
+                    ))
+                }),
+        })
+        .collect()
+}
+
+/// Returns whether a VALIDITY entry marks a valid slot, or an error if it is not 0 or 1
+fn json_is_valid(validity: u8) -> Result<bool> {
+    match validity {
+        0 => Ok(false),
+        1 => Ok(true),
+        v => Err(ArrowError::ParseError(format!(
+            "Validity data should be 0 or 1, found {}",
+            v
+        ))),
+    }
+}
+
+/// Returns whether the slot at `i` of `col` is valid, or an error if `col` has no
+/// valid VALIDITY entry for it
+fn json_slot_is_valid(col: &ArrowJsonColumn, i: usize) -> Result<bool> {
+    let validity = col.validity.get(i).ok_or_else(|| {
+        ArrowError::ParseError(format!(
+            "Column {} has {} slots, but only {} VALIDITY entries",
+            col.name,
+            col.count,
+            col.validity.len()
+        ))
+    })?;
+    json_is_valid(*validity)
+}
+
+/// Returns the children of an Arrow JSON column/array, or an error if it has none
+fn json_children(col: &ArrowJsonColumn) -> Result<&Vec<ArrowJsonColumn>> {
+    col.children.as_ref().ok_or_else(|| {
+        ArrowError::ParseError(format!("Column {} should have children", col.name))
+    })
+}
+
 /// Merge VALIDITY and DATA vectors from a primitive data type into a `Value` vector with nulls
-fn merge_json_array(validity: &Vec<u8>, data: &Vec<Value>) -> Vec<Value> {
+///
+/// DATA may be omitted, or hold fewer placeholder values than VALIDITY, for a column
+/// whose slots are all null, as the values of null slots are never read.
+fn merge_json_array(validity: &[u8], data: Option<&Vec<Value>>) -> Result<Vec<Value>> {
     validity
         .iter()
-        .zip(data)
-        .map(|(v, d)| match v {
-            0 => Value::Null,
-            1 => d.clone(),
-            _ => panic!("Validity data should be 0 or 1"),
+        .enumerate()
+        .map(|(i, v)| {
+            if !json_is_valid(*v)? {
+                return Ok(Value::Null);
+            }
+            data.and_then(|data| data.get(i)).cloned().ok_or_else(|| {
+                ArrowError::ParseError(format!(
+                    "DATA should have a value for valid slot {}",
+                    i
+                ))
+            })
         })
         .collect()
 }
 
 /// Convert an Arrow JSON column/array of a `DataType::Struct` into a vector of `Value`
-fn json_from_struct_col(col: &ArrowJsonColumn, fields: &Vec<Field>) -> Vec<Value> {
+fn json_from_struct_col(
+    col: &ArrowJsonColumn,
+    fields: &Vec<Field>,
+) -> Result<Vec<Value>> {
     let mut values = Vec::with_capacity(col.count);
 
-    let children: Vec<Vec<Value>> = col
-        .children
-        .clone()
-        .unwrap()
+    let children = json_children(col)?
         .iter()
         .zip(fields)
         .map(|(child, field)| json_from_col(child, field.data_type()))
-        .collect();
+        .collect::<Result<Vec<Vec<Value>>>>()?;
+    if let Some(child) = children.iter().find(|child| child.len() < col.count) {
+        return Err(ArrowError::ParseError(format!(
+            "Struct column {} has {} slots, but a child has only {}",
+            col.name,
+            col.count,
+            child.len()
+        )));
+    }
 
     // create a struct from children
     for j in 0..col.count {
@@ -209,50 +598,115 @@ fn json_from_struct_col(col: &ArrowJsonColumn, fields: &Vec<Field>) -> Vec<Value
         values.push(Value::Object(map));
     }
 
-    values
+    Ok(values)
 }
 
-/// Convert an Arrow JSON column/array of a `DataType::List` into a vector of `Value`
-fn json_from_list_col(col: &ArrowJsonColumn, data_type: &DataType) -> Vec<Value> {
-    let mut values = Vec::with_capacity(col.count);
-
+/// Convert an Arrow JSON column/array of a `DataType::List` or `DataType::LargeList`
+/// into a vector of `Value`
+fn json_from_list_col(col: &ArrowJsonColumn, data_type: &DataType) -> Result<Vec<Value>> {
     // get the inner array
-    let child = &col.children.clone().expect("list type must have children")[0];
+    let child = json_children(col)?.get(0).ok_or_else(|| {
+        ArrowError::ParseError(format!("List column {} has no child", col.name))
+    })?;
     let offsets: Vec<usize> = col
         .offset
-        .clone()
-        .unwrap()
+        .as_ref()
+        .ok_or_else(|| {
+            ArrowError::ParseError(format!("List column {} has no OFFSET", col.name))
+        })?
         .iter()
-        .map(|o| match o {
-            Value::String(s) => *&s.parse::<usize>().unwrap(),
-            Value::Number(n) => n.as_u64().unwrap() as usize,
-            _ => panic!(
-                "Offsets should be numbers or strings that are convertible to numbers"
-            ),
-        })
-        .collect();
+        .map(json_offset)
+        .collect::<Result<_>>()?;
+    if offsets.len() <= col.count {
+        return Err(ArrowError::ParseError(format!(
+            "List column {} has {} slots, but only {} OFFSET entries",
+            col.name,
+            col.count,
+            offsets.len()
+        )));
+    }
     let inner = match data_type {
-        DataType::List(ref dt) => json_from_col(child, &**dt),
-        DataType::Struct(fields) => json_from_struct_col(col, fields),
-        _ => merge_json_array(&child.validity, &child.data.clone().unwrap()),
+        DataType::List(ref dt) | DataType::LargeList(ref dt) => {
+            json_from_col(child, &**dt)?
+        }
+        DataType::Struct(fields) => json_from_struct_col(child, fields)?,
+        _ => merge_json_array(&child.validity, child.data.as_ref())?,
     };
 
-    for i in 0..col.count {
-        match col.validity[i] {
-            0 => values.push(Value::Null),
-            1 => values.push(Value::Array(inner[offsets[i]..offsets[i + 1]].to_vec())),
-            _ => panic!("Validity data should be 0 or 1"),
-        }
-    }
+    (0..col.count)
+        .map(|i| {
+            if !json_slot_is_valid(col, i)? {
+                return Ok(Value::Null);
+            }
+            inner
+                .get(offsets[i]..offsets[i + 1])
+                .map(|values| Value::Array(values.to_vec()))
+                .ok_or_else(|| {
+                    ArrowError::ParseError(format!(
+                        "List column {} has offsets {}..{} out of range of {} values",
+                        col.name,
+                        offsets[i],
+                        offsets[i + 1],
+                        inner.len()
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Convert an Arrow JSON column/array of a `DataType::FixedSizeList` into a vector of
+/// `Value`. Fixed-size lists have no OFFSET, the values of the list at index `i` are
+/// the `size` child values from `i * size`.
+fn json_from_fixed_size_list_col(
+    col: &ArrowJsonColumn,
+    data_type: &DataType,
+    size: usize,
+) -> Result<Vec<Value>> {
+    let child = json_children(col)?.get(0).ok_or_else(|| {
+        ArrowError::ParseError(format!("List column {} has no child", col.name))
+    })?;
+    let inner = json_from_col(child, data_type)?;
+    (0..col.count)
+        .map(|i| {
+            if !json_slot_is_valid(col, i)? {
+                return Ok(Value::Null);
+            }
+            inner
+                .get(i * size..(i + 1) * size)
+                .map(|values| Value::Array(values.to_vec()))
+                .ok_or_else(|| {
+                    ArrowError::ParseError(format!(
+                        "List column {} has too few child values for {} slots",
+                        col.name, col.count
+                    ))
+                })
+        })
+        .collect()
+}
 
-    values
+/// Parses an OFFSET entry, which is a number for 32-bit offsets and may be a string for
+/// 64-bit offsets. Offsets are parsed as `i64`, so a large list's offsets beyond
+/// `u32::MAX` are kept intact, and an offset that is negative or doesn't fit in a
+/// `usize` is an error rather than truncated.
+fn json_offset(offset: &Value) -> Result<usize> {
+    match offset {
+        Value::String(s) => s.parse::<i64>().ok(),
+        Value::Number(n) => n.as_i64(),
+        _ => None,
+    }
+    .and_then(|o| usize::try_from(o).ok())
+    .ok_or_else(|| {
+        ArrowError::ParseError(format!(
+            "Offset {} should be a non-negative integer, or a string of one",
+            offset
+        ))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use std::convert::TryFrom;
     use std::fs::File;
     use std::io::Read;
     use std::sync::Arc;
@@ -313,6 +767,94 @@ mod tests {
         assert!(json_schema.equals_schema(&schema));
     }
 
+    #[test]
+    fn test_schema_mismatches() {
+        let json = r#"
+        {
+            "fields": [
+                {
+                    "name": "c1",
+                    "type": {"name": "int", "isSigned": true, "bitWidth": 32},
+                    "nullable": true,
+                    "children": []
+                },
+                {
+                    "name": "c2",
+                    "type": {"name": "utf8"},
+                    "nullable": true,
+                    "children": []
+                }
+            ]
+        }"#;
+        let json_schema: ArrowJsonSchema = serde_json::from_str(json).unwrap();
+        let schema = Schema::new(vec![
+            Field::new("c1", DataType::Int32, true),
+            Field::new("c2", DataType::Int64, true),
+        ]);
+        assert!(!json_schema.equals_schema(&schema));
+        let mismatches = json_schema.schema_mismatches(&schema);
+        assert_eq!(1, mismatches.len());
+        assert!(mismatches[0].starts_with("field 1 'c2': expected "));
+        assert!(mismatches[0].contains(r#""name":"utf8""#));
+        assert!(mismatches[0].contains(r#""bitWidth":64"#));
+
+        let schema = Schema::new(vec![Field::new("c1", DataType::Int32, true)]);
+        assert!(!json_schema.equals_schema(&schema));
+        let mismatches = json_schema.schema_mismatches(&schema);
+        assert_eq!(1, mismatches.len());
+        assert!(mismatches[0].ends_with("actual no field"));
+
+        let schema = Schema::new(vec![
+            Field::new("c1", DataType::Int32, true),
+            Field::new("c2", DataType::Utf8, true),
+        ]);
+        assert!(json_schema.equals_schema(&schema));
+        assert!(json_schema.schema_mismatches(&schema).is_empty());
+    }
+
+    #[test]
+    fn test_schema_equality_ignores_key_order() {
+        let json = r#"
+        {
+            "fields": [
+                {
+                    "children": [
+                        {
+                            "type": {"bitWidth": 32, "isSigned": true, "name": "int"},
+                            "children": [],
+                            "nullable": false,
+                            "name": "a"
+                        },
+                        {
+                            "nullable": true,
+                            "name": "b",
+                            "children": [],
+                            "type": {"name": "utf8"}
+                        }
+                    ],
+                    "nullable": true,
+                    "type": {"name": "struct"},
+                    "name": "s"
+                }
+            ]
+        }"#;
+        let json_schema: ArrowJsonSchema = serde_json::from_str(json).unwrap();
+        let schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new("b", DataType::Utf8, true),
+            ]),
+            true,
+        )]);
+        assert!(json_schema.equals_schema(&schema));
+        assert_eq!(
+            serde_json::to_string(&canonicalize_json(&json_schema.fields[0])).unwrap(),
+            serde_json::to_string(&canonicalize_json(&schema.field(0).to_json()))
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_arrow_data_equality() {
         let schema = Schema::new(vec![
@@ -397,7 +939,7 @@ mod tests {
         let ts_micros = TimestampMicrosecondArray::from(vec![None, None, None]);
         let ts_nanos =
             TimestampNanosecondArray::from(vec![None, None, Some(-6473623571954960143)]);
-        let utf8s = BinaryArray::try_from(vec![Some("aa"), None, Some("bbb")]).unwrap();
+        let utf8s = StringArray::from(vec![Some("aa"), None, Some("bbb")]);
 
         let value_data = Int32Array::from(vec![None, Some(2), None, None]);
         let value_offsets = Buffer::from(&[0, 3, 4, 4].to_byte_slice());
@@ -410,8 +952,7 @@ mod tests {
         let lists = ListArray::from(list_data);
 
         let structs_int32s = Int32Array::from(vec![None, Some(-2), None]);
-        let structs_utf8s =
-            BinaryArray::try_from(vec![None, None, Some("aaaaaa")]).unwrap();
+        let structs_utf8s = StringArray::from(vec![None, None, Some("aaaaaa")]);
         let structs = StructArray::from(vec![
             (
                 Field::new("int32s", DataType::Int32, true),
@@ -460,6 +1001,581 @@ mod tests {
         // test schemas
         assert!(arrow_json.schema.equals_schema(&schema));
         // test record batch
-        assert!(arrow_json.batches[0].equals_batch(&record_batch));
+        assert!(arrow_json.batches[0].equals_batch(&record_batch).unwrap());
+    }
+
+    #[test]
+    fn test_arrow_json_round_trip() {
+        let mut lists = ListBuilder::new(Int32Builder::new(8));
+        lists.values().append_slice(&[1, 2]).unwrap();
+        lists.append(true).unwrap();
+        lists.append(false).unwrap();
+        lists.values().append_slice(&[3, 4, 5]).unwrap();
+        lists.append(true).unwrap();
+        lists.values().append_null().unwrap();
+        lists.append(true).unwrap();
+        let lists = lists.finish();
+        let structs = StructArray::from(vec![
+            (
+                Field::new("int32s", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3), Some(4)]))
+                    as ArrayRef,
+            ),
+            (
+                Field::new("utf8s", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    Some("bb"),
+                    None,
+                    Some(""),
+                ])) as ArrayRef,
+            ),
+        ]);
+        let schema = Schema::new(vec![
+            Field::new("float64s", DataType::Float64, true),
+            Field::new("utf8s", DataType::Utf8, true),
+            Field::new("lists", lists.data_type().clone(), true),
+            Field::new("structs", structs.data_type().clone(), true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Float64Array::from(vec![Some(1.5), None, Some(-2.0), None])),
+                Arc::new(StringArray::from(vec![
+                    Some("x"),
+                    None,
+                    Some("yy"),
+                    Some("z"),
+                ])),
+                Arc::new(lists),
+                Arc::new(structs),
+            ],
+        )
+        .unwrap();
+
+        for batch in &[batch.clone(), batch.slice(1, 3)] {
+            let json = ArrowJson::from_batches(&schema, &[batch.clone()]).unwrap();
+            assert!(json.schema.equals_schema(&schema));
+            assert!(json.batches[0].equals_batch(batch).unwrap());
+
+            let json = serde_json::to_string(&json).unwrap();
+            let json: ArrowJson = serde_json::from_str(&json).unwrap();
+            assert!(json.schema.equals_schema(&schema));
+            assert!(json.batches[0].equals_batch(batch).unwrap());
+        }
+
+        // the offsets of a slice start from zero
+        let json = ArrowJsonBatch::from_batch(&batch.slice(2, 2)).unwrap();
+        let utf8s = &json.columns[1];
+        assert_eq!(
+            Some(vec![Value::from(0), Value::from(2), Value::from(3)]),
+            utf8s.offset
+        );
+        assert_eq!(vec![1, 1], utf8s.validity);
+        let lists = &json.columns[2];
+        assert_eq!(
+            Some(vec![Value::from(0), Value::from(3), Value::from(4)]),
+            lists.offset
+        );
+        assert_eq!(4, lists.children.as_ref().unwrap()[0].count);
+
+        let unsupported = Schema::new(vec![Field::new(
+            "dict",
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+            true,
+        )]);
+        let dict: Int8DictionaryArray = vec!["a", "b"].into_iter().collect();
+        let batch =
+            RecordBatch::try_new(Arc::new(unsupported), vec![Arc::new(dict)]).unwrap();
+        assert!(ArrowJsonBatch::from_batch(&batch).is_err());
+    }
+
+    #[test]
+    fn test_dictionary_equality() {
+        let json = r#"
+        {
+            "schema": {
+                "fields": [
+                    {
+                        "name": "dict",
+                        "type": {"name": "utf8"},
+                        "nullable": true,
+                        "children": [],
+                        "dictionary": {
+                            "id": 7,
+                            "indexType": {"name": "int", "isSigned": true, "bitWidth": 32},
+                            "isOrdered": false
+                        }
+                    }
+                ]
+            },
+            "dictionaries": [
+                {
+                    "id": 7,
+                    "data": {
+                        "count": 2,
+                        "columns": [
+                            {
+                                "name": "DICT7",
+                                "count": 2,
+                                "VALIDITY": [1, 1],
+                                "OFFSET": [0, 1, 2],
+                                "DATA": ["a", "b"]
+                            }
+                        ]
+                    }
+                }
+            ],
+            "batches": [
+                {
+                    "count": 4,
+                    "columns": [
+                        {
+                            "name": "dict",
+                            "count": 4,
+                            "VALIDITY": [1, 0, 1, 1],
+                            "DATA": [0, 9, 1, 0]
+                        }
+                    ]
+                },
+                {
+                    "count": 1,
+                    "columns": [
+                        {
+                            "name": "dict",
+                            "count": 1,
+                            "VALIDITY": [1],
+                            "DATA": [2]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let arrow_json: ArrowJson = serde_json::from_str(json).unwrap();
+        let schema = Schema::new(vec![Field::new(
+            "dict",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            true,
+        )]);
+        let dict: Int32DictionaryArray = vec![Some("a"), None, Some("b"), Some("a")]
+            .into_iter()
+            .collect();
+        let batch =
+            RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(dict)]).unwrap();
+        let dictionaries = arrow_json.dictionary_columns().unwrap();
+        assert!(arrow_json.batches[0]
+            .equals_batch_with_dictionaries(&batch, &dictionaries)
+            .unwrap());
+
+        let other: Int32DictionaryArray = vec![Some("a"), None, Some("a"), Some("a")]
+            .into_iter()
+            .collect();
+        let other =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(other)]).unwrap();
+        assert!(!arrow_json.batches[0]
+            .equals_batch_with_dictionaries(&other, &dictionaries)
+            .unwrap());
+
+        // the key of the second batch is out of range, and a dictionary is required
+        let single = batch.slice(0, 1);
+        let err = arrow_json.batches[1]
+            .equals_batch_with_dictionaries(&single, &dictionaries)
+            .unwrap_err();
+        assert!(format!("{:?}", err).contains("Dictionary key 2 is out of range"));
+        assert!(arrow_json.batches[0].equals_batch(&batch).is_err());
+    }
+
+    #[test]
+    fn test_large_list_equality() {
+        let json = r#"
+        {
+            "count": 3,
+            "columns": [
+                {
+                    "name": "large",
+                    "count": 3,
+                    "VALIDITY": [1, 0, 1],
+                    "OFFSET": ["0", "2", "2", "5"],
+                    "children": [
+                        {
+                            "name": "item",
+                            "count": 5,
+                            "VALIDITY": [1, 1, 1, 0, 1],
+                            "DATA": [1, 2, 3, 0, 5]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let json_batch: ArrowJsonBatch = serde_json::from_str(json).unwrap();
+        let data_type = DataType::LargeList(Box::new(DataType::Int32));
+        let values = Int32Array::from(vec![Some(1), Some(2), Some(3), None, Some(5)]);
+        let data = ArrayData::builder(data_type.clone())
+            .len(3)
+            .add_buffer(Buffer::from(&[0i64, 2, 2, 5].to_byte_slice()))
+            .add_child_data(values.data())
+            .null_bit_buffer(Buffer::from([0b101]))
+            .build();
+        let schema = Schema::new(vec![Field::new("large", data_type, true)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(LargeListArray::from(data))],
+        )
+        .unwrap();
+        assert!(json_batch.equals_batch(&batch).unwrap());
+
+        // 64-bit offsets are not truncated to 32 bits
+        assert_eq!(1 << 32, json_offset(&Value::from("4294967296")).unwrap());
+        assert_eq!(2, json_offset(&Value::from(2)).unwrap());
+        assert!(json_offset(&Value::from(-1)).is_err());
+        assert!(json_offset(&Value::from("x")).is_err());
+        assert!(json_offset(&Value::from(1.5)).is_err());
+    }
+
+    #[test]
+    fn test_list_of_struct_equality() {
+        let json = r#"
+        {
+            "count": 3,
+            "columns": [
+                {
+                    "name": "list_of_structs",
+                    "count": 3,
+                    "VALIDITY": [1, 0, 1],
+                    "OFFSET": [0, 2, 2, 3],
+                    "children": [
+                        {
+                            "name": "item",
+                            "count": 3,
+                            "VALIDITY": [1, 0, 1],
+                            "children": [
+                                {
+                                    "name": "a",
+                                    "count": 3,
+                                    "VALIDITY": [1, 0, 0],
+                                    "DATA": [1, 0, 0]
+                                },
+                                {
+                                    "name": "b",
+                                    "count": 3,
+                                    "VALIDITY": [1, 0, 1],
+                                    "OFFSET": [0, 1, 1, 2],
+                                    "DATA": ["x", "", "y"]
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let json_batch: ArrowJsonBatch = serde_json::from_str(json).unwrap();
+
+        let fields = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ];
+        let a = Int32Array::from(vec![Some(1), None, None]);
+        let b = StringArray::from(vec![Some("x"), None, Some("y")]);
+        let struct_data = ArrayData::builder(DataType::Struct(fields.clone()))
+            .len(3)
+            .null_bit_buffer(Buffer::from([0b101]))
+            .add_child_data(a.data())
+            .add_child_data(b.data())
+            .build();
+        let data_type = DataType::List(Box::new(DataType::Struct(fields)));
+        let list_data = ArrayData::builder(data_type.clone())
+            .len(3)
+            .add_buffer(Buffer::from(&[0, 2, 2, 3].to_byte_slice()))
+            .null_bit_buffer(Buffer::from([0b101]))
+            .add_child_data(struct_data)
+            .build();
+        let schema = Schema::new(vec![Field::new("list_of_structs", data_type, true)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![Arc::new(ListArray::from(list_data.clone()))],
+        )
+        .unwrap();
+        assert!(json_batch.equals_batch(&batch).unwrap());
+
+        // a different value inside a list element is detected
+        let b = StringArray::from(vec![Some("x"), None, Some("z")]);
+        let struct_data =
+            ArrayData::builder(list_data.child_data()[0].data_type().clone())
+                .len(3)
+                .null_bit_buffer(Buffer::from([0b101]))
+                .add_child_data(a.data())
+                .add_child_data(b.data())
+                .build();
+        let list_data = ArrayData::builder(list_data.data_type().clone())
+            .len(3)
+            .add_buffer(Buffer::from(&[0, 2, 2, 3].to_byte_slice()))
+            .null_bit_buffer(Buffer::from([0b101]))
+            .add_child_data(struct_data)
+            .build();
+        let other = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(ListArray::from(list_data))],
+        )
+        .unwrap();
+        assert!(!json_batch.equals_batch(&other).unwrap());
+    }
+
+    #[test]
+    fn test_fixed_size_equality() {
+        let json = r#"
+        {
+            "count": 3,
+            "columns": [
+                {
+                    "name": "fixed_binary",
+                    "count": 3,
+                    "VALIDITY": [1, 0, 1],
+                    "DATA": ["0102", "0000", "ABCD"]
+                },
+                {
+                    "name": "fixed_list",
+                    "count": 3,
+                    "VALIDITY": [1, 1, 0],
+                    "children": [
+                        {
+                            "name": "item",
+                            "count": 6,
+                            "VALIDITY": [1, 0, 1, 1, 0, 0],
+                            "DATA": [1, 0, 3, 4, 0, 0]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let json_batch: ArrowJsonBatch = serde_json::from_str(json).unwrap();
+
+        let binary_data = ArrayData::builder(DataType::FixedSizeBinary(2))
+            .len(3)
+            .add_buffer(Buffer::from(&[1u8, 2, 0, 0, 0xab, 0xcd][..]))
+            .null_bit_buffer(Buffer::from([0b101]))
+            .build();
+        let values = Int32Array::from(vec![Some(1), None, Some(3), Some(4), None, None]);
+        let list_type = DataType::FixedSizeList((Box::new(DataType::Int32), 2));
+        let list_data = ArrayData::builder(list_type.clone())
+            .len(3)
+            .add_child_data(values.data())
+            .null_bit_buffer(Buffer::from([0b011]))
+            .build();
+        let schema = Schema::new(vec![
+            Field::new("fixed_binary", DataType::FixedSizeBinary(2), true),
+            Field::new("fixed_list", list_type, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(FixedSizeBinaryArray::from(binary_data)),
+                Arc::new(FixedSizeListArray::from(list_data)),
+            ],
+        )
+        .unwrap();
+        assert!(json_batch.equals_batch(&batch).unwrap());
+    }
+
+    #[test]
+    fn test_binary_equality() {
+        let json = r#"
+        {
+            "count": 3,
+            "columns": [
+                {
+                    "name": "binary",
+                    "count": 3,
+                    "VALIDITY": [1, 0, 1],
+                    "OFFSET": [0, 2, 2, 3],
+                    "DATA": ["01ab", "", "FF"]
+                }
+            ]
+        }"#;
+        let json_batch: ArrowJsonBatch = serde_json::from_str(json).unwrap();
+
+        let data = ArrayData::builder(DataType::Binary)
+            .len(3)
+            .add_buffer(Buffer::from(&[0, 2, 2, 3].to_byte_slice()))
+            .add_buffer(Buffer::from(&[1u8, 0xab, 0xff][..]))
+            .null_bit_buffer(Buffer::from([0b101]))
+            .build();
+        let schema = Schema::new(vec![Field::new("binary", DataType::Binary, true)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![Arc::new(BinaryArray::from(data))],
+        )
+        .unwrap();
+        assert!(json_batch.equals_batch(&batch).unwrap());
+
+        let written = ArrowJsonBatch::from_batch(&batch).unwrap();
+        let data = written.columns[0].data.as_ref().unwrap();
+        assert_eq!(&Value::from("01AB"), &data[0]);
+        assert!(written.equals_batch(&batch).unwrap());
+
+        let data = ArrayData::builder(DataType::Binary)
+            .len(3)
+            .add_buffer(Buffer::from(&[0, 2, 2, 3].to_byte_slice()))
+            .add_buffer(Buffer::from(&[1u8, 0xac, 0xff][..]))
+            .null_bit_buffer(Buffer::from([0b101]))
+            .build();
+        let other = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(BinaryArray::from(data))],
+        )
+        .unwrap();
+        assert!(!json_batch.equals_batch(&other).unwrap());
+    }
+
+    #[test]
+    fn test_all_null_column_without_data() {
+        let json = r#"
+        {
+            "count": 3,
+            "columns": [
+                {
+                    "name": "int32s",
+                    "count": 3,
+                    "VALIDITY": [0, 0, 0]
+                },
+                {
+                    "name": "float64s",
+                    "count": 3,
+                    "VALIDITY": [0, 0, 0],
+                    "DATA": []
+                }
+            ]
+        }"#;
+        let json_batch: ArrowJsonBatch = serde_json::from_str(json).unwrap();
+        let schema = Schema::new(vec![
+            Field::new("int32s", DataType::Int32, true),
+            Field::new("float64s", DataType::Float64, true),
+        ]);
+        let all_null = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int32Array::from(vec![None, None, None])),
+                Arc::new(Float64Array::from(vec![None, None, None])),
+            ],
+        )
+        .unwrap();
+        assert!(json_batch.equals_batch(&all_null).unwrap());
+
+        let some_valid = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![None, Some(1), None])),
+                Arc::new(Float64Array::from(vec![None, None, None])),
+            ],
+        )
+        .unwrap();
+        assert!(!json_batch.equals_batch(&some_valid).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_validity_is_an_error() {
+        let json = r#"
+        {
+            "count": 2,
+            "columns": [
+                {
+                    "name": "c1",
+                    "count": 2,
+                    "VALIDITY": [1, 2],
+                    "DATA": [1, 2]
+                }
+            ]
+        }"#;
+        let json_batch: ArrowJsonBatch = serde_json::from_str(json).unwrap();
+
+        let schema = Schema::new(vec![Field::new("c1", DataType::Int32, true)]);
+        let a = Int32Array::from(vec![1, 2]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a)]).unwrap();
+        assert!(json_batch.equals_batch(&batch).is_err());
+    }
+
+    #[test]
+    fn test_truncated_list_offsets_is_an_error() {
+        let json = r#"
+        {
+            "count": 2,
+            "columns": [
+                {
+                    "name": "c1",
+                    "count": 2,
+                    "VALIDITY": [1, 1],
+                    "OFFSET": [0, 1],
+                    "children": [
+                        {
+                            "name": "item",
+                            "count": 2,
+                            "VALIDITY": [1, 1],
+                            "DATA": [1, 2]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let json_batch: ArrowJsonBatch = serde_json::from_str(json).unwrap();
+
+        let data_type = DataType::List(Box::new(DataType::Int32));
+        let values = Int32Array::from(vec![1, 2]);
+        let data = ArrayData::builder(data_type.clone())
+            .len(2)
+            .add_buffer(Buffer::from(&[0, 1, 2].to_byte_slice()))
+            .add_child_data(values.data())
+            .build();
+        let schema = Schema::new(vec![Field::new("c1", data_type, true)]);
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(ListArray::from(data))])
+                .unwrap();
+        match json_batch.equals_batch(&batch) {
+            Err(ArrowError::ParseError(message)) => {
+                assert!(message.contains("only 2 OFFSET entries"))
+            }
+            result => panic!("Expected a parse error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_short_list_validity_is_an_error() {
+        let json = r#"
+        {
+            "count": 2,
+            "columns": [
+                {
+                    "name": "c1",
+                    "count": 2,
+                    "VALIDITY": [1],
+                    "OFFSET": [0, 1, 2],
+                    "children": [
+                        {
+                            "name": "item",
+                            "count": 2,
+                            "VALIDITY": [1, 1],
+                            "DATA": [1, 2]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let json_batch: ArrowJsonBatch = serde_json::from_str(json).unwrap();
+
+        let data_type = DataType::List(Box::new(DataType::Int32));
+        let values = Int32Array::from(vec![1, 2]);
+        let data = ArrayData::builder(data_type.clone())
+            .len(2)
+            .add_buffer(Buffer::from(&[0, 1, 2].to_byte_slice()))
+            .add_child_data(values.data())
+            .build();
+        let schema = Schema::new(vec![Field::new("c1", data_type, true)]);
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(ListArray::from(data))])
+                .unwrap();
+        match json_batch.equals_batch(&batch) {
+            Err(ArrowError::ParseError(message)) => {
+                assert!(message.contains("only 1 VALIDITY entries"))
+            }
+            result => panic!("Expected a parse error, got {:?}", result),
+        }
     }
 }
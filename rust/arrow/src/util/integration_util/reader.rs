@@ -0,0 +1,489 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reads Arrow JSON integration files into record batches
+//!
+//! The integration format stores the schema as JSON and each column of a batch as its
+//! VALIDITY, DATA and OFFSET vectors, with the columns of nested types as `children`.
+//!
+//! Example:
+//!
+//! ```no_run
+//! use arrow::util::integration_util::reader::read_json_file;
+//!
+//! let (schema, batches) = read_json_file("test/data/integration.json").unwrap();
+//! assert_eq!(schema.fields().len(), batches[0].num_columns());
+//! ```
+
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+
+use super::{json_offset, ArrowJson, ArrowJsonColumn};
+use crate::array::*;
+use crate::buffer::{Buffer, MutableBuffer};
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::util::bit_util;
+
+/// Reads the schema and record batches of the Arrow JSON integration file at `path`
+///
+/// Dictionary encoded columns are decoded against the dictionaries of the file.
+/// Returns an error if the file can't be read or parsed, or if it holds a data type
+/// that can't be read yet, such as decimals and intervals. Columns whose OFFSETs
+/// decrease or point past their child, or whose children don't match their length,
+/// are rejected with a `ParseError`.
+pub fn read_json_file(path: &str) -> Result<(Schema, Vec<RecordBatch>)> {
+    let file = File::open(path)?;
+    let arrow_json: ArrowJson = serde_json::from_reader(BufReader::new(file))
+        .map_err(|e| ArrowError::JsonError(e.to_string()))?;
+
+    let mut schema_json = Map::new();
+    schema_json.insert(
+        "fields".to_string(),
+        Value::Array(arrow_json.schema.fields.clone()),
+    );
+    let schema = Schema::from(&Value::Object(schema_json))?;
+    let schema_ref = Arc::new(schema.clone());
+    let dictionaries = arrow_json.dictionary_columns()?;
+
+    let batches = arrow_json
+        .batches
+        .iter()
+        .map(|batch| {
+            if batch.columns.len() != schema.fields().len() {
+                return Err(ArrowError::ParseError(format!(
+                    "Batch has {} columns, but the schema has {} fields",
+                    batch.columns.len(),
+                    schema.fields().len()
+                )));
+            }
+            let columns = batch
+                .columns
+                .iter()
+                .zip(schema.fields())
+                .zip(&dictionaries)
+                .map(|((col, field), dictionary)| {
+                    array_from_json(field.data_type(), col, *dictionary)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            RecordBatch::try_new(schema_ref.clone(), columns)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((schema, batches))
+}
+
+/// Creates a primitive array of `$T` from the valid DATA values of `$col`, converting
+/// each value with `$convert`
+macro_rules! primitive_array {
+    ($T:ty, $col:expr, $convert:expr) => {{
+        let col = $col;
+        let mut builder = PrimitiveBuilder::<$T>::new(col.count);
+        for i in 0..col.count {
+            if is_valid(col, i)? {
+                builder.append_value($convert(data_value(col, i)?)?)?;
+            } else {
+                builder.append_null()?;
+            }
+        }
+        Arc::new(builder.finish()) as ArrayRef
+    }};
+}
+
+/// Creates an array of `data_type` from the Arrow JSON column `col`, where `dictionary`
+/// holds the values of a dictionary encoded column
+fn array_from_json(
+    data_type: &DataType,
+    col: &ArrowJsonColumn,
+    dictionary: Option<&ArrowJsonColumn>,
+) -> Result<ArrayRef> {
+    match data_type {
+        DataType::Null => Ok(Arc::new(NullArray::new(col.count)) as ArrayRef),
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::new(col.count);
+            for i in 0..col.count {
+                if is_valid(col, i)? {
+                    let value = data_value(col, i)?
+                        .as_bool()
+                        .ok_or_else(|| parse_error(col, i, "a boolean"))?;
+                    builder.append_value(value)?;
+                } else {
+                    builder.append_null()?;
+                }
+            }
+            Ok(Arc::new(builder.finish()))
+        }
+        DataType::Int8 => Ok(primitive_array!(Int8Type, col, |v| int::<i8>(col, v))),
+        DataType::Int16 => Ok(primitive_array!(Int16Type, col, |v| int::<i16>(col, v))),
+        DataType::Int32 => Ok(primitive_array!(Int32Type, col, |v| int::<i32>(col, v))),
+        DataType::Int64 => Ok(primitive_array!(Int64Type, col, |v| int::<i64>(col, v))),
+        DataType::UInt8 => Ok(primitive_array!(UInt8Type, col, |v| int::<u8>(col, v))),
+        DataType::UInt16 => Ok(primitive_array!(UInt16Type, col, |v| int::<u16>(col, v))),
+        DataType::UInt32 => Ok(primitive_array!(UInt32Type, col, |v| int::<u32>(col, v))),
+        DataType::UInt64 => Ok(primitive_array!(UInt64Type, col, |v| int::<u64>(col, v))),
+        DataType::Float32 => {
+            Ok(primitive_array!(Float32Type, col, |v| float(col, v).map(|f| f as f32)))
+        }
+        DataType::Float64 => Ok(primitive_array!(Float64Type, col, |v| float(col, v))),
+        DataType::Date32(_) | DataType::Time32(_) => {
+            let array = array_from_json(&DataType::Int32, col, None)?;
+            Ok(with_data_type(&array, data_type))
+        }
+        DataType::Date64(_) | DataType::Time64(_) | DataType::Timestamp(_) => {
+            let array = array_from_json(&DataType::Int64, col, None)?;
+            Ok(with_data_type(&array, data_type))
+        }
+        DataType::Utf8 | DataType::Binary => {
+            let mut builder = BinaryBuilder::new(col.count);
+            for i in 0..col.count {
+                if is_valid(col, i)? {
+                    let value = data_value(col, i)?
+                        .as_str()
+                        .ok_or_else(|| parse_error(col, i, "a string"))?;
+                    if data_type == &DataType::Utf8 {
+                        builder.append_string(value)?;
+                    } else {
+                        builder.append_value(&hex_bytes(col, i, value)?)?;
+                    }
+                } else {
+                    builder.append(false)?;
+                }
+            }
+            let array = Arc::new(builder.finish()) as ArrayRef;
+            Ok(with_data_type(&array, data_type))
+        }
+        DataType::FixedSizeBinary(width) => {
+            let width = usize::try_from(*width)
+                .map_err(|_| parse_error(col, 0, "a non-negative byte width"))?;
+            let mut values = Vec::with_capacity(col.count * width);
+            for i in 0..col.count {
+                if is_valid(col, i)? {
+                    let value = data_value(col, i)?
+                        .as_str()
+                        .ok_or_else(|| parse_error(col, i, "a string"))?;
+                    let bytes = hex_bytes(col, i, value)?;
+                    if bytes.len() != width {
+                        return Err(parse_error(col, i, "a value of the fixed width"));
+                    }
+                    values.extend_from_slice(&bytes);
+                } else {
+                    values.extend(std::iter::repeat(0).take(width));
+                }
+            }
+            let data = ArrayData::builder(data_type.clone())
+                .len(col.count)
+                .null_bit_buffer(validity_buffer(col)?)
+                .add_buffer(Buffer::from(&values[..]))
+                .build();
+            Ok(make_array(data))
+        }
+        DataType::List(value_type) | DataType::LargeList(value_type) => {
+            let offsets = col
+                .offset
+                .as_ref()
+                .ok_or_else(|| missing(col, "OFFSET"))?
+                .iter()
+                .map(json_offset)
+                .collect::<Result<Vec<usize>>>()?;
+            if offsets.len() != col.count + 1 {
+                return Err(missing(col, "an OFFSET for each slot"));
+            }
+            if let Some(i) = (1..offsets.len()).find(|&i| offsets[i] < offsets[i - 1]) {
+                return Err(parse_error(col, i, "an OFFSET not below the previous one"));
+            }
+            let values = array_from_json(value_type, child(col, 0)?, None)?;
+            let last_offset = offsets[col.count];
+            if last_offset > values.len() {
+                return Err(ArrowError::ParseError(format!(
+                    "Column {} has an OFFSET of {}, beyond the {} values of its child",
+                    col.name,
+                    last_offset,
+                    values.len()
+                )));
+            }
+            let offsets = match data_type {
+                DataType::List(_) => {
+                    let offsets = offsets
+                        .iter()
+                        .map(|o| i32::try_from(*o))
+                        .collect::<std::result::Result<Vec<i32>, _>>()
+                        .map_err(|_| parse_error(col, 0, "32-bit offsets"))?;
+                    Buffer::from(offsets.to_byte_slice())
+                }
+                _ => {
+                    let offsets = offsets.iter().map(|o| *o as i64).collect::<Vec<i64>>();
+                    Buffer::from(offsets.to_byte_slice())
+                }
+            };
+            let data = ArrayData::builder(data_type.clone())
+                .len(col.count)
+                .null_bit_buffer(validity_buffer(col)?)
+                .add_buffer(offsets)
+                .add_child_data(values.data())
+                .build();
+            Ok(make_array(data))
+        }
+        DataType::FixedSizeList((value_type, size)) => {
+            let values = array_from_json(value_type, child(col, 0)?, None)?;
+            let expected = usize::try_from(*size)
+                .ok()
+                .and_then(|size| size.checked_mul(col.count));
+            if expected != Some(values.len()) {
+                return Err(child_length_error(col, values.len()));
+            }
+            let data = ArrayData::builder(data_type.clone())
+                .len(col.count)
+                .null_bit_buffer(validity_buffer(col)?)
+                .add_child_data(values.data())
+                .build();
+            Ok(make_array(data))
+        }
+        DataType::Struct(fields) => {
+            let mut builder = ArrayData::builder(data_type.clone())
+                .len(col.count)
+                .null_bit_buffer(validity_buffer(col)?);
+            for (i, field) in fields.iter().enumerate() {
+                let array = array_from_json(field.data_type(), child(col, i)?, None)?;
+                if array.len() != col.count {
+                    return Err(child_length_error(col, array.len()));
+                }
+                builder = builder.add_child_data(array.data());
+            }
+            Ok(make_array(builder.build()))
+        }
+        DataType::Dictionary(key_type, value_type) => {
+            let dictionary = dictionary.ok_or_else(|| {
+                ArrowError::ParseError(format!("No dictionary for column {}", col.name))
+            })?;
+            let keys = array_from_json(key_type, col, None)?;
+            let values = array_from_json(value_type, dictionary, None)?;
+            let keys = keys.data();
+            let mut builder = ArrayData::builder(data_type.clone())
+                .len(keys.len())
+                .add_buffer(keys.buffers()[0].clone())
+                .add_child_data(values.data());
+            if let Some(bitmap) = keys.null_bitmap() {
+                builder = builder.null_bit_buffer(bitmap.bits.clone());
+            }
+            Ok(make_array(builder.build()))
+        }
+        t => Err(ArrowError::ParseError(format!(
+            "Reading {:?} from the integration JSON format is not supported yet",
+            t
+        ))),
+    }
+}
+
+/// Returns whether the slot at `i` of `col` is valid
+fn is_valid(col: &ArrowJsonColumn, i: usize) -> Result<bool> {
+    match col.validity.get(i).cloned() {
+        Some(0) => Ok(false),
+        Some(1) => Ok(true),
+        _ => Err(parse_error(col, i, "a VALIDITY of 0 or 1")),
+    }
+}
+
+/// Returns the DATA value at `i` of `col`
+fn data_value(col: &ArrowJsonColumn, i: usize) -> Result<&Value> {
+    col.data
+        .as_ref()
+        .and_then(|data| data.get(i))
+        .ok_or_else(|| missing(col, "DATA for each valid slot"))
+}
+
+/// Returns the child column at `i` of `col`
+fn child(col: &ArrowJsonColumn, i: usize) -> Result<&ArrowJsonColumn> {
+    col.children
+        .as_ref()
+        .and_then(|children| children.get(i))
+        .ok_or_else(|| missing(col, "children"))
+}
+
+/// Converts the VALIDITY of `col` into a null bitmap
+fn validity_buffer(col: &ArrowJsonColumn) -> Result<Buffer> {
+    let num_bytes = bit_util::ceil(col.count, 8);
+    let mut buffer = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+    {
+        let slice = buffer.data_mut();
+        for i in 0..col.count {
+            if is_valid(col, i)? {
+                bit_util::set_bit(slice, i);
+            }
+        }
+    }
+    Ok(buffer.freeze())
+}
+
+/// Parses an integer DATA value, which is a number, or a string for 64-bit integers
+fn int<T: TryFrom<i128>>(col: &ArrowJsonColumn, value: &Value) -> Result<T> {
+    let int = match value {
+        Value::Number(n) => n
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from)),
+        Value::String(s) => s.parse::<i128>().ok(),
+        _ => None,
+    };
+    int.and_then(|int| T::try_from(int).ok()).ok_or_else(|| {
+        ArrowError::ParseError(format!(
+            "Column {} has an invalid integer {}",
+            col.name, value
+        ))
+    })
+}
+
+/// Parses a floating point DATA value
+fn float(col: &ArrowJsonColumn, value: &Value) -> Result<f64> {
+    value.as_f64().ok_or_else(|| {
+        ArrowError::ParseError(format!(
+            "Column {} has an invalid floating point number {}",
+            col.name, value
+        ))
+    })
+}
+
+/// Decodes the hexadecimal DATA value at `i` of a binary column
+fn hex_bytes(col: &ArrowJsonColumn, i: usize, hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err(parse_error(col, i, "a hexadecimal string"));
+    }
+    Ok((0..hex.len())
+        .step_by(2)
+        .map(|j| u8::from_str_radix(&hex[j..j + 2], 16).unwrap())
+        .collect())
+}
+
+/// Returns a copy of `array` with the same buffers and `data_type`
+fn with_data_type(array: &ArrayRef, data_type: &DataType) -> ArrayRef {
+    let data = array.data();
+    make_array(Arc::new(ArrayData::new(
+        data_type.clone(),
+        data.len(),
+        Some(data.null_count()),
+        data.null_bitmap().clone().map(|bitmap| bitmap.bits),
+        data.offset(),
+        data.buffers().to_vec(),
+        vec![],
+    )))
+}
+
+fn parse_error(col: &ArrowJsonColumn, i: usize, expected: &str) -> ArrowError {
+    ArrowError::ParseError(format!(
+        "Column {} should have {} at index {}",
+        col.name, expected, i
+    ))
+}
+
+fn missing(col: &ArrowJsonColumn, what: &str) -> ArrowError {
+    ArrowError::ParseError(format!("Column {} is missing {}", col.name, what))
+}
+
+fn child_length_error(col: &ArrowJsonColumn, child_len: usize) -> ArrowError {
+    ArrowError::ParseError(format!(
+        "Column {} of {} slots has a child of mismatched length {}",
+        col.name, col.count, child_len
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_read_json_file() {
+        let (schema, batches) = read_json_file("test/data/integration.json").unwrap();
+        assert_eq!(1, batches.len());
+        assert_eq!(&schema, batches[0].schema().as_ref());
+
+        let json = File::open("test/data/integration.json").unwrap();
+        let arrow_json: ArrowJson = serde_json::from_reader(json).unwrap();
+        assert!(arrow_json.schema.equals_schema(&schema));
+        for (json_batch, batch) in arrow_json.batches.iter().zip(&batches) {
+            assert!(json_batch.equals_batch(batch).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_read_json_file_missing() {
+        assert!(read_json_file("test/data/does_not_exist.json").is_err());
+    }
+
+    fn column(json: Value) -> ArrowJsonColumn {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn assert_parse_error(data_type: &DataType, col: &ArrowJsonColumn) {
+        match array_from_json(data_type, col, None) {
+            Err(ArrowError::ParseError(_)) => {}
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_read_invalid_list_offsets() {
+        let list_type = DataType::List(Box::new(DataType::Int32));
+        let values = json!({
+            "name": "item", "count": 3, "VALIDITY": [1, 1, 1], "DATA": [1, 2, 3]
+        });
+        let col = |offsets: Value| {
+            column(json!({
+                "name": "list", "count": 2, "VALIDITY": [1, 1],
+                "OFFSET": offsets, "children": [values.clone()]
+            }))
+        };
+
+        assert!(array_from_json(&list_type, &col(json!([0, 1, 3])), None).is_ok());
+        assert_parse_error(&list_type, &col(json!([0, 2, 1])));
+        assert_parse_error(&list_type, &col(json!([0, 1, 4])));
+        assert_parse_error(
+            &DataType::LargeList(Box::new(DataType::Int32)),
+            &col(json!(["0", "1", "4"])),
+        );
+    }
+
+    #[test]
+    fn test_read_mismatched_child_length() {
+        let values = json!({
+            "name": "item", "count": 3, "VALIDITY": [1, 1, 1], "DATA": [1, 2, 3]
+        });
+        let col = column(json!({
+            "name": "parent", "count": 2, "VALIDITY": [1, 1], "children": [values.clone()]
+        }));
+
+        let struct_type =
+            DataType::Struct(vec![Field::new("item", DataType::Int32, false)]);
+        assert_parse_error(&struct_type, &col);
+        assert_parse_error(
+            &DataType::FixedSizeList((Box::new(DataType::Int32), 2)),
+            &col,
+        );
+        let col = column(json!({
+            "name": "parent", "count": 3, "VALIDITY": [1, 1, 1], "children": [values]
+        }));
+        assert!(array_from_json(&struct_type, &col, None).is_ok());
+        assert_parse_error(
+            &DataType::FixedSizeList((Box::new(DataType::Int32), 2)),
+            &col,
+        );
+    }
+}
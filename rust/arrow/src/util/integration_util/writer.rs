@@ -0,0 +1,116 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Writes record batches as Arrow JSON integration files
+//!
+//! Example:
+//!
+//! ```no_run
+//! use arrow::util::integration_util::reader::read_json_file;
+//! use arrow::util::integration_util::writer::write_json_file;
+//!
+//! let (schema, batches) = read_json_file("test/data/integration.json").unwrap();
+//! write_json_file("/tmp/integration.json", &schema, &batches).unwrap();
+//! ```
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use super::ArrowJson;
+use crate::datatypes::Schema;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+
+/// Writes `batches` of `schema` to the Arrow JSON integration file at `path`
+///
+/// Returns an error if the file can't be written, or if a column has a data type that
+/// can't be represented yet, such as dictionaries.
+pub fn write_json_file(
+    path: &str,
+    schema: &Schema,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    let arrow_json = ArrowJson::from_batches(schema, batches)?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut writer, &arrow_json)
+        .map_err(|e| ArrowError::JsonError(e.to_string()))?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::array::*;
+    use crate::buffer::Buffer;
+    use crate::datatypes::{DataType, Field, ToByteSlice};
+    use crate::util::integration_util::reader::read_json_file;
+
+    #[test]
+    fn test_write_json_file() {
+        let (schema, batches) = read_json_file("test/data/integration.json").unwrap();
+        let path = std::env::temp_dir().join("arrow_write_json_file.json");
+        let path = path.to_str().unwrap();
+        write_json_file(path, &schema, &batches).unwrap();
+
+        let (written_schema, written_batches) = read_json_file(path).unwrap();
+        assert_eq!(schema, written_schema);
+        assert_eq!(batches.len(), written_batches.len());
+        for (batch, written) in batches.iter().zip(&written_batches) {
+            assert_eq!(batch.num_rows(), written.num_rows());
+            for i in 0..batch.num_columns() {
+                assert!(array_equals(
+                    batch.column(i).as_ref(),
+                    written.column(i).as_ref()
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_large_list_offsets_as_strings() {
+        let mut lists = ListBuilder::new(Int32Builder::new(4));
+        lists.values().append_slice(&[1, 2, 3]).unwrap();
+        lists.append(true).unwrap();
+        let lists = lists.finish();
+        let data_type = DataType::LargeList(Box::new(DataType::Int32));
+        let data = ArrayData::builder(data_type.clone())
+            .len(1)
+            .add_buffer(Buffer::from([0i64, 3].to_byte_slice()))
+            .add_child_data(lists.values().data())
+            .build();
+        let schema = Schema::new(vec![Field::new("large", data_type, false)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![Arc::new(LargeListArray::from(data))],
+        )
+        .unwrap();
+
+        let json = ArrowJson::from_batches(&schema, &[batch.clone()]).unwrap();
+        assert_eq!(
+            Some(vec![
+                serde_json::Value::from("0"),
+                serde_json::Value::from("3")
+            ]),
+            json.batches[0].columns[0].offset
+        );
+        assert!(json.batches[0].equals_batch(&batch).unwrap());
+    }
+}
@@ -0,0 +1,347 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Utilities for printing record batches as text tables, which is mostly useful in
+//! tests and for debugging.
+//!
+//! Example:
+//!
+//! ```
+//! use arrow::array::*;
+//! use arrow::datatypes::*;
+//! use arrow::record_batch::RecordBatch;
+//! use arrow::util::pretty::pretty_format_batches;
+//! use std::sync::Arc;
+//!
+//! let schema = Schema::new(vec![
+//!     Field::new("a", DataType::Int32, true),
+//!     Field::new("b", DataType::Utf8, false),
+//! ]);
+//! let batch = RecordBatch::try_new(
+//!     Arc::new(schema),
+//!     vec![
+//!         Arc::new(Int32Array::from(vec![Some(1), None, Some(100)])),
+//!         Arc::new(BinaryArray::from(vec!["x", "y", "z"])),
+//!     ],
+//! )
+//! .unwrap();
+//!
+//! let table = pretty_format_batches(&[batch]).unwrap();
+//! assert_eq!(
+//!     table,
+//!     "+-----+---+\n\
+//!      | a   | b |\n\
+//!      +-----+---+\n\
+//!      |   1 | x |\n\
+//!      |     | y |\n\
+//!      | 100 | z |\n\
+//!      +-----+---+"
+//! );
+//! ```
+
+use std::fmt::Write;
+
+use crate::array::{array_value_to_string, Array};
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+
+/// Options that define how record batches are printed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrettyOptions {
+    /// The maximum number of characters of a cell. Longer values are truncated and
+    /// end with `...`. Defaults to `None`, for no limit.
+    pub max_width: Option<usize>,
+}
+
+/// Returns the rows of `results` as an aligned text table, with a header row of the
+/// field names of their schema.
+///
+/// Numbers are aligned to the right and all other values to the left, lists and
+/// structs are written like `[1, null]` and `{a: 1, b: "x"}`, and null values are
+/// left empty. Returns an error if the batches have different schemas.
+pub fn pretty_format_batches(results: &[RecordBatch]) -> Result<String> {
+    pretty_format_batches_with_options(results, &PrettyOptions::default())
+}
+
+/// Returns the rows of `results` as an aligned text table like `pretty_format_batches`,
+/// formatted according to `options`.
+pub fn pretty_format_batches_with_options(
+    results: &[RecordBatch],
+    options: &PrettyOptions,
+) -> Result<String> {
+    let schema = match results.first() {
+        Some(batch) => batch.schema(),
+        None => return Ok(String::new()),
+    };
+    if results.iter().any(|batch| batch.schema() != schema) {
+        return Err(ArrowError::InvalidArgumentError(
+            "Cannot format record batches with different schemas as one table"
+                .to_string(),
+        ));
+    }
+
+    let header: Vec<String> = schema
+        .fields()
+        .iter()
+        .map(|field| truncate(field.name().clone(), options))
+        .collect();
+    let mut rows = vec![];
+    for batch in results {
+        for row in 0..batch.num_rows() {
+            rows.push(
+                batch
+                    .columns()
+                    .iter()
+                    .map(|column| cell(column.as_ref(), row, options))
+                    .collect::<Vec<String>>(),
+            );
+        }
+    }
+
+    let mut widths: Vec<usize> = header.iter().map(|name| name.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let right_aligned: Vec<bool> = schema
+        .fields()
+        .iter()
+        .map(|field| is_numeric(field.data_type()))
+        .collect();
+
+    let mut table = String::new();
+    write_separator(&mut table, &widths);
+    write_row(&mut table, &header, &widths, &vec![false; widths.len()]);
+    write_separator(&mut table, &widths);
+    for row in &rows {
+        write_row(&mut table, row, &widths, &right_aligned);
+    }
+    table.push_str(&separator(&widths));
+    Ok(table)
+}
+
+/// Prints the rows of `results` as an aligned text table to stdout
+pub fn print_batches(results: &[RecordBatch]) -> Result<()> {
+    println!("{}", pretty_format_batches(results)?);
+    Ok(())
+}
+
+/// Returns the value at `index` of `column` as a cell, which is empty if it is null
+fn cell(column: &Array, index: usize, options: &PrettyOptions) -> String {
+    if column.is_null(index) {
+        String::new()
+    } else {
+        truncate(array_value_to_string(column, index), options)
+    }
+}
+
+fn truncate(value: String, options: &PrettyOptions) -> String {
+    match options.max_width {
+        Some(max_width) if value.chars().count() > max_width => {
+            let ellipsis = "...";
+            if max_width < ellipsis.len() {
+                value.chars().take(max_width).collect()
+            } else {
+                let mut value: String =
+                    value.chars().take(max_width - ellipsis.len()).collect();
+                value.push_str(ellipsis);
+                value
+            }
+        }
+        _ => value,
+    }
+}
+
+fn is_numeric(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Float16
+        | DataType::Float32
+        | DataType::Float64 => true,
+        _ => false,
+    }
+}
+
+fn separator(widths: &[usize]) -> String {
+    let mut line = String::from("+");
+    for width in widths {
+        line.push_str(&"-".repeat(width + 2));
+        line.push('+');
+    }
+    line
+}
+
+fn write_separator(table: &mut String, widths: &[usize]) {
+    table.push_str(&separator(widths));
+    table.push('\n');
+}
+
+fn write_row(table: &mut String, cells: &[String], widths: &[usize], right: &[bool]) {
+    table.push('|');
+    for ((cell, width), right) in cells.iter().zip(widths).zip(right) {
+        // writing to a `String` can't fail
+        if *right {
+            write!(table, " {:>width$} |", cell, width = width).unwrap();
+        } else {
+            write!(table, " {:<width$} |", cell, width = width).unwrap();
+        }
+    }
+    table.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+    use std::sync::Arc;
+
+    use crate::array::*;
+    use crate::datatypes::{Field, Schema};
+
+    #[test]
+    fn test_pretty_format_batches() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Utf8, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let first = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(BinaryArray::try_from(vec![Some("a"), None]).unwrap()),
+                Arc::new(Int32Array::from(vec![Some(1), Some(10)])),
+            ],
+        )
+        .unwrap();
+        let second = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(BinaryArray::from(vec!["long"])),
+                Arc::new(Int32Array::from(vec![None])),
+            ],
+        )
+        .unwrap();
+
+        let table = pretty_format_batches(&[first, second]).unwrap();
+        let expected = vec![
+            "+------+----+",
+            "| a    | b  |",
+            "+------+----+",
+            "| a    |  1 |",
+            "|      | 10 |",
+            "| long |    |",
+            "+------+----+",
+        ];
+        assert_eq!(expected.join("\n"), table);
+    }
+
+    #[test]
+    fn test_pretty_format_nested() {
+        let mut builder = ListBuilder::new(Int32Builder::new(4));
+        builder.values().append_value(1).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        let list = builder.finish();
+        let strukt = StructArray::from(vec![
+            (
+                Field::new("x", DataType::Boolean, false),
+                Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef,
+            ),
+            (
+                Field::new("y", DataType::Utf8, false),
+                Arc::new(BinaryArray::from(vec!["p", "q"])) as ArrayRef,
+            ),
+        ]);
+        let schema = Schema::new(vec![
+            Field::new("l", list.data_type().clone(), true),
+            Field::new("s", strukt.data_type().clone(), false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(list), Arc::new(strukt)],
+        )
+        .unwrap();
+
+        let table = pretty_format_batches(&[batch]).unwrap();
+        let expected = vec![
+            "+-----------+--------------------+",
+            "| l         | s                  |",
+            "+-----------+--------------------+",
+            "| [1, null] | {x: true, y: \"p\"}  |",
+            "|           | {x: false, y: \"q\"} |",
+            "+-----------+--------------------+",
+        ];
+        assert_eq!(expected.join("\n"), table);
+    }
+
+    #[test]
+    fn test_pretty_format_max_width() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Utf8, false)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(BinaryArray::from(vec!["abcdefghij", "abc"]))],
+        )
+        .unwrap();
+
+        let options = PrettyOptions { max_width: Some(6) };
+        let table = pretty_format_batches_with_options(&[batch], &options).unwrap();
+        let expected = vec![
+            "+--------+",
+            "| a      |",
+            "+--------+",
+            "| abc... |",
+            "| abc    |",
+            "+--------+",
+        ];
+        assert_eq!(expected.join("\n"), table);
+    }
+
+    #[test]
+    fn test_pretty_format_empty_batch() {
+        let schema = Schema::new(vec![
+            Field::new("first", DataType::Int32, false),
+            Field::new("second", DataType::Utf8, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(Vec::<i32>::new())),
+                Arc::new(BinaryArray::from(Vec::<&str>::new())),
+            ],
+        )
+        .unwrap();
+
+        let table = pretty_format_batches(&[batch]).unwrap();
+        let expected = vec![
+            "+-------+--------+",
+            "| first | second |",
+            "+-------+--------+",
+            "+-------+--------+",
+        ];
+        assert_eq!(expected.join("\n"), table);
+    }
+}
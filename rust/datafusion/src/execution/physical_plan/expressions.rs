@@ -190,66 +190,72 @@ impl Accumulator for SumAccumulator {
     fn accumulate_batch(&mut self, array: &ArrayRef) -> Result<()> {
         let sum = match array.data_type() {
             DataType::UInt8 => {
-                match compute::sum(array.as_any().downcast_ref::<UInt8Array>().unwrap()) {
+                match compute::sum(array.as_any().downcast_ref::<UInt8Array>().unwrap())?
+                {
                     Some(n) => Ok(Some(ScalarValue::UInt8(n))),
                     None => Ok(None),
                 }
             }
             DataType::UInt16 => {
-                match compute::sum(array.as_any().downcast_ref::<UInt16Array>().unwrap())
+                match compute::sum(array.as_any().downcast_ref::<UInt16Array>().unwrap())?
                 {
                     Some(n) => Ok(Some(ScalarValue::UInt16(n))),
                     None => Ok(None),
                 }
             }
             DataType::UInt32 => {
-                match compute::sum(array.as_any().downcast_ref::<UInt32Array>().unwrap())
+                match compute::sum(array.as_any().downcast_ref::<UInt32Array>().unwrap())?
                 {
                     Some(n) => Ok(Some(ScalarValue::UInt32(n))),
                     None => Ok(None),
                 }
             }
             DataType::UInt64 => {
-                match compute::sum(array.as_any().downcast_ref::<UInt64Array>().unwrap())
+                match compute::sum(array.as_any().downcast_ref::<UInt64Array>().unwrap())?
                 {
                     Some(n) => Ok(Some(ScalarValue::UInt64(n))),
                     None => Ok(None),
                 }
             }
             DataType::Int8 => {
-                match compute::sum(array.as_any().downcast_ref::<Int8Array>().unwrap()) {
+                match compute::sum(array.as_any().downcast_ref::<Int8Array>().unwrap())? {
                     Some(n) => Ok(Some(ScalarValue::Int8(n))),
                     None => Ok(None),
                 }
             }
             DataType::Int16 => {
-                match compute::sum(array.as_any().downcast_ref::<Int16Array>().unwrap()) {
+                match compute::sum(array.as_any().downcast_ref::<Int16Array>().unwrap())?
+                {
                     Some(n) => Ok(Some(ScalarValue::Int16(n))),
                     None => Ok(None),
                 }
             }
             DataType::Int32 => {
-                match compute::sum(array.as_any().downcast_ref::<Int32Array>().unwrap()) {
+                match compute::sum(array.as_any().downcast_ref::<Int32Array>().unwrap())?
+                {
                     Some(n) => Ok(Some(ScalarValue::Int32(n))),
                     None => Ok(None),
                 }
             }
             DataType::Int64 => {
-                match compute::sum(array.as_any().downcast_ref::<Int64Array>().unwrap()) {
+                match compute::sum(array.as_any().downcast_ref::<Int64Array>().unwrap())?
+                {
                     Some(n) => Ok(Some(ScalarValue::Int64(n))),
                     None => Ok(None),
                 }
             }
             DataType::Float32 => {
-                match compute::sum(array.as_any().downcast_ref::<Float32Array>().unwrap())
-                {
+                match compute::sum(
+                    array.as_any().downcast_ref::<Float32Array>().unwrap(),
+                )? {
                     Some(n) => Ok(Some(ScalarValue::Float32(n))),
                     None => Ok(None),
                 }
             }
             DataType::Float64 => {
-                match compute::sum(array.as_any().downcast_ref::<Float64Array>().unwrap())
-                {
+                match compute::sum(
+                    array.as_any().downcast_ref::<Float64Array>().unwrap(),
+                )? {
                     Some(n) => Ok(Some(ScalarValue::Float64(n))),
                     None => Ok(None),
                 }
@@ -1194,7 +1200,7 @@ mod tests {
     #[test]
     fn literal_i32() -> Result<()> {
         // create an arbitrary record bacth
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
         let a = Int32Array::from(vec![Some(1), None, Some(3), Some(4), Some(5)]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
 
@@ -1371,7 +1377,7 @@ mod tests {
 
     #[test]
     fn sum_i32_with_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
         let a = Int32Array::from(vec![Some(1), None, Some(3), Some(4), Some(5)]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
@@ -1383,7 +1389,7 @@ mod tests {
 
     #[test]
     fn avg_i32_with_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
         let a = Int32Array::from(vec![Some(1), None, Some(3), Some(4), Some(5)]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
@@ -1395,7 +1401,7 @@ mod tests {
 
     #[test]
     fn max_i32_with_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
         let a = Int32Array::from(vec![Some(1), None, Some(3), Some(4), Some(5)]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
@@ -1407,7 +1413,7 @@ mod tests {
 
     #[test]
     fn min_i32_with_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
         let a = Int32Array::from(vec![Some(1), None, Some(3), Some(4), Some(5)]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
@@ -1419,7 +1425,7 @@ mod tests {
 
     #[test]
     fn sum_i32_all_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
         let a = Int32Array::from(vec![None, None]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
@@ -1431,7 +1437,7 @@ mod tests {
 
     #[test]
     fn max_i32_all_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
         let a = Int32Array::from(vec![None, None]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
@@ -1443,7 +1449,7 @@ mod tests {
 
     #[test]
     fn min_i32_all_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
         let a = Int32Array::from(vec![None, None]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
@@ -1455,7 +1461,7 @@ mod tests {
 
     #[test]
     fn avg_i32_all_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
         let a = Int32Array::from(vec![None, None]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
@@ -1620,7 +1626,7 @@ mod tests {
 
     #[test]
     fn count_with_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
         let a = Int32Array::from(vec![Some(1), Some(2), None, None, Some(3), None]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
         assert_eq!(do_count(&batch)?, Some(ScalarValue::UInt64(3)));
@@ -1629,7 +1635,7 @@ mod tests {
 
     #[test]
     fn count_all_nulls() -> Result<()> {
-        let schema = Schema::new(vec![Field::new("a", DataType::Boolean, false)]);
+        let schema = Schema::new(vec![Field::new("a", DataType::Boolean, true)]);
         let a = BooleanArray::from(vec![None, None, None, None, None, None, None, None]);
         let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(a)])?;
         assert_eq!(do_count(&batch)?, Some(ScalarValue::UInt64(0)));